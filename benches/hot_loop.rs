@@ -0,0 +1,93 @@
+//! Time and heap allocations of one HHO iteration on a large population (500 hawks, 1000
+//! dimensions).
+//!
+//! Before timing, each case prints the number of allocations per iteration, counted by a
+//! wrapping global allocator. Run with `cargo bench --bench hot_loop`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use kambo_hho::{
+    core::{Bounds, Decoder, DecoderError},
+    hho::{Hho, HhoBuilder, UpdateMode},
+};
+
+const POP: usize = 500;
+const DIM: usize = 1000;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+struct Sphere;
+
+impl Decoder for Sphere {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(solution.iter().map(|x| x * x).sum())
+    }
+}
+
+fn hho(mode: UpdateMode) -> Hho {
+    HhoBuilder::new(Bounds::uniform(-10.0, 10.0, DIM).unwrap())
+        .pop_size(POP)
+        .max_iters(usize::MAX)
+        .update_mode(mode)
+        .seed(7)
+        .build()
+}
+
+fn iteration(c: &mut Criterion) {
+    let cases = [
+        ("full_vector", UpdateMode::FullVector),
+        ("block_greedy", UpdateMode::BlockGreedy { block_size: 250 }),
+    ];
+    for (name, mode) in cases {
+        let hho = hho(mode);
+        let mut run = hho.start(&Sphere).unwrap();
+        // Aquecimento: as curvas e os buffers atingem seu tamanho de regime
+        for _ in 0..5 {
+            run.step();
+        }
+        let steps = 20;
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for _ in 0..steps {
+            run.step();
+        }
+        let per_iter = (ALLOCATIONS.load(Ordering::Relaxed) - before) / steps;
+        println!("{name}: {per_iter} allocations per iteration ({POP} x {DIM})");
+
+        c.bench_function(&format!("iteration/{name}"), |b| {
+            b.iter(|| black_box(run.step()));
+        });
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = iteration
+}
+criterion_main!(benches);
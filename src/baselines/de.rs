@@ -0,0 +1,203 @@
+use std::marker::PhantomData;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    baselines::{evaluate_initial, failed, pso::best_of},
+    core::{
+        Bounds, Budget, Decoder, Minimization, Objective, Optimizer, Population, Report,
+        TerminationReason, clock::Instant, utils::dimension_wise_diversity,
+    },
+    hho::HhoError,
+};
+
+/// Classic `DE/rand/1/bin` differential evolution (Storn & Price, 1997).
+#[derive(Debug, Clone)]
+pub struct DifferentialEvolution<O = Minimization> {
+    pub bounds: Bounds,
+    pub pop_size: usize,
+    /// Fator de escala F
+    pub scale: f64,
+    /// Taxa de cruzamento CR
+    pub crossover: f64,
+    /// Semente do gerador; `None` sorteia uma do sistema
+    pub seed: Option<u64>,
+    objective: PhantomData<fn() -> O>,
+}
+
+impl<O: Objective> DifferentialEvolution<O> {
+    /// Population of 30 with `F = 0.5`, `CR = 0.9`.
+    #[must_use]
+    pub const fn new(bounds: Bounds) -> Self {
+        Self {
+            bounds,
+            pop_size: 30,
+            scale: 0.5,
+            crossover: 0.9,
+            seed: None,
+            objective: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_pop_size(mut self, pop_size: usize) -> Self {
+        self.pop_size = pop_size;
+        self
+    }
+
+    /// Seeds the generator, making runs reproducible.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<O: Objective> Optimizer for DifferentialEvolution<O> {
+    fn name(&self) -> &'static str {
+        "de"
+    }
+
+    fn run(&mut self, decoder: &dyn Decoder, budget: Budget) -> Result<Report, HhoError> {
+        let start = Instant::now();
+        let mut rng = self
+            .seed
+            .map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+        let dim = self.bounds.dim();
+        // rand/1 precisa de quatro indivíduos distintos
+        let size = self.pop_size.max(4);
+
+        let mut pop = Population::zeros(size, dim);
+        for x in &mut pop {
+            self.bounds.fill_random(x, &mut rng);
+        }
+        let mut fit = evaluate_initial::<O>(decoder, &pop, start)?;
+        let mut evals = size;
+        let mut curve = Vec::new();
+        let mut curve_iters = Vec::new();
+        let mut curve_evals = Vec::new();
+        let mut diversity = Vec::new();
+        let mut iters = 0;
+        let mut trial = vec![0.0; dim];
+        let mut failure = None;
+
+        'run: while !budget.exhausted(iters, evals, start.elapsed()) {
+            for i in 0..size {
+                if matches!(budget, Budget::Evaluations(max) if evals >= max) {
+                    break;
+                }
+                let [r1, r2, r3] = distinct_three(size, i, &mut rng);
+                let jrand = rng.random_range(0..dim);
+                for j in 0..dim {
+                    trial[j] = if j == jrand || rng.random::<f64>() < self.crossover {
+                        self.scale.mul_add(pop[r2][j] - pop[r3][j], pop[r1][j])
+                    } else {
+                        pop[i][j]
+                    };
+                }
+                self.bounds.project_slice(&mut trial);
+                evals += 1;
+                let f = match decoder.decode(&trial) {
+                    Ok(f) => f,
+                    Err(source) => {
+                        failure = Some(source);
+                        break 'run;
+                    }
+                };
+                if !O::better(fit[i], f) {
+                    pop[i].copy_from_slice(&trial);
+                    fit[i] = f;
+                }
+            }
+            curve.push(fit[best_of::<O>(&fit)]);
+            curve_iters.push(iters + 1);
+            curve_evals.push(evals);
+            diversity.push(dimension_wise_diversity(&pop));
+            iters += 1;
+        }
+
+        let g = best_of::<O>(&fit);
+        let report = Report {
+            best_fitness: fit[g],
+            best_position: pop[g].to_vec(),
+            convergence_curve: curve,
+            curve_iters,
+            curve_evals,
+            diversity,
+            iters,
+            evals,
+            stop_reason: Some(if failure.is_some() {
+                TerminationReason::Error
+            } else {
+                budget.reason()
+            }),
+            seed: self.seed,
+            ..Report::empty_for::<O>()
+        }
+        .with_duration(start.elapsed());
+        match failure {
+            Some(source) => Err(failed(source, report)),
+            None => Ok(report),
+        }
+    }
+}
+
+/// Sorteia três índices distintos entre si e de `exclude`
+fn distinct_three<R: Rng>(n: usize, exclude: usize, rng: &mut R) -> [usize; 3] {
+    let mut out = [exclude; 3];
+    for k in 0..3 {
+        loop {
+            let c = rng.random_range(0..n);
+            if c != exclude && !out[..k].contains(&c) {
+                out[k] = c;
+                break;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{baselines::Pso, benchmarks::Function::Sphere, core::DecoderError};
+
+    /// Falha na 101ª avaliação
+    struct Flaky(AtomicUsize);
+
+    impl Decoder for Flaky {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            if self.0.fetch_add(1, Ordering::Relaxed) == 100 {
+                return Err(DecoderError::UnknownError);
+            }
+            Sphere.decode(solution)
+        }
+    }
+
+    #[test]
+    fn baselines_minimize_sphere_within_budget() {
+        let bounds = Bounds::uniform(-5.0, 5.0, 5).unwrap();
+        let mut algos: Vec<Box<dyn Optimizer>> = vec![
+            Box::new(Pso::<Minimization>::new(bounds.clone()).with_seed(6)),
+            Box::new(DifferentialEvolution::<Minimization>::new(bounds).with_seed(6)),
+        ];
+        for algo in &mut algos {
+            let report = algo.run(&Sphere, Budget::Evaluations(6_000)).unwrap();
+            assert_eq!(report.evals, 6_000, "{}", algo.name());
+            assert!(
+                report.best_fitness < 1e-3,
+                "{}: {}",
+                algo.name(),
+                report.best_fitness
+            );
+            // Um decoder que falha encerra a execução com o relatório parcial, sem pânico
+            let flaky = Flaky(AtomicUsize::new(0));
+            let err = algo.run(&flaky, Budget::Evaluations(6_000)).unwrap_err();
+            let partial = err.report().unwrap();
+            assert_eq!(partial.evals, 101, "{}", algo.name());
+            assert_eq!(partial.stop_reason, Some(TerminationReason::Error));
+        }
+    }
+}
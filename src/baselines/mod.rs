@@ -0,0 +1,48 @@
+//! Reference implementations of classic metaheuristics, used as baselines in experiments.
+//!
+//! They share [`crate::core::Bounds`], [`crate::core::Decoder`] and
+//! [`crate::core::Report`] with HHO and implement [`crate::core::Optimizer`].
+
+pub mod de;
+pub mod pso;
+
+pub use de::DifferentialEvolution;
+pub use pso::Pso;
+
+use crate::{
+    core::{
+        Decoder, DecoderError, Objective, Population, Report, TerminationReason, clock::Instant,
+    },
+    hho::HhoError,
+};
+
+/// Avalia a população inicial, parando no primeiro erro do decoder
+pub(crate) fn evaluate_initial<O: Objective>(
+    decoder: &dyn Decoder,
+    population: &Population,
+    start: Instant,
+) -> Result<Vec<f64>, HhoError> {
+    let mut fitness = Vec::with_capacity(population.len());
+    for x in population {
+        match decoder.decode(x) {
+            Ok(f) => fitness.push(f),
+            Err(source) => {
+                let report = Report {
+                    evals: fitness.len() + 1,
+                    stop_reason: Some(TerminationReason::Error),
+                    ..Report::empty_for::<O>()
+                };
+                return Err(failed(source, report.with_duration(start.elapsed())));
+            }
+        }
+    }
+    Ok(fitness)
+}
+
+/// Erro de uma execução interrompida pelo decoder, com o relatório até a falha
+pub(crate) fn failed(source: DecoderError, report: Report) -> HhoError {
+    HhoError::Decoder {
+        source,
+        report: Box::new(report),
+    }
+}
@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    baselines::{evaluate_initial, failed},
+    core::{
+        Bounds, Budget, Decoder, Minimization, Objective, Optimizer, Population, Report,
+        TerminationReason, clock::Instant, utils::dimension_wise_diversity,
+    },
+    hho::HhoError,
+};
+
+/// Global-best particle swarm optimization with constriction coefficients
+/// (Clerc & Kennedy, 2002).
+#[derive(Debug, Clone)]
+pub struct Pso<O = Minimization> {
+    pub bounds: Bounds,
+    pub pop_size: usize,
+    /// Peso de inércia
+    pub inertia: f64,
+    pub cognitive: f64,
+    pub social: f64,
+    /// Semente do gerador; `None` sorteia uma do sistema
+    pub seed: Option<u64>,
+    objective: PhantomData<fn() -> O>,
+}
+
+impl<O: Objective> Pso<O> {
+    /// Swarm of 30 particles with `w = 0.7298`, `c1 = c2 = 1.49618`.
+    #[must_use]
+    pub const fn new(bounds: Bounds) -> Self {
+        Self {
+            bounds,
+            pop_size: 30,
+            inertia: 0.729_8,
+            cognitive: 1.496_18,
+            social: 1.496_18,
+            seed: None,
+            objective: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_pop_size(mut self, pop_size: usize) -> Self {
+        self.pop_size = pop_size;
+        self
+    }
+
+    /// Seeds the generator, making runs reproducible.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<O: Objective> Optimizer for Pso<O> {
+    fn name(&self) -> &'static str {
+        "pso"
+    }
+
+    fn run(&mut self, decoder: &dyn Decoder, budget: Budget) -> Result<Report, HhoError> {
+        let start = Instant::now();
+        let mut rng = self
+            .seed
+            .map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+        let dim = self.bounds.dim();
+        let size = self.pop_size.max(1);
+
+        let mut x = Population::zeros(size, dim);
+        for xi in &mut x {
+            self.bounds.fill_random(xi, &mut rng);
+        }
+        let mut v = Population::zeros(size, dim);
+        let mut pbest = x.clone();
+        let mut pbest_f = evaluate_initial::<O>(decoder, &x, start)?;
+        let mut evals = size;
+        let mut g = best_of::<O>(&pbest_f);
+        let mut curve = Vec::new();
+        let mut curve_iters = Vec::new();
+        let mut curve_evals = Vec::new();
+        let mut diversity = Vec::new();
+        let mut iters = 0;
+        let mut failure = None;
+
+        'run: while !budget.exhausted(iters, evals, start.elapsed()) {
+            for i in 0..size {
+                if matches!(budget, Budget::Evaluations(max) if evals >= max) {
+                    break;
+                }
+                for j in 0..dim {
+                    let (r1, r2): (f64, f64) = (rng.random(), rng.random());
+                    let vmax = self.bounds.span_at(j);
+                    let cognitive = self.cognitive * r1 * (pbest[i][j] - x[i][j]);
+                    let social = self.social * r2 * (pbest[g][j] - x[i][j]);
+                    v[i][j] = self
+                        .inertia
+                        .mul_add(v[i][j], cognitive + social)
+                        .clamp(-vmax, vmax);
+                    x[i][j] += v[i][j];
+                }
+                self.bounds.project_slice(&mut x[i]);
+                evals += 1;
+                let f = match decoder.decode(&x[i]) {
+                    Ok(f) => f,
+                    Err(source) => {
+                        failure = Some(source);
+                        break 'run;
+                    }
+                };
+                if O::better(f, pbest_f[i]) {
+                    pbest[i].copy_from_slice(&x[i]);
+                    pbest_f[i] = f;
+                    if O::better(f, pbest_f[g]) {
+                        g = i;
+                    }
+                }
+            }
+            curve.push(pbest_f[g]);
+            curve_iters.push(iters + 1);
+            curve_evals.push(evals);
+            diversity.push(dimension_wise_diversity(&x));
+            iters += 1;
+        }
+
+        let report = Report {
+            best_fitness: pbest_f[g],
+            best_position: pbest[g].to_vec(),
+            convergence_curve: curve,
+            curve_iters,
+            curve_evals,
+            diversity,
+            iters,
+            evals,
+            stop_reason: Some(if failure.is_some() {
+                TerminationReason::Error
+            } else {
+                budget.reason()
+            }),
+            seed: self.seed,
+            ..Report::empty_for::<O>()
+        }
+        .with_duration(start.elapsed());
+        match failure {
+            Some(source) => Err(failed(source, report)),
+            None => Ok(report),
+        }
+    }
+}
+
+/// Índice do melhor valor (a população nunca é vazia aqui)
+pub(crate) fn best_of<O: Objective>(fitness: &[f64]) -> usize {
+    let mut b = 0;
+    for (i, &f) in fitness.iter().enumerate().skip(1) {
+        if O::better(f, fitness[b]) {
+            b = i;
+        }
+    }
+    b
+}
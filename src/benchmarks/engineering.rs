@@ -0,0 +1,350 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use crate::{
+    constraints::Constrained,
+    core::{Bounds, Decoder, DecoderError, bounds::BoundsError},
+};
+
+/// Peso da penalidade estática aplicada à soma das violações
+const PENALTY: f64 = 1e6;
+
+/// Coeficiente `0.7854` do redutor, mantido como publicado em vez de `π/4`
+#[allow(clippy::approx_constant)]
+const QUARTER: f64 = 0.7854;
+
+/// Classic constrained engineering design problems, all minimized, in the formulations used
+/// by the HHO literature.
+///
+/// Constraints are written as `g(x) <= 0` (see [`Engineering::constraints`]). As a
+/// [`Decoder`], a problem returns the cost plus a static penalty of `10⁶ · Σ max(0, g(x))`,
+/// so feasible designs are scored by their cost alone; as a [`Constrained`] problem it can be
+/// scored with any [`crate::constraints::Penalty`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Engineering {
+    /// Cylindrical vessel with hemispherical heads: shell and head thicknesses `Ts`, `Th`,
+    /// inner radius `R` and length `L`; 4 constraints.
+    PressureVessel,
+    /// Weld thickness `h`, weld length `l`, bar height `t` and bar thickness `b`, with
+    /// shear, bending, buckling and deflection limits; 7 constraints.
+    WeldedBeam,
+    /// Tension/compression spring: wire diameter `d`, coil diameter `D` and number of active
+    /// coils `N`; 4 constraints.
+    Spring,
+    /// Gearbox speed reducer with 7 design variables and 11 constraints.
+    SpeedReducer,
+}
+
+impl Engineering {
+    pub const ALL: [Self; 4] = [
+        Self::PressureVessel,
+        Self::WeldedBeam,
+        Self::Spring,
+        Self::SpeedReducer,
+    ];
+
+    /// Lowercase name, e.g. `"welded_beam"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::PressureVessel => "pressure_vessel",
+            Self::WeldedBeam => "welded_beam",
+            Self::Spring => "spring",
+            Self::SpeedReducer => "speed_reducer",
+        }
+    }
+
+    /// Problem named `name` (case-insensitive), see [`Engineering::name`].
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Number of design variables.
+    #[must_use]
+    pub const fn dim(self) -> usize {
+        match self {
+            Self::PressureVessel | Self::WeldedBeam => 4,
+            Self::Spring => 3,
+            Self::SpeedReducer => 7,
+        }
+    }
+
+    /// Variable ranges of the problem.
+    ///
+    /// # Errors
+    ///
+    /// Never in practice; the ranges are fixed and valid.
+    pub fn bounds(self) -> Result<Bounds, BoundsError> {
+        let (lo, hi) = match self {
+            Self::PressureVessel => (vec![0.0, 0.0, 10.0, 10.0], vec![99.0, 99.0, 200.0, 200.0]),
+            Self::WeldedBeam => (vec![0.1, 0.1, 0.1, 0.1], vec![2.0, 10.0, 10.0, 2.0]),
+            Self::Spring => (vec![0.05, 0.25, 2.0], vec![2.0, 1.3, 15.0]),
+            Self::SpeedReducer => (
+                vec![2.6, 0.7, 17.0, 7.3, 7.3, 2.9, 5.0],
+                vec![3.6, 0.8, 28.0, 8.3, 8.3, 3.9, 5.5],
+            ),
+        };
+        Bounds::per_dim(lo, hi)
+    }
+
+    /// Best cost reported in the literature, for comparison tables.
+    #[must_use]
+    pub const fn best_known(self) -> f64 {
+        match self {
+            Self::PressureVessel => 6_059.714,
+            Self::WeldedBeam => 1.724_852,
+            Self::Spring => 0.012_665,
+            Self::SpeedReducer => 2_994.471,
+        }
+    }
+
+    /// Cost of the design `x`, ignoring the constraints.
+    ///
+    /// # Panics
+    ///
+    /// If `x` has fewer than [`Engineering::dim`] values.
+    #[must_use]
+    pub fn cost(self, x: &[f64]) -> f64 {
+        match self {
+            Self::PressureVessel => {
+                let (ts, th, r, l) = (x[0], x[1], x[2], x[3]);
+                (0.6224 * ts * r).mul_add(
+                    l,
+                    (1.7781 * th * r)
+                        .mul_add(r, (3.1661 * ts * ts).mul_add(l, 19.84 * ts * ts * r)),
+                )
+            }
+            Self::WeldedBeam => {
+                let (weld, length, height, thickness) = (x[0], x[1], x[2], x[3]);
+                (1.104_71 * weld * weld)
+                    .mul_add(length, 0.048_11 * height * thickness * (14.0 + length))
+            }
+            Self::Spring => (x[2] + 2.0) * x[1] * x[0] * x[0],
+            Self::SpeedReducer => {
+                let (x1, x2, x3, x4, x5, x6, x7) = (x[0], x[1], x[2], x[3], x[4], x[5], x[6]);
+                let gears = 3.3333f64.mul_add(x3 * x3, 14.9334f64.mul_add(x3, -43.0934));
+                let squares = x6.mul_add(x6, x7 * x7);
+                let cubes = x6.powi(3) + x7.powi(3);
+                let shafts = (x4 * x6).mul_add(x6, x5 * x7 * x7);
+                (QUARTER * x1 * x2 * x2).mul_add(
+                    gears,
+                    (-1.508 * x1).mul_add(squares, 7.4777f64.mul_add(cubes, QUARTER * shafts)),
+                )
+            }
+        }
+    }
+
+    /// Constraint values `g(x)`; the design is feasible when all are `<= 0`.
+    ///
+    /// # Panics
+    ///
+    /// If `x` has fewer than [`Engineering::dim`] values.
+    #[must_use]
+    pub fn constraints(self, x: &[f64]) -> Vec<f64> {
+        match self {
+            Self::PressureVessel => {
+                let (ts, th, r, l) = (x[0], x[1], x[2], x[3]);
+                vec![
+                    0.0193f64.mul_add(r, -ts),
+                    0.00954f64.mul_add(r, -th),
+                    (-PI * r * r).mul_add(l, (-4.0 / 3.0) * PI * r.powi(3)) + 1_296_000.0,
+                    l - 240.0,
+                ]
+            }
+            Self::WeldedBeam => welded_beam(x[0], x[1], x[2], x[3]),
+            Self::Spring => {
+                let (d, coil, n) = (x[0], x[1], x[2]);
+                vec![
+                    1.0 - coil.powi(3) * n / (71_785.0 * d.powi(4)),
+                    coil.mul_add(4.0 * coil, -d * coil)
+                        / (12_566.0 * coil.mul_add(d.powi(3), -d.powi(4)))
+                        + (5_108.0 * d * d).recip()
+                        - 1.0,
+                    1.0 - 140.45 * d / (coil * coil * n),
+                    (d + coil) / 1.5 - 1.0,
+                ]
+            }
+            Self::SpeedReducer => {
+                let (x1, x2, x3, x4, x5, x6, x7) = (x[0], x[1], x[2], x[3], x[4], x[5], x[6]);
+                vec![
+                    27.0 / (x1 * x2 * x2 * x3) - 1.0,
+                    397.5 / (x1 * x2 * x2 * x3 * x3) - 1.0,
+                    1.93 * x4.powi(3) / (x2 * x3 * x6.powi(4)) - 1.0,
+                    1.93 * x5.powi(3) / (x2 * x3 * x7.powi(4)) - 1.0,
+                    (745.0 * x4 / (x2 * x3)).hypot(16.9e6f64.sqrt()) / (110.0 * x6.powi(3)) - 1.0,
+                    (745.0 * x5 / (x2 * x3)).hypot(157.5e6f64.sqrt()) / (85.0 * x7.powi(3)) - 1.0,
+                    x2 * x3 / 40.0 - 1.0,
+                    5.0 * x2 / x1 - 1.0,
+                    x1 / (12.0 * x2) - 1.0,
+                    1.5f64.mul_add(x6, 1.9) / x4 - 1.0,
+                    1.1f64.mul_add(x7, 1.9) / x5 - 1.0,
+                ]
+            }
+        }
+    }
+
+    /// Total violation `Σ max(0, g(x))`, zero for feasible designs.
+    #[must_use]
+    pub fn violation(self, x: &[f64]) -> f64 {
+        self.constraints(x).into_iter().map(|g| g.max(0.0)).sum()
+    }
+}
+
+/// Restrições da viga soldada (formulação de Coello, `J` com `l²/12`)
+fn welded_beam(h: f64, l: f64, t: f64, b: f64) -> Vec<f64> {
+    const P: f64 = 6_000.0;
+    const L: f64 = 14.0;
+    const E: f64 = 30e6;
+    const G: f64 = 12e6;
+
+    let half = h.midpoint(t);
+    let tau_1 = P / (SQRT_2 * h * l);
+    let moment = P * (L + l / 2.0);
+    let radius = (l / 2.0).hypot(half);
+    let inertia = 2.0 * SQRT_2 * h * l * half.mul_add(half, l * l / 12.0);
+    let tau_2 = moment * radius / inertia;
+    let tau = tau_1
+        .mul_add(tau_1, tau_2.mul_add(tau_2, tau_1 * tau_2 * l / radius))
+        .sqrt();
+    let sigma = 6.0 * P * L / (b * t * t);
+    let delta = 4.0 * P * L.powi(3) / (E * t.powi(3) * b);
+    let buckling = 4.013 * E * (t * t * b.powi(6) / 36.0).sqrt() / (L * L)
+        * (-t / (2.0 * L)).mul_add((E / (4.0 * G)).sqrt(), 1.0);
+    vec![
+        tau - 13_600.0,
+        sigma - 30_000.0,
+        h - b,
+        (0.104_71 * h).mul_add(h, 0.048_11 * t * b * (14.0 + l)) - 5.0,
+        0.125 - h,
+        delta - 0.25,
+        P - buckling,
+    ]
+}
+
+impl Constrained for Engineering {
+    fn cost(&self, x: &[f64]) -> Result<f64, DecoderError> {
+        if x.len() != self.dim() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.dim(),
+                received: x.len(),
+            });
+        }
+        Ok(Self::cost(*self, x))
+    }
+
+    fn constraints(&self, x: &[f64]) -> Vec<f64> {
+        Self::constraints(*self, x)
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.dim())
+    }
+}
+
+impl Decoder for Engineering {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        if solution.len() != self.dim() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.dim(),
+                received: solution.len(),
+            });
+        }
+        Ok(PENALTY.mul_add(
+            Self::violation(*self, solution),
+            Self::cost(*self, solution),
+        ))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.dim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hho::HhoBuilder,
+        local_search::{LocalRefiner, PatternSearch},
+    };
+
+    #[test]
+    fn best_known_designs_match_the_literature() {
+        let designs: [(Engineering, &[f64]); 4] = [
+            (
+                Engineering::PressureVessel,
+                &[0.8125, 0.4375, 42.0984, 176.6366],
+            ),
+            (
+                Engineering::WeldedBeam,
+                &[0.205_730, 3.470_489, 9.036_624, 0.205_730],
+            ),
+            (Engineering::Spring, &[0.051_689, 0.356_718, 11.288_966]),
+            (
+                Engineering::SpeedReducer,
+                &[3.5, 0.7, 17.0, 7.3, 7.715_320, 3.350_215, 5.286_654],
+            ),
+        ];
+        for (p, x) in designs {
+            assert_eq!(x.len(), p.dim());
+            assert!(p.bounds().unwrap().dim() == p.dim());
+            let rel = (p.cost(x) - p.best_known()).abs() / p.best_known();
+            assert!(rel < 1e-4, "{}: {}", p.name(), p.cost(x));
+            // Os pontos publicados são arredondados: violações só no arredondamento, e a do
+            // volume do vaso (em pol³) é medida em relação aos 1 296 000 exigidos
+            let mut g = p.constraints(x);
+            if p == Engineering::PressureVessel {
+                g[2] /= 1_296_000.0;
+            }
+            let worst = g.into_iter().fold(f64::MIN, f64::max);
+            assert!(worst < 1e-5, "{}: {worst}", p.name());
+            assert_eq!(Engineering::from_name(&p.name().to_uppercase()), Some(p));
+        }
+        // Inviável: a penalidade domina o custo
+        assert!(Engineering::Spring.decode(&[0.05, 1.3, 15.0]).unwrap() > 1e3);
+        assert!(Engineering::Spring.decode(&[0.05; 4]).is_err());
+    }
+
+    #[test]
+    fn seeded_runs_stay_feasible_and_never_beat_the_best_known_cost() {
+        for p in Engineering::ALL {
+            let report = HhoBuilder::new(p.bounds().unwrap())
+                .pop_size(30)
+                .max_iters(500)
+                .seed(1)
+                .local_refiner(LocalRefiner::Pattern(PatternSearch::default()))
+                .build()
+                .run(&p)
+                .unwrap();
+            let x = &report.best_position;
+            assert!(
+                p.violation(x) < 1e-6,
+                "{}: {:?}",
+                p.name(),
+                p.constraints(x)
+            );
+            assert!((report.best_fitness - p.cost(x)).abs() < 1e-9);
+            // Uma penalidade quebrada ou uma restrição com o sinal trocado deixaria a busca
+            // passar do ótimo; o vaso contínuo (espessuras fora dos múltiplos de 0.0625)
+            // chega a 5885.33, uns 2.9% abaixo do publicado
+            let floor = match p {
+                Engineering::PressureVessel => 5_885.33,
+                _ => p.best_known() * (1.0 - 1e-5),
+            };
+            assert!(
+                report.best_fitness > floor,
+                "{}: {}",
+                p.name(),
+                report.best_fitness
+            );
+            // O redutor é suave o bastante para a busca em padrão fechar a distância
+            if p == Engineering::SpeedReducer {
+                assert!(report.best_fitness < p.best_known() * 1.01);
+            }
+        }
+    }
+}
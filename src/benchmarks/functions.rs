@@ -0,0 +1,147 @@
+use std::f64::consts::{E, TAU};
+
+use crate::core::{Bounds, Decoder, DecoderError, bounds::BoundsError};
+
+/// Classic scalable test functions, all minimized with an optimum of `0`.
+///
+/// Every function is defined for any dimension and is evaluated on the usual symmetric box
+/// returned by [`Function::bounds`]. The optimum is the origin, except for
+/// [`Function::Rosenbrock`] whose minimizer is the all-ones vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Function {
+    /// `Σ xᵢ²` on `[-100, 100]`.
+    Sphere,
+    /// `10n + Σ (xᵢ² − 10 cos 2πxᵢ)` on `[-5.12, 5.12]`.
+    Rastrigin,
+    /// `Σ 100 (xᵢ₊₁ − xᵢ²)² + (1 − xᵢ)²` on `[-30, 30]`.
+    Rosenbrock,
+    /// Ackley's function on `[-32, 32]`.
+    Ackley,
+    /// `1 + Σ xᵢ² / 4000 − Π cos(xᵢ / √i)` on `[-600, 600]`.
+    Griewank,
+    /// Schwefel 2.22, `Σ |xᵢ| + Π |xᵢ|`, on `[-10, 10]`.
+    Schwefel222,
+}
+
+impl Function {
+    pub const ALL: [Self; 6] = [
+        Self::Sphere,
+        Self::Rastrigin,
+        Self::Rosenbrock,
+        Self::Ackley,
+        Self::Griewank,
+        Self::Schwefel222,
+    ];
+
+    /// Lowercase name, e.g. `"rastrigin"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sphere => "sphere",
+            Self::Rastrigin => "rastrigin",
+            Self::Rosenbrock => "rosenbrock",
+            Self::Ackley => "ackley",
+            Self::Griewank => "griewank",
+            Self::Schwefel222 => "schwefel_2_22",
+        }
+    }
+
+    /// Function named `name` (case-insensitive), see [`Function::name`].
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Per-coordinate search interval.
+    #[must_use]
+    pub const fn domain(self) -> (f64, f64) {
+        match self {
+            Self::Sphere => (-100.0, 100.0),
+            Self::Rastrigin => (-5.12, 5.12),
+            Self::Rosenbrock => (-30.0, 30.0),
+            Self::Ackley => (-32.0, 32.0),
+            Self::Griewank => (-600.0, 600.0),
+            Self::Schwefel222 => (-10.0, 10.0),
+        }
+    }
+
+    /// The [`Function::domain`] box in `dim` dimensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BoundsError::InvalidDim`] if `dim == 0`.
+    pub fn bounds(self, dim: usize) -> Result<Bounds, BoundsError> {
+        let (lo, hi) = self.domain();
+        Bounds::uniform(lo, hi, dim)
+    }
+
+    /// Value of the function at `x`.
+    #[must_use]
+    pub fn eval(self, x: &[f64]) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let n = x.len() as f64;
+        match self {
+            Self::Sphere => x.iter().map(|v| v * v).sum(),
+            Self::Rastrigin => 10.0f64.mul_add(
+                n,
+                x.iter()
+                    .map(|v| v.mul_add(*v, -10.0 * (TAU * v).cos()))
+                    .sum::<f64>(),
+            ),
+            Self::Rosenbrock => x
+                .windows(2)
+                .map(|w| 100.0f64.mul_add(w[0].mul_add(-w[0], w[1]).powi(2), (1.0 - w[0]).powi(2)))
+                .sum(),
+            Self::Ackley => {
+                if x.is_empty() {
+                    return 0.0;
+                }
+                let squares: f64 = x.iter().map(|v| v * v).sum();
+                let cosines: f64 = x.iter().map(|v| (TAU * v).cos()).sum();
+                (-20.0f64).mul_add((-0.2 * (squares / n).sqrt()).exp(), -(cosines / n).exp())
+                    + 20.0
+                    + E
+            }
+            Self::Griewank => {
+                let squares: f64 = x.iter().map(|v| v * v).sum();
+                let product: f64 = x
+                    .iter()
+                    .zip(1u32..)
+                    .map(|(v, i)| (v / f64::from(i).sqrt()).cos())
+                    .product();
+                squares / 4000.0 - product + 1.0
+            }
+            Self::Schwefel222 => {
+                x.iter().map(|v| v.abs()).sum::<f64>() + x.iter().map(|v| v.abs()).product::<f64>()
+            }
+        }
+    }
+}
+
+impl Decoder for Function {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(self.eval(solution))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optima_are_zero() {
+        for f in Function::ALL {
+            let optimum = if f == Function::Rosenbrock { 1.0 } else { 0.0 };
+            assert!(f.eval(&[optimum; 5]).abs() < 1e-12, "{}", f.name());
+            assert!(f.eval(&[2.5; 5]) > 0.0, "{}", f.name());
+            assert_eq!(Function::from_name(&f.name().to_uppercase()), Some(f));
+        }
+        assert!((Function::Rastrigin.eval(&[1.0, 0.0]) - 1.0).abs() < 1e-12);
+        assert_eq!(Function::from_name("nope"), None);
+        assert!((Function::Griewank.bounds(3).unwrap().hi_at(2) - 600.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,7 @@
+//! Standard test problems for experiments and examples.
+
+pub mod engineering;
+pub mod functions;
+
+pub use engineering::Engineering;
+pub use functions::Function;
@@ -0,0 +1,243 @@
+//! Command-line runner: reads a JSON run specification and writes the reports.
+//!
+//! ```text
+//! kambo-hho spec.json
+//! ```
+//!
+//! A specification names the problem, the budget and the seeds, e.g.
+//!
+//! ```json
+//! {
+//!   "problem": { "benchmark": { "name": "rastrigin", "dim": 10 } },
+//!   "budget": { "Evaluations": 20000 },
+//!   "pop_size": 30,
+//!   "replications": 5,
+//!   "seed": 42,
+//!   "output": { "csv": "runs.csv", "json": "runs.json" }
+//! }
+//! ```
+//!
+//! `"problem"` may also be a spec of [`ProblemRegistry`], e.g. `"problem": "rastrigin(dim=10)"`
+//! or `"problem": "welded_beam"`, whose problem brings its own bounds.
+//!
+//! An external evaluator replaces the benchmark with
+//! `{ "command": { "program": "./simulate", "args": ["--fast"] } }` and then needs
+//! `"bounds": { "lower": -1.0, "upper": 1.0, "dim": 4 }` (or per-dimension `lower`/`upper`
+//! lists). The command receives the candidate on stdin as whitespace-separated numbers (or in
+//! a temporary file with `"input": "file"`) and must print its fitness on stdout; optional
+//! `"timeout_secs"` and `"max_concurrency"` are passed to
+//! [`kambo_hho::decoders::ExternalDecoder`]. Explicit `"seeds": [..]` take precedence over
+//! `replications`/`seed`. Without an output file each run is summarized on stdout.
+//!
+//! `"output": { "solution": "best.toml" }` also saves the best solution over all runs with
+//! [`Report::save_solution`], as TOML for a `.toml` file and as JSON otherwise.
+#![warn(
+    clippy::all,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo
+)]
+
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
+
+use kambo_hho::{
+    benchmarks::Function,
+    core::{Bounds, Budget, Decoder, Optimizer, Report, ReportSet, SeedStreams, SolutionFormat},
+    decoders::{ExternalDecoder, Input},
+    experiments::{Cell, ProblemRegistry, Results},
+    hho::{HhoBuilder, HhoError},
+};
+use serde::Deserialize;
+
+const USAGE: &str = "usage: kambo-hho <spec.json>";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    problem: ProblemField,
+    #[serde(default)]
+    bounds: Option<BoundsSpec>,
+    budget: Budget,
+    #[serde(default = "default_pop_size")]
+    pop_size: usize,
+    #[serde(default)]
+    seed: u64,
+    #[serde(default = "default_replications")]
+    replications: usize,
+    #[serde(default)]
+    seeds: Option<Vec<u64>>,
+    #[serde(default)]
+    output: Output,
+}
+
+const fn default_pop_size() -> usize {
+    30
+}
+
+const fn default_replications() -> usize {
+    1
+}
+
+/// Problema por nome do registro ou descrito por extenso
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ProblemField {
+    Named(String),
+    Spec(ProblemSpec),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ProblemSpec {
+    Benchmark {
+        name: String,
+        dim: usize,
+    },
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        input: Input,
+        #[serde(default)]
+        timeout_secs: Option<f64>,
+        #[serde(default)]
+        max_concurrency: usize,
+    },
+}
+
+/// Limites escalares (com `dim`) ou por dimensão
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BoundsSpec {
+    Uniform { lower: f64, upper: f64, dim: usize },
+    PerDim { lower: Vec<f64>, upper: Vec<f64> },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Output {
+    csv: Option<PathBuf>,
+    json: Option<PathBuf>,
+    solution: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [flag] if flag == "-h" || flag == "--help" => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        [path] => match run(path) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("kambo-hho: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(path: &str) -> Result<(), Box<dyn Error>> {
+    let spec: Spec = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let (name, decoder, default_bounds): (String, Arc<dyn Decoder + Send + Sync>, Option<Bounds>) =
+        match spec.problem {
+            ProblemField::Named(spec) => {
+                let problem = ProblemRegistry::new().create(&spec)?;
+                (problem.name, problem.decoder, Some(problem.bounds))
+            }
+            ProblemField::Spec(ProblemSpec::Benchmark { name, dim }) => {
+                let f = Function::from_name(&name)
+                    .ok_or_else(|| format!("unknown benchmark function `{name}`"))?;
+                (f.name().to_owned(), Arc::new(f), Some(f.bounds(dim)?))
+            }
+            ProblemField::Spec(ProblemSpec::Command {
+                program,
+                args,
+                input,
+                timeout_secs,
+                max_concurrency,
+            }) => {
+                let mut decoder = ExternalDecoder::new(&program)
+                    .args(args)
+                    .input(input)
+                    .max_concurrency(max_concurrency);
+                if let Some(secs) = timeout_secs {
+                    decoder = decoder.timeout(Duration::try_from_secs_f64(secs)?);
+                }
+                (program, Arc::new(decoder), None)
+            }
+        };
+    let bounds = match spec.bounds {
+        Some(BoundsSpec::Uniform { lower, upper, dim }) => Bounds::uniform(lower, upper, dim)?,
+        Some(BoundsSpec::PerDim { lower, upper }) => Bounds::per_dim(lower, upper)?,
+        None => default_bounds.ok_or("`bounds` is required for a command problem")?,
+    };
+    let seeds = spec.seeds.unwrap_or_else(|| {
+        let streams = SeedStreams::new(spec.seed);
+        (0..spec.replications as u64)
+            .map(|k| streams.replication(k))
+            .collect()
+    });
+
+    let reports: Vec<Report> = seeds
+        .iter()
+        .map(|&seed| {
+            let mut hho = HhoBuilder::new(bounds.clone())
+                .pop_size(spec.pop_size)
+                .seed(seed)
+                .build();
+            // Uma execução interrompida pelo decoder entra na saída com o relatório parcial
+            let mut report = match Optimizer::run(&mut hho, decoder.as_ref(), spec.budget) {
+                Err(e) if e.report().is_some() => e.into_report(),
+                outcome => outcome?,
+            };
+            report.seed.get_or_insert(seed);
+            Ok(report)
+        })
+        .collect::<Result<_, HhoError>>()?;
+
+    if let Some(path) = &spec.output.json {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, &reports)?;
+        out.flush()?;
+    }
+    if let Some(path) = &spec.output.solution {
+        let best = reports
+            .iter()
+            .reduce(|a, b| if b.is_better_than(a) { b } else { a });
+        if let Some(best) = best {
+            best.save_solution(path, SolutionFormat::from_path(path))?;
+        }
+    }
+    if spec.output.csv.is_none() && spec.output.json.is_none() {
+        for (k, report) in reports.iter().enumerate() {
+            println!("run {k}: {}", report.summary());
+        }
+    }
+    if let Some(path) = &spec.output.csv {
+        let results = Results {
+            cells: vec![Cell {
+                problem: name,
+                algorithm: "hho".to_owned(),
+                reports: ReportSet::new(reports),
+            }],
+        };
+        results.to_csv(path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use crate::{
+    constraints::Constrained,
+    core::{Decoder, DecoderError, Direction, TerminationReason},
+    hho::ChangeSignal,
+    termination::{Criterion, Status},
+};
+
+/// Schedule of the feasibility tolerance `ε` of [`EpsilonConstrained`].
+///
+/// `ε(t) = initial · (1 - t/cutoff)^exponent` once `t` iterations are done (the
+/// initialization uses `ε(0)`), and `0` after `cutoff` iterations, as in the ε-constrained method of
+/// Takahama and Sakai. A large `initial`, around the typical violation of a random point,
+/// lets the search cross infeasible regions early; equality constraints, written as
+/// `|h(x)| - δ <= 0`, are then approached gradually instead of being almost never met.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpsilonSchedule {
+    /// Tolerance at the initialization, finite and non-negative.
+    pub initial: f64,
+    /// Iteration from which only truly feasible points count as feasible, at least 1.
+    pub cutoff: usize,
+    /// Shape of the decay, finite and positive; larger values shrink `ε` faster early on.
+    pub exponent: f64,
+}
+
+impl Default for EpsilonSchedule {
+    fn default() -> Self {
+        Self {
+            initial: 1.0,
+            cutoff: 100,
+            exponent: 2.0,
+        }
+    }
+}
+
+impl EpsilonSchedule {
+    /// Tolerance after `t` iterations.
+    #[must_use]
+    pub fn at(&self, t: usize) -> f64 {
+        if t >= self.cutoff {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let progress = t as f64 / self.cutoff as f64;
+        self.initial * (1.0 - progress).powf(self.exponent)
+    }
+
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self {
+            initial,
+            cutoff,
+            exponent,
+        } = *self;
+        (!(initial.is_finite() && initial >= 0.0)
+            || cutoff == 0
+            || !(exponent.is_finite() && exponent > 0.0))
+            .then(|| {
+                format!(
+                    "epsilon schedule needs a finite non-negative initial = {initial}, cutoff = \
+                     {cutoff} >= 1 and a finite positive exponent = {exponent}"
+                )
+            })
+    }
+}
+
+/// Decoder of a [`Constrained`] problem with the ε-constrained method.
+///
+/// Points whose violation `v = Σ max(0, g(x))` is at most the current `ε` (see
+/// [`EpsilonSchedule`]) are ranked by their cost alone; the others come after all of them,
+/// ranked by violation. The optimizers compare plain fitness values, so the order is
+/// expressed as a fitness: an ε-infeasible point scores the worst cost of an ε-feasible point
+/// seen so far plus its violation, as in Deb's feasibility rules.
+///
+/// Like [`crate::constraints::Penalized`], the schedule follows the HHO iterations through
+/// [`Decoder::end_iteration`] and the current `ε` is recorded in
+/// [`crate::core::Report::penalty_coefficients`]. Call [`EpsilonConstrained::reset`] before
+/// reusing the decoder, and [`EpsilonConstrained::feasible_target`] to stop once a truly
+/// feasible point is good enough.
+///
+/// The hawks keep the fitness they were scored with, so a point accepted under a loose `ε`
+/// would keep its cost as fitness after `ε` shrank below its violation. Handing
+/// [`EpsilonConstrained::change_signal`] to the optimizer as a
+/// [`crate::hho::ChangeDetection::Manual`] detection re-scores the population whenever `ε`
+/// has halved and once more when it reaches zero.
+#[derive(Debug)]
+pub struct EpsilonConstrained<P> {
+    problem: P,
+    schedule: EpsilonSchedule,
+    direction: Direction,
+    /// Iteração corrente, a partir de 0
+    iteration: AtomicUsize,
+    /// Bits do pior custo ε-viável visto até agora (NaN enquanto não há nenhum)
+    worst_feasible: AtomicU64,
+    /// Bits do melhor custo de um ponto realmente viável, lido pelo critério de parada
+    best_feasible: Arc<AtomicU64>,
+    /// Bits de `ε` na última notificação de mudança
+    notified: AtomicU64,
+    signal: ChangeSignal,
+}
+
+impl<P: Constrained> EpsilonConstrained<P> {
+    /// Minimizes the cost of `problem` with the tolerance of `schedule`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`super::PenaltyError`] if `schedule` is out of range (see
+    /// [`EpsilonSchedule`]).
+    pub fn new(problem: P, schedule: EpsilonSchedule) -> Result<Self, super::PenaltyError> {
+        if let Some(problem) = schedule.problem() {
+            return Err(super::PenaltyError::new(problem));
+        }
+        Ok(Self {
+            problem,
+            schedule,
+            direction: Direction::Minimize,
+            iteration: AtomicUsize::new(0),
+            worst_feasible: AtomicU64::new(f64::NAN.to_bits()),
+            best_feasible: Arc::new(AtomicU64::new(f64::NAN.to_bits())),
+            notified: AtomicU64::new(schedule.initial.to_bits()),
+            signal: ChangeSignal::new(),
+        })
+    }
+
+    /// Optimizes the cost in `direction` instead.
+    #[must_use]
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    #[must_use]
+    pub const fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Current tolerance `ε`.
+    #[must_use]
+    pub fn epsilon(&self) -> f64 {
+        self.schedule.at(self.iteration.load(Ordering::Relaxed))
+    }
+
+    /// Best cost of a truly feasible point (`v = 0`) evaluated so far.
+    #[must_use]
+    pub fn best_feasible(&self) -> Option<f64> {
+        let f = f64::from_bits(self.best_feasible.load(Ordering::Relaxed));
+        (!f.is_nan()).then_some(f)
+    }
+
+    /// Restarts the schedule and forgets the costs seen.
+    pub fn reset(&self) {
+        self.iteration.store(0, Ordering::Relaxed);
+        self.worst_feasible
+            .store(f64::NAN.to_bits(), Ordering::Relaxed);
+        self.best_feasible
+            .store(f64::NAN.to_bits(), Ordering::Relaxed);
+        self.notified
+            .store(self.schedule.initial.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Signal notified whenever `ε` has halved since the last notification, and when it
+    /// reaches zero, for [`crate::hho::ChangeDetection::Manual`].
+    #[must_use]
+    pub fn change_signal(&self) -> ChangeSignal {
+        self.signal.clone()
+    }
+
+    /// Stopping rule that fires once a truly feasible point within `tolerance` of `target`,
+    /// or better, has been evaluated, whatever the current `ε`.
+    #[must_use]
+    pub fn feasible_target(&self, target: f64, tolerance: f64) -> FeasibleTarget {
+        FeasibleTarget {
+            best: Arc::clone(&self.best_feasible),
+            target,
+            tolerance,
+        }
+    }
+
+    /// Guarda `cost` em `slot` se for pior (`worse = true`) ou melhor que o valor atual
+    fn offer(&self, slot: &AtomicU64, cost: f64, worse: bool) {
+        let _ = slot.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            let old = f64::from_bits(bits);
+            let replace = old.is_nan() || self.direction.better(old, cost) == worse;
+            (replace && old.to_bits() != cost.to_bits()).then_some(cost.to_bits())
+        });
+    }
+}
+
+impl<P: Constrained> Decoder for EpsilonConstrained<P> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let cost = self.problem.cost(solution)?;
+        let violation = self.problem.violation(solution);
+        if violation <= 0.0 {
+            self.offer(&self.best_feasible, cost, false);
+        }
+        if violation <= self.epsilon() {
+            self.offer(&self.worst_feasible, cost, true);
+            return Ok(cost);
+        }
+        let worst = f64::from_bits(self.worst_feasible.load(Ordering::Relaxed));
+        let base = if worst.is_nan() { 0.0 } else { worst };
+        Ok(match self.direction {
+            Direction::Minimize => base + violation,
+            Direction::Maximize => base - violation,
+        })
+    }
+
+    fn end_iteration(&self, iter: usize) {
+        self.iteration.store(iter, Ordering::Relaxed);
+        let (eps, last) = (
+            self.epsilon(),
+            f64::from_bits(self.notified.load(Ordering::Relaxed)),
+        );
+        if last > 0.0 && (eps <= 0.5 * last || eps <= 0.0) {
+            self.notified.store(eps.to_bits(), Ordering::Relaxed);
+            self.signal.notify();
+        }
+    }
+
+    fn penalty_coefficient(&self) -> Option<f64> {
+        Some(self.epsilon())
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        self.problem.expected_dim()
+    }
+}
+
+/// Stops when a truly feasible point of an [`EpsilonConstrained`] decoder reached `target`,
+/// built with [`EpsilonConstrained::feasible_target`].
+///
+/// The best fitness of the run may belong to a point that is only ε-feasible, so this reads
+/// the best feasible cost kept by the decoder instead of [`Status::best_fitness`].
+#[derive(Debug, Clone)]
+pub struct FeasibleTarget {
+    best: Arc<AtomicU64>,
+    target: f64,
+    tolerance: f64,
+}
+
+impl Criterion for FeasibleTarget {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        let f = f64::from_bits(self.best.load(Ordering::Relaxed));
+        (!f.is_nan()
+            && ((f - self.target).abs() <= self.tolerance || (status.better)(f, self.target)))
+        .then_some(TerminationReason::TargetReached)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Bounds,
+        hho::{ChangeDetection, Dynamic, HhoBuilder},
+    };
+
+    /// Mínimo de `x² + y²` sobre a reta `x + y = 1` (igualdade como `|h| - 1e-4 <= 0`)
+    struct OnTheLine;
+
+    impl Constrained for OnTheLine {
+        fn cost(&self, x: &[f64]) -> Result<f64, DecoderError> {
+            Ok(x[0].mul_add(x[0], x[1] * x[1]))
+        }
+
+        fn constraints(&self, x: &[f64]) -> Vec<f64> {
+            vec![(x[0] + x[1] - 1.0).abs() - 1e-4]
+        }
+    }
+
+    #[test]
+    fn equality_constraints_are_met_as_epsilon_shrinks() {
+        let schedule = EpsilonSchedule {
+            initial: 5.0,
+            cutoff: 150,
+            exponent: 2.0,
+        };
+        let eps = EpsilonConstrained::new(OnTheLine, schedule).unwrap();
+        let report = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(30)
+            .max_iters(300)
+            .seed(2)
+            .termination(eps.feasible_target(0.5, 1e-3))
+            .dynamic(Dynamic {
+                detection: ChangeDetection::Manual(eps.change_signal()),
+                rerandomize: 0.0,
+                ..Dynamic::default()
+            })
+            .build()
+            .run(&eps)
+            .unwrap();
+        assert_eq!(report.stop_reason, Some(TerminationReason::TargetReached));
+        assert!(eps.best_feasible().unwrap() < 0.5 + 1e-3);
+        assert!(report.iters < 300);
+        // ε decresce até zero no corte
+        assert!((report.penalty_coefficients[0] - schedule.initial).abs() < 1e-12);
+        assert!(report.penalty_coefficients.windows(2).all(|w| w[1] <= w[0]));
+        assert!(schedule.at(150).abs() < f64::EPSILON);
+
+        eps.reset();
+        assert!(eps.best_feasible().is_none());
+        assert!((eps.epsilon() - 5.0).abs() < 1e-12);
+    }
+}
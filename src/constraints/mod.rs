@@ -0,0 +1,37 @@
+//! Problems with inequality constraints and the penalty schemes that turn them into decoders.
+
+pub mod epsilon;
+pub mod penalty;
+
+pub use epsilon::{EpsilonConstrained, EpsilonSchedule, FeasibleTarget};
+pub use penalty::{Penalized, Penalty, PenaltyError};
+
+use crate::core::DecoderError;
+
+/// A problem with a cost and inequality constraints `g(x) <= 0`, turned into a [`Decoder`]
+/// by [`Penalized`].
+///
+/// [`Decoder`]: crate::core::Decoder
+pub trait Constrained {
+    /// Cost of `x`, ignoring the constraints.
+    ///
+    /// # Errors
+    ///
+    /// A [`DecoderError`] if `x` cannot be evaluated, e.g. because of its dimension.
+    fn cost(&self, x: &[f64]) -> Result<f64, DecoderError>;
+
+    /// Constraint values `g(x)`; `x` is feasible when all are `<= 0`. Only called after
+    /// [`Constrained::cost`] accepted `x`.
+    fn constraints(&self, x: &[f64]) -> Vec<f64>;
+
+    /// Total violation `Σ max(0, g(x))`, zero for feasible points.
+    fn violation(&self, x: &[f64]) -> f64 {
+        self.constraints(x).into_iter().map(|g| g.max(0.0)).sum()
+    }
+
+    /// Dimension the problem needs, if fixed; forwarded by the wrappers as
+    /// [`Decoder::expected_dim`](crate::core::Decoder::expected_dim).
+    fn expected_dim(&self) -> Option<usize> {
+        None
+    }
+}
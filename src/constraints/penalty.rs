@@ -0,0 +1,382 @@
+use std::sync::{
+    Mutex, PoisonError, RwLock,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use thiserror::Error;
+
+use crate::{
+    constraints::Constrained,
+    core::{Decoder, DecoderError, Direction, FitnessScaling, Scaler},
+    hho::ChangeSignal,
+};
+
+/// How [`Penalized`] scores the infeasible points of a [`Constrained`] problem.
+///
+/// Except for [`Penalty::Death`], an infeasible point scores `cost + λ·v`, where
+/// `v = Σ max(0, g(x))` is its total violation and `λ` the coefficient of the scheme (the
+/// penalty is subtracted when maximizing). Feasible points always score their cost alone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Penalty {
+    /// Fixed `λ = weight`, finite and positive.
+    Static { weight: f64 },
+
+    /// `λ = (c·t)^alpha` at iteration `t` (counting from 1), growing so that the search may
+    /// cross infeasible regions early and is pushed back to feasibility later (Joines and
+    /// Houck use `c = 0.5`, `alpha = 2`). `c` finite and positive, `alpha` finite and
+    /// non-negative.
+    Dynamic { c: f64, alpha: f64 },
+
+    /// `λ` starts at `initial` and, after each iteration, is multiplied by `factor` when the
+    /// fraction of feasible evaluations of that iteration fell below `target`, and divided by
+    /// it when it rose above, so the search hovers around the feasibility boundary where
+    /// constrained optima usually lie. `initial` finite and positive, `target` in `[0, 1]`,
+    /// `factor` finite and greater than 1.
+    Adaptive {
+        initial: f64,
+        target: f64,
+        factor: f64,
+    },
+
+    /// Infeasible points get the worst fitness of the direction, whatever their violation.
+    /// Simple, but gives no guidance towards the feasible region.
+    Death,
+}
+
+impl Default for Penalty {
+    fn default() -> Self {
+        Self::Adaptive {
+            initial: 1.0,
+            target: 0.5,
+            factor: 1.5,
+        }
+    }
+}
+
+impl Penalty {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        match *self {
+            Self::Static { weight } if !positive(weight) => Some(format!(
+                "static penalty needs a finite positive weight, got {weight}"
+            )),
+            Self::Dynamic { c, alpha } if !(positive(c) && alpha.is_finite() && alpha >= 0.0) => {
+                Some(format!(
+                    "dynamic penalty needs a finite positive c = {c} and a finite non-negative \
+                     alpha = {alpha}"
+                ))
+            }
+            Self::Adaptive {
+                initial,
+                target,
+                factor,
+            } if !positive(initial)
+                || !(0.0..=1.0).contains(&target)
+                || !positive(factor - 1.0) =>
+            {
+                Some(format!(
+                    "adaptive penalty needs a finite positive initial = {initial}, target = \
+                     {target} in [0, 1] and a finite factor = {factor} > 1"
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+#[error("invalid penalty: {0}")]
+pub struct PenaltyError(String);
+
+impl PenaltyError {
+    pub(crate) const fn new(problem: String) -> Self {
+        Self(problem)
+    }
+}
+
+/// Decoder of a [`Constrained`] problem, scored with a [`Penalty`].
+///
+/// The dynamic and adaptive schemes follow the run through [`Decoder::end_iteration`], which
+/// only the HHO optimizer calls, and the coefficient in force at each recorded iteration ends
+/// up in [`crate::core::Report::penalty_coefficients`] for tuning. The scheme state lives in
+/// the decoder: call [`Penalized::reset`] before reusing it for another run. It is not saved
+/// in checkpoints either, so a resumed run starts the schedule over.
+///
+/// With [`Penalized::with_scaling`] the cost is rescaled before the penalty is added, so the
+/// same coefficients work whatever the magnitude of the cost.
+#[derive(Debug)]
+pub struct Penalized<P> {
+    problem: P,
+    penalty: Penalty,
+    direction: Direction,
+    /// Bits de `λ` (esquema adaptativo)
+    coefficient: AtomicU64,
+    /// Iteração corrente, a partir de 1 (esquema dinâmico)
+    iteration: AtomicUsize,
+    /// Avaliações e avaliações viáveis da iteração corrente (esquema adaptativo)
+    evaluated: AtomicUsize,
+    feasible: AtomicUsize,
+    scaling: FitnessScaling,
+    /// Escala do custo; `None` enquanto a amostra de referência está sendo coletada
+    scaler: RwLock<Option<Scaler>>,
+    /// Custos avaliados antes do fim da primeira iteração
+    sample: Mutex<Vec<f64>>,
+    signal: ChangeSignal,
+}
+
+impl<P: Constrained> Penalized<P> {
+    /// Minimizes the cost of `problem`, scoring infeasible points with `penalty`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PenaltyError`] if the parameters of `penalty` are out of range (see
+    /// [`Penalty`]).
+    pub fn new(problem: P, penalty: Penalty) -> Result<Self, PenaltyError> {
+        if let Some(problem) = penalty.problem() {
+            return Err(PenaltyError(problem));
+        }
+        let initial = match penalty {
+            Penalty::Adaptive { initial, .. } => initial,
+            _ => 0.0,
+        };
+        Ok(Self {
+            problem,
+            penalty,
+            direction: Direction::Minimize,
+            coefficient: AtomicU64::new(initial.to_bits()),
+            iteration: AtomicUsize::new(1),
+            evaluated: AtomicUsize::new(0),
+            feasible: AtomicUsize::new(0),
+            scaling: FitnessScaling::Raw,
+            scaler: RwLock::new(Some(Scaler::default())),
+            sample: Mutex::new(Vec::new()),
+            signal: ChangeSignal::new(),
+        })
+    }
+
+    /// Rescales the cost with `scaling` before adding the penalty, and scores feasible points
+    /// with the scaled cost too.
+    ///
+    /// [`FitnessScaling::Log`] applies from the start. [`FitnessScaling::ZScore`] and
+    /// [`FitnessScaling::Rank`] are fitted to the costs evaluated up to the end of the first
+    /// iteration and then kept for the whole run, so fitness values stay comparable; until
+    /// then costs are used raw. The [`Penalized::change_signal`] fires when the fitted
+    /// scaling takes over, so the population can be re-scored.
+    #[must_use]
+    pub fn with_scaling(mut self, scaling: FitnessScaling) -> Self {
+        self.scaling = scaling;
+        *self
+            .scaler
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = Self::fixed(scaling);
+        self
+    }
+
+    /// Escala que não depende de amostra, se houver
+    fn fixed(scaling: FitnessScaling) -> Option<Scaler> {
+        matches!(scaling, FitnessScaling::Raw | FitnessScaling::Log)
+            .then(|| Scaler::fit(scaling, &[]))
+    }
+
+    /// Signal notified when a fitted cost scaling takes over (see [`Penalized::with_scaling`]),
+    /// for [`crate::hho::ChangeDetection::Manual`].
+    #[must_use]
+    pub fn change_signal(&self) -> ChangeSignal {
+        self.signal.clone()
+    }
+
+    /// Custo na escala configurada, guardando-o na amostra enquanto ela é coletada
+    fn scaled(&self, cost: f64) -> f64 {
+        if let Some(s) = &*self.scaler.read().unwrap_or_else(PoisonError::into_inner) {
+            return s.apply(cost);
+        }
+        self.sample
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(cost);
+        cost
+    }
+
+    /// Optimizes the cost in `direction` instead, subtracting the penalty when maximizing.
+    #[must_use]
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    #[must_use]
+    pub const fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Restarts the schedule: iteration 1 and, for [`Penalty::Adaptive`], the initial
+    /// coefficient.
+    pub fn reset(&self) {
+        if let Penalty::Adaptive { initial, .. } = self.penalty {
+            self.coefficient.store(initial.to_bits(), Ordering::Relaxed);
+        }
+        self.iteration.store(1, Ordering::Relaxed);
+        self.evaluated.store(0, Ordering::Relaxed);
+        self.feasible.store(0, Ordering::Relaxed);
+        *self.scaler.write().unwrap_or_else(PoisonError::into_inner) = Self::fixed(self.scaling);
+        self.sample
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Coefficient `λ` in force, `None` for [`Penalty::Death`].
+    #[must_use]
+    pub fn coefficient(&self) -> Option<f64> {
+        match self.penalty {
+            Penalty::Static { weight } => Some(weight),
+            Penalty::Dynamic { c, alpha } => {
+                #[allow(clippy::cast_precision_loss)]
+                let t = self.iteration.load(Ordering::Relaxed) as f64;
+                Some((c * t).powf(alpha))
+            }
+            Penalty::Adaptive { .. } => {
+                Some(f64::from_bits(self.coefficient.load(Ordering::Relaxed)))
+            }
+            Penalty::Death => None,
+        }
+    }
+}
+
+impl<P: Constrained> Decoder for Penalized<P> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let cost = self.scaled(self.problem.cost(solution)?);
+        let violation = self.problem.violation(solution);
+        self.evaluated.fetch_add(1, Ordering::Relaxed);
+        if violation <= 0.0 {
+            self.feasible.fetch_add(1, Ordering::Relaxed);
+            return Ok(cost);
+        }
+        let Some(lambda) = self.coefficient() else {
+            return Ok(self.direction.worst());
+        };
+        Ok(match self.direction {
+            Direction::Minimize => lambda.mul_add(violation, cost),
+            Direction::Maximize => (-lambda).mul_add(violation, cost),
+        })
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        self.problem.expected_dim()
+    }
+
+    fn end_iteration(&self, iter: usize) {
+        self.iteration.store(iter + 1, Ordering::Relaxed);
+        let evaluated = self.evaluated.swap(0, Ordering::Relaxed);
+        let feasible = self.feasible.swap(0, Ordering::Relaxed);
+        let mut scaler = self.scaler.write().unwrap_or_else(PoisonError::into_inner);
+        if scaler.is_none() {
+            let sample =
+                std::mem::take(&mut *self.sample.lock().unwrap_or_else(PoisonError::into_inner));
+            *scaler = Some(Scaler::fit(self.scaling, &sample));
+            self.signal.notify();
+        }
+        drop(scaler);
+        if let Penalty::Adaptive { target, factor, .. } = self.penalty
+            && evaluated > 0
+        {
+            #[allow(clippy::cast_precision_loss)]
+            let ratio = feasible as f64 / evaluated as f64;
+            let lambda = f64::from_bits(self.coefficient.load(Ordering::Relaxed));
+            let lambda = if ratio < target {
+                lambda * factor
+            } else if ratio > target {
+                lambda / factor
+            } else {
+                lambda
+            };
+            self.coefficient.store(lambda.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    fn penalty_coefficient(&self) -> Option<f64> {
+        self.coefficient()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Engineering, hho::HhoBuilder};
+
+    #[test]
+    fn every_scheme_finds_a_feasible_spring_and_reports_its_coefficients() {
+        let problem = Engineering::Spring;
+        let schemes = [
+            Penalty::Static { weight: 1e6 },
+            Penalty::Dynamic { c: 0.5, alpha: 2.0 },
+            Penalty::default(),
+            Penalty::Death,
+        ];
+        for penalty in schemes {
+            let decoder = Penalized::new(problem, penalty).unwrap();
+            let report = HhoBuilder::new(problem.bounds().unwrap())
+                .pop_size(30)
+                .max_iters(200)
+                .seed(4)
+                .build()
+                .run(&decoder)
+                .unwrap();
+            let x = &report.best_position;
+            if penalty == Penalty::Death && report.best_fitness.is_infinite() {
+                // Sem orientação, a pena de morte pode não achar nenhum ponto viável
+                assert!(report.penalty_coefficients.is_empty());
+                continue;
+            }
+            assert!(problem.violation(x) <= 0.0, "{penalty:?}");
+            assert!((report.best_fitness - problem.cost(x)).abs() < 1e-12);
+            assert!(
+                report.best_fitness < 2.0 * problem.best_known(),
+                "{penalty:?}"
+            );
+            match penalty {
+                Penalty::Death => assert!(report.penalty_coefficients.is_empty()),
+                _ => assert_eq!(report.penalty_coefficients.len(), report.curve_iters.len()),
+            }
+            if let Penalty::Dynamic { .. } = penalty {
+                assert!((report.penalty_coefficients[1] - 1.0).abs() < 1e-12);
+            }
+        }
+
+        // O coeficiente adaptativo sobe enquanto nada é viável e desce quando tudo é
+        let decoder = Penalized::new(problem, Penalty::default()).unwrap();
+        let infeasible = [0.05, 1.3, 15.0];
+        assert!(decoder.decode(&infeasible).unwrap() > problem.cost(&infeasible));
+        decoder.end_iteration(1);
+        assert!((decoder.coefficient().unwrap() - 1.5).abs() < 1e-12);
+        decoder.decode(&[0.06, 0.5, 10.0]).unwrap();
+        decoder.end_iteration(2);
+        assert!((decoder.coefficient().unwrap() - 1.0).abs() < 1e-12);
+        decoder.reset();
+        assert!((decoder.coefficient().unwrap() - 1.0).abs() < 1e-12);
+
+        // A escala ajustada entra em vigor no fim da primeira iteração e avisa a troca
+        let scaled = Penalized::new(problem, Penalty::Static { weight: 1.0 })
+            .unwrap()
+            .with_scaling(FitnessScaling::ZScore);
+        // Molas viáveis
+        let designs = [[0.06, 0.5, 10.0], [0.07, 0.6, 8.0], [0.065, 0.55, 9.0]];
+        for x in &designs {
+            assert!((scaled.decode(x).unwrap() - problem.cost(x)).abs() < 1e-12);
+        }
+        scaled.end_iteration(1);
+        assert!(scaled.change_signal().take());
+        let z: f64 = designs.iter().map(|x| scaled.decode(x).unwrap()).sum();
+        assert!(z.abs() < 1e-9);
+
+        let bad = Penalty::Adaptive {
+            initial: 1.0,
+            target: 0.5,
+            factor: 1.0,
+        };
+        assert!(Penalized::new(problem, bad).is_err());
+    }
+}
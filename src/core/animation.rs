@@ -0,0 +1,543 @@
+//! Animated GIF of the search path of a run (feature `plotting`).
+//!
+//! Frames are rasterized and LZW-compressed one at a time and streamed to the file, so the
+//! memory used is one frame whatever the length of the run.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::core::{PlotError, Report};
+
+/// Plane the positions are projected on, see [`Animation::projection`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Projection {
+    /// The first two coordinates; a one-dimensional problem is drawn on a line.
+    #[default]
+    FirstTwo,
+    /// The two principal components of the recorded positions.
+    Pca,
+}
+
+/// Settings of [`Report::animate_trajectory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation {
+    pub width: u16,
+    pub height: u16,
+    /// Delay between frames, in hundredths of a second.
+    pub delay: u16,
+    /// Frames written at most: a longer trajectory is sampled evenly, keeping its last point.
+    pub max_frames: usize,
+    pub projection: Projection,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            width: 480,
+            height: 480,
+            delay: 8,
+            max_frames: 200,
+            projection: Projection::FirstTwo,
+        }
+    }
+}
+
+impl Animation {
+    #[must_use]
+    pub const fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    #[must_use]
+    pub const fn delay(mut self, hundredths: u16) -> Self {
+        self.delay = hundredths;
+        self
+    }
+
+    #[must_use]
+    pub const fn max_frames(mut self, frames: usize) -> Self {
+        self.max_frames = frames;
+        self
+    }
+
+    #[must_use]
+    pub const fn projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+}
+
+/// Paleta fixa: fundo, eixos, rastro, trajeto, posição atual e melhor final
+const PALETTE: [[u8; 3]; 8] = [
+    [255, 255, 255],
+    [220, 220, 220],
+    [160, 160, 160],
+    [70, 110, 200],
+    [210, 40, 40],
+    [30, 160, 60],
+    [0, 0, 0],
+    [0, 0, 0],
+];
+const AXES: u8 = 1;
+const TRAIL: u8 = 2;
+const PATH: u8 = 3;
+const CURRENT: u8 = 4;
+const BEST: u8 = 5;
+
+/// Bits por índice da paleta, o mínimo do LZW do GIF para 8 cores
+const MIN_CODE_SIZE: u8 = 3;
+
+impl Report {
+    /// Writes the search path of [`Report::trajectory`] as an animated GIF to `path`.
+    ///
+    /// Frame `k` shows the rabbit position recorded `k`-th in red, joined to the earlier ones
+    /// by the path travelled, with the final best position in green. The trajectory holds
+    /// the rabbit only, so the animation follows the best solution rather than the whole
+    /// population; enable it with [`crate::hho::HhoBuilder::record_trajectory`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PlotError`] if `path` is not a `.gif` file, if the trajectory is empty, if
+    /// the frame has a zero side or if the file cannot be written.
+    pub fn animate_trajectory(
+        &self,
+        path: impl AsRef<Path>,
+        animation: &Animation,
+    ) -> Result<(), PlotError> {
+        let path = path.as_ref();
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if ext != "gif" {
+            return Err(PlotError::UnsupportedFormat(ext));
+        }
+        if self.trajectory.is_empty() {
+            return Err(PlotError::EmptyTrajectory);
+        }
+        if animation.width == 0 || animation.height == 0 {
+            return Err(PlotError::Draw("frames need a positive size".to_owned()));
+        }
+
+        let axes = match animation.projection {
+            Projection::FirstTwo => None,
+            Projection::Pca => Some(principal_axes(&self.trajectory)),
+        };
+        let project = |x: &[f64]| match &axes {
+            None => (
+                x.first().copied().unwrap_or(0.0),
+                x.get(1).copied().unwrap_or(0.0),
+            ),
+            Some((mean, u, v)) => {
+                let dot = |w: &[f64]| -> f64 {
+                    x.iter()
+                        .zip(mean)
+                        .zip(w)
+                        .map(|((a, m), b)| (a - m) * b)
+                        .sum()
+                };
+                (dot(u), dot(v))
+            }
+        };
+        let points: Vec<(f64, f64)> = self.trajectory.iter().map(|x| project(x)).collect();
+        let best = (!self.best_position.is_empty()).then(|| project(&self.best_position));
+        let canvas = Canvas::fit(animation, points.iter().chain(&best));
+
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        write_header(&mut out, animation.width, animation.height)?;
+        let mut pixels = vec![0u8; usize::from(animation.width) * usize::from(animation.height)];
+        for k in frames(points.len(), animation.max_frames) {
+            pixels.fill(0);
+            canvas.draw_axes(&mut pixels);
+            for w in points[..=k].windows(2) {
+                canvas.line(&mut pixels, w[0], w[1], PATH);
+            }
+            for &p in &points[..k] {
+                canvas.dot(&mut pixels, p, 1, TRAIL);
+            }
+            if let Some(b) = best {
+                canvas.dot(&mut pixels, b, 2, BEST);
+            }
+            canvas.dot(&mut pixels, points[k], 3, CURRENT);
+            write_frame(&mut out, animation, &pixels)?;
+        }
+        out.write_all(&[0x3B])?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Índices dos pontos mostrados: no máximo `max` igualmente espaçados, sempre com o último
+fn frames(n: usize, max: usize) -> impl Iterator<Item = usize> {
+    let stride = n.div_ceil(max.max(1));
+    let last = n - 1;
+    (0..n).filter(move |k| (last - k).is_multiple_of(stride))
+}
+
+/// Média e os dois primeiros componentes principais dos pontos, por iteração da potência
+/// sobre `Xᵀ(X v)` sem formar a covariância
+fn principal_axes(points: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let dim = points[0].len();
+    #[allow(clippy::cast_precision_loss)]
+    let n = points.len() as f64;
+    let mut mean = vec![0.0; dim];
+    for x in points {
+        for (m, v) in mean.iter_mut().zip(x) {
+            *m += v / n;
+        }
+    }
+    let component = |deflate: Option<&[f64]>| {
+        #[allow(clippy::cast_precision_loss)]
+        let mut v: Vec<f64> = (0..dim).map(|j| (j as f64).mul_add(0.1, 1.0)).collect();
+        for _ in 0..100 {
+            let mut next = vec![0.0; dim];
+            for x in points {
+                let s: f64 = x
+                    .iter()
+                    .zip(&mean)
+                    .zip(&v)
+                    .map(|((a, m), b)| (a - m) * b)
+                    .sum();
+                for ((w, a), m) in next.iter_mut().zip(x).zip(&mean) {
+                    *w = s.mul_add(a - m, *w);
+                }
+            }
+            if let Some(u) = deflate {
+                let s: f64 = next.iter().zip(u).map(|(a, b)| a * b).sum();
+                for (w, b) in next.iter_mut().zip(u) {
+                    *w = (-s).mul_add(*b, *w);
+                }
+            }
+            let norm = next.iter().map(|w| w * w).sum::<f64>().sqrt();
+            if norm <= f64::MIN_POSITIVE {
+                return vec![0.0; dim];
+            }
+            v = next.into_iter().map(|w| w / norm).collect();
+        }
+        v
+    };
+    let u = component(None);
+    let v = component(Some(&u));
+    (mean, u, v)
+}
+
+/// Mapeia o plano projetado nos pixels de um quadro
+struct Canvas {
+    width: usize,
+    height: usize,
+    x: (f64, f64),
+    y: (f64, f64),
+}
+
+impl Canvas {
+    /// Enquadra os pontos com uma margem de 5%
+    fn fit<'a>(animation: &Animation, points: impl Iterator<Item = &'a (f64, f64)>) -> Self {
+        let fold = |r: (f64, f64), v: f64| {
+            if v.is_finite() {
+                (r.0.min(v), r.1.max(v))
+            } else {
+                r
+            }
+        };
+        let empty = (f64::INFINITY, f64::NEG_INFINITY);
+        let (x, y) = points.fold((empty, empty), |(x, y), p| (fold(x, p.0), fold(y, p.1)));
+        let pad = |(lo, hi): (f64, f64)| {
+            if lo > hi {
+                return (-1.0, 1.0);
+            }
+            let pad = ((hi - lo) * 0.05).max(1e-9);
+            (lo - pad, hi + pad)
+        };
+        Self {
+            width: usize::from(animation.width),
+            height: usize::from(animation.height),
+            x: pad(x),
+            y: pad(y),
+        }
+    }
+
+    /// Pixel de um ponto, fora do quadro para valores não finitos
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn pixel(&self, (x, y): (f64, f64)) -> (i64, i64) {
+        let px = (x - self.x.0) / (self.x.1 - self.x.0) * (self.width - 1) as f64;
+        let py = (self.y.1 - y) / (self.y.1 - self.y.0) * (self.height - 1) as f64;
+        if px.is_finite() && py.is_finite() {
+            (px.round() as i64, py.round() as i64)
+        } else {
+            (-1, -1)
+        }
+    }
+
+    #[allow(
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_truncation
+    )]
+    fn set(&self, pixels: &mut [u8], (px, py): (i64, i64), color: u8) {
+        if (0..self.width as i64).contains(&px) && (0..self.height as i64).contains(&py) {
+            pixels[py as usize * self.width + px as usize] = color;
+        }
+    }
+
+    /// Eixos do plano nas coordenadas zero, quando visíveis
+    fn draw_axes(&self, pixels: &mut [u8]) {
+        let (ox, oy) = self.pixel((0.0, 0.0));
+        #[allow(clippy::cast_possible_wrap)]
+        let (w, h) = (self.width as i64, self.height as i64);
+        for i in 0..w {
+            self.set(pixels, (i, oy), AXES);
+        }
+        for j in 0..h {
+            self.set(pixels, (ox, j), AXES);
+        }
+    }
+
+    fn dot(&self, pixels: &mut [u8], p: (f64, f64), radius: i64, color: u8) {
+        let (cx, cy) = self.pixel(p);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if dx * dx + dy * dy <= radius * radius {
+                    self.set(pixels, (cx + dx, cy + dy), color);
+                }
+            }
+        }
+    }
+
+    /// Segmento de Bresenham entre dois pontos
+    fn line(&self, pixels: &mut [u8], a: (f64, f64), b: (f64, f64), color: u8) {
+        let ((mut x0, mut y0), (x1, y1)) = (self.pixel(a), self.pixel(b));
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let mut err = dx + dy;
+        loop {
+            self.set(pixels, (x0, y0), color);
+            if x0 == x1 && y0 == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+}
+
+/// Cabeçalho `GIF89a` com a paleta global e repetição infinita
+fn write_header(out: &mut impl Write, width: u16, height: u16) -> std::io::Result<()> {
+    out.write_all(b"GIF89a")?;
+    out.write_all(&width.to_le_bytes())?;
+    out.write_all(&height.to_le_bytes())?;
+    // Paleta global de 2^(2+1) cores, resolução de 8 bits
+    out.write_all(&[0xF2, 0, 0])?;
+    for rgb in PALETTE {
+        out.write_all(&rgb)?;
+    }
+    out.write_all(&[0x21, 0xFF, 0x0B])?;
+    out.write_all(b"NETSCAPE2.0")?;
+    out.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])
+}
+
+fn write_frame(out: &mut impl Write, animation: &Animation, pixels: &[u8]) -> std::io::Result<()> {
+    out.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+    out.write_all(&animation.delay.to_le_bytes())?;
+    out.write_all(&[0x00, 0x00, 0x2C, 0, 0, 0, 0])?;
+    out.write_all(&animation.width.to_le_bytes())?;
+    out.write_all(&animation.height.to_le_bytes())?;
+    out.write_all(&[0x00, MIN_CODE_SIZE])?;
+    for block in lzw(pixels, MIN_CODE_SIZE).chunks(255) {
+        #[allow(clippy::cast_possible_truncation)]
+        out.write_all(&[block.len() as u8])?;
+        out.write_all(block)?;
+    }
+    out.write_all(&[0x00])
+}
+
+/// Compressão LZW de tamanho de código variável, como o GIF a espera
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut bits = Bits::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut size = u32::from(min_code_size) + 1;
+    let mut next = end + 1;
+    bits.put(clear, size);
+    let Some((&first, rest)) = indices.split_first() else {
+        bits.put(end, size);
+        return bits.finish();
+    };
+    let mut code = u16::from(first);
+    for &k in rest {
+        if let Some(&c) = table.get(&(code, k)) {
+            code = c;
+            continue;
+        }
+        bits.put(code, size);
+        if next < 4096 {
+            table.insert((code, k), next);
+            if u32::from(next) == 1 << size && size < 12 {
+                size += 1;
+            }
+            next += 1;
+        } else {
+            bits.put(clear, size);
+            table.clear();
+            size = u32::from(min_code_size) + 1;
+            next = end + 1;
+        }
+        code = u16::from(k);
+    }
+    bits.put(code, size);
+    bits.put(end, size);
+    bits.finish()
+}
+
+/// Escritor de códigos com o bit menos significativo primeiro
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u32,
+}
+
+impl Bits {
+    fn put(&mut self, code: u16, size: u32) {
+        self.acc |= u32::from(code) << self.len;
+        self.len += size;
+        while self.len >= 8 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Descompressor LZW de referência, lendo códigos no sentido do GIF
+    fn unlzw(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut out = Vec::new();
+        let (mut pos, mut size) = (0usize, usize::from(min_code_size) + 1);
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut prev: Option<Vec<u8>> = None;
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..=u8::MAX).take(clear).map(|i| vec![i]).collect();
+            table.extend([Vec::new(), Vec::new()]);
+        };
+        reset(&mut table);
+        loop {
+            let code = (0..size).fold(0usize, |c, b| {
+                let bit = (data[(pos + b) / 8] >> ((pos + b) % 8)) & 1;
+                c | usize::from(bit) << b
+            });
+            pos += size;
+            if code == clear {
+                reset(&mut table);
+                size = usize::from(min_code_size) + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (&prev, table.get(code)) {
+                (_, Some(e)) => e.clone(),
+                (Some(p), None) => [p.clone(), vec![p[0]]].concat(),
+                (None, None) => panic!("code {code} before any entry"),
+            };
+            if let Some(p) = prev.take()
+                && table.len() < 4096
+            {
+                table.push([p, vec![entry[0]]].concat());
+            }
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+            out.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn gif_frames_decode_to_the_drawn_pixels() {
+        // Sequência longa e repetitiva o bastante para encher a tabela e reiniciá-la
+        let pixels: Vec<u8> = (0..60_000u32)
+            .map(|i| ((i * i / 7 + i / 300) % 8) as u8)
+            .collect();
+        assert_eq!(unlzw(&lzw(&pixels, 3), 3), pixels);
+        assert_eq!(unlzw(&lzw(&[], 3), 3), Vec::<u8>::new());
+
+        let report = Report {
+            trajectory: (0..50)
+                .map(|i| vec![f64::from(i).sin(), f64::from(i) * 0.1, 1.0])
+                .collect(),
+            best_position: vec![0.0, 5.0, 1.0],
+            ..Report::empty()
+        };
+        let path = std::env::temp_dir().join(format!("kambo-path-{}.gif", std::process::id()));
+        for projection in [Projection::FirstTwo, Projection::Pca] {
+            let animation = Animation::default()
+                .size(64, 48)
+                .max_frames(20)
+                .projection(projection);
+            report.animate_trajectory(&path, &animation).unwrap();
+            let gif = fs::read(&path).unwrap();
+            assert!(gif.starts_with(b"GIF89a"));
+            // Percorre os blocos: cabeçalho, paleta e extensão de repetição ocupam 56 bytes
+            let (mut pos, mut frames) = (56, 0);
+            while gif[pos] != 0x3B {
+                if gif[pos] == 0x21 {
+                    pos += 8;
+                    continue;
+                }
+                assert_eq!(gif[pos], 0x2C);
+                pos += 11;
+                let mut data = Vec::new();
+                while gif[pos] != 0 {
+                    let len = usize::from(gif[pos]);
+                    data.extend(&gif[pos + 1..=pos + len]);
+                    pos += len + 1;
+                }
+                pos += 1;
+                let pixels = unlzw(&data, MIN_CODE_SIZE);
+                assert_eq!(pixels.len(), 64 * 48);
+                assert!(pixels.contains(&CURRENT) && pixels.contains(&BEST));
+                frames += 1;
+            }
+            // 50 pontos em passos de 3, terminando no último
+            assert_eq!(frames, 17);
+        }
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            Report::empty().animate_trajectory(&path, &Animation::default()),
+            Err(PlotError::EmptyTrajectory)
+        ));
+        assert!(matches!(
+            report.animate_trajectory("path.png", &Animation::default()),
+            Err(PlotError::UnsupportedFormat(_))
+        ));
+    }
+}
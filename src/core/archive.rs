@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use crate::core::{Distance, Norm, Objective, report::Solution};
+
+/// Bounded archive of the best distinct solutions ever evaluated ("hall of fame").
+///
+/// Two solutions closer than `min_distance` (Euclidean unless set with
+/// [`HallOfFame::with_distance`]) are considered the same; only the better one is kept.
+/// Entries are stored best first. On exact fitness ties the earlier offer wins: it keeps its
+/// place ahead of later equal entries and is only displaced by a strictly better twin, so
+/// with the main loop offering points in hawk order the archive is the same in serial and
+/// parallel runs.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HallOfFame {
+    capacity: usize,
+    min_distance: f64,
+    entries: Vec<Solution>,
+    /// Não é salva; quem retoma a execução a restaura
+    #[cfg_attr(feature = "serde", serde(skip, default = "euclidean"))]
+    distance: Arc<dyn Distance>,
+}
+
+fn euclidean() -> Arc<dyn Distance> {
+    Arc::new(Norm::Euclidean)
+}
+
+impl HallOfFame {
+    #[must_use]
+    pub fn new(capacity: usize, min_distance: f64) -> Self {
+        Self {
+            capacity,
+            min_distance,
+            entries: Vec::new(),
+            distance: euclidean(),
+        }
+    }
+
+    /// Measures `min_distance` with `distance` instead of the Euclidean distance.
+    #[must_use]
+    pub fn with_distance(mut self, distance: Arc<dyn Distance>) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn entries(&self) -> &[Solution] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn into_entries(self) -> Vec<Solution> {
+        self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Offers an evaluated point to the archive. Returns `true` if it was stored.
+    pub fn offer<O: Objective>(&mut self, position: &[f64], fitness: f64) -> bool {
+        if self.capacity == 0 || fitness.is_nan() {
+            return false;
+        }
+        // Caminho rápido: arquivo cheio e candidato não supera o pior
+        if self.entries.len() == self.capacity
+            && !O::better(fitness, self.entries[self.capacity - 1].fitness)
+        {
+            return false;
+        }
+
+        let twin = self
+            .entries
+            .iter()
+            .position(|e| self.distance.distance(&e.position, position) < self.min_distance);
+        if let Some(k) = twin {
+            if !O::better(fitness, self.entries[k].fitness) {
+                return false;
+            }
+            self.entries.remove(k);
+        }
+
+        let at = self
+            .entries
+            .iter()
+            .position(|e| O::better(fitness, e.fitness))
+            .unwrap_or(self.entries.len());
+        self.entries.insert(
+            at,
+            Solution {
+                position: position.to_vec(),
+                fitness,
+            },
+        );
+        self.entries.truncate(self.capacity);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    #[test]
+    fn keeps_best_distinct_entries() {
+        let mut hof = HallOfFame::new(2, 0.5);
+        assert!(hof.offer::<Minimization>(&[0.0], 3.0));
+        assert!(hof.offer::<Minimization>(&[5.0], 2.0));
+        // Vizinho de [0.0] e melhor: substitui
+        assert!(hof.offer::<Minimization>(&[0.1], 1.0));
+        // Vizinho de [5.0] mas pior: rejeitado
+        assert!(!hof.offer::<Minimization>(&[5.1], 2.5));
+        // Pior que o último com o arquivo cheio
+        assert!(!hof.offer::<Minimization>(&[9.0], 4.0));
+
+        let f: Vec<f64> = hof.entries().iter().map(|e| e.fitness).collect();
+        assert_eq!(f, vec![1.0, 2.0]);
+        assert!((hof.entries()[0].position[0] - 0.1).abs() < f64::EPSILON);
+    }
+}
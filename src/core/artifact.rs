@@ -0,0 +1,348 @@
+//! Best solution of a run as a standalone file (feature `serde`).
+//!
+//! The artifact holds what is needed to reuse or check a solution without the full report:
+//! the best position and fitness, the bounds, the seed and the configuration hash. It is
+//! written as JSON or as TOML; [`Report::load_solution`] reads both back into a report that
+//! [`crate::init::WarmStart::from_report`] accepts.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::{Direction, Report};
+
+#[derive(Debug, Error)]
+pub enum ArtifactError {
+    #[error("Solution I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Invalid JSON solution: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid TOML solution at line {line}: {message}")]
+    Toml { line: usize, message: String },
+
+    #[error("Invalid solution: {0}")]
+    Invalid(String),
+}
+
+/// File format of a [`SolutionArtifact`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SolutionFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl SolutionFormat {
+    /// TOML for a `.toml` extension, JSON otherwise.
+    #[must_use]
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Best solution of a run, see [`Report::save_solution`].
+///
+/// `lower` and `upper` are empty, and `seed` and `config_hash` missing, when the report did
+/// not record them. In TOML the seed and the hash are written as strings, TOML integers being
+/// signed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolutionArtifact {
+    pub direction: Direction,
+    pub fitness: f64,
+    pub position: Vec<f64>,
+    #[serde(default)]
+    pub lower: Vec<f64>,
+    #[serde(default)]
+    pub upper: Vec<f64>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// See [`crate::hho::Hho::config_hash`].
+    #[serde(default)]
+    pub config_hash: Option<u64>,
+}
+
+impl SolutionArtifact {
+    /// Artifact of the best solution of `report`.
+    #[must_use]
+    pub fn from_report(report: &Report) -> Self {
+        Self {
+            direction: report.direction,
+            fitness: report.best_fitness,
+            position: report.best_position.clone(),
+            lower: report.bounds.iter().map(|b| b.0).collect(),
+            upper: report.bounds.iter().map(|b| b.1).collect(),
+            seed: report.seed,
+            config_hash: report.config_hash,
+        }
+    }
+
+    /// Report holding only this solution, e.g. to warm start a new run.
+    #[must_use]
+    pub fn into_report(self) -> Report {
+        Report {
+            direction: self.direction,
+            best_fitness: self.fitness,
+            best_position: self.position,
+            bounds: self.lower.into_iter().zip(self.upper).collect(),
+            seed: self.seed,
+            config_hash: self.config_hash,
+            ..Report::empty()
+        }
+    }
+
+    /// The artifact as TOML.
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let list = |values: &[f64]| {
+            let items: Vec<String> = values.iter().map(|&x| toml_float(x)).collect();
+            format!("[{}]", items.join(", "))
+        };
+        let mut out = String::new();
+        // Escrever numa String não falha
+        let _ = writeln!(out, "direction = \"{:?}\"", self.direction);
+        let _ = writeln!(out, "fitness = {}", toml_float(self.fitness));
+        let _ = writeln!(out, "position = {}", list(&self.position));
+        if !self.lower.is_empty() {
+            let _ = writeln!(out, "lower = {}", list(&self.lower));
+            let _ = writeln!(out, "upper = {}", list(&self.upper));
+        }
+        if let Some(seed) = self.seed {
+            let _ = writeln!(out, "seed = \"{seed}\"");
+        }
+        if let Some(hash) = self.config_hash {
+            let _ = writeln!(out, "config_hash = \"{hash}\"");
+        }
+        out
+    }
+
+    /// Reads the TOML written by [`SolutionArtifact::to_toml`]: `key = value` lines with
+    /// strings, numbers and one-line arrays of numbers. Comments and unknown keys are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArtifactError::Toml`] on a line that cannot be read and
+    /// [`ArtifactError::Invalid`] if `direction`, `fitness` or `position` is missing.
+    pub fn from_toml(text: &str) -> Result<Self, ArtifactError> {
+        let mut direction = None;
+        let mut fitness = None;
+        let mut position = None;
+        let (mut lower, mut upper) = (Vec::new(), Vec::new());
+        let (mut seed, mut config_hash) = (None, None);
+        for (k, raw) in text.lines().enumerate() {
+            let fail = |message: String| ArtifactError::Toml {
+                line: k + 1,
+                message,
+            };
+            let line = raw.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| fail(format!("expected `key = value`, found `{line}`")))?;
+            let value = value.trim();
+            match key.trim() {
+                "direction" => {
+                    direction = Some(match unquote(value) {
+                        Some("Minimize") => Direction::Minimize,
+                        Some("Maximize") => Direction::Maximize,
+                        _ => return Err(fail(format!("unknown direction {value}"))),
+                    });
+                }
+                "fitness" => fitness = Some(parse_float(value).map_err(fail)?),
+                "position" => position = Some(parse_list(value).map_err(fail)?),
+                "lower" => lower = parse_list(value).map_err(fail)?,
+                "upper" => upper = parse_list(value).map_err(fail)?,
+                "seed" => seed = Some(parse_u64(value).map_err(fail)?),
+                "config_hash" => config_hash = Some(parse_u64(value).map_err(fail)?),
+                _ => {}
+            }
+        }
+        let missing = |key: &str| ArtifactError::Invalid(format!("missing `{key}`"));
+        Ok(Self {
+            direction: direction.ok_or_else(|| missing("direction"))?,
+            fitness: fitness.ok_or_else(|| missing("fitness"))?,
+            position: position.ok_or_else(|| missing("position"))?,
+            lower,
+            upper,
+            seed,
+            config_hash,
+        })
+    }
+
+    /// Writes the artifact to `path` in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(
+        &self,
+        path: impl AsRef<Path>,
+        format: SolutionFormat,
+    ) -> Result<(), ArtifactError> {
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        match format {
+            SolutionFormat::Json => serde_json::to_writer_pretty(&mut out, self)?,
+            SolutionFormat::Toml => out.write_all(self.to_toml().as_bytes())?,
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Reads an artifact from `path`, JSON if the file starts with `{` and TOML otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, cannot be parsed, or its position, lower
+    /// and upper bounds disagree in dimension.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ArtifactError> {
+        let text = fs::read_to_string(path)?;
+        let artifact = if text.trim_start().starts_with('{') {
+            serde_json::from_str(&text)?
+        } else {
+            Self::from_toml(&text)?
+        };
+        artifact.check()?;
+        Ok(artifact)
+    }
+
+    /// Confere as dimensões da posição e dos limites
+    fn check(&self) -> Result<(), ArtifactError> {
+        let dim = self.position.len();
+        if dim == 0 {
+            return Err(ArtifactError::Invalid("empty position".to_owned()));
+        }
+        let bounded = self.lower.len() == dim && self.upper.len() == dim;
+        let unbounded = self.lower.is_empty() && self.upper.is_empty();
+        if !(bounded || unbounded) {
+            return Err(ArtifactError::Invalid(format!(
+                "position of dimension {dim} with {} lower and {} upper bounds",
+                self.lower.len(),
+                self.upper.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    /// Writes the best solution of this report to `path` as a [`SolutionArtifact`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_solution(
+        &self,
+        path: impl AsRef<Path>,
+        format: SolutionFormat,
+    ) -> Result<(), ArtifactError> {
+        SolutionArtifact::from_report(self).write(path, format)
+    }
+
+    /// Reads a solution saved with [`Report::save_solution`], in either format, as a report
+    /// holding only that solution, ready for [`crate::hho::HhoBuilder::warm_start`].
+    ///
+    /// # Errors
+    ///
+    /// See [`SolutionArtifact::read`].
+    pub fn load_solution(path: impl AsRef<Path>) -> Result<Self, ArtifactError> {
+        SolutionArtifact::read(path).map(SolutionArtifact::into_report)
+    }
+}
+
+/// Número em TOML, que escreve `nan` e `inf` em minúsculas
+fn toml_float(x: f64) -> String {
+    if x.is_nan() {
+        "nan".to_owned()
+    } else if x.is_infinite() {
+        if x > 0.0 { "inf" } else { "-inf" }.to_owned()
+    } else {
+        format!("{x:?}")
+    }
+}
+
+fn parse_float(value: &str) -> Result<f64, String> {
+    value
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("expected a number, found `{value}`"))
+}
+
+fn parse_list(value: &str) -> Result<Vec<f64>, String> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| format!("expected a one-line array, found `{value}`"))?;
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_float)
+        .collect()
+}
+
+/// Inteiro sem sinal, entre aspas ou não
+fn parse_u64(value: &str) -> Result<u64, String> {
+    unquote(value)
+        .unwrap_or(value)
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("expected an unsigned integer, found `{value}`"))
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_round_trip_in_both_formats() {
+        let report = Report {
+            best_fitness: 1.25e-9,
+            best_position: vec![0.1, -3.0, f64::MIN_POSITIVE],
+            bounds: vec![(-5.0, 5.0); 3],
+            seed: Some(u64::MAX),
+            config_hash: Some(0xdead_beef),
+            ..Report::empty()
+        };
+        let dir = std::env::temp_dir();
+        for (name, format) in [
+            ("json", SolutionFormat::Json),
+            ("toml", SolutionFormat::Toml),
+        ] {
+            let path = dir.join(format!("kambo-solution-{}.{name}", std::process::id()));
+            assert_eq!(SolutionFormat::from_path(&path), format);
+            report.save_solution(&path, format).unwrap();
+            let loaded = Report::load_solution(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+            assert_eq!(
+                SolutionArtifact::from_report(&loaded),
+                SolutionArtifact::from_report(&report)
+            );
+        }
+
+        let text = "# hand-written\ndirection = \"Maximize\"\nfitness = inf\nposition = [1, 2_0]\n";
+        let artifact = SolutionArtifact::from_toml(text).unwrap();
+        assert_eq!(artifact.position, vec![1.0, 20.0]);
+        assert!(artifact.fitness.is_infinite() && artifact.lower.is_empty());
+        assert!(matches!(
+            SolutionArtifact::from_toml("fitness = 1.0\nposition = oops"),
+            Err(ArtifactError::Toml { line: 2, .. })
+        ));
+    }
+}
@@ -1,6 +1,10 @@
+use std::borrow::Cow;
+
 use rand::Rng;
 use thiserror::Error;
 
+use crate::core::{Position, Scalar, vecops};
+
 #[derive(Debug, Error)]
 pub enum BoundsError {
     #[error("Dimension mismatch: lo({lo}) != hi({hi})")]
@@ -11,31 +15,35 @@ pub enum BoundsError {
 
     #[error("Invalid dimension: dim={dim}")]
     InvalidDim { dim: usize },
+
+    #[error("Point dimension mismatch: expected {expected}, received {received}")]
+    PointDim { expected: usize, received: usize },
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Policy {
     #[default]
     Clamp,
 }
 
 #[derive(Clone, Debug)]
-enum Spec {
+enum Spec<T> {
     /// Limites fixos para todas as variaveis, ou seja, para cada falcão cada "gene" vai estar
     /// entre lo e hi
-    Uniform { lo: f64, hi: f64, dim: usize },
+    Uniform { lo: T, hi: T, dim: usize },
 
     /// Cada gene tem um upper-bound e um lower-bound especifico
-    PerDim { lo: Vec<f64>, hi: Vec<f64> },
+    PerDim { lo: Vec<T>, hi: Vec<T> },
 }
 
 #[derive(Clone, Debug)]
-pub struct Bounds {
-    spec: Spec,
+pub struct Bounds<T: Scalar = f64> {
+    spec: Spec<T>,
     policy: Policy,
 }
 
-impl Bounds {
+impl<T: Scalar> Bounds<T> {
     /// Creates a **uniform** bound for all dimensions.
     ///
     /// Each dimension receives the same lower (`lo`) and upper (`hi`) limits.
@@ -59,12 +67,16 @@ impl Bounds {
     /// - [`BoundsError::InvalidDim`] if `dim == 0`.
     /// - [`BoundsError::InvalidInterval`] if `lo > hi`.
     ///
-    pub fn uniform(lo: f64, hi: f64, dim: usize) -> Result<Self, BoundsError> {
+    pub fn uniform(lo: T, hi: T, dim: usize) -> Result<Self, BoundsError> {
         if dim == 0 {
             return Err(BoundsError::InvalidDim { dim });
         }
         if lo > hi {
-            return Err(BoundsError::InvalidInterval { i: 0, lo, hi });
+            return Err(BoundsError::InvalidInterval {
+                i: 0,
+                lo: lo.into(),
+                hi: hi.into(),
+            });
         }
         Ok(Self {
             spec: Spec::Uniform { lo, hi, dim },
@@ -95,7 +107,7 @@ impl Bounds {
     /// - [`BoundsError::InvalidDim`] if `lo` and `hi` are empty.
     /// - [`BoundsError::InvalidInterval`] if for any index `i`, `lo[i] > hi[i]`.
     ///
-    pub fn per_dim(lo: Vec<f64>, hi: Vec<f64>) -> Result<Self, BoundsError> {
+    pub fn per_dim(lo: Vec<T>, hi: Vec<T>) -> Result<Self, BoundsError> {
         if lo.len() != hi.len() {
             return Err(BoundsError::DimMismatch {
                 lo: lo.len(),
@@ -107,7 +119,11 @@ impl Bounds {
         }
         for (i, (&l, &h)) in lo.iter().zip(&hi).enumerate() {
             if l > h {
-                return Err(BoundsError::InvalidInterval { i, lo: l, hi: h });
+                return Err(BoundsError::InvalidInterval {
+                    i,
+                    lo: l.into(),
+                    hi: h.into(),
+                });
             }
         }
         Ok(Self {
@@ -123,6 +139,11 @@ impl Bounds {
         self
     }
 
+    #[must_use]
+    pub const fn policy(&self) -> Policy {
+        self.policy
+    }
+
     #[inline]
     #[must_use]
     pub const fn dim(&self) -> usize {
@@ -134,7 +155,7 @@ impl Bounds {
 
     #[inline]
     #[must_use]
-    pub fn lo_at(&self, i: usize) -> f64 {
+    pub fn lo_at(&self, i: usize) -> T {
         match &self.spec {
             Spec::Uniform { lo, .. } => *lo,
             Spec::PerDim { lo, .. } => lo[i],
@@ -143,61 +164,259 @@ impl Bounds {
 
     #[inline]
     #[must_use]
-    pub fn hi_at(&self, i: usize) -> f64 {
+    pub fn hi_at(&self, i: usize) -> T {
         match &self.spec {
             Spec::Uniform { hi, .. } => *hi,
             Spec::PerDim { hi, .. } => hi[i],
         }
     }
 
+    /// Width of dimension `i`, zero for a fixed one (see [`Bounds::is_fixed`]); callers that
+    /// normalize by it must treat that case apart.
     #[inline]
     #[must_use]
-    pub fn span_at(&self, i: usize) -> f64 {
+    pub fn span_at(&self, i: usize) -> T {
         self.hi_at(i) - self.lo_at(i)
     }
 
-    pub fn project_slice(&self, x: &mut [f64]) {
-        debug_assert_eq!(x.len(), self.dim(), "slice dim != bounds dim");
+    /// Whether dimension `i` is fixed, i.e. its lower and upper bounds coincide. Such a
+    /// dimension is a constant of the problem: sampling returns it, projection keeps every
+    /// position on it and the diversity of a run ignores it.
+    #[inline]
+    #[must_use]
+    pub fn is_fixed(&self, i: usize) -> bool {
+        self.lo_at(i) == self.hi_at(i)
+    }
+
+    /// Which dimensions are fixed, or an empty vector when none is.
+    #[must_use]
+    pub fn fixed_mask(&self) -> Vec<bool> {
+        if (0..self.dim()).any(|i| self.is_fixed(i)) {
+            (0..self.dim()).map(|i| self.is_fixed(i)).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Lower bound of every dimension, e.g. to build an `ndarray::Array1`. Borrowed from
+    /// per-dimension bounds; uniform bounds store a single value, so this builds the vector.
+    #[must_use]
+    pub fn lower(&self) -> Cow<'_, [T]> {
+        match &self.spec {
+            Spec::PerDim { lo, .. } => Cow::Borrowed(lo),
+            Spec::Uniform { lo, dim, .. } => Cow::Owned(vec![*lo; *dim]),
+        }
+    }
+
+    /// Upper bound of every dimension, borrowed like [`Bounds::lower`].
+    #[must_use]
+    pub fn upper(&self) -> Cow<'_, [T]> {
+        match &self.spec {
+            Spec::PerDim { hi, .. } => Cow::Borrowed(hi),
+            Spec::Uniform { hi, dim, .. } => Cow::Owned(vec![*hi; *dim]),
+        }
+    }
+
+    /// The same box in another [`Scalar`] type, e.g. `f32` bounds for an `f32` decoder.
+    #[must_use]
+    pub fn cast<U: Scalar>(&self) -> Bounds<U> {
+        let cast = |v: &[T]| v.iter().map(|&x| U::from_f64(x.into())).collect();
+        let spec = match &self.spec {
+            Spec::Uniform { lo, hi, dim } => Spec::Uniform {
+                lo: U::from_f64((*lo).into()),
+                hi: U::from_f64((*hi).into()),
+                dim: *dim,
+            },
+            Spec::PerDim { lo, hi } => Spec::PerDim {
+                lo: cast(lo),
+                hi: cast(hi),
+            },
+        };
+        Bounds {
+            spec,
+            policy: self.policy,
+        }
+    }
+
+    /// Checks that `x` has the dimension of these bounds.
+    ///
+    /// # Errors
+    ///
+    /// [`BoundsError::PointDim`] if it does not.
+    pub const fn check_dim(&self, x: &[T]) -> Result<(), BoundsError> {
+        if x.len() == self.dim() {
+            Ok(())
+        } else {
+            Err(BoundsError::PointDim {
+                expected: self.dim(),
+                received: x.len(),
+            })
+        }
+    }
+
+    /// A [`Position`] of this search space, projected into the box.
+    ///
+    /// # Errors
+    ///
+    /// [`BoundsError::PointDim`] if `coords` does not have the dimension of these bounds.
+    pub fn position(&self, coords: Vec<T>) -> Result<Position<T>, BoundsError> {
+        self.check_dim(&coords)?;
+        let mut position = Position::new_unchecked(coords);
+        self.project_slice(position.as_mut_slice());
+        Ok(position)
+    }
+
+    /// Projects `x` into the box according to the policy.
+    ///
+    /// # Panics
+    ///
+    /// If `x` does not have the dimension of these bounds; [`Bounds::try_project_slice`]
+    /// returns an error instead.
+    pub fn project_slice(&self, x: &mut [T]) {
+        assert_dim(self.dim(), x.len());
         match (self.policy, &self.spec) {
-            (Policy::Clamp, Spec::Uniform { lo, hi, dim: _ }) => {
-                let (lo, hi) = (*lo, *hi);
-                for xi in x {
-                    *xi = xi.clamp(lo, hi);
-                }
-            }
-            (Policy::Clamp, Spec::PerDim { lo, hi }) => {
-                // Estou usando unsafe aqui apenas para que o rust pule as verificações, já que o
-                // debug_assert_eq já faz a verificação de que as dimensões são iguais
-                for i in 0..x.len() {
-                    let l = unsafe { *lo.get_unchecked(i) };
-                    let h = unsafe { *hi.get_unchecked(i) };
-                    // clamp
-                    let xi = unsafe { x.get_unchecked_mut(i) };
-                    *xi = (*xi).clamp(l, h);
-                }
-            }
+            (Policy::Clamp, Spec::Uniform { lo, hi, dim: _ }) => vecops::clamp(x, *lo, *hi),
+            (Policy::Clamp, Spec::PerDim { lo, hi }) => vecops::clamp_each(x, lo, hi),
         }
     }
 
-    pub fn gen_random_vec<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
-        let d = self.dim();
-        let mut s = Vec::with_capacity(d);
+    pub fn gen_random_vec<R: Rng>(&self, rng: &mut R) -> Vec<T> {
+        let mut s = vec![T::zero(); self.dim()];
+        self.fill_random(&mut s, rng);
+        s
+    }
 
+    /// Like [`Bounds::project_slice`], for points of unchecked dimension.
+    ///
+    /// # Errors
+    ///
+    /// [`BoundsError::PointDim`] if `x` does not have the dimension of these bounds; `x` is
+    /// left as is.
+    pub fn try_project_slice(&self, x: &mut [T]) -> Result<(), BoundsError> {
+        self.check_dim(x)?;
+        self.project_slice(x);
+        Ok(())
+    }
+
+    /// Like [`Bounds::fill_random`], for points of unchecked dimension.
+    ///
+    /// # Errors
+    ///
+    /// [`BoundsError::PointDim`] if `x` does not have the dimension of these bounds; `x` is
+    /// left as is.
+    pub fn try_fill_random<R: Rng>(&self, x: &mut [T], rng: &mut R) -> Result<(), BoundsError> {
+        self.check_dim(x)?;
+        self.fill_random(x, rng);
+        Ok(())
+    }
+
+    /// Overwrites `x` with a uniformly random point of the box, without allocating.
+    ///
+    /// # Panics
+    ///
+    /// If `x` does not have the dimension of these bounds; [`Bounds::try_fill_random`]
+    /// returns an error instead.
+    pub fn fill_random<R: Rng>(&self, x: &mut [T], rng: &mut R) {
+        assert_dim(self.dim(), x.len());
         match &self.spec {
-            Spec::Uniform { lo, hi, dim } => {
-                for _ in 0..*dim {
-                    let random_val = rng.random_range(*lo..=*hi);
-                    s.push(random_val);
+            Spec::Uniform { lo, hi, dim: _ } if lo == hi => x.fill(*lo),
+            Spec::Uniform { lo, hi, dim: _ } => {
+                for xi in x {
+                    *xi = rng.random_range(*lo..=*hi);
                 }
             }
             Spec::PerDim { lo, hi } => {
-                for i in 0..d {
-                    let random_val = rng.random_range(lo[i]..=hi[i]);
-                    s.push(random_val);
+                for (i, xi) in x.iter_mut().enumerate() {
+                    // Dimensão fixa: a constante, sem consumir o gerador
+                    *xi = if lo[i] == hi[i] {
+                        lo[i]
+                    } else {
+                        rng.random_range(lo[i]..=hi[i])
+                    };
                 }
             }
         }
+    }
+}
 
-        s
+/// Falha com uma mensagem clara quando um ponto não tem a dimensão dos limites; a comparação
+/// é barata o bastante para valer também em release
+#[inline]
+#[track_caller]
+fn assert_dim(expected: usize, received: usize) {
+    assert!(
+        expected == received,
+        "point has {received} dimensions but the bounds have {expected}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{
+        benchmarks::Function,
+        core::{
+            Population,
+            utils::{dimension_wise_diversity, dimension_wise_diversity_within},
+        },
+        hho::{DiveShape, HhoBuilder},
+    };
+
+    #[test]
+    fn fixed_dimensions_stay_constant_and_out_of_the_diversity() {
+        let bounds: Bounds = Bounds::per_dim(vec![-5.0, 2.0, -5.0], vec![5.0, 2.0, 5.0]).unwrap();
+        assert_eq!(bounds.fixed_mask(), [false, true, false]);
+        assert!(
+            Bounds::uniform(-1.0, 1.0, 2)
+                .unwrap()
+                .fixed_mask()
+                .is_empty()
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut positions = Population::zeros(8, 3);
+        for x in &mut positions {
+            bounds.fill_random(x, &mut rng);
+            assert!(x[1].to_bits() == 2f64.to_bits());
+        }
+        let mut short = [9.0; 2];
+        assert!(bounds.try_fill_random(&mut short, &mut rng).is_err());
+        assert!(bounds.try_project_slice(&mut short).is_err());
+        assert!(short.iter().all(|x| x.to_bits() == 9f64.to_bits()));
+        let within = dimension_wise_diversity_within(&positions, &bounds);
+        assert!((within / dimension_wise_diversity(&positions) - 1.5).abs() < 1e-12);
+
+        let report = HhoBuilder::new(bounds)
+            .pop_size(10)
+            .max_iters(40)
+            .dive_shape(DiveShape::Covariance)
+            .seed(5)
+            .build()
+            .run(&Function::Sphere)
+            .unwrap();
+        assert!(report.best_position[1].to_bits() == 2f64.to_bits());
+        assert!(report.diversity.iter().all(|d| d.is_finite()));
+        assert!(report.diversity[0] > 1.0);
+
+        let constant = HhoBuilder::new(Bounds::uniform(1.0, 1.0, 2).unwrap())
+            .pop_size(4)
+            .max_iters(5)
+            .build()
+            .run(&Function::Sphere)
+            .unwrap();
+        assert!((constant.best_fitness - 2.0).abs() < 1e-12);
+        assert!(constant.diversity.iter().all(|d| d.abs() < f64::EPSILON));
+    }
+
+    #[test]
+    fn per_dimension_bounds_are_borrowed() {
+        let bounds: Bounds = Bounds::per_dim(vec![-5.0, 2.0], vec![5.0, 3.0]).unwrap();
+        assert!(matches!(bounds.lower(), Cow::Borrowed(&[-5.0, 2.0])));
+        assert!(matches!(bounds.upper(), Cow::Borrowed(&[5.0, 3.0])));
+        let uniform: Bounds = Bounds::uniform(-1.0, 1.0, 3).unwrap();
+        assert_eq!(*uniform.lower(), [-1.0; 3]);
+        assert_eq!(*uniform.upper(), [1.0; 3]);
     }
 }
@@ -0,0 +1,42 @@
+//! Monotonic clock used for time budgets, durations and progress reporting.
+//!
+//! On native targets this is [`std::time::Instant`]. `wasm32-unknown-unknown` has no system
+//! clock in `std` (calling `Instant::now` panics there), so it gets a stand-in that reads
+//! `Date.now()` with the `wasm` feature and otherwise never advances, which leaves wall-clock
+//! budgets and limits inert but everything else working.
+
+cfg_if::cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+        use std::time::Duration;
+
+        /// A point in time with millisecond resolution.
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct Instant(f64);
+
+        impl Instant {
+            #[must_use]
+            pub fn now() -> Self {
+                Self(now_ms())
+            }
+
+            /// Time elapsed since `self`.
+            #[must_use]
+            pub fn elapsed(&self) -> Duration {
+                Duration::from_secs_f64(((now_ms() - self.0) / 1000.0).max(0.0))
+            }
+        }
+
+        /// Milissegundos desde a época Unix, ou 0 sem acesso ao relógio do JS
+        fn now_ms() -> f64 {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "wasm")] {
+                    js_sys::Date::now()
+                } else {
+                    0.0
+                }
+            }
+        }
+    } else {
+        pub use std::time::Instant;
+    }
+}
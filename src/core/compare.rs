@@ -0,0 +1,189 @@
+//! Head-to-head comparison of two runs, e.g. before and after a tweak.
+
+use std::fmt;
+
+use crate::core::{Direction, Report};
+
+/// Which of two compared runs came out ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Verdict {
+    Better,
+    Worse,
+    Tie,
+}
+
+/// Outcome of [`Report::compare`]: how a run fares against a baseline run, in the direction
+/// of the run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comparison {
+    pub direction: Direction,
+    /// Final best fitness of the run minus the baseline's; a better run has a negative delta
+    /// when minimizing and a positive one when maximizing.
+    pub fitness_delta: f64,
+    /// Decided by the final best fitness.
+    pub verdict: Verdict,
+    /// The quality both runs reached: the final best fitness of the worse one.
+    pub target: f64,
+    /// Evaluations the run needed to reach `target`, or `None` if its curve carries no
+    /// evaluation counts.
+    pub evals_to_target: Option<usize>,
+    /// Same for the baseline.
+    pub baseline_evals_to_target: Option<usize>,
+    /// Fraction of the evaluation range covered by both curves where the run's best so far
+    /// is strictly better than the baseline's, or `None` without evaluation counts.
+    pub dominance: Option<f64>,
+    /// Fraction of that range where the baseline is strictly better.
+    pub dominated: Option<f64>,
+}
+
+impl Comparison {
+    /// Baseline evaluations to target divided by the run's: above `1` the run got there
+    /// faster.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn speedup(&self) -> Option<f64> {
+        match (self.evals_to_target, self.baseline_evals_to_target) {
+            (Some(a), Some(b)) if a > 0 => Some(b as f64 / a as f64),
+            _ => None,
+        }
+    }
+}
+
+impl Report {
+    /// Compares this run with `baseline`, in this report's direction: final fitness,
+    /// evaluations each needed to reach the quality both achieved, and how much of the way
+    /// each curve was ahead. The [`Display`](fmt::Display) of the result answers "did this
+    /// tweak help?" in a few lines.
+    #[must_use]
+    pub fn compare(&self, baseline: &Self) -> Comparison {
+        let direction = self.direction;
+        let (a, b) = (self.best_fitness, baseline.best_fitness);
+        let verdict = if direction.better(a, b) {
+            Verdict::Better
+        } else if direction.better(b, a) {
+            Verdict::Worse
+        } else {
+            Verdict::Tie
+        };
+        let target = if verdict == Verdict::Better { b } else { a };
+        let (dominance, dominated) = match dominance(self, baseline) {
+            Some((ahead, behind)) => (Some(ahead), Some(behind)),
+            None => (None, None),
+        };
+        Comparison {
+            direction,
+            fitness_delta: a - b,
+            verdict,
+            target,
+            evals_to_target: self.evals_to_target(target),
+            baseline_evals_to_target: baseline.evals_to_target(target),
+            dominance,
+            dominated,
+        }
+    }
+}
+
+/// Frações do trecho comum de avaliações em que cada curva está estritamente à frente,
+/// ponderadas pelo número de avaliações de cada trecho
+#[allow(clippy::cast_precision_loss)]
+fn dominance(a: &Report, b: &Report) -> Option<(f64, f64)> {
+    let start = (*a.curve_evals.first()?).max(*b.curve_evals.first()?);
+    let end = (*a.curve_evals.last()?).min(*b.curve_evals.last()?);
+    if end <= start {
+        return None;
+    }
+    let mut points: Vec<usize> = a
+        .curve_evals
+        .iter()
+        .chain(&b.curve_evals)
+        .copied()
+        .filter(|&e| (start..end).contains(&e))
+        .collect();
+    points.sort_unstable();
+    points.dedup();
+    let (mut ahead, mut behind) = (0, 0);
+    for (k, &e) in points.iter().enumerate() {
+        let width = points.get(k + 1).copied().unwrap_or(end) - e;
+        let (fa, fb) = (a.best_at_evals(e)?, b.best_at_evals(e)?);
+        if a.direction.better(fa, fb) {
+            ahead += width;
+        } else if a.direction.better(fb, fa) {
+            behind += width;
+        }
+    }
+    let span = (end - start) as f64;
+    Some((ahead as f64 / span, behind as f64 / span))
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self.verdict {
+            Verdict::Better => "better",
+            Verdict::Worse => "worse",
+            Verdict::Tie => "tie",
+        };
+        writeln!(f, "verdict       {verdict}")?;
+        writeln!(f, "fitness delta {:+.6e}", self.fitness_delta)?;
+        let evals = |e: Option<usize>| e.map_or_else(|| "-".to_string(), |e| e.to_string());
+        write!(
+            f,
+            "evals to {:.6e}: {} vs {}",
+            self.target,
+            evals(self.evals_to_target),
+            evals(self.baseline_evals_to_target)
+        )?;
+        if let Some(s) = self.speedup() {
+            write!(f, " ({s:.2}x)")?;
+        }
+        writeln!(f)?;
+        match (self.dominance, self.dominated) {
+            (Some(ahead), Some(behind)) => write!(
+                f,
+                "curve ahead   {:.1}% of the way, behind {:.1}%",
+                100.0 * ahead,
+                100.0 * behind
+            ),
+            _ => write!(f, "curve ahead   -"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(curve: &[f64], evals: &[usize]) -> Report {
+        Report {
+            best_fitness: *curve.last().unwrap(),
+            convergence_curve: curve.to_vec(),
+            curve_evals: evals.to_vec(),
+            ..Report::empty()
+        }
+    }
+
+    #[test]
+    fn faster_and_better_run_wins() {
+        let tweak = run(&[5.0, 1.0, 0.5, 0.1], &[10, 20, 30, 40]);
+        let baseline = run(&[5.0, 3.0, 1.0, 0.5], &[10, 20, 30, 40]);
+        let c = tweak.compare(&baseline);
+        assert_eq!(c.verdict, Verdict::Better);
+        assert!((c.fitness_delta + 0.4).abs() < 1e-12);
+        assert!((c.target - 0.5).abs() < 1e-12);
+        assert_eq!(
+            (c.evals_to_target, c.baseline_evals_to_target),
+            (Some(30), Some(40))
+        );
+        assert!((c.speedup().unwrap() - 4.0 / 3.0).abs() < 1e-12);
+        // Empatadas em [10, 20), à frente em [20, 40)
+        assert!((c.dominance.unwrap() - 2.0 / 3.0).abs() < 1e-12);
+        assert!(c.dominated.unwrap().abs() < 1e-12);
+        assert!(c.to_string().starts_with("verdict       better"));
+
+        let back = baseline.compare(&tweak);
+        assert_eq!(back.verdict, Verdict::Worse);
+        assert!((back.dominated.unwrap() - 2.0 / 3.0).abs() < 1e-12);
+    }
+}
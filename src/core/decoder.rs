@@ -1,6 +1,8 @@
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+use crate::core::{Scalar, SeedStreams, StreamRng};
+
+#[derive(Debug, Clone, Error)]
 pub enum DecoderError {
     #[error("Invalid dimension: expected size was {expected}, but received {received}")]
     InvalidDimension { expected: usize, received: usize },
@@ -10,22 +12,76 @@ pub enum DecoderError {
 
     #[error("Unknown error while decoding the solution")]
     UnknownError,
+
+    #[error("External evaluator failed: {0}")]
+    External(String),
+
+    #[error("Non-finite value: coordinate {index} of the solution is {value}")]
+    NonFiniteInput { index: usize, value: f64 },
+
+    #[error("Non-finite value: the decoder returned a NaN fitness")]
+    NanFitness,
+
+    #[error("Non-finite value: the decoder returned {value} for {position:?}")]
+    NonFiniteFitness { value: f64, position: Vec<f64> },
 }
 
-pub trait Decoder {
+/// Progress of the run at one evaluation, passed to [`Decoder::decode_with`].
+///
+/// Lets a decoder anneal an internal tolerance with the run, or draw its random numbers from
+/// a stream the optimizer controls instead of its own, so a seeded run stays reproducible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalContext {
+    /// Iterations completed when the evaluation was requested, as in
+    /// [`crate::termination::Status::iter`]: the initial population and the first iteration
+    /// both see `0`.
+    pub iteration: usize,
+    /// Evaluations counted before this one. In a parallel sweep every hawk counts from the
+    /// start of its batch, so two hawks may see the same index.
+    pub evaluation: usize,
+    /// Fraction of the budget used when the evaluation was requested, in `[0, 1]`.
+    pub progress: f64,
+    /// Seed of the random stream of this evaluation, see [`EvalContext::rng`].
+    pub seed: u64,
+}
+
+impl EvalContext {
+    /// Context of an evaluation outside any run: iteration, index and progress `0`, seed
+    /// `seed`.
+    #[must_use]
+    pub const fn detached(seed: u64) -> Self {
+        Self {
+            iteration: 0,
+            evaluation: 0,
+            progress: 0.0,
+            seed,
+        }
+    }
+
+    /// Random generator of this evaluation, drawn from [`EvalContext::seed`]; two contexts
+    /// with the same seed give the same numbers.
+    #[must_use]
+    pub fn rng(&self) -> StreamRng {
+        SeedStreams::new(self.seed).init()
+    }
+}
+
+/// Maps a position to its fitness. `T` is the [`Scalar`] type of the positions, `f64` by
+/// default.
+pub trait Decoder<T: Scalar = f64> {
     /// Decodes the given `solution` vector into a valid solution and returns its fitness.
     ///
-    /// The `solution` vector is expected to contain `f64` values that need to be
+    /// The `solution` vector is expected to contain `T` values that need to be
     /// mapped and validated. The implementation of this method should:
     ///
     /// 1. Validate the dimensions of the input `solution` vector.
     /// 2. Ensure that the values within the vector are within the established limits
-    /// 3. Transform the raw `f64` values into a valid problem-specific solution.
+    /// 3. Transform the raw `T` values into a valid problem-specific solution.
     /// 4. Calculate the fitness of the valid solution.
     ///
     /// # Parameters
     ///
-    /// - `solution`: A slice of `f64` values representing the encoded solution.
+    /// - `solution`: A slice of `T` values representing the encoded solution.
     ///
     /// # Errors
     ///
@@ -33,5 +89,378 @@ pub trait Decoder {
     /// - The `solution` vector has an invalid dimension.
     /// - Any value within the `solution` vector is out of the established bounds.
     /// - An unknown error occurs during decoding.
-    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError>;
+    fn decode(&self, solution: &[T]) -> Result<T, DecoderError>;
+
+    /// Decodes `solution` knowing where the run stands, see [`EvalContext`].
+    ///
+    /// The HHO optimizer calls this for every full-fidelity evaluation; the default ignores
+    /// `ctx` and calls [`Decoder::decode`], so only decoders that follow the run need it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Decoder::decode`].
+    fn decode_with(&self, solution: &[T], ctx: &EvalContext) -> Result<T, DecoderError> {
+        let _ = ctx;
+        self.decode(solution)
+    }
+
+    /// Decodes `solution` at the given `fidelity`, in `(0, 1]`, where `1` is the exact
+    /// evaluation of [`Decoder::decode`] and lower values ask for a cheaper approximation
+    /// (a coarser mesh, fewer samples, a shorter simulation).
+    ///
+    /// Only called when low-fidelity screening is enabled (see [`crate::hho::Fidelity`]).
+    /// The default ignores `fidelity` and evaluates exactly.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Decoder::decode`].
+    fn decode_at(&self, solution: &[T], fidelity: f64) -> Result<T, DecoderError> {
+        let _ = fidelity;
+        self.decode(solution)
+    }
+
+    /// Called by the HHO optimizer after each iteration `iter` (counting from 1), for decoders
+    /// whose fitness follows the progress of the run, such as the dynamic and adaptive
+    /// penalties of [`crate::constraints::Penalized`]. The default does nothing.
+    fn end_iteration(&self, iter: usize) {
+        let _ = iter;
+    }
+
+    /// Penalty coefficient currently in force, recorded by the HHO optimizer in
+    /// [`crate::core::Report::penalty_coefficients`]. `None`, the default, records nothing.
+    fn penalty_coefficient(&self) -> Option<f64> {
+        None
+    }
+
+    /// Dimension of the positions this decoder accepts, if it only accepts one. The HHO
+    /// optimizer checks it against [`crate::core::Bounds::dim`] before initializing the
+    /// population and fails with [`crate::hho::HhoError::Config`] on a mismatch, instead of a
+    /// [`DecoderError::InvalidDimension`] at the first evaluation. `None`, the default, skips
+    /// the check.
+    fn expected_dim(&self) -> Option<usize> {
+        None
+    }
+
+    /// This decoder as an [`IncrementalDecoder`], if it is one. Implementors of
+    /// [`IncrementalDecoder`] return `Some(self)` so the optimizer can find it behind a
+    /// `&dyn Decoder`; `None`, the default, always decodes in full.
+    fn incremental(&self) -> Option<&dyn IncrementalDecoder<T>> {
+        None
+    }
+}
+
+/// A decoder that can compute the fitness of a position from a nearby one whose fitness is
+/// known, e.g. a graph objective that only rescores the vertices a move changed.
+///
+/// The HHO optimizer calls [`IncrementalDecoder::decode_delta`] for the candidates it derives
+/// from a hawk, passing the hawk's position and the fitness the run holds for it, and
+/// [`Decoder::decode_with`] for everything else (initialization, block-greedy merges,
+/// re-evaluations). Wrappers such as [`Checked`] decode in full. Also return `Some(self)` from
+/// [`Decoder::incremental`], or the optimizer never sees this trait.
+pub trait IncrementalDecoder<T: Scalar = f64>: Decoder<T> {
+    /// Fitness of `solution`, given `previous` and its fitness `previous_fitness`, or `None`
+    /// to fall back to a full [`Decoder::decode_with`], e.g. when too many coordinates
+    /// changed for the delta to pay off.
+    ///
+    /// `previous_fitness` is the value the optimizer holds, so decoders whose fitness is noisy
+    /// or changes over the run (see [`Decoder::end_iteration`]) should decode in full.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Decoder::decode`].
+    fn decode_delta(
+        &self,
+        previous: &[T],
+        previous_fitness: T,
+        solution: &[T],
+        ctx: &EvalContext,
+    ) -> Option<Result<T, DecoderError>>;
+}
+
+/// Checks every call to the wrapped decoder and turns silent garbage into errors.
+///
+/// Rejects positions whose dimension is not `dim` with [`DecoderError::InvalidDimension`] and
+/// positions with a NaN or infinite coordinate with [`DecoderError::NonFiniteInput`], before
+/// the decoder sees them, and a NaN fitness with [`DecoderError::NanFitness`]. An infinite
+/// fitness passes, since it is a common penalty. The optimizers then stop with the error
+/// instead of ranking a NaN, at the cost of one pass over each position; see also the
+/// `strict` feature, which keeps the internal consistency checks in release builds.
+#[derive(Debug, Clone, Copy)]
+pub struct Checked<D> {
+    inner: D,
+    dim: usize,
+}
+
+impl<D> Checked<D> {
+    /// Wraps `inner`, which expects `dim`-dimensional positions.
+    #[must_use]
+    pub const fn new(inner: D, dim: usize) -> Self {
+        Self { inner, dim }
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Valida a posição antes de chamar o decodificador
+    fn check<T: Scalar>(&self, solution: &[T]) -> Result<(), DecoderError> {
+        if solution.len() != self.dim {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.dim,
+                received: solution.len(),
+            });
+        }
+        solution
+            .iter()
+            .position(|v| !v.is_finite())
+            .map_or(Ok(()), |index| {
+                Err(DecoderError::NonFiniteInput {
+                    index,
+                    value: solution[index].into(),
+                })
+            })
+    }
+}
+
+impl<T: Scalar, D: Decoder<T>> Decoder<T> for Checked<D> {
+    fn decode(&self, solution: &[T]) -> Result<T, DecoderError> {
+        self.check(solution)?;
+        let f = self.inner.decode(solution)?;
+        if f.is_nan() {
+            return Err(DecoderError::NanFitness);
+        }
+        Ok(f)
+    }
+
+    fn decode_with(&self, solution: &[T], ctx: &EvalContext) -> Result<T, DecoderError> {
+        self.check(solution)?;
+        let f = self.inner.decode_with(solution, ctx)?;
+        if f.is_nan() {
+            return Err(DecoderError::NanFitness);
+        }
+        Ok(f)
+    }
+
+    fn decode_at(&self, solution: &[T], fidelity: f64) -> Result<T, DecoderError> {
+        self.check(solution)?;
+        let f = self.inner.decode_at(solution, fidelity)?;
+        if f.is_nan() {
+            return Err(DecoderError::NanFitness);
+        }
+        Ok(f)
+    }
+
+    fn end_iteration(&self, iter: usize) {
+        self.inner.end_iteration(iter);
+    }
+
+    fn penalty_coefficient(&self) -> Option<f64> {
+        self.inner.penalty_coefficient()
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.dim)
+    }
+}
+
+/// Runs an `f32` decoder in the `f64` optimizers.
+///
+/// Each call rounds the position to `f32` and widens the fitness back to `f64`, so the
+/// objective can keep its own data in single precision.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct F32Adapter<D>(pub D);
+
+impl<D: Decoder<f32>> Decoder for F32Adapter<D> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let x: Vec<f32> = solution.iter().map(|&v| f32::from_f64(v)).collect();
+        self.0.decode(&x).map(f64::from)
+    }
+
+    fn decode_with(&self, solution: &[f64], ctx: &EvalContext) -> Result<f64, DecoderError> {
+        let x: Vec<f32> = solution.iter().map(|&v| f32::from_f64(v)).collect();
+        self.0.decode_with(&x, ctx).map(f64::from)
+    }
+
+    fn decode_at(&self, solution: &[f64], fidelity: f64) -> Result<f64, DecoderError> {
+        let x: Vec<f32> = solution.iter().map(|&v| f32::from_f64(v)).collect();
+        self.0.decode_at(&x, fidelity).map(f64::from)
+    }
+
+    fn end_iteration(&self, iter: usize) {
+        self.0.end_iteration(iter);
+    }
+
+    fn penalty_coefficient(&self) -> Option<f64> {
+        self.0.penalty_coefficient()
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        self.0.expected_dim()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Bounds,
+        hho::{HhoBuilder, HhoError},
+    };
+
+    /// NaN fora do círculo unitário, como um modelo que diverge
+    struct Fragile;
+
+    impl Decoder for Fragile {
+        fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+            let r: f64 = x.iter().map(|v| v * v).sum();
+            Ok(if r > 1.0 { f64::NAN } else { r })
+        }
+    }
+
+    #[test]
+    fn checked_decoder_reports_garbage_as_errors() {
+        let checked = Checked::new(Fragile, 2);
+        assert!((checked.decode(&[0.5, 0.5]).unwrap() - 0.5).abs() < 1e-12);
+        assert!(matches!(
+            checked.decode(&[0.0; 3]),
+            Err(DecoderError::InvalidDimension {
+                expected: 2,
+                received: 3
+            })
+        ));
+        assert!(matches!(
+            checked.decode(&[0.0, f64::INFINITY]),
+            Err(DecoderError::NonFiniteInput { index: 1, .. })
+        ));
+        assert!(matches!(
+            checked.decode(&[2.0, 0.0]),
+            Err(DecoderError::NanFitness)
+        ));
+
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .seed(1)
+            .max_iters(20)
+            .build();
+        let err = hho.run(&checked).unwrap_err();
+        assert!(matches!(
+            err,
+            HhoError::Decoder {
+                source: DecoderError::NanFitness,
+                ..
+            }
+        ));
+
+        // Dimensão errada: falha antes de avaliar a população inicial
+        let err = hho.run(&Checked::new(Fragile, 3)).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                HhoError::DimensionMismatch {
+                    expected: 3,
+                    actual: 2
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    /// Esfera com ruído tirado do contexto, guardando cada contexto recebido
+    #[derive(Default)]
+    struct Recorder {
+        seen: std::sync::Mutex<Vec<EvalContext>>,
+    }
+
+    impl Decoder for Recorder {
+        fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+            Ok(x.iter().map(|v| v * v).sum())
+        }
+
+        fn decode_with(&self, x: &[f64], ctx: &EvalContext) -> Result<f64, DecoderError> {
+            self.seen.lock().unwrap().push(*ctx);
+            let noise: f64 = rand::Rng::random(&mut ctx.rng());
+            Ok(1e-3f64.mul_add(noise, self.decode(x)?))
+        }
+    }
+
+    #[test]
+    fn decoders_see_the_progress_of_the_run() {
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(15)
+            .seed(8)
+            .build();
+        let (a, b) = (Recorder::default(), Recorder::default());
+        let report = hho.run(&a).unwrap();
+        assert_eq!(
+            hho.run(&b).unwrap().best_fitness.to_bits(),
+            report.best_fitness.to_bits()
+        );
+
+        let seen = a.seen.into_inner().unwrap();
+        assert_eq!(seen, b.seen.into_inner().unwrap());
+        assert_eq!(seen.len(), report.evals);
+        assert!(seen.iter().enumerate().all(|(k, c)| c.evaluation == k));
+        assert!(seen.windows(2).all(|w| w[0].iteration <= w[1].iteration));
+        assert_eq!(seen.last().unwrap().iteration, 14);
+        assert!(seen.iter().all(|c| (0.0..=1.0).contains(&c.progress)));
+        let mut seeds: Vec<u64> = seen.iter().map(|c| c.seed).collect();
+        seeds.sort_unstable();
+        seeds.dedup();
+        assert_eq!(seeds.len(), seen.len());
+    }
+
+    /// Esfera que atualiza a fitness só nas coordenadas alteradas, contando os dois caminhos
+    #[derive(Default)]
+    struct DeltaSphere {
+        full: std::sync::atomic::AtomicUsize,
+        deltas: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Decoder for DeltaSphere {
+        fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+            self.full.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(x.iter().map(|v| v * v).sum())
+        }
+
+        fn incremental(&self) -> Option<&dyn IncrementalDecoder> {
+            Some(self)
+        }
+    }
+
+    impl IncrementalDecoder for DeltaSphere {
+        fn decode_delta(
+            &self,
+            previous: &[f64],
+            previous_fitness: f64,
+            solution: &[f64],
+            _ctx: &EvalContext,
+        ) -> Option<Result<f64, DecoderError>> {
+            self.deltas
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let delta = previous
+                .iter()
+                .zip(solution)
+                .filter(|(p, x)| p.to_bits() != x.to_bits())
+                .map(|(p, x)| x.mul_add(*x, -p * p))
+                .sum::<f64>();
+            Some(Ok(previous_fitness + delta))
+        }
+    }
+
+    #[test]
+    fn incremental_decoders_evaluate_hawk_moves_from_the_difference() {
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(40)
+            .seed(3)
+            .build();
+        let decoder = DeltaSphere::default();
+        let report = hho.run(&decoder).unwrap();
+        let (full, deltas) = (decoder.full.into_inner(), decoder.deltas.into_inner());
+        assert_eq!(full + deltas, report.evals);
+        assert!(deltas > full, "{deltas} deltas, {full} full");
+        let exact: f64 = report.best_position.iter().map(|v| v * v).sum();
+        assert!((report.best_fitness - exact).abs() < 1e-9);
+    }
 }
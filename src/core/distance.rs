@@ -0,0 +1,100 @@
+//! Distances between positions, shared by everything that compares hawks: niching, the
+//! duplicate purge, the hall of fame and the diversity metrics.
+
+use std::fmt::Debug;
+
+use crate::core::{Bounds, vecops};
+
+/// A distance between two positions of the same dimension.
+pub trait Distance: Debug + Send + Sync {
+    fn distance(&self, a: &[f64], b: &[f64]) -> f64;
+}
+
+/// The usual norms of the coordinate differences, on raw coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Norm {
+    /// `√Σ dᵢ²`.
+    #[default]
+    Euclidean,
+
+    /// `Σ |dᵢ|`.
+    Manhattan,
+
+    /// `max |dᵢ|`.
+    Chebyshev,
+}
+
+impl Norm {
+    /// Combina as diferenças por coordenada
+    fn fold(self, diffs: impl Iterator<Item = f64>) -> f64 {
+        match self {
+            Self::Euclidean => diffs.map(|d| d * d).sum::<f64>().sqrt(),
+            Self::Manhattan => diffs.map(f64::abs).sum(),
+            Self::Chebyshev => diffs.fold(0.0, |m, d| m.max(d.abs())),
+        }
+    }
+}
+
+impl Distance for Norm {
+    fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            Self::Euclidean => vecops::squared_distance(a, b).sqrt(),
+            _ => self.fold(a.iter().zip(b).map(|(x, y)| x - y)),
+        }
+    }
+}
+
+/// A [`Norm`] of the coordinate differences divided by the span of each dimension.
+///
+/// Dimensions with wide ranges then do not drown out the others, and distances are fractions
+/// of the search box, e.g. `0.1` under [`Norm::Chebyshev`] is a tenth of every range.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normalized {
+    norm: Norm,
+    /// Inverso da largura de cada dimensão (0 nas degeneradas)
+    scale: Vec<f64>,
+}
+
+impl Normalized {
+    #[must_use]
+    pub fn new(norm: Norm, bounds: &Bounds) -> Self {
+        let scale = (0..bounds.dim())
+            .map(|j| {
+                let span = bounds.span_at(j);
+                if span > 0.0 { span.recip() } else { 0.0 }
+            })
+            .collect();
+        Self { norm, scale }
+    }
+}
+
+impl Distance for Normalized {
+    fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        self.norm.fold(
+            a.iter()
+                .zip(b)
+                .zip(&self.scale)
+                .map(|((x, y), s)| (x - y) * s),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn norms_measure_the_same_pair_differently() {
+        let (a, b) = ([0.0, 0.0], [3.0, -4.0]);
+        assert!((Norm::Euclidean.distance(&a, &b) - 5.0).abs() < 1e-12);
+        assert!((Norm::Manhattan.distance(&a, &b) - 7.0).abs() < 1e-12);
+        assert!((Norm::Chebyshev.distance(&a, &b) - 4.0).abs() < 1e-12);
+
+        let bounds = Bounds::per_dim(vec![0.0, -40.0], vec![10.0, 40.0]).unwrap();
+        let normalized = Normalized::new(Norm::Chebyshev, &bounds);
+        assert!((normalized.distance(&a, &b) - 0.3).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use crate::core::{Distance, Norm, report::Solution};
+
+/// Every point evaluated during a run, in evaluation order, with nearest-neighbour queries.
+///
+/// Queries go through a vantage-point tree, which works in any dimension and with any
+/// [`Distance`] that is a metric (every [`Norm`] and [`crate::core::Normalized`] are). New
+/// points wait in an unindexed tail that queries scan linearly; the tree is rebuilt once the
+/// tail is as long as the indexed part, so recording costs amortized `O(log n)` distance
+/// computations per point and a query touches `O(log n)` tree nodes plus the tail.
+///
+/// The tree is not serialized; a deserialized archive answers the same queries from the
+/// tail and rebuilds the tree on the next [`EvaluationArchive::push`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvaluationArchive {
+    entries: Vec<Solution>,
+    /// Nós da árvore; a raiz é o primeiro
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tree: Vec<Node>,
+    /// Quantos dos primeiros pontos estão na árvore
+    #[cfg_attr(feature = "serde", serde(skip))]
+    indexed: usize,
+    #[cfg_attr(feature = "serde", serde(skip, default = "euclidean"))]
+    distance: Arc<dyn Distance>,
+}
+
+/// Nó da árvore: pontos a até `radius` do ponto de vantagem ficam em `inside`
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    point: usize,
+    radius: f64,
+    inside: Option<usize>,
+    outside: Option<usize>,
+}
+
+fn euclidean() -> Arc<dyn Distance> {
+    Arc::new(Norm::Euclidean)
+}
+
+impl Default for EvaluationArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EvaluationArchive {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            tree: Vec::new(),
+            indexed: 0,
+            distance: euclidean(),
+        }
+    }
+
+    /// Measures neighbours with `distance` instead of the Euclidean distance.
+    #[must_use]
+    pub fn with_distance(mut self, distance: Arc<dyn Distance>) -> Self {
+        self.distance = distance;
+        self.tree.clear();
+        self.indexed = 0;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Recorded evaluations, oldest first.
+    #[inline]
+    #[must_use]
+    pub fn entries(&self) -> &[Solution] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn into_entries(self) -> Vec<Solution> {
+        self.entries
+    }
+
+    pub fn push(&mut self, position: &[f64], fitness: f64) {
+        self.entries.push(Solution {
+            position: position.to_vec(),
+            fitness,
+        });
+        if self.entries.len() >= 2 * self.indexed.max(8) {
+            self.rebuild();
+        }
+    }
+
+    /// Closest recorded evaluation to `x` and its distance, `None` if the archive is empty.
+    #[must_use]
+    pub fn nearest(&self, x: &[f64]) -> Option<(&Solution, f64)> {
+        self.k_nearest(x, 1)
+            .first()
+            .map(|&(i, d)| (&self.entries[i], d))
+    }
+
+    /// Indices into [`EvaluationArchive::entries`] of the `k` evaluations closest to `x`,
+    /// with their distances, closest first (ties by index).
+    #[must_use]
+    pub fn k_nearest(&self, x: &[f64], k: usize) -> Vec<(usize, f64)> {
+        let mut best = Vec::with_capacity(k + 1);
+        if k == 0 {
+            return best;
+        }
+        if !self.tree.is_empty() {
+            self.search(0, x, k, &mut best);
+        }
+        for i in self.indexed..self.entries.len() {
+            let d = self.distance.distance(x, &self.entries[i].position);
+            Self::offer(&mut best, k, i, d);
+        }
+        best
+    }
+
+    /// Indices of the evaluations within `radius` of `x` (inclusive), in index order.
+    #[must_use]
+    pub fn within(&self, x: &[f64], radius: f64) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = if self.tree.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(n) = stack.pop() {
+            let node = self.tree[n];
+            let d = self
+                .distance
+                .distance(x, &self.entries[node.point].position);
+            if d <= radius {
+                found.push(node.point);
+            }
+            if let Some(c) = node.inside
+                && d - radius <= node.radius
+            {
+                stack.push(c);
+            }
+            if let Some(c) = node.outside
+                && d + radius >= node.radius
+            {
+                stack.push(c);
+            }
+        }
+        found.extend(
+            (self.indexed..self.entries.len())
+                .filter(|&i| self.distance.distance(x, &self.entries[i].position) <= radius),
+        );
+        found.sort_unstable();
+        found
+    }
+
+    /// Reconstrói a árvore com todos os pontos
+    fn rebuild(&mut self) {
+        self.tree.clear();
+        let mut items: Vec<(usize, f64)> = (0..self.entries.len()).map(|i| (i, 0.0)).collect();
+        self.build(&mut items);
+        self.indexed = self.entries.len();
+    }
+
+    /// Monta a subárvore de `items` e devolve o índice da sua raiz
+    fn build(&mut self, items: &mut [(usize, f64)]) -> Option<usize> {
+        let (&mut (point, _), rest) = items.split_first_mut()?;
+        let at = self.tree.len();
+        self.tree.push(Node {
+            point,
+            radius: 0.0,
+            inside: None,
+            outside: None,
+        });
+        if rest.is_empty() {
+            return Some(at);
+        }
+        for item in rest.iter_mut() {
+            item.1 = self.distance.distance(
+                &self.entries[point].position,
+                &self.entries[item.0].position,
+            );
+        }
+        let mid = (rest.len() - 1) / 2;
+        rest.select_nth_unstable_by(mid, |a, b| a.1.total_cmp(&b.1));
+        let radius = rest[mid].1;
+        let (inside, outside) = rest.split_at_mut(mid + 1);
+        let inside = self.build(inside);
+        let outside = self.build(outside);
+        self.tree[at] = Node {
+            point,
+            radius,
+            inside,
+            outside,
+        };
+        Some(at)
+    }
+
+    /// Busca os `k` vizinhos na subárvore de `n`, podando pela desigualdade triangular
+    fn search(&self, n: usize, x: &[f64], k: usize, best: &mut Vec<(usize, f64)>) {
+        let node = self.tree[n];
+        let d = self
+            .distance
+            .distance(x, &self.entries[node.point].position);
+        Self::offer(best, k, node.point, d);
+        let tau = |best: &Vec<(usize, f64)>| {
+            if best.len() < k {
+                f64::INFINITY
+            } else {
+                best[k - 1].1
+            }
+        };
+        let (near, far) = if d <= node.radius {
+            (node.inside, node.outside)
+        } else {
+            (node.outside, node.inside)
+        };
+        if let Some(c) = near {
+            self.search(c, x, k, best);
+        }
+        if let Some(c) = far
+            && (d - node.radius).abs() <= tau(best)
+        {
+            self.search(c, x, k, best);
+        }
+    }
+
+    /// Insere `(i, d)` na lista ordenada dos `k` melhores
+    fn offer(best: &mut Vec<(usize, f64)>, k: usize, i: usize, d: f64) {
+        let at = best.partition_point(|&(j, e)| e < d || (e.total_cmp(&d).is_eq() && j < i));
+        if at < k {
+            best.insert(at, (i, d));
+            best.truncate(k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_queries_match_a_linear_scan() {
+        let mut archive = EvaluationArchive::new();
+        // Pontos numa espiral, determinísticos
+        for i in 0..300_u32 {
+            let t = f64::from(i) * 0.37;
+            archive.push(&[t.cos() * t, t.sin() * t, (t * 0.1).sin()], t);
+        }
+        assert_eq!(archive.len(), 300);
+        let q = [3.0, -2.0, 0.5];
+        let mut brute: Vec<(usize, f64)> = archive
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i, Norm::Euclidean.distance(&q, &s.position)))
+            .collect();
+        brute.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        assert_eq!(archive.k_nearest(&q, 5), brute[..5]);
+        let (nearest, d) = archive.nearest(&q).unwrap();
+        assert!((d - brute[0].1).abs() < 1e-12);
+        assert!((nearest.fitness - archive.entries()[brute[0].0].fitness).abs() < 1e-12);
+
+        let radius = brute[20].1;
+        let mut inside: Vec<usize> = brute[..=20].iter().map(|&(i, _)| i).collect();
+        inside.sort_unstable();
+        assert_eq!(archive.within(&q, radius), inside);
+        assert!(EvaluationArchive::new().nearest(&q).is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::core::{Decoder, DecoderError, EvalContext};
+
+/// Builds independent instances of a decoder whose state cannot be shared between threads
+/// (FFI handles, caches behind `Cell`/`RefCell`), see [`PerThread`].
+///
+/// Implemented for every `Fn() -> D` closure that is `Sync`.
+pub trait DecoderFactory: Sync {
+    type Decoder: Decoder + Send;
+
+    fn create(&self) -> Self::Decoder;
+}
+
+impl<F, D> DecoderFactory for F
+where
+    F: Fn() -> D + Sync,
+    D: Decoder + Send,
+{
+    type Decoder = D;
+
+    fn create(&self) -> D {
+        self()
+    }
+}
+
+/// Thread-safe decoder made of one instance of a non-`Sync` decoder per evaluating thread,
+/// for the parallel runs (e.g. [`crate::hho::Hho::run_parallel`]).
+///
+/// Each call borrows an idle instance, or creates one with the factory when all are busy,
+/// and gives it back afterwards, so there are never more instances than threads evaluating
+/// at the same time and no instance is used by two threads at once. Instances are kept for
+/// the following calls and dropped with the `PerThread`. The decoder only needs to be
+/// `Send`, to be handed from one worker to the next.
+#[derive(Debug)]
+pub struct PerThread<F: DecoderFactory> {
+    factory: F,
+    /// Instâncias ociosas
+    idle: Mutex<Vec<F::Decoder>>,
+}
+
+impl<F: DecoderFactory> PerThread<F> {
+    #[must_use]
+    pub const fn new(factory: F) -> Self {
+        Self {
+            factory,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Instances created so far and not in use right now.
+    #[must_use]
+    pub fn idle(&self) -> usize {
+        self.idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Empresta uma instância a `call` e a devolve depois
+    fn with<T>(&self, call: impl FnOnce(&F::Decoder) -> T) -> T {
+        let taken = self
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        let decoder = taken.unwrap_or_else(|| self.factory.create());
+        let result = call(&decoder);
+        self.idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(decoder);
+        result
+    }
+}
+
+impl<F: DecoderFactory> Decoder for PerThread<F> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        self.with(|d| d.decode(solution))
+    }
+
+    fn decode_with(&self, solution: &[f64], ctx: &EvalContext) -> Result<f64, DecoderError> {
+        self.with(|d| d.decode_with(solution, ctx))
+    }
+
+    fn decode_at(&self, solution: &[f64], fidelity: f64) -> Result<f64, DecoderError> {
+        self.with(|d| d.decode_at(solution, fidelity))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        self.with(Decoder::expected_dim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    // Guarda a última avaliação numa `Cell`: é `Send` mas não `Sync`
+    struct Cached {
+        calls: Cell<usize>,
+    }
+
+    impl Decoder for Cached {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(solution.iter().map(|x| x * x).sum())
+        }
+    }
+
+    #[test]
+    fn threads_never_share_an_instance() {
+        let decoder = PerThread::new(|| Cached {
+            calls: Cell::new(0),
+        });
+        std::thread::scope(|s| {
+            for t in 0..4_u32 {
+                let decoder = &decoder;
+                s.spawn(move || {
+                    for _ in 0..50 {
+                        let f = decoder.decode(&[f64::from(t), 1.0]).unwrap();
+                        assert!((f - f64::from(t * t + 1)).abs() < 1e-12);
+                    }
+                });
+            }
+        });
+        let idle = decoder.idle();
+        assert!((1..=4).contains(&idle), "idle = {idle}");
+        let calls: usize = decoder
+            .idle
+            .into_inner()
+            .unwrap()
+            .iter()
+            .map(|d| d.calls.get())
+            .sum();
+        assert_eq!(calls, 200);
+    }
+}
@@ -0,0 +1,125 @@
+//! Provenance of a run: what produced a report, where and when.
+
+use std::{collections::BTreeMap, time::Duration};
+
+/// Provenance of a run, see [`crate::core::Report::metadata`].
+///
+/// Keeps a results file interpretable on its own. The seed and the configuration fingerprint
+/// are [`crate::core::Report::seed`] and [`crate::core::Report::config_hash`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RunMetadata {
+    /// Version of this crate that ran; empty when unknown, e.g. for a report built by hand.
+    pub crate_version: String,
+    /// See [`crate::hho::HhoBuilder::problem_name`]; experiments fill in the name of the
+    /// problem.
+    pub problem: Option<String>,
+    /// Machine that ran, when the environment tells.
+    pub hostname: Option<String>,
+    /// Start of the run, in milliseconds since the Unix epoch; `None` without a system clock.
+    pub started_at: Option<u64>,
+    /// See [`crate::hho::HhoBuilder::tag`].
+    pub tags: BTreeMap<String, String>,
+}
+
+impl RunMetadata {
+    /// Metadata of nothing in particular.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            crate_version: String::new(),
+            problem: None,
+            hostname: None,
+            started_at: None,
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Metadados deste processo para uma execução iniciada há `elapsed`
+    pub(crate) fn current(elapsed: Duration) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            hostname: hostname(),
+            started_at: unix_millis(elapsed),
+            ..Self::new()
+        }
+    }
+
+    /// Junta os metadados de uma continuação: o início e a origem ficam, as etiquetas se somam
+    pub(crate) fn merge(&mut self, next: Self) {
+        if self.crate_version.is_empty() {
+            self.crate_version = next.crate_version;
+        }
+        self.problem = self.problem.take().or(next.problem);
+        self.hostname = self.hostname.take().or(next.hostname);
+        self.started_at = self.started_at.or(next.started_at);
+        self.tags.extend(next.tags);
+    }
+}
+
+/// Nome da máquina pelas variáveis de ambiente ou por `/etc/hostname`
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+}
+
+/// Milissegundos Unix de `ago` atrás; `None` sem relógio do sistema (wasm)
+fn unix_millis(ago: Duration) -> Option<u64> {
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+            let _ = ago;
+            None
+        } else {
+            let start = std::time::SystemTime::now().checked_sub(ago)?;
+            let since = start.duration_since(std::time::UNIX_EPOCH).ok()?;
+            u64::try_from(since.as_millis()).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn reports_record_where_and_when_they_ran() {
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(6)
+            .max_iters(10)
+            .seed(2)
+            .problem_name("sphere(dim=2)")
+            .tag("run", "a")
+            .tag("machine", "ci")
+            .build();
+        let before = unix_millis(Duration::ZERO).unwrap();
+        let report = hho.run(&Function::Sphere).unwrap();
+
+        let meta = &report.metadata;
+        assert_eq!(meta.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(meta.problem.as_deref(), Some("sphere(dim=2)"));
+        assert_eq!(meta.tags["run"], "a");
+        let started = meta.started_at.unwrap();
+        assert!(started.abs_diff(before) < 5_000, "{started} vs {before}");
+        // As etiquetas não mudam a impressão digital da configuração
+        let untagged = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(6)
+            .max_iters(10)
+            .build();
+        assert_eq!(report.config_hash, Some(untagged.config_hash()));
+
+        let mut next = RunMetadata::current(Duration::ZERO);
+        next.tags.insert("resumed".to_owned(), "yes".to_owned());
+        let chained = report.clone().chain(crate::core::Report {
+            metadata: next,
+            ..report
+        });
+        assert_eq!(chained.metadata.started_at, Some(started));
+        assert_eq!(chained.metadata.tags.len(), 3);
+    }
+}
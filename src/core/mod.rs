@@ -1,10 +1,54 @@
+#[cfg(feature = "plotting")]
+pub mod animation;
+pub mod archive;
+#[cfg(feature = "serde")]
+pub mod artifact;
 pub mod bounds;
+pub mod clock;
+pub mod compare;
 pub mod decoder;
+pub mod distance;
+pub mod evaluations;
+pub mod factory;
+pub mod metadata;
 pub mod objective;
+pub mod optimizer;
+#[cfg(feature = "plotting")]
+pub mod plot;
+pub mod population;
+pub mod position;
+pub mod replay;
 pub mod report;
+pub mod rng;
+pub mod scalar;
+pub mod scaling;
+pub mod stats;
 pub mod utils;
+pub mod vecops;
 
+#[cfg(feature = "plotting")]
+pub use animation::{Animation, Projection};
+pub use archive::HallOfFame;
+#[cfg(feature = "serde")]
+pub use artifact::{ArtifactError, SolutionArtifact, SolutionFormat};
 pub use bounds::{Bounds, Policy};
-pub use decoder::{Decoder, DecoderError};
-pub use objective::{Maximization, Minimization, Objective};
-pub use report::Report;
+pub use compare::{Comparison, Verdict};
+pub use decoder::{Checked, Decoder, DecoderError, EvalContext, F32Adapter, IncrementalDecoder};
+pub use distance::{Distance, Norm, Normalized};
+pub use evaluations::EvaluationArchive;
+pub use factory::{DecoderFactory, PerThread};
+pub use metadata::RunMetadata;
+pub use objective::{Direction, Maximization, Minimization, Objective};
+pub use optimizer::{Budget, Optimizer};
+#[cfg(feature = "plotting")]
+pub use plot::PlotError;
+pub use population::{Population, ScoredPopulation};
+pub use position::Position;
+pub use replay::{Replay, ReplaySource};
+pub use report::{
+    CurveSampling, Degradation, Improvement, Niche, Report, Solution, TerminationReason,
+};
+pub use rng::{RngFactory, RngKind, SeedStreams, StreamRng, Xoshiro256PlusPlus};
+pub use scalar::Scalar;
+pub use scaling::{FitnessScaling, Scaler};
+pub use stats::{CurveBands, ReportSet, Robustness, Summary};
@@ -1,5 +1,54 @@
+/// Optimization direction, the runtime counterpart of an [`Objective`] type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+impl Direction {
+    /// Whether fitness `a` is strictly better than `b` in this direction.
+    #[inline]
+    #[must_use]
+    pub fn better(self, a: f64, b: f64) -> bool {
+        match self {
+            Self::Minimize => a < b,
+            Self::Maximize => a > b,
+        }
+    }
+
+    /// Fitness worse than any finite value in this direction.
+    #[inline]
+    #[must_use]
+    pub const fn worst(self) -> f64 {
+        match self {
+            Self::Minimize => f64::INFINITY,
+            Self::Maximize => f64::NEG_INFINITY,
+        }
+    }
+}
+
 pub trait Objective {
     fn better(a: f64, b: f64) -> bool;
+
+    /// Direction of this objective.
+    #[inline]
+    #[must_use]
+    fn direction() -> Direction {
+        if Self::better(0.0, 1.0) {
+            Direction::Minimize
+        } else {
+            Direction::Maximize
+        }
+    }
+
+    /// Fitness worse than any finite value, given to points that could not be evaluated.
+    #[inline]
+    #[must_use]
+    fn worst() -> f64 {
+        Self::direction().worst()
+    }
 }
 
 pub struct Minimization;
@@ -33,4 +82,12 @@ mod tests {
         assert!(Minimization::better(b, a));
         assert!(!Minimization::better(a, b));
     }
+
+    #[test]
+    fn direction_matches_objective() {
+        assert_eq!(Minimization::direction(), Direction::Minimize);
+        assert_eq!(Maximization::direction(), Direction::Maximize);
+        assert!(Maximization::direction().better(2.0, 1.0));
+        assert!(Maximization::worst() < f64::MIN);
+    }
 }
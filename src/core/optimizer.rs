@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use crate::{
+    core::{Decoder, Report, TerminationReason},
+    hho::HhoError,
+};
+
+/// Amount of work an optimizer may spend in a single run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Budget {
+    /// Number of iterations (generations) after initialization.
+    Iterations(usize),
+
+    /// Number of decoder calls, initialization included.
+    Evaluations(usize),
+
+    /// Wall-clock time, initialization included. Optimizers estimate the cost of an iteration
+    /// and do not start one they do not expect to finish before the deadline.
+    WallClock(Duration),
+}
+
+impl Budget {
+    /// Whether a run that performed `iters` iterations and `evals` evaluations in `elapsed`
+    /// is done.
+    ///
+    /// Pass the elapsed time plus the expected cost of the next iteration to ask whether that
+    /// iteration still fits in a wall-clock budget.
+    #[inline]
+    #[must_use]
+    pub fn exhausted(&self, iters: usize, evals: usize, elapsed: Duration) -> bool {
+        match *self {
+            Self::Iterations(n) => iters >= n,
+            Self::Evaluations(n) => evals >= n,
+            Self::WallClock(d) => elapsed >= d,
+        }
+    }
+
+    /// Reason recorded in [`Report::stop_reason`] when the budget ends a run.
+    #[inline]
+    #[must_use]
+    pub const fn reason(&self) -> TerminationReason {
+        match self {
+            Self::Iterations(_) => TerminationReason::MaxIters,
+            Self::Evaluations(_) => TerminationReason::MaxEvals,
+            Self::WallClock(_) => TerminationReason::TimeLimit,
+        }
+    }
+
+    /// Fraction of the budget already used, in `[0, 1]`.
+    #[inline]
+    #[must_use]
+    pub fn progress(&self, iters: usize, evals: usize, elapsed: Duration) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let p = match *self {
+            Self::Iterations(n) => iters as f64 / n.max(1) as f64,
+            Self::Evaluations(n) => evals as f64 / n.max(1) as f64,
+            Self::WallClock(d) => elapsed.as_secs_f64() / d.as_secs_f64().max(f64::MIN_POSITIVE),
+        };
+        p.min(1.0)
+    }
+}
+
+/// Common interface of every optimization algorithm in the crate, so experiments can treat
+/// HHO and the baselines interchangeably.
+///
+/// Note that [`crate::hho::Hho`] also has an inherent `run(&self, decoder)`; use
+/// `Optimizer::run(&mut hho, decoder, budget)` (or generic code) to reach this one.
+pub trait Optimizer {
+    /// Short identifier used in experiment tables.
+    fn name(&self) -> &'static str;
+
+    /// Optimizes `decoder` within `budget` and returns the resulting [`Report`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HhoError`] if the optimizer cannot run with its settings or if the
+    /// decoder fails; in the last case [`HhoError::report`] describes the run up to the
+    /// failure.
+    fn run(&mut self, decoder: &dyn Decoder, budget: Budget) -> Result<Report, HhoError>;
+
+    /// Fingerprint of the settings, as in [`Report::config_hash`]; `None` (the default) for
+    /// optimizers that do not compute one.
+    fn config_hash(&self) -> Option<u64> {
+        None
+    }
+}
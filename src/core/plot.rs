@@ -0,0 +1,210 @@
+//! Convergence plots rendered with `plotters` (feature `plotting`).
+
+use std::{ops::Range, path::Path};
+
+use plotters::prelude::*;
+use thiserror::Error;
+
+use crate::core::{Report, ReportSet};
+
+#[derive(Debug, Error)]
+pub enum PlotError {
+    /// Plots are SVG and animations GIF; the extension of the path selects the format.
+    #[error("unsupported plot format: {0:?} (use .svg, or .gif for animations)")]
+    UnsupportedFormat(String),
+
+    #[error("nothing to plot: the convergence curve is empty")]
+    Empty,
+
+    #[error("nothing to animate: the trajectory is empty")]
+    EmptyTrajectory,
+
+    #[error("writing the plot failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("drawing failed: {0}")]
+    Draw(String),
+}
+
+impl Report {
+    /// Renders the convergence curve (best fitness per recorded iteration) to an SVG file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PlotError`] if `path` is not an `.svg` file, if the curve is empty or if
+    /// drawing fails.
+    pub fn plot_convergence(&self, path: impl AsRef<Path>) -> Result<(), PlotError> {
+        let x = iterations(self);
+        let curves = Curves {
+            x: &x,
+            main: &self.convergence_curve,
+            band: None,
+        };
+        curves.draw(path.as_ref(), "Convergence")
+    }
+}
+
+impl ReportSet {
+    /// Renders the median convergence curve of the runs with the `[lower_pct, upper_pct]`
+    /// percentile band (see [`ReportSet::curve_bands`]) to an SVG file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PlotError`] if `path` is not an `.svg` file, if every curve is empty or if
+    /// drawing fails.
+    pub fn plot_convergence(
+        &self,
+        path: impl AsRef<Path>,
+        lower_pct: f64,
+        upper_pct: f64,
+    ) -> Result<(), PlotError> {
+        let bands = self.curve_bands(lower_pct, upper_pct);
+        // As curvas são alinhadas pelo índice; o eixo x vem da execução mais longa
+        let x = self
+            .reports
+            .iter()
+            .max_by_key(|r| r.convergence_curve.len())
+            .map(iterations)
+            .unwrap_or_default();
+        let curves = Curves {
+            x: &x,
+            main: &bands.median,
+            band: Some((&bands.lower, &bands.upper)),
+        };
+        curves.draw(path.as_ref(), "Convergence (median)")
+    }
+}
+
+/// Iteração de cada ponto da curva (relatórios antigos não têm `curve_iters`)
+fn iterations(report: &Report) -> Vec<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    (0..report.convergence_curve.len())
+        .map(|i| report.curve_iters.get(i).copied().unwrap_or(i + 1) as f64)
+        .collect()
+}
+
+struct Curves<'a> {
+    x: &'a [f64],
+    main: &'a [f64],
+    band: Option<(&'a [f64], &'a [f64])>,
+}
+
+impl Curves<'_> {
+    fn draw(&self, path: &Path, title: &str) -> Result<(), PlotError> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if ext != "svg" {
+            return Err(PlotError::UnsupportedFormat(ext));
+        }
+        if self.main.is_empty() {
+            return Err(PlotError::Empty);
+        }
+        let root = SVGBackend::new(path, (800, 500)).into_drawing_area();
+        self.render(&root, title)
+            .map_err(|e| PlotError::Draw(e.to_string()))
+    }
+
+    fn render<DB: DrawingBackend>(
+        &self,
+        root: &DrawingArea<DB, plotters::coord::Shift>,
+        title: &str,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        root.fill(&WHITE)?;
+        let values = self
+            .main
+            .iter()
+            .chain(
+                self.band
+                    .into_iter()
+                    .flat_map(|(lo, hi)| lo.iter().chain(hi)),
+            )
+            .copied()
+            .filter(|v| v.is_finite());
+        let y = padded(values);
+        let x = padded(self.x.iter().copied());
+
+        let mut chart = ChartBuilder::on(root)
+            .caption(title, ("sans-serif", 24))
+            .margin(12)
+            .x_label_area_size(40)
+            .y_label_area_size(70)
+            .build_cartesian_2d(x, y)?;
+        chart
+            .configure_mesh()
+            .x_desc("iteration")
+            .y_desc("best fitness")
+            .draw()?;
+
+        if let Some((lo, hi)) = self.band {
+            let upper = self.x.iter().copied().zip(hi.iter().copied());
+            let lower = self.x.iter().copied().zip(lo.iter().copied()).rev();
+            chart.draw_series(std::iter::once(Polygon::new(
+                upper.chain(lower).collect::<Vec<_>>(),
+                BLUE.mix(0.2).filled(),
+            )))?;
+        }
+        chart.draw_series(LineSeries::new(
+            self.x.iter().copied().zip(self.main.iter().copied()),
+            BLUE.stroke_width(2),
+        ))?;
+        root.present()
+    }
+}
+
+/// Intervalo que contém todos os valores, com uma pequena folga
+fn padded(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (lo, hi) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if !lo.is_finite() {
+        return 0.0..1.0;
+    }
+    let pad = ((hi - lo) * 0.05).max(1e-12);
+    (lo - pad)..(hi + pad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(curve: &[f64]) -> Report {
+        Report {
+            convergence_curve: curve.to_vec(),
+            ..Report::empty()
+        }
+    }
+
+    #[test]
+    fn writes_svg_files() {
+        let dir = std::env::temp_dir();
+        let single = dir.join(format!("kambo-plot-{}.svg", std::process::id()));
+        let bands = dir.join(format!("kambo-bands-{}.svg", std::process::id()));
+
+        report(&[4.0, 2.0, 1.0]).plot_convergence(&single).unwrap();
+        ReportSet::new(vec![report(&[4.0, 2.0, 1.0]), report(&[3.0, 2.5])])
+            .plot_convergence(&bands, 25.0, 75.0)
+            .unwrap();
+
+        for path in [single, bands] {
+            let svg = std::fs::read_to_string(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert!(svg.contains("<svg"));
+            assert!(svg.contains("Convergence"));
+        }
+    }
+
+    #[test]
+    fn rejects_other_formats_and_empty_curves() {
+        assert!(matches!(
+            report(&[1.0]).plot_convergence("out.png"),
+            Err(PlotError::UnsupportedFormat(ext)) if ext == "png"
+        ));
+        assert!(matches!(
+            report(&[]).plot_convergence("out.svg"),
+            Err(PlotError::Empty)
+        ));
+    }
+}
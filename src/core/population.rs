@@ -0,0 +1,458 @@
+use std::{
+    ops::{Index, IndexMut},
+    slice::{ChunksExact, ChunksExactMut},
+};
+
+use crate::core::{
+    Decoder, DecoderError, Objective, Scalar,
+    utils::{self, ShapeError},
+};
+
+/// Positions of a population stored row-major in one contiguous buffer (`len × dim`).
+///
+/// Row `i` is the position of individual `i`. Compared to `Vec<Vec<f64>>` this needs a single
+/// allocation and keeps the whole population in one cache-friendly block. The element type
+/// is any [`Scalar`], `f64` by default.
+///
+/// The buffer has the standard (C order) layout of a `len × dim` matrix, so matrix libraries
+/// can borrow it without copying: with `ndarray`,
+/// `ArrayView2::from_shape(pop.shape(), pop.as_slice())` views it and
+/// `Population::from_flat(array.into_raw_vec(), dim)` takes a standard-layout `Array2` back.
+#[allow(clippy::derive_partial_eq_without_eq)] // floats não são Eq
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Population<T: Scalar = f64> {
+    data: Vec<T>,
+    dim: usize,
+}
+
+impl<T: Scalar> Population<T> {
+    /// Empty population of `dim`-dimensional positions.
+    #[must_use]
+    pub const fn new(dim: usize) -> Self {
+        Self {
+            data: Vec::new(),
+            dim,
+        }
+    }
+
+    /// Empty population with room for `n` positions.
+    #[must_use]
+    pub fn with_capacity(n: usize, dim: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(n * dim),
+            dim,
+        }
+    }
+
+    /// `n` positions filled with zeros.
+    #[must_use]
+    pub fn zeros(n: usize, dim: usize) -> Self {
+        Self {
+            data: vec![T::zero(); n * dim],
+            dim,
+        }
+    }
+
+    /// Population holding a copy of `rows`, or `None` if they do not all have the same length.
+    #[must_use]
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> Option<Self> {
+        let dim = rows.first().map_or(0, |r| r.as_ref().len());
+        let mut population = Self::with_capacity(rows.len(), dim);
+        for row in rows {
+            let row = row.as_ref();
+            if row.len() != dim {
+                return None;
+            }
+            population.data.extend_from_slice(row);
+        }
+        Some(population)
+    }
+
+    /// Wraps a row-major buffer, or returns `None` if its length is not a multiple of `dim`.
+    #[must_use]
+    pub fn from_flat(data: Vec<T>, dim: usize) -> Option<Self> {
+        let fits = if dim == 0 {
+            data.is_empty()
+        } else {
+            data.len().is_multiple_of(dim)
+        };
+        fits.then_some(Self { data, dim })
+    }
+
+    /// Number of positions.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self.dim {
+            0 => 0,
+            d => self.data.len() / d,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Dimension of each position.
+    #[inline]
+    #[must_use]
+    pub const fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// `(len, dim)`, the shape of the population as a matrix.
+    #[inline]
+    #[must_use]
+    pub const fn shape(&self) -> (usize, usize) {
+        (self.len(), self.dim)
+    }
+
+    /// Position `i`, or `None` if out of range.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, i: usize) -> Option<&[T]> {
+        self.data.get(i * self.dim..(i + 1) * self.dim)
+    }
+
+    /// Position `i` for in-place edits, or `None` if out of range.
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut [T]> {
+        self.data.get_mut(i * self.dim..(i + 1) * self.dim)
+    }
+
+    /// Iterates over the positions in order.
+    #[inline]
+    pub fn iter(&self) -> ChunksExact<'_, T> {
+        self.data.chunks_exact(self.dim.max(1))
+    }
+
+    /// Iterates mutably over the positions in order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.data.chunks_exact_mut(self.dim.max(1))
+    }
+
+    /// Appends a position.
+    ///
+    /// # Errors
+    ///
+    /// [`ShapeError::Length`] if `x` does not have `dim` elements; nothing is appended.
+    pub fn push(&mut self, x: &[T]) -> Result<(), ShapeError> {
+        utils::check_len(self.dim, x.len())?;
+        self.data.extend_from_slice(x);
+        Ok(())
+    }
+
+    /// Removes every position, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Swaps positions `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        if i == j {
+            return;
+        }
+        let (lo, hi) = (i.min(j), i.max(j));
+        let (head, tail) = self.data.split_at_mut(hi * self.dim);
+        head[lo * self.dim..(lo + 1) * self.dim].swap_with_slice(&mut tail[..self.dim]);
+    }
+
+    /// The row-major buffer.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// Copies the positions into one vector each.
+    #[must_use]
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        self.iter().map(<[T]>::to_vec).collect()
+    }
+
+    /// Returns the row-major buffer.
+    #[must_use]
+    pub fn into_flat(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: Scalar> Clone for Population<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            dim: self.dim,
+        }
+    }
+
+    /// Reuses the buffer of `self` when it is large enough.
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.dim = source.dim;
+    }
+}
+
+impl<T: Scalar> Index<usize> for Population<T> {
+    type Output = [T];
+
+    #[inline]
+    fn index(&self, i: usize) -> &[T] {
+        &self.data[i * self.dim..(i + 1) * self.dim]
+    }
+}
+
+impl<T: Scalar> IndexMut<usize> for Population<T> {
+    #[inline]
+    fn index_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.dim..(i + 1) * self.dim]
+    }
+}
+
+impl<'a, T: Scalar> IntoIterator for &'a Population<T> {
+    type Item = &'a [T];
+    type IntoIter = ChunksExact<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Scalar> IntoIterator for &'a mut Population<T> {
+    type Item = &'a mut [T];
+    type IntoIter = ChunksExactMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Positions paired with their fitness values, always of the same length.
+///
+/// Replaces a [`Population`] and a `Vec<f64>` passed side by side: every constructor and
+/// mutator keeps one fitness value per position, so the accessors never need to check it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoredPopulation {
+    positions: Population,
+    fitness: Vec<f64>,
+}
+
+impl ScoredPopulation {
+    /// Empty population of `dim`-dimensional positions.
+    #[must_use]
+    pub const fn new(dim: usize) -> Self {
+        Self {
+            positions: Population::new(dim),
+            fitness: Vec::new(),
+        }
+    }
+
+    /// Pairs `positions` with `fitness`, or returns `None` if their lengths differ.
+    #[must_use]
+    pub fn from_parts(positions: Population, fitness: Vec<f64>) -> Option<Self> {
+        (positions.len() == fitness.len()).then_some(Self { positions, fitness })
+    }
+
+    /// Evaluates every position with `decoder`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the decoder.
+    pub fn evaluate(decoder: &dyn Decoder, positions: Population) -> Result<Self, DecoderError> {
+        let fitness = utils::try_evaluate_all(decoder, &positions)?;
+        Ok(Self { positions, fitness })
+    }
+
+    /// Number of positions.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.fitness.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.fitness.is_empty()
+    }
+
+    #[must_use]
+    pub const fn dim(&self) -> usize {
+        self.positions.dim()
+    }
+
+    #[must_use]
+    pub const fn positions(&self) -> &Population {
+        &self.positions
+    }
+
+    #[must_use]
+    pub fn fitness(&self) -> &[f64] {
+        &self.fitness
+    }
+
+    /// Position `i` and its fitness, or `None` if out of range.
+    #[must_use]
+    pub fn get(&self, i: usize) -> Option<(&[f64], f64)> {
+        Some((self.positions.get(i)?, self.fitness[i]))
+    }
+
+    /// Replaces individual `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of range or `x` does not have `dim` elements.
+    pub fn set(&mut self, i: usize, x: &[f64], fitness: f64) {
+        self.positions[i].copy_from_slice(x);
+        self.fitness[i] = fitness;
+    }
+
+    /// Appends an individual.
+    ///
+    /// # Errors
+    ///
+    /// [`ShapeError::Length`] if `x` does not have `dim` elements; nothing is appended.
+    pub fn push(&mut self, x: &[f64], fitness: f64) -> Result<(), ShapeError> {
+        self.positions.push(x)?;
+        self.fitness.push(fitness);
+        Ok(())
+    }
+
+    /// Iterates over `(position, fitness)` pairs in order.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&[f64], f64)> + '_ {
+        self.positions.iter().zip(self.fitness.iter().copied())
+    }
+
+    /// Index of the best individual (the first one on ties), or `None` if empty.
+    #[must_use]
+    pub fn best_index<O: Objective>(&self) -> Option<usize> {
+        utils::best_index_with::<O>(&self.fitness)
+    }
+
+    /// The best individual, or `None` if empty.
+    #[must_use]
+    pub fn best<O: Objective>(&self) -> Option<(&[f64], f64)> {
+        self.get(self.best_index::<O>()?)
+    }
+
+    /// The worst individual (the first one on ties), or `None` if empty.
+    #[must_use]
+    pub fn worst<O: Objective>(&self) -> Option<(&[f64], f64)> {
+        let i = (0..self.len()).reduce(|w, i| {
+            if O::better(self.fitness[w], self.fitness[i]) {
+                i
+            } else {
+                w
+            }
+        })?;
+        self.get(i)
+    }
+
+    /// Indices from best to worst; ties keep their original order.
+    #[must_use]
+    pub fn sorted_indices<O: Objective>(&self) -> Vec<usize> {
+        utils::argsort_by_fitness::<O>(&self.fitness)
+    }
+
+    /// Sorts from best to worst in place and returns the permutation applied, see
+    /// [`utils::sort_by_fitness_with`].
+    pub fn sort<O: Objective>(&mut self) -> Vec<usize> {
+        utils::sort_pairs::<O>(&mut self.positions, &mut self.fitness)
+    }
+
+    /// Mean position, or `None` if empty.
+    #[must_use]
+    pub fn centroid(&self) -> Option<Vec<f64>> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut mean = vec![0.0; self.dim()];
+        for x in &self.positions {
+            for (m, xi) in mean.iter_mut().zip(x) {
+                *m += xi;
+            }
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = self.len() as f64;
+        for m in &mut mean {
+            *m /= n;
+        }
+        Some(mean)
+    }
+
+    /// Splits into the positions and the fitness values.
+    #[must_use]
+    pub fn into_parts(self) -> (Population, Vec<f64>) {
+        (self.positions, self.fitness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Maximization, Minimization};
+
+    #[test]
+    fn rows_are_contiguous() {
+        let mut pop = Population::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+        pop.push(&[5.0, 6.0]).unwrap();
+        assert!(pop.push(&[7.0]).is_err());
+        pop[0][1] = 9.0;
+        pop.swap(2, 0);
+
+        assert_eq!(pop.len(), 3);
+        assert_eq!(pop.shape(), (3, 2));
+        assert_eq!(pop.as_slice(), &[5.0, 6.0, 3.0, 4.0, 1.0, 9.0]);
+        assert_eq!(pop.get(1), Some(&[3.0, 4.0][..]));
+        assert_eq!(pop.get(3), None);
+        assert_eq!(
+            pop.to_rows(),
+            vec![vec![5.0, 6.0], vec![3.0, 4.0], vec![1.0, 9.0]]
+        );
+    }
+
+    #[test]
+    fn scored_population_keeps_pairs_together() {
+        let positions = Population::from_rows(&[[1.0, 1.0], [0.0, 0.0], [3.0, -1.0]]).unwrap();
+        assert!(ScoredPopulation::from_parts(positions.clone(), vec![1.0]).is_none());
+        let mut scored = ScoredPopulation::from_parts(positions, vec![2.0, 0.0, 5.0]).unwrap();
+
+        assert_eq!(scored.best::<Minimization>(), Some((&[0.0, 0.0][..], 0.0)));
+        assert_eq!(
+            scored.worst::<Minimization>(),
+            Some((&[3.0, -1.0][..], 5.0))
+        );
+        assert_eq!(
+            scored.best::<Maximization>(),
+            scored.worst::<Minimization>()
+        );
+        assert_eq!(scored.sorted_indices::<Minimization>(), vec![1, 0, 2]);
+        assert_eq!(scored.centroid(), Some(vec![4.0 / 3.0, 0.0]));
+
+        scored.push(&[-1.0, 0.0], -1.0).unwrap();
+        assert_eq!(scored.sort::<Minimization>(), vec![3, 1, 0, 2]);
+        assert_eq!(scored.fitness(), &[-1.0, 0.0, 2.0, 5.0]);
+        assert_eq!(scored.positions()[0], [-1.0, 0.0]);
+        assert_eq!(scored.iter().len(), 4);
+        assert!(ScoredPopulation::new(2).best::<Minimization>().is_none());
+    }
+
+    #[test]
+    fn rejects_ragged_input() {
+        assert!(Population::from_rows(&[vec![1.0], vec![2.0, 3.0]]).is_none());
+        assert!(Population::from_flat(vec![1.0, 2.0, 3.0], 2).is_none());
+        assert_eq!(Population::<f64>::from_flat(vec![], 0).unwrap().len(), 0);
+    }
+}
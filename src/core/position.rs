@@ -0,0 +1,91 @@
+use std::ops::Deref;
+
+use crate::core::Scalar;
+
+/// A point of a search space whose dimension was checked against its [`crate::core::Bounds`].
+///
+/// Built with [`crate::core::Bounds::position`], which rejects coordinates of another
+/// dimension with [`crate::core::bounds::BoundsError::PointDim`] instead of letting them reach
+/// the optimizer. It dereferences to a slice, so it goes wherever a `&[T]` does, and
+/// [`crate::hho::Run::inject`] takes it directly.
+#[allow(clippy::derive_partial_eq_without_eq)] // floats não são Eq
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Position<T: Scalar = f64> {
+    coords: Vec<T>,
+}
+
+impl<T: Scalar> Position<T> {
+    /// Posição sem verificação; quem chama garante a dimensão
+    pub(crate) const fn new_unchecked(coords: Vec<T>) -> Self {
+        Self { coords }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn dim(&self) -> usize {
+        self.coords.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.coords
+    }
+
+    /// Coordinates, mutably; the dimension cannot change through a slice.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.coords
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.coords
+    }
+}
+
+impl<T: Scalar> Deref for Position<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.coords
+    }
+}
+
+impl<T: Scalar> AsRef<[T]> for Position<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.coords
+    }
+}
+
+impl<T: Scalar> From<Position<T>> for Vec<T> {
+    fn from(position: Position<T>) -> Self {
+        position.coords
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Bounds, bounds::BoundsError};
+
+    #[test]
+    fn positions_of_another_dimension_are_rejected() {
+        let bounds = Bounds::uniform(-1.0, 1.0, 3).unwrap();
+        let x = bounds.position(vec![0.5, 2.0, -3.0]).unwrap();
+        assert_eq!(x.dim(), 3);
+        assert_eq!(*x, [0.5, 1.0, -1.0]);
+
+        let wrong = bounds.position(vec![0.0; 2]);
+        assert!(matches!(
+            wrong,
+            Err(BoundsError::PointDim {
+                expected: 3,
+                received: 2
+            })
+        ));
+        assert!(bounds.check_dim(&[0.0; 4]).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! A finished run re-evaluated with another decoder, e.g. the true model behind a surrogate.
+
+use crate::core::{Decoder, DecoderError, Direction, Report};
+
+/// Positions of the run that [`Report::replay`] re-evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReplaySource {
+    /// Each best so far of [`Report::evaluations`], in the order the run found them.
+    Evaluations,
+    /// Each point of [`Report::trajectory`].
+    Trajectory,
+    /// Only [`Report::best_position`].
+    Best,
+}
+
+/// Outcome of [`Report::replay`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub direction: Direction,
+    pub source: ReplaySource,
+    /// Fitness of each replayed position under the new decoder, in run order: the true
+    /// quality of what the run held as its best, which need not improve monotonically.
+    pub fitness: Vec<f64>,
+    /// Fitness of the same positions in the run, aligned with `fitness`; empty for
+    /// [`ReplaySource::Trajectory`], whose fitness the run does not record.
+    pub original: Vec<f64>,
+    /// Entries of the evaluation archive up to and including each position, aligned with
+    /// `fitness`; empty unless the source is [`ReplaySource::Evaluations`].
+    pub evals: Vec<usize>,
+    /// Best of `fitness` so far, the convergence curve under the new decoder.
+    pub curve: Vec<f64>,
+}
+
+impl Replay {
+    /// Position in run order of the best replayed fitness, or `None` if nothing was replayed.
+    #[must_use]
+    pub fn best_index(&self) -> Option<usize> {
+        (0..self.fitness.len()).reduce(|best, i| {
+            if self.direction.better(self.fitness[i], self.fitness[best]) {
+                i
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Pearson correlation between the original and the replayed fitness, or `None` without
+    /// two original values or with a constant series. Close to 1 when the run's decoder ranks
+    /// positions as the new one does.
+    #[must_use]
+    pub fn correlation(&self) -> Option<f64> {
+        let n = self.original.len();
+        if n < 2 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / n as f64;
+        let (ma, mb) = (mean(&self.original), mean(&self.fitness));
+        let (mut ab, mut aa, mut bb) = (0.0, 0.0, 0.0);
+        for (a, b) in self.original.iter().zip(&self.fitness) {
+            let (da, db) = (a - ma, b - mb);
+            ab = da.mul_add(db, ab);
+            aa = da.mul_add(da, aa);
+            bb = db.mul_add(db, bb);
+        }
+        let r = ab / (aa * bb).sqrt();
+        r.is_finite().then_some(r)
+    }
+}
+
+impl Report {
+    /// Re-evaluates the positions this run recorded with `decoder`, e.g. a higher-fidelity
+    /// model than the surrogate the run optimized, or another metric.
+    ///
+    /// The positions come from the richest record available: the successive best solutions
+    /// of [`Report::evaluations`] when evaluations were recorded, else the rabbit positions
+    /// of [`Report::trajectory`], else the best position alone. The replayed fitness is
+    /// compared in the direction of the run. An archive capped by the memory budget (see
+    /// [`crate::core::Degradation::EvaluationsCapped`]) misses the later improvements.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `decoder`.
+    pub fn replay(&self, decoder: &dyn Decoder) -> Result<Replay, DecoderError> {
+        let mut replay = Replay {
+            direction: self.direction,
+            source: ReplaySource::Best,
+            fitness: Vec::new(),
+            original: Vec::new(),
+            evals: Vec::new(),
+            curve: Vec::new(),
+        };
+        if let Some(archive) = self
+            .evaluations
+            .as_ref()
+            .filter(|a| !a.entries().is_empty())
+        {
+            replay.source = ReplaySource::Evaluations;
+            let mut best = self.direction.worst();
+            for (k, s) in archive.entries().iter().enumerate() {
+                if replay.original.is_empty() || self.direction.better(s.fitness, best) {
+                    best = s.fitness;
+                    replay.push(decoder.decode(&s.position)?);
+                    replay.original.push(s.fitness);
+                    replay.evals.push(k + 1);
+                }
+            }
+        } else if !self.trajectory.is_empty() {
+            replay.source = ReplaySource::Trajectory;
+            for x in &self.trajectory {
+                replay.push(decoder.decode(x)?);
+            }
+        } else if !self.best_position.is_empty() {
+            replay.push(decoder.decode(&self.best_position)?);
+            replay.original.push(self.best_fitness);
+        }
+        Ok(replay)
+    }
+}
+
+impl Replay {
+    /// Acrescenta uma fitness reavaliada e estende a curva
+    fn push(&mut self, f: f64) {
+        let best = match self.curve.last() {
+            Some(&b) if !self.direction.better(f, b) => b,
+            _ => f,
+        };
+        self.fitness.push(f);
+        self.curve.push(best);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, core::Bounds, hho::HhoBuilder};
+
+    /// Esfera deslocada: o verdadeiro ótimo não é o do substituto
+    struct Shifted;
+
+    impl Decoder for Shifted {
+        fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+            Ok(x.iter().map(|v| (v - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn replay_follows_the_best_solutions_of_the_run() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+                .pop_size(10)
+                .max_iters(30)
+                .seed(4)
+        };
+        let report = builder()
+            .record_evaluations()
+            .build()
+            .run(&Function::Sphere)
+            .unwrap();
+        let replay = report.replay(&Shifted).unwrap();
+        assert_eq!(replay.source, ReplaySource::Evaluations);
+        assert_eq!(replay.fitness.len(), replay.evals.len());
+        assert!(replay.evals.windows(2).all(|w| w[0] < w[1]));
+        assert!(replay.original.windows(2).all(|w| w[1] < w[0]));
+        assert_eq!(replay.original.last(), Some(&report.best_fitness));
+        assert!(replay.curve.windows(2).all(|w| w[1] <= w[0]));
+        // O substituto converge para 0, longe do ótimo em 0.5
+        let last = *replay.fitness.last().unwrap();
+        assert!((last - 0.75).abs() < 0.1, "{last}");
+        assert!(replay.correlation().unwrap() > 0.5);
+        assert!(replay.best_index().is_some());
+
+        let report = builder()
+            .record_trajectory(5)
+            .build()
+            .run(&Function::Sphere)
+            .unwrap();
+        let replay = report.replay(&Shifted).unwrap();
+        assert_eq!(replay.source, ReplaySource::Trajectory);
+        assert_eq!(replay.fitness.len(), report.trajectory.len());
+        assert!(replay.original.is_empty() && replay.correlation().is_none());
+    }
+}
@@ -1,28 +1,461 @@
-use std::time::Duration;
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
 
+use crate::{
+    core::{Direction, EvaluationArchive, Objective, RunMetadata},
+    hho::{OperatorStats, PhaseCounts},
+};
+
+/// An evaluated point of the search space.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution {
+    pub position: Vec<f64>,
+    pub fitness: f64,
+}
+
+/// A strict improvement of the best fitness found, see [`Report::improvements`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Improvement {
+    /// Iteration during which it happened, counting from 1 like [`Report::curve_iters`];
+    /// improvements of the initial population carry the iteration count the run started
+    /// from (0 unless its counters continue another run).
+    pub iteration: usize,
+    /// Evaluations spent when it was found, this one included.
+    pub evaluation: usize,
+    pub fitness: f64,
+    /// Distance to the previous improvement, `0` for the first one.
+    pub delta: f64,
+}
+
+/// Best hawk of one niche, as found by the niching mode.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Niche {
+    pub position: Vec<f64>,
+    pub fitness: f64,
+    /// Número de falcões na espécie ao final da execução
+    pub size: usize,
+}
+
+/// Why a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminationReason {
+    /// The iteration budget was used up.
+    MaxIters,
+    /// The evaluation budget was used up.
+    MaxEvals,
+    /// The best fitness reached the target of a [`crate::termination::TargetFitness`].
+    TargetReached,
+    /// The best fitness stopped improving ([`crate::termination::MaxStall`]).
+    Stalled,
+    /// A wall-clock budget or time criterion ran out.
+    TimeLimit,
+    /// A [`crate::termination::CancellationToken`] was cancelled.
+    Cancelled,
+    /// The early-stop closure asked the run to stop.
+    EarlyStop,
+    /// A step-wise run was finished by the caller before any rule fired.
+    Manual,
+    /// The decoder returned an error; the report holds the state before the failed call.
+    Error,
+    /// A user-defined [`crate::termination::Criterion`] fired.
+    Custom(&'static str),
+}
+
+impl TerminationReason {
+    /// Short snake-case name, e.g. `"max_iters"`.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::MaxIters => "max_iters",
+            Self::MaxEvals => "max_evals",
+            Self::TargetReached => "target_reached",
+            Self::Stalled => "stalled",
+            Self::TimeLimit => "time_limit",
+            Self::Cancelled => "cancelled",
+            Self::EarlyStop => "early_stop",
+            Self::Manual => "manual",
+            Self::Error => "error",
+            Self::Custom(name) => name,
+        }
+    }
+
+    /// Whether the run ended because the search converged (target reached or stalled), as
+    /// opposed to running out of budget or being interrupted.
+    #[must_use]
+    pub const fn converged(&self) -> bool {
+        matches!(self, Self::TargetReached | Self::Stalled)
+    }
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Serializado pelo nome curto, como no `Display`
+#[cfg(feature = "serde")]
+impl serde::Serialize for TerminationReason {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TerminationReason {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "max_iters" => Self::MaxIters,
+            "max_evals" => Self::MaxEvals,
+            "target_reached" => Self::TargetReached,
+            "stalled" => Self::Stalled,
+            "time_limit" => Self::TimeLimit,
+            "cancelled" => Self::Cancelled,
+            "early_stop" => Self::EarlyStop,
+            "manual" => Self::Manual,
+            "error" => Self::Error,
+            // Nomes de critérios do usuário precisam viver para sempre; são poucos e curtos
+            _ => Self::Custom(Box::leak(name.into_boxed_str())),
+        })
+    }
+}
+
+/// Which iterations are recorded in the convergence curve (and the per-iteration series that
+/// go with it). The last iteration of a run is always recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveSampling {
+    /// Every iteration.
+    #[default]
+    All,
+
+    /// Every `k`-th iteration.
+    Every(usize),
+
+    /// About `per_decade` log-spaced iterations per power of ten (1, 2, 3, 4, 6, 7, 8, 10, 13,
+    /// 16, 20, … with `per_decade = 10`), which keeps million-iteration curves small while
+    /// preserving the early descent.
+    Log { per_decade: usize },
+
+    /// Memory-bounded mode: a uniform reservoir sample of at most `size` iterations, kept in
+    /// iteration order, whose last point is always the final iteration. Every
+    /// per-iteration series of the report then uses `O(size)` memory however long the run
+    /// is; the sample is reproducible for a seeded run.
+    Reservoir { size: usize },
+}
+
+impl CurveSampling {
+    /// Whether iteration `t` (counting from 1) is recorded; for [`CurveSampling::Reservoir`],
+    /// whether it is offered to the reservoir.
+    #[must_use]
+    pub fn records(self, t: usize) -> bool {
+        match self {
+            Self::All | Self::Reservoir { .. } => true,
+            Self::Every(k) => t.is_multiple_of(k.max(1)),
+            Self::Log { per_decade } => {
+                if t <= 1 {
+                    return true;
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let bucket = |t: usize| (per_decade.max(1) as f64 * (t as f64).log10()).floor();
+                bucket(t) > bucket(t - 1)
+            }
+        }
+    }
+}
+
+/// A record scaled down during a run to stay within its memory budget, see
+/// [`crate::hho::HhoBuilder::memory_budget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Degradation {
+    /// [`Report::evaluations`] stopped growing after iteration `iteration`, at `kept`
+    /// entries.
+    EvaluationsCapped { iteration: usize, kept: usize },
+
+    /// [`Report::evaluations`] was dropped after iteration `iteration`.
+    EvaluationsDropped { iteration: usize },
+
+    /// From iteration `iteration` on, [`Report::trajectory`] keeps about one point in
+    /// `stride` of those it would have recorded, and the older points were thinned alike.
+    TrajectoryThinned { iteration: usize, stride: usize },
+
+    /// Same as [`Degradation::TrajectoryThinned`] for the convergence curve and every
+    /// per-iteration series aligned with it. [`Report::improvements`] is thinned along, always
+    /// keeping the latest improvement.
+    CurvesThinned { iteration: usize, stride: usize },
+
+    /// The final population was not copied to [`Report::population`].
+    PopulationDropped,
+}
+
+/// Outcome of an optimization run.
+///
+/// With the `serde` feature it (de)serializes with serde; [`Report::to_json_file`] and
+/// [`Report::to_csv`] cover the usual exports.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Report {
+    /// Direction of the objective the run optimized; decides which fitness is better.
+    pub direction: Direction,
     pub best_fitness: f64,
     pub best_position: Vec<f64>,
     pub convergence_curve: Vec<f64>,
+    /// Iteration (counting from 1) at which each point of `convergence_curve` was recorded.
+    pub curve_iters: Vec<usize>,
+    /// Evaluations spent when each point of `convergence_curve` was recorded.
+    pub curve_evals: Vec<usize>,
+    /// Rabbit position every k-th iteration, oldest first. Empty unless trajectory recording
+    /// is enabled.
+    pub trajectory: Vec<Vec<f64>>,
+    /// Dimension-wise diversity of the population at each recorded iteration, see
+    /// [`crate::core::utils::dimension_wise_diversity`] and [`Report::exploration`].
+    pub diversity: Vec<f64>,
+    /// Mean escape energy `|E|` of the hawks at each recorded iteration (HHO only).
+    pub energy: Vec<f64>,
+    /// Branches the hawks took at each recorded iteration (HHO only); a run that stalled
+    /// early in exploitation shows up here.
+    pub phases: Vec<PhaseCounts>,
+    /// How often each update operator improved the hawk it moved, over the whole run (HHO
+    /// only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub operators: OperatorStats,
+    /// Wall-clock time of each recorded iteration (HHO only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub iteration_times: Vec<Duration>,
+    /// Time spent inside the decoder (HHO only). Parallel evaluations add up, so in a
+    /// parallel run this can exceed [`Report::duration`]; see [`Report::overhead`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eval_time: Duration,
     pub iters: usize,
+    /// Decoder calls, in full-fidelity equivalents when low-fidelity screening is on.
     pub evals: usize,
     pub duration: Option<Duration>,
+    /// One entry per niche, best first. Empty unless niching is enabled.
+    pub niches: Vec<Niche>,
+    /// Number of landscape changes detected (dynamic objectives only).
+    pub landscape_changes: usize,
+    /// Hawks moved away for duplicating a better one (duplicate purge only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub duplicates_purged: usize,
+    /// Penalty coefficient in force at each recorded iteration, aligned with
+    /// [`Report::curve_iters`] (HHO with a decoder that reports one, such as
+    /// [`crate::constraints::Penalized`]; empty otherwise).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub penalty_coefficients: Vec<f64>,
+    /// Clusters formed by the population at each recorded iteration, aligned with
+    /// [`Report::curve_iters`], see [`crate::metrics::Grid`]. Empty unless cluster recording
+    /// is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub clusters: Vec<usize>,
+    /// Normalized occupancy entropy of the same grid, aligned with [`Report::clusters`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub occupancy_entropy: Vec<f64>,
+    /// NaN or infinite fitness values returned by the decoder (HHO only), see
+    /// [`crate::hho::NonFinite`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub non_finite: usize,
+    /// Low-fidelity screenings, whose cost is included in [`Report::evals`] (low-fidelity
+    /// screening only, see [`crate::hho::Fidelity`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub screened: usize,
+    /// Decoder calls spent by the local refiner of the main loop, included in
+    /// [`Report::evals`] (HHO with a local refiner only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refiner_evals: usize,
+    /// Best distinct solutions ever evaluated, best first. Empty unless the hall of fame is
+    /// enabled.
+    pub hall_of_fame: Vec<Solution>,
+    /// Every full-fidelity evaluation of the run, with nearest-neighbour queries. `None`
+    /// unless evaluation recording is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evaluations: Option<EvaluationArchive>,
+    /// Final position and fitness of every member of the population, in population order.
+    /// Empty unless population snapshots are enabled.
+    pub population: Vec<Solution>,
+    /// Every strict improvement of the best fitness found, oldest first (HHO only). The
+    /// evaluation stamps are exact when the hawks are evaluated one by one; evaluations of a
+    /// parallel sweep are stamped with the count at the end of the sweep. Counts against the
+    /// memory budget, see [`Degradation::CurvesThinned`] and [`Report::evals_to_target`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub improvements: Vec<Improvement>,
+    /// Records scaled down to respect the memory budget, in the order it happened. Empty
+    /// unless a memory budget is set and was exceeded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub degradations: Vec<Degradation>,
+    /// Why the run ended.
+    pub stop_reason: Option<TerminationReason>,
+    /// Master seed of the run; running again with the same seed reproduces it exactly.
+    pub seed: Option<u64>,
+    /// Lower and upper bound of each dimension searched (HHO only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bounds: Vec<(f64, f64)>,
+    /// Fingerprint of the settings of the run, see [`crate::hho::Hho::config_hash`] (HHO
+    /// only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub config_hash: Option<u64>,
+    /// Version, problem, machine, start time and tags of the run (HHO only).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: RunMetadata,
 }
 
 impl Report {
+    /// Report of a minimization run that found nothing yet. See [`Report::empty_for`] for a
+    /// given objective.
     #[must_use]
     pub const fn empty() -> Self {
         Self {
+            direction: Direction::Minimize,
             best_fitness: f64::INFINITY,
             best_position: Vec::new(),
             convergence_curve: Vec::new(),
+            curve_iters: Vec::new(),
+            curve_evals: Vec::new(),
+            trajectory: Vec::new(),
+            diversity: Vec::new(),
+            energy: Vec::new(),
+            phases: Vec::new(),
+            operators: OperatorStats::new(),
+            iteration_times: Vec::new(),
+            eval_time: Duration::ZERO,
             iters: 0,
             evals: 0,
             duration: None,
+            niches: Vec::new(),
+            landscape_changes: 0,
+            duplicates_purged: 0,
+            penalty_coefficients: Vec::new(),
+            clusters: Vec::new(),
+            occupancy_entropy: Vec::new(),
+            non_finite: 0,
+            screened: 0,
+            refiner_evals: 0,
+            hall_of_fame: Vec::new(),
+            evaluations: None,
+            population: Vec::new(),
+            improvements: Vec::new(),
+            degradations: Vec::new(),
+            stop_reason: None,
+            seed: None,
+            bounds: Vec::new(),
+            config_hash: None,
+            metadata: RunMetadata::new(),
         }
     }
 
+    /// Report of a run of objective `O` that found nothing yet: its best fitness is the worst
+    /// possible value in that direction.
+    #[must_use]
+    pub fn empty_for<O: Objective>() -> Self {
+        Self {
+            direction: O::direction(),
+            best_fitness: O::worst(),
+            ..Self::empty()
+        }
+    }
+
+    /// Whether this report's best fitness is strictly better than `other`'s, according to
+    /// this report's direction.
+    #[inline]
+    #[must_use]
+    pub fn is_better_than(&self, other: &Self) -> bool {
+        self.direction.better(self.best_fitness, other.best_fitness)
+    }
+
+    /// Joins the report of a run continued from this one (see
+    /// [`crate::hho::HhoBuilder::continue_counters`]) into a single report.
+    ///
+    /// The per-iteration records are concatenated and the best solution is the better of the
+    /// two. The counters, final population, niches and stop reason are those of `next`, whose
+    /// counters already include this run; times, operator statistics and event counts add up,
+    /// and the halls of fame are merged without duplicates.
+    #[must_use]
+    pub fn chain(mut self, next: Self) -> Self {
+        if self.best_position.is_empty() || next.is_better_than(&self) {
+            self.best_fitness = next.best_fitness;
+            self.best_position = next.best_position;
+            self.bounds = next.bounds;
+            self.config_hash = next.config_hash;
+        }
+        self.convergence_curve.extend(next.convergence_curve);
+        self.curve_iters.extend(next.curve_iters);
+        self.curve_evals.extend(next.curve_evals);
+        self.trajectory.extend(next.trajectory);
+        self.diversity.extend(next.diversity);
+        self.energy.extend(next.energy);
+        self.phases.extend(next.phases);
+        self.operators.merge(&next.operators);
+        self.iteration_times.extend(next.iteration_times);
+        self.eval_time += next.eval_time;
+        self.iters = next.iters;
+        self.evals = next.evals;
+        self.duration = match (self.duration, next.duration) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.niches = next.niches;
+        self.landscape_changes += next.landscape_changes;
+        self.duplicates_purged += next.duplicates_purged;
+        self.penalty_coefficients.extend(next.penalty_coefficients);
+        self.clusters.extend(next.clusters);
+        self.occupancy_entropy.extend(next.occupancy_entropy);
+        self.non_finite += next.non_finite;
+        self.screened += next.screened;
+        self.refiner_evals += next.refiner_evals;
+        self.metadata.merge(next.metadata);
+
+        // Mantém o maior dos dois halls, sem repetir posições
+        let capacity = self.hall_of_fame.len().max(next.hall_of_fame.len());
+        let direction = self.direction;
+        self.hall_of_fame.extend(next.hall_of_fame);
+        self.hall_of_fame.sort_by(|a, b| match direction {
+            Direction::Minimize => a.fitness.total_cmp(&b.fitness),
+            Direction::Maximize => b.fitness.total_cmp(&a.fitness),
+        });
+        let mut kept: Vec<Solution> = Vec::with_capacity(capacity);
+        for s in self.hall_of_fame.drain(..) {
+            if kept.len() < capacity && kept.iter().all(|k| k.position != s.position) {
+                kept.push(s);
+            }
+        }
+        self.hall_of_fame = kept;
+
+        match (&mut self.evaluations, next.evaluations) {
+            (Some(archive), Some(later)) => {
+                for s in later.entries() {
+                    archive.push(&s.position, s.fitness);
+                }
+            }
+            (slot, later) => {
+                if later.is_some() {
+                    *slot = later;
+                }
+            }
+        }
+        self.population = next.population;
+        // Só contam as melhoras da continuação que superam as anteriores
+        for mut event in next.improvements {
+            let last = self.improvements.last().map(|e| e.fitness);
+            if last.is_none_or(|l| direction.better(event.fitness, l)) {
+                event.delta = last.map_or(0.0, |l| (l - event.fitness).abs());
+                self.improvements.push(event);
+            }
+        }
+        self.degradations.extend(next.degradations);
+        self.stop_reason = next.stop_reason;
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn last(&self) -> Option<f64> {
@@ -35,4 +468,264 @@ impl Report {
         self.duration = Some(d);
         self
     }
+
+    /// Time the run spent outside the decoder (population updates, bookkeeping, reporting),
+    /// or `None` without a duration. Zero when parallel evaluation time exceeds the
+    /// wall-clock time; `eval_time / duration` then tells how many workers were kept busy.
+    #[must_use]
+    pub fn overhead(&self) -> Option<Duration> {
+        self.duration.map(|d| d.saturating_sub(self.eval_time))
+    }
+
+    /// Convergence curve indexed by evaluation count: `(evals, best_fitness)` pairs, the basis
+    /// of budget-fair comparisons between algorithms with different costs per iteration.
+    pub fn eval_curve(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.curve_evals
+            .iter()
+            .copied()
+            .zip(self.convergence_curve.iter().copied())
+    }
+
+    /// Best fitness recorded after at most `evals` evaluations, or `None` if the curve has no
+    /// point that early.
+    #[must_use]
+    pub fn best_at_evals(&self, evals: usize) -> Option<f64> {
+        let k = self.curve_evals.partition_point(|&e| e <= evals);
+        k.checked_sub(1).map(|k| self.convergence_curve[k])
+    }
+
+    /// Evaluations spent when the best fitness first reached `target` (was at least as good),
+    /// or `None` if it never did. Read from [`Report::improvements`] when recorded, and from
+    /// the evaluation-indexed curve otherwise.
+    #[must_use]
+    pub fn evals_to_target(&self, target: f64) -> Option<usize> {
+        let reached = |f: f64| !self.direction.better(target, f);
+        if self.improvements.is_empty() {
+            self.eval_curve().find(|&(_, f)| reached(f)).map(|(e, _)| e)
+        } else {
+            self.improvements
+                .iter()
+                .find(|e| reached(e.fitness))
+                .map(|e| e.evaluation)
+        }
+    }
+
+    /// Exploration percentage of each recorded iteration, `100 * Div / Div_max`, where `Div` is the
+    /// population's dimension-wise diversity and `Div_max` its maximum over the run.
+    #[must_use]
+    pub fn exploration(&self) -> Vec<f64> {
+        let max = self.diversity.iter().copied().fold(0.0, f64::max);
+        if max <= 0.0 {
+            return vec![0.0; self.diversity.len()];
+        }
+        self.diversity.iter().map(|d| 100.0 * d / max).collect()
+    }
+
+    /// First recorded iteration at which the population formed a single cluster, see
+    /// [`Report::clusters`]; `None` if it never did or clusters were not recorded.
+    #[must_use]
+    pub fn collapse_iteration(&self) -> Option<usize> {
+        self.clusters
+            .iter()
+            .position(|&c| c <= 1)
+            .map(|k| self.curve_iters[k])
+    }
+
+    /// Exploitation percentage of each iteration, `100 * |Div - Div_max| / Div_max`; the
+    /// complement of [`Report::exploration`].
+    #[must_use]
+    pub fn exploitation(&self) -> Vec<f64> {
+        self.exploration().into_iter().map(|p| 100.0 - p).collect()
+    }
+
+    /// Compact multi-line table with the headline numbers of the run; the same text as the
+    /// [`Display`](fmt::Display) implementation.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        self.to_string()
+    }
+
+    /// Writes the report as pretty-printed JSON to `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(feature = "serde")]
+    pub fn to_json_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        out.flush()
+    }
+
+    /// Writes the convergence curve as CSV to `path`, one row per recorded iteration with the
+    /// iteration number, the evaluations spent so far and the best fitness.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut out)?;
+        out.flush()
+    }
+
+    /// Writes the CSV of [`Report::to_csv`] to any writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+        writeln!(out, "iteration,evals,best_fitness")?;
+        for (i, f) in self.convergence_curve.iter().enumerate() {
+            let t = self.curve_iters.get(i).copied().unwrap_or(i + 1);
+            // Relatórios antigos podem não ter as avaliações por iteração
+            match self.curve_evals.get(i) {
+                Some(e) => writeln!(out, "{t},{e},{f}")?,
+                None => writeln!(out, "{t},,{f}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "best fitness  {:.6e}", self.best_fitness)?;
+        writeln!(f, "iterations    {}", self.iters)?;
+        writeln!(f, "evaluations   {}", self.evals)?;
+        match self.duration {
+            Some(d) => writeln!(f, "duration      {d:.3?}")?,
+            None => writeln!(f, "duration      -")?,
+        }
+        match self.stop_reason {
+            Some(r) => writeln!(f, "stop reason   {r}")?,
+            None => writeln!(f, "stop reason   -")?,
+        }
+        // Melhora em relação à primeira iteração registrada
+        match self.convergence_curve.first() {
+            Some(&first) if first.is_finite() && self.best_fitness.is_finite() => {
+                let gain = (first - self.best_fitness).abs();
+                write!(f, "improvement   {gain:.6e}")?;
+                if first != 0.0 {
+                    write!(f, " ({:.2}%)", 100.0 * gain / first.abs())?;
+                }
+                Ok(())
+            }
+            _ => write!(f, "improvement   -"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Maximization;
+
+    fn sample() -> Report {
+        Report {
+            best_fitness: 0.5,
+            best_position: vec![1.0, -2.0],
+            convergence_curve: vec![3.0, 1.5, 0.5],
+            curve_iters: vec![1, 2, 3],
+            curve_evals: vec![20, 30, 40],
+            iters: 3,
+            evals: 40,
+            stop_reason: Some(TerminationReason::MaxIters),
+            seed: Some(7),
+            ..Report::empty()
+        }
+        .with_duration(Duration::from_millis(12))
+    }
+
+    #[test]
+    fn comparison_follows_the_direction() {
+        let low = Report {
+            best_fitness: 1.0,
+            ..Report::empty_for::<Maximization>()
+        };
+        let high = Report {
+            best_fitness: 2.0,
+            ..Report::empty_for::<Maximization>()
+        };
+
+        assert!(high.is_better_than(&low));
+        assert!(!low.is_better_than(&high));
+        assert!(low.is_better_than(&Report::empty_for::<Maximization>()));
+        assert!(sample().is_better_than(&Report::empty()));
+    }
+
+    #[test]
+    fn summary_is_a_compact_table() {
+        assert_eq!(
+            sample().summary(),
+            "best fitness  5.000000e-1\n\
+             iterations    3\n\
+             evaluations   40\n\
+             duration      12.000ms\n\
+             stop reason   max_iters\n\
+             improvement   2.500000e0 (83.33%)"
+        );
+        assert!(Report::empty().summary().ends_with("improvement   -"));
+    }
+
+    #[test]
+    fn csv_has_one_row_per_iteration() {
+        let mut out = Vec::new();
+        sample().write_csv(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "iteration,evals,best_fitness\n1,20,3\n2,30,1.5\n3,40,0.5\n"
+        );
+    }
+
+    #[test]
+    fn log_sampling_thins_out_late_iterations() {
+        let sampling = CurveSampling::Log { per_decade: 10 };
+        let recorded: Vec<usize> = (1..=1000).filter(|&t| sampling.records(t)).collect();
+
+        assert_eq!(&recorded[..10], &[1, 2, 3, 4, 6, 7, 8, 10, 13, 16]);
+        assert!(recorded.len() <= 31, "{recorded:?}");
+        assert!(recorded.contains(&1000));
+    }
+
+    #[test]
+    fn best_at_evals_reads_the_eval_indexed_curve() {
+        let report = sample();
+
+        assert_eq!(report.best_at_evals(10), None);
+        assert_eq!(report.best_at_evals(35), Some(1.5));
+        assert_eq!(report.best_at_evals(40), Some(0.5));
+        assert_eq!(report.eval_curve().count(), 3);
+    }
+
+    #[test]
+    fn exploration_and_exploitation_are_complementary() {
+        let report = Report {
+            diversity: vec![4.0, 2.0, 1.0],
+            ..sample()
+        };
+
+        assert_eq!(report.exploration(), vec![100.0, 50.0, 25.0]);
+        assert_eq!(report.exploitation(), vec![0.0, 50.0, 75.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let path = std::env::temp_dir().join(format!("kambo-report-{}.json", std::process::id()));
+        let report = Report {
+            stop_reason: Some(TerminationReason::Custom("plateau")),
+            ..sample()
+        };
+        report.to_json_file(&path).unwrap();
+        let back: Report = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(back.convergence_curve, report.convergence_curve);
+        assert_eq!(back.curve_evals, report.curve_evals);
+        assert_eq!(back.best_position, report.best_position);
+        assert_eq!(back.duration, report.duration);
+        assert_eq!(back.stop_reason, report.stop_reason);
+    }
 }
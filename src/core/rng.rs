@@ -0,0 +1,352 @@
+use rand::{RngCore, SeedableRng, rand_core::impls};
+use rand_chacha::{ChaCha8Rng, ChaCha12Rng, ChaCha20Rng};
+
+/// Generator behind every random stream of a run.
+///
+/// The `ChaCha` variants are counter-based: each stream is the same key on its own stream
+/// id, so streams are provably disjoint, and fewer rounds are faster. [`RngKind::Xoshiro`]
+/// is the fastest, for large populations and dimensions, but its streams are seeded from a
+/// hash of the stream id and are only statistically independent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RngKind {
+    #[cfg_attr(feature = "serde", serde(rename = "chacha8"))]
+    ChaCha8,
+
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "chacha12"))]
+    ChaCha12,
+
+    #[cfg_attr(feature = "serde", serde(rename = "chacha20"))]
+    ChaCha20,
+
+    /// `xoshiro256++`.
+    #[cfg_attr(feature = "serde", serde(rename = "xoshiro"))]
+    Xoshiro,
+}
+
+/// A random stream drawn from [`SeedStreams`], with the generator chosen by its [`RngKind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StreamRng {
+    #[cfg_attr(feature = "serde", serde(rename = "chacha8"))]
+    ChaCha8(ChaCha8Rng),
+
+    #[cfg_attr(feature = "serde", serde(rename = "chacha12"))]
+    ChaCha12(ChaCha12Rng),
+
+    #[cfg_attr(feature = "serde", serde(rename = "chacha20"))]
+    ChaCha20(ChaCha20Rng),
+
+    Xoshiro(Xoshiro256PlusPlus),
+}
+
+impl StreamRng {
+    #[must_use]
+    pub const fn kind(&self) -> RngKind {
+        match self {
+            Self::ChaCha8(_) => RngKind::ChaCha8,
+            Self::ChaCha12(_) => RngKind::ChaCha12,
+            Self::ChaCha20(_) => RngKind::ChaCha20,
+            Self::Xoshiro(_) => RngKind::Xoshiro,
+        }
+    }
+}
+
+impl RngCore for StreamRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::ChaCha8(rng) => rng.next_u32(),
+            Self::ChaCha12(rng) => rng.next_u32(),
+            Self::ChaCha20(rng) => rng.next_u32(),
+            Self::Xoshiro(rng) => rng.next_u32(),
+        }
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::ChaCha8(rng) => rng.next_u64(),
+            Self::ChaCha12(rng) => rng.next_u64(),
+            Self::ChaCha20(rng) => rng.next_u64(),
+            Self::Xoshiro(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        match self {
+            Self::ChaCha8(rng) => rng.fill_bytes(dst),
+            Self::ChaCha12(rng) => rng.fill_bytes(dst),
+            Self::ChaCha20(rng) => rng.fill_bytes(dst),
+            Self::Xoshiro(rng) => rng.fill_bytes(dst),
+        }
+    }
+}
+
+/// The `xoshiro256++` generator of Blackman and Vigna: 256 bits of state, period `2²⁵⁶ − 1`.
+///
+/// Same algorithm as `rand`'s `SmallRng` on 64-bit targets, but identical on every platform
+/// and serializable, so runs using it can be checkpointed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+impl SeedableRng for Xoshiro256PlusPlus {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        // O estado nulo é ponto fixo do gerador
+        if seed.iter().all(|&b| b == 0) {
+            return Self::seed_from_u64(0);
+        }
+        let mut s = [0; 4];
+        for (word, chunk) in s.iter_mut().zip(seed.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().expect("chunks of 8 bytes"));
+        }
+        Self { s }
+    }
+
+    fn seed_from_u64(mut state: u64) -> Self {
+        // Expande a semente com o SplitMix64, como recomendam os autores
+        let mut s = [0; 4];
+        for word in &mut s {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Self { s }
+    }
+}
+
+impl RngCore for Xoshiro256PlusPlus {
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn next_u32(&mut self) -> u32 {
+        // Os bits altos são os de melhor qualidade
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let out = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        out
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst);
+    }
+}
+
+/// Master seed from which every random stream of a run is derived.
+///
+/// Each stream is the generator of [`SeedStreams::kind`] (`ChaCha12` by default) keyed by the
+/// master seed on its own stream id, so streams do not depend on how many of them are drawn,
+/// in which order, or on which thread they are consumed. Nested work (islands, replications, the runs of an
+/// experiment) gets its own factory from [`SeedStreams::split`], so adding parallelism at any
+/// level leaves every stream unchanged.
+///
+/// This is the crate's RNG factory, also exported as [`RngFactory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedStreams {
+    seed: u64,
+    kind: RngKind,
+}
+
+/// The factory of reproducible random streams of a run, see [`SeedStreams`].
+pub type RngFactory = SeedStreams;
+
+impl SeedStreams {
+    const INIT: u64 = 0;
+    const ITERATIONS: u64 = 1;
+    /// Primeiro id reservado para tarefas paralelas
+    const TASKS: u64 = 2;
+    /// Chave das sementes entregues ao decodificador
+    const EVALUATIONS: u64 = u64::MAX;
+
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            kind: RngKind::ChaCha12,
+        }
+    }
+
+    /// The same streams drawn from the generator `kind`.
+    #[must_use]
+    pub const fn with_kind(mut self, kind: RngKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Draws a fresh master seed from the operating system.
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn seed(self) -> u64 {
+        self.seed
+    }
+
+    #[must_use]
+    pub const fn kind(self) -> RngKind {
+        self.kind
+    }
+
+    /// Stream used to create the initial population.
+    #[must_use]
+    pub fn init(self) -> StreamRng {
+        self.stream(Self::INIT)
+    }
+
+    /// Stream consumed by the main loop.
+    #[must_use]
+    pub fn iterations(self) -> StreamRng {
+        self.stream(Self::ITERATIONS)
+    }
+
+    /// Stream of the `index`-th parallel work item. Keying streams by work item instead of by
+    /// worker thread keeps results identical for any thread count.
+    #[must_use]
+    pub fn task(self, index: u64) -> StreamRng {
+        self.stream(Self::TASKS.saturating_add(index))
+    }
+
+    /// Stream of hawk `index` in iteration `iter` of a population of `n` hawks, i.e. the task
+    /// `iter · n + index`. Every hawk update gets its own stream, whatever the thread that
+    /// runs it.
+    #[must_use]
+    pub fn hawk(self, iter: u64, n: u64, index: u64) -> StreamRng {
+        self.task(iter.wrapping_mul(n).wrapping_add(index))
+    }
+
+    /// Factory of the sub-computation `key` (an island, a replication...), seeded with a
+    /// `SplitMix64` jump of the master seed and drawing from the same generator. Splitting is
+    /// deterministic and can be nested.
+    #[must_use]
+    pub const fn split(self, key: u64) -> Self {
+        Self::new(self.mix(key)).with_kind(self.kind)
+    }
+
+    /// Semente da `k`-ésima avaliação pedida pelo falcão `hawk` na iteração `iter`, entregue
+    /// ao decodificador (ver [`crate::core::EvalContext`]); com `common` ela só depende da
+    /// iteração
+    pub(crate) const fn evaluation(self, iter: u64, hawk: u64, k: u64, common: bool) -> u64 {
+        let iteration = self.split(Self::EVALUATIONS).split(iter);
+        if common {
+            return iteration.seed();
+        }
+        iteration.split(hawk).replication(k)
+    }
+
+    /// Master seed of the `k`-th independent replication of an experiment seeded with this
+    /// seed, the same as `self.split(k).seed()`.
+    #[must_use]
+    pub const fn replication(self, k: u64) -> u64 {
+        self.split(k).seed()
+    }
+
+    fn stream(self, id: u64) -> StreamRng {
+        match self.kind {
+            RngKind::ChaCha8 => {
+                let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+                rng.set_stream(id);
+                StreamRng::ChaCha8(rng)
+            }
+            RngKind::ChaCha12 => {
+                let mut rng = ChaCha12Rng::seed_from_u64(self.seed);
+                rng.set_stream(id);
+                StreamRng::ChaCha12(rng)
+            }
+            RngKind::ChaCha20 => {
+                let mut rng = ChaCha20Rng::seed_from_u64(self.seed);
+                rng.set_stream(id);
+                StreamRng::ChaCha20(rng)
+            }
+            // Sem fluxos nativos: cada id semeia um gerador próprio
+            RngKind::Xoshiro => StreamRng::Xoshiro(Xoshiro256PlusPlus::seed_from_u64(self.mix(id))),
+        }
+    }
+
+    /// Hash determinístico de `(seed, key)` (finalizador do `SplitMix64`), para sorteios que
+    /// não devem consumir nenhum fluxo
+    #[must_use]
+    pub(crate) const fn mix(self, key: u64) -> u64 {
+        let mut z = (self.seed ^ key).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn streams_are_reproducible_and_distinct() {
+        let a = SeedStreams::new(42);
+        let b = SeedStreams::new(42);
+        let x: [u64; 4] = a.iterations().random();
+        assert_eq!(x, b.iterations().random::<[u64; 4]>());
+        assert_ne!(x, a.init().random::<[u64; 4]>());
+        assert_ne!(a.task(0).random::<u64>(), a.task(1).random::<u64>());
+        assert_eq!(a.hawk(3, 10, 4).random::<u64>(), b.task(34).random::<u64>());
+
+        // Fábricas filhas: reprodutíveis, distintas entre si e da mãe
+        let island = a.split(7);
+        assert_eq!(island, b.split(7));
+        assert_ne!(island, a.split(8));
+        assert_ne!(island.split(0).seed(), a.split(0).seed());
+        assert_eq!(a.replication(7), island.seed());
+    }
+
+    #[test]
+    fn every_kind_is_reproducible() {
+        let kinds = [
+            RngKind::ChaCha8,
+            RngKind::ChaCha12,
+            RngKind::ChaCha20,
+            RngKind::Xoshiro,
+        ];
+        let draws: Vec<u64> = kinds
+            .iter()
+            .map(|&kind| {
+                let streams = SeedStreams::new(42).with_kind(kind);
+                let x = streams.task(3).random::<u64>();
+                assert_eq!(x, streams.task(3).random::<u64>());
+                assert_ne!(x, streams.task(4).random::<u64>());
+                assert_eq!(streams.split(1).kind(), kind);
+                assert_eq!(streams.init().kind(), kind);
+                x
+            })
+            .collect();
+        assert_eq!(draws[1], SeedStreams::new(42).task(3).random::<u64>());
+        for (i, a) in draws.iter().enumerate() {
+            assert!(draws[i + 1..].iter().all(|b| a != b));
+        }
+
+        // Vetor de referência do xoshiro256++ com estado [1, 2, 3, 4]
+        let mut rng = Xoshiro256PlusPlus { s: [1, 2, 3, 4] };
+        let out: [u64; 3] = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+        assert_eq!(out, [41_943_041, 58_720_359, 3_588_806_011_781_223]);
+    }
+}
@@ -0,0 +1,73 @@
+use std::{fmt::Debug, iter::Sum};
+
+use num_traits::Float;
+use rand::distr::uniform::SampleUniform;
+
+/// Floating-point type of positions, bounds and fitness values: `f64` or `f32`.
+///
+/// [`crate::core::Population`], [`crate::core::Bounds`], [`crate::core::Decoder`] and the
+/// kernels of [`crate::core::vecops`] are generic over it, with `f64` as the default, so
+/// `f32` storage halves the memory traffic of large populations. The HHO loop itself runs in
+/// `f64`; an `f32` objective plugs into it through [`crate::core::F32Adapter`].
+pub trait Scalar: Float + Into<f64> + SampleUniform + Sum + Default + Debug + Send + Sync {
+    /// Converts from `f64`, rounding to the nearest representable value.
+    fn from_f64(x: f64) -> Self;
+}
+
+impl Scalar for f64 {
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+}
+
+impl Scalar for f32 {
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_f64(x: f64) -> Self {
+        x as Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        core::{Bounds, Decoder, DecoderError, F32Adapter, Population, vecops},
+        hho::HhoBuilder,
+    };
+
+    struct Sphere32;
+
+    impl Decoder<f32> for Sphere32 {
+        fn decode(&self, solution: &[f32]) -> Result<f32, DecoderError> {
+            Ok(vecops::squared_distance(solution, &[0.0; 4]))
+        }
+    }
+
+    #[test]
+    fn single_precision_storage_and_decoders() {
+        let bounds: Bounds<f32> = Bounds::<f64>::uniform(-2.0, 2.0, 4).unwrap().cast();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut pop = Population::<f32>::zeros(3, 4);
+        for x in &mut pop {
+            bounds.fill_random(x, &mut rng);
+            x[0] = 5.0;
+            bounds.project_slice(x);
+        }
+        assert!(
+            pop.iter()
+                .all(|x| (x[0] - 2.0).abs() < f32::EPSILON && x.iter().all(|v| v.abs() <= 2.0))
+        );
+
+        let hho = HhoBuilder::new(Bounds::uniform(-2.0, 2.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(6)
+            .build();
+        let report = hho.run(&F32Adapter(Sphere32)).unwrap();
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+}
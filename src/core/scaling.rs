@@ -0,0 +1,130 @@
+/// Transformation of raw fitness values before they are used as magnitudes.
+///
+/// Comparisons never need it, but the mechanisms that weigh improvements (the credit of
+/// adaptive exploitation, the success history of self-adaptive parameters) or add fitness to
+/// something else (penalties) behave differently on an objective around `1e-8` than on one
+/// around `1e12`. Every scaling is increasing, so it keeps the order of the values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FitnessScaling {
+    /// Values as returned by the decoder.
+    #[default]
+    Raw,
+
+    /// Signed logarithm `sign(f)·ln(1 + |f|)`, for objectives spanning orders of magnitude.
+    Log,
+
+    /// `(f - mean) / std` over a reference sample, usually the current population.
+    ZScore,
+
+    /// Position within a reference sample, from `0` at its smallest value to `1` at its
+    /// largest, interpolated between sample values and extended linearly beyond them.
+    Rank,
+}
+
+/// A [`FitnessScaling`] fitted to a reference sample of fitness values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scaler {
+    scaling: FitnessScaling,
+    mean: f64,
+    std: f64,
+    /// Valores distintos da amostra, em ordem crescente (só para `Rank`)
+    sorted: Vec<f64>,
+}
+
+impl Scaler {
+    /// Fits `scaling` to the finite values of `sample`.
+    #[must_use]
+    pub fn fit(scaling: FitnessScaling, sample: &[f64]) -> Self {
+        let finite = || sample.iter().copied().filter(|f| f.is_finite());
+        let mut scaler = Self {
+            scaling,
+            ..Self::default()
+        };
+        match scaling {
+            FitnessScaling::Raw | FitnessScaling::Log => {}
+            FitnessScaling::ZScore => {
+                #[allow(clippy::cast_precision_loss)]
+                let n = finite().count().max(1) as f64;
+                scaler.mean = finite().sum::<f64>() / n;
+                scaler.std = (finite().map(|f| (f - scaler.mean).powi(2)).sum::<f64>() / n).sqrt();
+            }
+            FitnessScaling::Rank => {
+                scaler.sorted = finite().collect();
+                scaler.sorted.sort_by(f64::total_cmp);
+                scaler.sorted.dedup();
+            }
+        }
+        scaler
+    }
+
+    #[must_use]
+    pub const fn scaling(&self) -> FitnessScaling {
+        self.scaling
+    }
+
+    /// Scaled value of `f`.
+    #[must_use]
+    pub fn apply(&self, f: f64) -> f64 {
+        match self.scaling {
+            FitnessScaling::Raw => f,
+            FitnessScaling::Log => f.signum() * f.abs().ln_1p(),
+            FitnessScaling::ZScore if self.std > 0.0 => (f - self.mean) / self.std,
+            FitnessScaling::ZScore => f - self.mean,
+            FitnessScaling::Rank => self.rank(f),
+        }
+    }
+
+    /// Posição interpolada de `f` entre os valores distintos da amostra
+    fn rank(&self, f: f64) -> f64 {
+        let u = &self.sorted;
+        let (Some(&lo), Some(&hi)) = (u.first(), u.last()) else {
+            return f;
+        };
+        if u.len() < 2 {
+            return f - lo;
+        }
+        let (range, last) = (hi - lo, u.len() - 1);
+        #[allow(clippy::cast_precision_loss)]
+        let steps = last as f64;
+        if f <= lo {
+            return (f - lo) / range;
+        }
+        if f >= hi {
+            return 1.0 + (f - hi) / range;
+        }
+        // Segmento [u[k], u[k + 1]) que contém f
+        let k = u.partition_point(|&v| v <= f) - 1;
+        #[allow(clippy::cast_precision_loss)]
+        let base = k as f64;
+        (base + (f - u[k]) / (u[k + 1] - u[k])) / steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalings_keep_the_order_and_remove_the_scale() {
+        let tiny = [3e-8, 1e-8, 2e-8, 5e-8];
+        let huge: Vec<f64> = tiny.iter().map(|f| f * 1e20).collect();
+        for scaling in [FitnessScaling::ZScore, FitnessScaling::Rank] {
+            let (a, b) = (Scaler::fit(scaling, &tiny), Scaler::fit(scaling, &huge));
+            for (x, y) in tiny.iter().zip(&huge) {
+                assert!((a.apply(*x) - b.apply(*y)).abs() < 1e-9, "{scaling:?}");
+            }
+        }
+        let rank = Scaler::fit(FitnessScaling::Rank, &[1.0, 2.0, 2.0, 4.0]);
+        let values = [0.0, 1.0, 1.5, 2.0, 3.0, 4.0, 7.0];
+        let scaled: Vec<f64> = values.iter().map(|&f| rank.apply(f)).collect();
+        assert!(scaled.windows(2).all(|w| w[0] < w[1]));
+        assert!((scaled[1]).abs() < 1e-12 && (scaled[5] - 1.0).abs() < 1e-12);
+        assert!((scaled[3] - 0.5).abs() < 1e-12);
+
+        let log = Scaler::fit(FitnessScaling::Log, &[]);
+        assert!((log.apply(-(std::f64::consts::E - 1.0)) + 1.0).abs() < 1e-12);
+        assert!((Scaler::fit(FitnessScaling::Raw, &tiny).apply(7.0) - 7.0).abs() < 1e-12);
+    }
+}
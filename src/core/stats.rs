@@ -0,0 +1,316 @@
+use crate::core::{
+    Direction, Report,
+    utils::{self, RunningStats, quantile_sorted},
+};
+
+/// Descriptive statistics of a sample of fitness values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    /// Sample standard deviation (`n - 1` denominator); `0.0` for a single value.
+    pub std: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    /// Statistics of `values`, or `None` if it is empty.
+    #[must_use]
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let moments: RunningStats = values.iter().copied().collect();
+        Some(Self {
+            mean: moments.mean()?,
+            std: moments.std_dev()?,
+            median: quantile_sorted(&sorted, 0.5),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// How much the outcome of a configuration depends on the seed, see
+/// [`ReportSet::robustness`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Robustness {
+    /// Statistics of the best fitness of each run.
+    pub summary: Summary,
+    /// First and third quartiles of the best fitness.
+    pub q1: f64,
+    pub q3: f64,
+    /// `q3 - q1`.
+    pub iqr: f64,
+    /// `iqr / |median|`: `0` when both are zero, infinite when only the median is.
+    pub relative_iqr: f64,
+    /// `std / |mean|`, with the same conventions.
+    pub coefficient_of_variation: f64,
+    /// Whether `relative_iqr` exceeds the threshold: results this spread out describe the
+    /// seeds as much as the configuration, and a single run should not be reported.
+    pub seed_dominated: bool,
+    /// Seeds of the runs with the best and the worst result, to reproduce both ends.
+    pub best_seed: Option<u64>,
+    pub worst_seed: Option<u64>,
+}
+
+/// Razão `spread / |center|`, 0 quando ambos são nulos
+fn relative(spread: f64, center: f64) -> f64 {
+    if spread == 0.0 {
+        0.0
+    } else {
+        spread / center.abs()
+    }
+}
+
+/// Mean convergence curve of several runs with a percentile band around the median.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveBands {
+    pub mean: Vec<f64>,
+    pub median: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+}
+
+/// Independent runs of the same experiment, for the statistics every comparison needs.
+///
+/// Target-based statistics follow the direction of the reports (see [`Report::direction`]);
+/// all reports are expected to share it.
+#[derive(Debug, Clone, Default)]
+pub struct ReportSet {
+    pub reports: Vec<Report>,
+}
+
+impl ReportSet {
+    #[must_use]
+    pub const fn new(reports: Vec<Report>) -> Self {
+        Self { reports }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    fn direction(&self) -> Direction {
+        self.reports
+            .first()
+            .map_or_else(Direction::default, |r| r.direction)
+    }
+
+    /// Statistics of the best fitness of each run, or `None` for an empty set.
+    #[must_use]
+    pub fn best_fitness(&self) -> Option<Summary> {
+        let values: Vec<f64> = self.reports.iter().map(|r| r.best_fitness).collect();
+        Summary::of(&values)
+    }
+
+    /// Dispersion of the best fitness across the runs, usually replications of one
+    /// configuration that differ only by their seed, flagged as seed-dominated when the
+    /// interquartile range exceeds `threshold` times the absolute median (e.g. `0.1`).
+    ///
+    /// Returns `None` for an empty set.
+    #[must_use]
+    pub fn robustness(&self, threshold: f64) -> Option<Robustness> {
+        let values: Vec<f64> = self.reports.iter().map(|r| r.best_fitness).collect();
+        let summary = Summary::of(&values)?;
+        let mut sorted = values;
+        sorted.sort_by(f64::total_cmp);
+        let (q1, q3) = (
+            quantile_sorted(&sorted, 0.25),
+            quantile_sorted(&sorted, 0.75),
+        );
+        let relative_iqr = relative(q3 - q1, summary.median);
+        let direction = self.direction();
+        // Semente da melhor (ou da pior) execução; empates ficam com a primeira
+        let seed_at = |worst: bool| {
+            self.reports
+                .iter()
+                .reduce(|a, b| {
+                    let (x, y) = if worst { (a, b) } else { (b, a) };
+                    if direction.better(x.best_fitness, y.best_fitness) {
+                        b
+                    } else {
+                        a
+                    }
+                })
+                .and_then(|r| r.seed)
+        };
+        Some(Robustness {
+            summary,
+            q1,
+            q3,
+            iqr: q3 - q1,
+            relative_iqr,
+            coefficient_of_variation: relative(summary.std, summary.mean),
+            seed_dominated: relative_iqr > threshold,
+            best_seed: seed_at(false),
+            worst_seed: seed_at(true),
+        })
+    }
+
+    /// Fraction of runs whose best fitness reached `target` (is at least as good).
+    #[must_use]
+    pub fn success_rate(&self, target: f64) -> f64 {
+        let values: Vec<f64> = self.reports.iter().map(|r| r.best_fitness).collect();
+        utils::success_rate(&values, target, 0.0, self.direction())
+    }
+
+    /// Mean number of evaluations the successful runs needed to reach `target`, see
+    /// [`Report::evals_to_target`]; `None` if no run recorded reaching it.
+    #[must_use]
+    pub fn mean_evals_to_target(&self, target: f64) -> Option<f64> {
+        let evals: Vec<usize> = self
+            .reports
+            .iter()
+            .filter_map(|r| r.evals_to_target(target))
+            .collect();
+        if evals.is_empty() {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = evals.iter().sum::<usize>() as f64 / evals.len() as f64;
+        Some(mean)
+    }
+
+    /// Point-wise mean, median and `[lower_pct, upper_pct]` percentile band of the convergence
+    /// curves (percentiles in `0..=100`, e.g. `25.0` and `75.0`).
+    ///
+    /// Curves are aligned by index; a run that stopped early keeps its last value, since its
+    /// best-so-far does not change afterwards.
+    #[must_use]
+    pub fn curve_bands(&self, lower_pct: f64, upper_pct: f64) -> CurveBands {
+        let len = self
+            .reports
+            .iter()
+            .map(|r| r.convergence_curve.len())
+            .max()
+            .unwrap_or(0);
+        let mut bands = CurveBands {
+            mean: Vec::with_capacity(len),
+            median: Vec::with_capacity(len),
+            lower: Vec::with_capacity(len),
+            upper: Vec::with_capacity(len),
+        };
+        let mut column = Vec::with_capacity(self.len());
+        for i in 0..len {
+            column.clear();
+            column.extend(self.reports.iter().filter_map(|r| {
+                r.convergence_curve
+                    .get(i)
+                    .or_else(|| r.convergence_curve.last())
+                    .copied()
+            }));
+            column.sort_by(f64::total_cmp);
+            bands.mean.push(utils::mean(&column).unwrap_or(f64::NAN));
+            bands.median.push(quantile_sorted(&column, 0.5));
+            bands
+                .lower
+                .push(quantile_sorted(&column, lower_pct / 100.0));
+            bands
+                .upper
+                .push(quantile_sorted(&column, upper_pct / 100.0));
+        }
+        bands
+    }
+}
+
+impl From<Vec<Report>> for ReportSet {
+    fn from(reports: Vec<Report>) -> Self {
+        Self::new(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(curve: &[f64], evals_per_iter: usize) -> Report {
+        Report {
+            best_fitness: *curve.last().unwrap(),
+            convergence_curve: curve.to_vec(),
+            curve_evals: (1..=curve.len()).map(|i| i * evals_per_iter).collect(),
+            ..Report::empty()
+        }
+    }
+
+    fn set() -> ReportSet {
+        ReportSet::from(vec![
+            run(&[4.0, 2.0, 1.0], 10),
+            run(&[3.0, 3.0, 3.0], 10),
+            run(&[5.0, 0.5], 20),
+        ])
+    }
+
+    #[test]
+    fn best_fitness_summary() {
+        let s = set().best_fitness().unwrap();
+
+        assert!((s.mean - 1.5).abs() < 1e-12);
+        assert!((s.median - 1.0).abs() < 1e-12);
+        assert!((s.min - 0.5).abs() < 1e-12);
+        assert!((s.max - 3.0).abs() < 1e-12);
+        assert!((s.std - 1.322_875_655_532_295).abs() < 1e-12);
+        assert!(ReportSet::default().best_fitness().is_none());
+    }
+
+    #[test]
+    fn robustness_flags_seed_dominated_results() {
+        let seeded = |f: f64, seed: u64| Report {
+            best_fitness: f,
+            seed: Some(seed),
+            ..Report::empty()
+        };
+        let steady: ReportSet = (0..8_u32)
+            .map(|k| seeded(0.01_f64.mul_add(f64::from(k), 10.0), u64::from(k)))
+            .collect::<Vec<_>>()
+            .into();
+        let r = steady.robustness(0.1).unwrap();
+        assert!(!r.seed_dominated);
+        assert!(r.relative_iqr < 0.01);
+        assert_eq!((r.best_seed, r.worst_seed), (Some(0), Some(7)));
+
+        let lucky: ReportSet = vec![
+            seeded(1.0, 1),
+            seeded(9.0, 2),
+            seeded(2.0, 3),
+            seeded(30.0, 4),
+        ]
+        .into();
+        let r = lucky.robustness(0.1).unwrap();
+        assert!(r.seed_dominated);
+        assert!((r.iqr - (r.q3 - r.q1)).abs() < 1e-12 && r.iqr > r.summary.median);
+        assert_eq!((r.best_seed, r.worst_seed), (Some(1), Some(4)));
+        assert!(ReportSet::default().robustness(0.1).is_none());
+    }
+
+    #[test]
+    fn target_statistics() {
+        let set = set();
+
+        assert!((set.success_rate(1.0) - 2.0 / 3.0).abs() < 1e-12);
+        // 30 avaliações na primeira execução, 40 na terceira
+        assert_eq!(set.mean_evals_to_target(1.0), Some(35.0));
+        assert_eq!(set.mean_evals_to_target(0.1), None);
+    }
+
+    #[test]
+    fn bands_pad_short_curves() {
+        let bands = set().curve_bands(0.0, 100.0);
+
+        assert_eq!(bands.mean.len(), 3);
+        assert_eq!(bands.lower, vec![3.0, 0.5, 0.5]);
+        assert_eq!(bands.upper, vec![5.0, 3.0, 3.0]);
+        assert_eq!(bands.median, vec![4.0, 2.0, 1.0]);
+    }
+}
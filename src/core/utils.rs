@@ -1,64 +1,470 @@
 use std::cmp::Ordering;
 
-use crate::core::{Decoder, Objective};
+use rand::Rng;
+use thiserror::Error;
 
-/// # Panics
+use crate::core::{Bounds, Decoder, DecoderError, Direction, Objective, Population};
+
+/// `debug_assert_eq!` que, com a feature `strict`, continua valendo em release
+macro_rules! strict_assert_eq {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "strict") {
+            assert_eq!($($arg)*);
+        } else {
+            debug_assert_eq!($($arg)*);
+        }
+    };
+}
+pub(crate) use strict_assert_eq;
+
+/// Arguments of a ranking helper that do not fit together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ShapeError {
+    /// Two slices that go together element by element have different lengths.
+    #[error("length mismatch: expected {expected}, received {received}")]
+    Length { expected: usize, received: usize },
+
+    /// A permutation of `0..len` that is not one.
+    #[error("not a permutation of 0..{len}")]
+    NotPermutation { len: usize },
+}
+
+/// Checks that a slice of length `received` goes with one of length `expected`.
+///
+/// # Errors
 ///
-/// Lança um Panic decoder failed caso aconteça algum erro durante a execução de docoder
+/// [`ShapeError::Length`] if they differ.
+pub const fn check_len(expected: usize, received: usize) -> Result<(), ShapeError> {
+    if expected == received {
+        Ok(())
+    } else {
+        Err(ShapeError::Length { expected, received })
+    }
+}
+
+/// Evaluates every position, stopping at the first decoder error.
+///
+/// # Errors
+///
+/// Returns the first error of the decoder.
 #[inline]
-pub fn evaluate_all(decoder: &dyn Decoder, positions: &[Vec<f64>]) -> Vec<f64> {
-    positions
-        .iter()
-        .map(|x| decoder.decode(x).expect("decoder failed"))
-        .collect()
+pub fn try_evaluate_all(
+    decoder: &dyn Decoder,
+    positions: &Population,
+) -> Result<Vec<f64>, DecoderError> {
+    positions.iter().map(|x| decoder.decode(x)).collect()
 }
 
+/// Index of the best finite value of `fitness`, or `None` if it is empty.
+///
+/// Non-finite values are skipped, so a `NaN` (or an infinity) at index 0 cannot hide the
+/// finite values after it; only when none is finite does the best of the rest win, `NaN`s
+/// last. Exact ties go to the lower index, as in every ranking of the crate (see
+/// [`cmp_ranked`]).
 #[inline]
 #[must_use]
-pub fn best_index_with<O>(fitness: &[f64]) -> usize
+pub fn best_index_with<O>(fitness: &[f64]) -> Option<usize>
 where
     O: Objective,
 {
-    let mut best_i = 0;
-    let mut best_f = fitness[0];
-    (1..fitness.len()).for_each(|i| {
-        if O::better(fitness[i], best_f) {
-            best_i = i;
-            best_f = fitness[i];
+    let mut best: Option<usize> = None;
+    for (i, &f) in fitness.iter().enumerate() {
+        if f.is_finite() && best.is_none_or(|b| O::better(f, fitness[b])) {
+            best = Some(i);
         }
+    }
+    best.or_else(|| (0..fitness.len()).min_by(|&i, &j| cmp_ranked::<O>(fitness, i, j)))
+}
+
+/// Sorts the population from best to worst in place and returns the permutation applied.
+///
+/// The hawk now at position `k` was at position `perm[k]`; pass the permutation to [`permute`]
+/// to reorder auxiliary arrays the same way.
+/// Rows are swapped along the cycles of the permutation, so no position is cloned. Ties keep
+/// their original order (see [`cmp_ranked`]).
+///
+/// # Errors
+///
+/// [`ShapeError::Length`] if `fitness` does not have one value per position.
+pub fn sort_by_fitness_with<O>(
+    positions: &mut Population,
+    fitness: &mut [f64],
+) -> Result<Vec<usize>, ShapeError>
+where
+    O: Objective,
+{
+    check_len(positions.len(), fitness.len())?;
+    Ok(sort_pairs::<O>(positions, fitness))
+}
+
+/// [`sort_by_fitness_with`] para quem já garante um valor de fitness por posição
+pub(crate) fn sort_pairs<O>(positions: &mut Population, fitness: &mut [f64]) -> Vec<usize>
+where
+    O: Objective,
+{
+    debug_assert_eq!(positions.len(), fitness.len());
+    let perm = argsort_by_fitness::<O>(fitness);
+
+    apply_permutation(&perm, |a, b| {
+        positions.swap(a, b);
+        fitness.swap(a, b);
+    });
+    perm
+}
+
+/// Indices of `fitness` from best to worst, `NaN`s last whatever the direction; ties keep
+/// their original order (see [`cmp_ranked`]).
+#[must_use]
+pub fn argsort_by_fitness<O>(fitness: &[f64]) -> Vec<usize>
+where
+    O: Objective,
+{
+    let mut order: Vec<usize> = (0..fitness.len()).collect();
+    order.sort_unstable_by(|&i, &j| cmp_ranked::<O>(fitness, i, j));
+    order
+}
+
+/// Like [`argsort_by_fitness`], with exact fitness ties broken by `secondary` first (lower
+/// is better, e.g. a constraint violation) and only then by index.
+///
+/// # Errors
+///
+/// [`ShapeError::Length`] if `secondary` does not have one value per fitness.
+pub fn argsort_by_fitness_then<O>(
+    fitness: &[f64],
+    secondary: &[f64],
+) -> Result<Vec<usize>, ShapeError>
+where
+    O: Objective,
+{
+    check_len(fitness.len(), secondary.len())?;
+    let mut order: Vec<usize> = (0..fitness.len()).collect();
+    order.sort_unstable_by(|&i, &j| {
+        cmp_fitness::<O>(fitness[i], fitness[j])
+            .then_with(|| secondary[i].total_cmp(&secondary[j]))
+            .then(i.cmp(&j))
     });
-    best_i
+    Ok(order)
+}
+
+/// Orders entries `i` and `j` of `fitness` best first: by fitness with `NaN`s last whatever
+/// the direction, and on an exact tie (two `NaN`s included) by the lower index.
+///
+/// It is a total order, as `sort_unstable_by` and `select_nth_unstable_by` require: a `NaN`
+/// never ties with a number.
+///
+/// This is the tie-breaking rule of every ranking in the crate ([`best_index_with`],
+/// [`argsort_by_fitness`], [`top_k_indices`], the duplicate purge, niching, ...), so the
+/// winner of a tie never depends on iteration or evaluation order and serial and parallel
+/// runs agree. The hall of fame follows the same idea: a later offer must be strictly better
+/// to displace an entry.
+#[inline]
+#[must_use]
+pub fn cmp_ranked<O>(fitness: &[f64], i: usize, j: usize) -> Ordering
+where
+    O: Objective,
+{
+    cmp_fitness::<O>(fitness[i], fitness[j]).then(i.cmp(&j))
+}
+
+/// Indices of the `k` best values of `fitness` (all of them if `k >= fitness.len()`), best
+/// first.
+///
+/// Uses partial selection, so it runs in `O(n + k log k)` on average instead of sorting the
+/// whole slice. Ties at the cut go to the lower indices (see [`cmp_ranked`]).
+#[must_use]
+pub fn top_k_indices<O>(fitness: &[f64], k: usize) -> Vec<usize>
+where
+    O: Objective,
+{
+    let mut order: Vec<usize> = (0..fitness.len()).collect();
+    let k = k.min(order.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    let cmp = |i: &usize, j: &usize| cmp_ranked::<O>(fitness, *i, *j);
+    if k < order.len() {
+        order.select_nth_unstable_by(k - 1, cmp);
+        order.truncate(k);
+    }
+    order.sort_unstable_by(cmp);
+    order
 }
 
-/// # Panics
-/// Para a execução se o vetor se o tamanho do vetor de fitness for diferente do tamanho do vetor
-/// de hawks
-pub fn sort_by_fitness_with<O>(positions: &mut Vec<Vec<f64>>, fitness: &mut Vec<f64>)
+/// Tournament selection: the best of `k` indices drawn uniformly (with replacement) from
+/// `fitness`, or `None` if it is empty. `k = 1` (or `0`) is a uniform pick; larger `k` raise the
+/// selection pressure.
+pub fn tournament_index<O, R>(fitness: &[f64], k: usize, rng: &mut R) -> Option<usize>
 where
     O: Objective,
+    R: Rng + ?Sized,
+{
+    if fitness.is_empty() {
+        return None;
+    }
+    let mut best = rng.random_range(0..fitness.len());
+    for _ in 1..k {
+        let i = rng.random_range(0..fitness.len());
+        if O::better(fitness[i], fitness[best]) {
+            best = i;
+        }
+    }
+    Some(best)
+}
+
+/// Roulette-wheel selection on ranks, or `None` if `fitness` is empty.
+///
+/// Raw fitness-proportional selection only makes sense for positive values under
+/// maximization, so the wheel is built on ranks instead: out of `n` values, the best gets a
+/// slice of weight `n`, the next `n - 1`, down to `1` for the worst. This works the same for
+/// both objectives and for fitness of any sign or scale.
+pub fn roulette_index<O, R>(fitness: &[f64], rng: &mut R) -> Option<usize>
+where
+    O: Objective,
+    R: Rng + ?Sized,
 {
-    assert_eq!(positions.len(), fitness.len(), "len mismatch");
     let n = fitness.len();
-    let mut idx: Vec<usize> = (0..n).collect();
+    if n == 0 {
+        return None;
+    }
+    let order = argsort_by_fitness::<O>(fitness);
+    let mut ticket = rng.random_range(0..n * (n + 1) / 2);
+    for (rank, &i) in order.iter().enumerate() {
+        let weight = n - rank;
+        if ticket < weight {
+            return Some(i);
+        }
+        ticket -= weight;
+    }
+    order.last().copied()
+}
 
-    idx.sort_by(|&i, &j| cmp_fitness::<O>(fitness[i], fitness[j]));
+/// Reorders `items` in place so that `items[k]` becomes the old `items[perm[k]]`, e.g. with the
+/// permutation returned by [`sort_by_fitness_with`].
+///
+/// # Errors
+///
+/// [`ShapeError::Length`] if `perm` does not have one index per item, and
+/// [`ShapeError::NotPermutation`] if it repeats or skips an index; `items` is left as is.
+pub fn permute<T>(items: &mut [T], perm: &[usize]) -> Result<(), ShapeError> {
+    check_len(items.len(), perm.len())?;
+    let mut seen = vec![false; perm.len()];
+    for &i in perm {
+        if seen.get(i).copied() != Some(false) {
+            return Err(ShapeError::NotPermutation { len: perm.len() });
+        }
+        seen[i] = true;
+    }
+    apply_permutation(perm, |a, b| items.swap(a, b));
+    Ok(())
+}
 
-    let mut new_pos = Vec::with_capacity(n);
-    let mut new_fit = Vec::with_capacity(n);
-    for &k in &idx {
-        new_pos.push(positions[k].clone());
-        new_fit.push(fitness[k]);
+/// Percorre os ciclos de `perm` trocando os elementos dois a dois com `swap`
+fn apply_permutation(perm: &[usize], mut swap: impl FnMut(usize, usize)) {
+    let mut done = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        let mut cur = start;
+        while !done[cur] {
+            done[cur] = true;
+            let next = perm[cur];
+            if next == start {
+                break;
+            }
+            swap(cur, next);
+            cur = next;
+        }
     }
-    *positions = new_pos;
-    *fitness = new_fit;
 }
 
+/// Running mean and variance of a stream of values (Welford's algorithm).
+///
+/// Each value moves the mean by its deviation divided by the count instead of being added to a
+/// plain sum, so neither huge nor tiny magnitudes (think `1e±150`) overflow or lose the
+/// variance to cancellation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStats {
+    n: usize,
+    mean: f64,
+    /// Soma dos quadrados dos desvios em relação à média corrente
+    m2: f64,
+}
+
+impl RunningStats {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn push(&mut self, value: f64) {
+        self.n += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 = delta.mul_add(value - self.mean, self.m2);
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.n
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Mean of the values pushed so far, or `None` before the first one.
+    #[must_use]
+    pub const fn mean(&self) -> Option<f64> {
+        if self.n == 0 { None } else { Some(self.mean) }
+    }
+
+    /// Sample variance (`n - 1` denominator; `0.0` for a single value), or `None` before the
+    /// first value.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn variance(&self) -> Option<f64> {
+        match self.n {
+            0 => None,
+            1 => Some(0.0),
+            n => Some(self.m2 / (n - 1) as f64),
+        }
+    }
+
+    /// Sample standard deviation, see [`RunningStats::variance`].
+    #[must_use]
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl FromIterator<f64> for RunningStats {
+    fn from_iter<T: IntoIterator<Item = f64>>(iter: T) -> Self {
+        let mut stats = Self::new();
+        for v in iter {
+            stats.push(v);
+        }
+        stats
+    }
+}
+
+/// Mean of `values`, or `None` if it is empty. See [`RunningStats`].
+#[must_use]
+pub fn mean(values: &[f64]) -> Option<f64> {
+    values.iter().copied().collect::<RunningStats>().mean()
+}
+
+/// Sample variance of `values`, or `None` if it is empty. See [`RunningStats`].
+#[must_use]
+pub fn variance(values: &[f64]) -> Option<f64> {
+    values.iter().copied().collect::<RunningStats>().variance()
+}
+
+/// Sample standard deviation of `values`, or `None` if it is empty. See [`RunningStats`].
+#[must_use]
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    variance(values).map(f64::sqrt)
+}
+
+/// Quantiles of `values` at the levels `qs` (each clamped into `[0, 1]`, e.g. `0.5` for the
+/// median), interpolating linearly between order statistics. All `NaN` if `values` is empty.
+#[must_use]
+pub fn quantiles(values: &[f64], qs: &[f64]) -> Vec<f64> {
+    if values.is_empty() {
+        return vec![f64::NAN; qs.len()];
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    qs.iter().map(|&q| quantile_sorted(&sorted, q)).collect()
+}
+
+/// Quantil `q` de uma amostra ordenada e não vazia, com interpolação linear
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let lo = rank.floor() as usize;
+    let hi = (lo + 1).min(sorted.len() - 1);
+    let frac = rank - rank.floor();
+    (sorted[hi] - sorted[lo]).mul_add(frac, sorted[lo])
+}
+
+/// Fraction of `values` that reach `target` in `direction`, allowing a miss of up to
+/// `tolerance` (e.g. `f <= target + tolerance` when minimizing). `NaN` values never count;
+/// an empty slice gives `0.0`.
+#[must_use]
+pub fn success_rate(values: &[f64], target: f64, tolerance: f64, direction: Direction) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let hits = values
+        .iter()
+        .filter(|&&v| match direction {
+            Direction::Minimize => v <= target + tolerance.abs(),
+            Direction::Maximize => v >= target - tolerance.abs(),
+        })
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let rate = hits as f64 / values.len() as f64;
+    rate
+}
+
+/// Dimension-wise diversity of a population (Hussain et al., 2019): the mean absolute
+/// distance to the median, averaged over the dimensions.
+#[must_use]
+pub fn dimension_wise_diversity(positions: &Population) -> f64 {
+    dimension_wise_diversity_in(positions, &mut Vec::with_capacity(positions.len()))
+}
+
+/// [`dimension_wise_diversity`] using `column` as scratch, so repeated calls do not allocate.
+pub fn dimension_wise_diversity_in(positions: &Population, column: &mut Vec<f64>) -> f64 {
+    dimension_wise_diversity_masked(positions, &[], column)
+}
+
+/// [`dimension_wise_diversity`] over the dimensions `bounds` leaves free: the fixed ones (see
+/// [`Bounds::is_fixed`]) hold the same value in every position and would only dilute the mean.
+#[must_use]
+pub fn dimension_wise_diversity_within(positions: &Population, bounds: &Bounds) -> f64 {
+    let fixed = bounds.fixed_mask();
+    dimension_wise_diversity_masked(positions, &fixed, &mut Vec::with_capacity(positions.len()))
+}
+
+/// Diversidade por dimensão ignorando as dimensões marcadas em `skip` (vazio não ignora nada)
+pub(crate) fn dimension_wise_diversity_masked(
+    positions: &Population,
+    skip: &[bool],
+    column: &mut Vec<f64>,
+) -> f64 {
+    let (n, dim) = (positions.len(), positions.dim());
+    let free = dim - skip.iter().filter(|&&s| s).count();
+    if n == 0 || free == 0 {
+        return 0.0;
+    }
+    let mut total = 0.0;
+    for j in (0..dim).filter(|&j| !skip.get(j).is_some_and(|&s| s)) {
+        column.clear();
+        column.extend(positions.iter().map(|x| x[j]));
+        let (_, &mut median, _) = column.select_nth_unstable_by(n / 2, f64::total_cmp);
+        total += column.iter().map(|v| (median - v).abs()).sum::<f64>();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let d = total / (n * free) as f64;
+    d
+}
+
+/// Ordem total das fitness, melhor primeiro e `NaN` por último em qualquer direção
 #[inline]
-fn cmp_fitness<O>(a: f64, b: f64) -> Ordering
+pub(crate) fn cmp_fitness<O>(a: f64, b: f64) -> Ordering
 where
     O: Objective,
 {
-    if O::better(a, b) {
+    if a.is_nan() || b.is_nan() {
+        a.is_nan().cmp(&b.is_nan())
+    } else if O::better(a, b) {
         Ordering::Less
     } else if O::better(b, a) {
         Ordering::Greater
@@ -66,3 +472,196 @@ where
         Ordering::Equal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::core::{Maximization, Minimization};
+
+    #[test]
+    fn sorts_in_place_and_returns_the_permutation() {
+        let mut positions = Population::from_flat(vec![3.0, 1.0, 4.0, 1.5, 5.0], 1).unwrap();
+        let mut fitness = vec![3.0, 1.0, 4.0, 1.5, 5.0];
+        let mut labels = vec!['a', 'b', 'c', 'd', 'e'];
+
+        let perm = sort_by_fitness_with::<Minimization>(&mut positions, &mut fitness).unwrap();
+        permute(&mut labels, &perm).unwrap();
+
+        assert_eq!(perm, vec![1, 3, 0, 2, 4]);
+        assert_eq!(fitness, vec![1.0, 1.5, 3.0, 4.0, 5.0]);
+        assert_eq!(positions.as_slice(), fitness.as_slice());
+        assert_eq!(labels, vec!['b', 'd', 'a', 'c', 'e']);
+
+        assert_eq!(
+            sort_by_fitness_with::<Minimization>(&mut positions, &mut fitness[..4]),
+            Err(ShapeError::Length {
+                expected: 5,
+                received: 4
+            })
+        );
+        assert_eq!(
+            permute(&mut labels, &[0, 1, 1, 3, 4]),
+            Err(ShapeError::NotPermutation { len: 5 })
+        );
+        assert!(permute(&mut labels, &[0, 1, 2, 3, 9]).is_err());
+        assert_eq!(labels, vec!['b', 'd', 'a', 'c', 'e']);
+    }
+
+    #[test]
+    fn ranks_without_mutating() {
+        let fitness = [3.0, 1.0, 4.0, 1.5, 5.0, 0.5];
+
+        assert_eq!(
+            argsort_by_fitness::<Minimization>(&fitness),
+            vec![5, 1, 3, 0, 2, 4]
+        );
+        assert_eq!(
+            argsort_by_fitness::<Maximization>(&fitness),
+            vec![4, 2, 0, 3, 1, 5]
+        );
+        assert_eq!(top_k_indices::<Minimization>(&fitness, 3), vec![5, 1, 3]);
+        assert_eq!(top_k_indices::<Maximization>(&fitness, 1), vec![4]);
+        assert_eq!(top_k_indices::<Minimization>(&fitness, 10).len(), 6);
+        assert!(top_k_indices::<Minimization>(&fitness, 0).is_empty());
+        // `NaN` não é comparável, mas vai para o fim nas duas direções
+        let holes = [f64::NAN, 2.0, f64::NAN, 1.0, 3.0];
+        assert_eq!(
+            argsort_by_fitness::<Minimization>(&holes),
+            vec![3, 1, 4, 0, 2]
+        );
+        assert_eq!(
+            argsort_by_fitness::<Maximization>(&holes),
+            vec![4, 1, 3, 0, 2]
+        );
+        assert_eq!(top_k_indices::<Maximization>(&holes, 2), vec![4, 1]);
+        assert_eq!(best_index_with::<Minimization>(&fitness), Some(5));
+        assert_eq!(best_index_with::<Maximization>(&[]), None);
+        // Um `NaN` (ou um infinito) na frente não esconde os valores finitos
+        assert_eq!(
+            best_index_with::<Minimization>(&[f64::NAN, 2.0, 1.0]),
+            Some(2)
+        );
+        assert_eq!(
+            best_index_with::<Minimization>(&[f64::NEG_INFINITY, 2.0]),
+            Some(1)
+        );
+        assert_eq!(
+            best_index_with::<Maximization>(&[f64::NAN, f64::INFINITY, f64::NAN]),
+            Some(1)
+        );
+        assert_eq!(best_index_with::<Minimization>(&[f64::NAN; 3]), Some(0));
+    }
+
+    #[test]
+    fn nan_fitness_ranks_last_in_a_total_order() {
+        let mut rng = ChaCha12Rng::seed_from_u64(5);
+        for _ in 0..500 {
+            let n = rng.random_range(1..40);
+            let fitness: Vec<f64> = (0..n)
+                .map(|_| {
+                    if rng.random_bool(0.3) {
+                        f64::NAN
+                    } else {
+                        rng.random_range(-5.0..5.0)
+                    }
+                })
+                .collect();
+            let numbers = fitness.iter().filter(|f| !f.is_nan()).count();
+            let min = argsort_by_fitness::<Minimization>(&fitness);
+            let max = argsort_by_fitness::<Maximization>(&fitness);
+            for order in [&min, &max] {
+                assert!(order[numbers..].iter().all(|&i| fitness[i].is_nan()));
+                assert!(order[numbers..].is_sorted());
+            }
+            assert!(min[..numbers].is_sorted_by(|&i, &j| fitness[i] <= fitness[j]));
+            assert!(max[..numbers].is_sorted_by(|&i, &j| fitness[i] >= fitness[j]));
+            let k = rng.random_range(1..=n);
+            assert_eq!(top_k_indices::<Minimization>(&fitness, k), min[..k]);
+            assert_eq!(top_k_indices::<Maximization>(&fitness, k), max[..k]);
+        }
+    }
+
+    #[test]
+    fn ties_go_to_the_lower_index() {
+        let fitness = [2.0, 1.0, 2.0, 1.0, 1.0, 3.0];
+
+        assert_eq!(best_index_with::<Minimization>(&fitness), Some(1));
+        assert_eq!(
+            argsort_by_fitness::<Minimization>(&fitness),
+            vec![1, 3, 4, 0, 2, 5]
+        );
+        for k in 1..=fitness.len() {
+            assert_eq!(
+                top_k_indices::<Minimization>(&fitness, k),
+                argsort_by_fitness::<Minimization>(&fitness)[..k]
+            );
+        }
+        let violation = [0.0, 0.5, 0.0, 0.0, 0.1, 0.0];
+        assert_eq!(
+            argsort_by_fitness_then::<Minimization>(&fitness, &violation),
+            Ok(vec![3, 4, 1, 0, 2, 5])
+        );
+        assert!(argsort_by_fitness_then::<Minimization>(&fitness, &violation[1..]).is_err());
+    }
+
+    #[test]
+    fn moments_survive_extreme_magnitudes() {
+        assert_eq!(mean(&[]), None);
+        assert_eq!(variance(&[2.0]), Some(0.0));
+        for scale in [1e150, 1e-150, 1.0] {
+            // Média e desvio de 1, 2, 3, 4 multiplicados pela escala; uma soma ingênua dos
+            // quadrados estoura (ou some) nos extremos
+            let values: Vec<f64> = [1.0, 2.0, 3.0, 4.0].iter().map(|v| v * scale).collect();
+            let m = mean(&values).unwrap();
+            let s = std_dev(&values).unwrap();
+            assert!((m / scale - 2.5).abs() < 1e-12, "{scale}: {m}");
+            assert!(
+                (s / scale - (5.0f64 / 3.0).sqrt()).abs() < 1e-12,
+                "{scale}: {s}"
+            );
+        }
+        // Desvio pequeno sobre um deslocamento enorme: sem cancelamento catastrófico
+        let shifted: Vec<f64> = [4.0, 7.0, 13.0, 16.0].iter().map(|v| 1e9 + v).collect();
+        assert!((variance(&shifted).unwrap() - 30.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn quantiles_and_success_rate() {
+        let values = [5.0, 1.0, 3.0, 2.0, 4.0];
+        assert_eq!(
+            quantiles(&values, &[0.0, 0.5, 1.0, 0.125]),
+            vec![1.0, 3.0, 5.0, 1.5]
+        );
+        assert!(quantiles(&[], &[0.5])[0].is_nan());
+
+        let runs = [1e-9, 2e-3, 0.5, f64::NAN];
+        assert!((success_rate(&runs, 0.0, 1e-8, Direction::Minimize) - 0.25).abs() < 1e-12);
+        assert!((success_rate(&runs, 0.0, 1e-2, Direction::Minimize) - 0.5).abs() < 1e-12);
+        assert!((success_rate(&runs, 0.4, 0.0, Direction::Maximize) - 0.25).abs() < 1e-12);
+        assert!(success_rate(&[], 0.0, 0.0, Direction::Minimize).abs() < 1e-12);
+    }
+
+    #[test]
+    fn selection_favors_better_ranks() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(3);
+        // Fitness negativa e de escalas variadas: só a ordem importa
+        let fitness = [-1e6, 2.0, -3.0];
+        let mut wins = [[0; 3]; 2];
+        for _ in 0..6000 {
+            wins[0][tournament_index::<Minimization, _>(&fitness, 2, &mut rng).unwrap()] += 1;
+            wins[1][roulette_index::<Maximization, _>(&fitness, &mut rng).unwrap()] += 1;
+        }
+        // Torneio de 2: o melhor vence com chance 1 - (2/3)², o pior com (1/3)²
+        assert!(wins[0][0] > 3000 && wins[0][1] < 1000);
+        // Pesos 3, 2, 1 para os postos de 2.0, -3.0 e -1e6
+        assert!(wins[1][1] > wins[1][2] && wins[1][2] > wins[1][0] && wins[1][0] > 600);
+
+        assert_eq!(tournament_index::<Minimization, _>(&[], 3, &mut rng), None);
+        assert_eq!(roulette_index::<Minimization, _>(&[], &mut rng), None);
+    }
+}
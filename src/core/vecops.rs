@@ -0,0 +1,215 @@
+//! Slice kernels of the hot loops: projection, the vector arithmetic of the position updates
+//! and distances.
+//!
+//! With the `simd` feature the kernels work on fixed-width lanes, which removes bounds checks
+//! and lets the reductions keep independent accumulators the compiler can vectorize. Without
+//! it they are plain loops. Both give the same results, except for the rounding of the
+//! reductions, whose summation order differs. Every kernel is generic over the
+//! [`Scalar`] type.
+//!
+//! The kernels expect slices of the same length. Debug builds, and release builds with the
+//! `strict` feature, panic on a mismatch; otherwise the extra elements are ignored.
+
+use crate::core::{Scalar, utils::strict_assert_eq};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "simd")] {
+        /// Elementos processados juntos em cada passo
+        const LANES: usize = 4;
+
+        /// `x[i] = x[i].clamp(lo, hi)`.
+        #[inline]
+        pub fn clamp<T: Scalar>(x: &mut [T], lo: T, hi: T) {
+            let mut chunks = x.chunks_exact_mut(LANES);
+            for c in &mut chunks {
+                for v in c {
+                    *v = v.clamp(lo, hi);
+                }
+            }
+            for v in chunks.into_remainder() {
+                *v = v.clamp(lo, hi);
+            }
+        }
+
+        /// `x[i] = x[i].clamp(lo[i], hi[i])`.
+        #[inline]
+        pub fn clamp_each<T: Scalar>(x: &mut [T], lo: &[T], hi: &[T]) {
+strict_assert_eq!(x.len(), lo.len(), "bounds dim mismatch");
+strict_assert_eq!(x.len(), hi.len(), "bounds dim mismatch");
+            let n = x.len().min(lo.len()).min(hi.len());
+            let (x, lo, hi) = (&mut x[..n], &lo[..n], &hi[..n]);
+            let mut xs = x.chunks_exact_mut(LANES);
+            let (mut ls, mut hs) = (lo.chunks_exact(LANES), hi.chunks_exact(LANES));
+            for ((c, l), h) in (&mut xs).zip(&mut ls).zip(&mut hs) {
+                for k in 0..LANES {
+                    c[k] = c[k].clamp(l[k], h[k]);
+                }
+            }
+            let tail = xs.into_remainder().iter_mut().zip(ls.remainder()).zip(hs.remainder());
+            for ((v, &l), &h) in tail {
+                *v = v.clamp(l, h);
+            }
+        }
+
+        /// `out[i] = a[i] - b[i]`.
+        #[inline]
+        pub fn sub_into<T: Scalar>(out: &mut [T], a: &[T], b: &[T]) {
+strict_assert_eq!(out.len(), a.len(), "dim mismatch");
+strict_assert_eq!(out.len(), b.len(), "dim mismatch");
+            let n = out.len().min(a.len()).min(b.len());
+            let (out, a, b) = (&mut out[..n], &a[..n], &b[..n]);
+            let mut os = out.chunks_exact_mut(LANES);
+            let (mut as_, mut bs) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+            for ((o, a), b) in (&mut os).zip(&mut as_).zip(&mut bs) {
+                for k in 0..LANES {
+                    o[k] = a[k] - b[k];
+                }
+            }
+            let tail = os.into_remainder().iter_mut().zip(as_.remainder()).zip(bs.remainder());
+            for ((o, a), b) in tail {
+                *o = *a - *b;
+            }
+        }
+
+        /// `y[i] = a * x[i] + y[i]`.
+        #[inline]
+        pub fn axpy<T: Scalar>(y: &mut [T], a: T, x: &[T]) {
+strict_assert_eq!(y.len(), x.len(), "dim mismatch");
+            let n = y.len().min(x.len());
+            let (y, x) = (&mut y[..n], &x[..n]);
+            let mut ys = y.chunks_exact_mut(LANES);
+            let mut xs = x.chunks_exact(LANES);
+            for (y, x) in (&mut ys).zip(&mut xs) {
+                for k in 0..LANES {
+                    y[k] = a.mul_add(x[k], y[k]);
+                }
+            }
+            for (y, x) in ys.into_remainder().iter_mut().zip(xs.remainder()) {
+                *y = a.mul_add(*x, *y);
+            }
+        }
+
+        /// Squared Euclidean distance between `a` and `b`.
+        #[inline]
+        #[must_use]
+        pub fn squared_distance<T: Scalar>(a: &[T], b: &[T]) -> T {
+strict_assert_eq!(a.len(), b.len(), "dim mismatch");
+            let n = a.len().min(b.len());
+            let (a, b) = (&a[..n], &b[..n]);
+            let mut acc = [T::zero(); LANES];
+            let (mut as_, mut bs) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+            for (a, b) in (&mut as_).zip(&mut bs) {
+                for k in 0..LANES {
+                    let d = a[k] - b[k];
+                    acc[k] = d.mul_add(d, acc[k]);
+                }
+            }
+            let mut sum = (acc[0] + acc[1]) + (acc[2] + acc[3]);
+            for (a, b) in as_.remainder().iter().zip(bs.remainder()) {
+                let d = *a - *b;
+                sum = d.mul_add(d, sum);
+            }
+            sum
+        }
+    } else {
+        /// `x[i] = x[i].clamp(lo, hi)`.
+        #[inline]
+        pub fn clamp<T: Scalar>(x: &mut [T], lo: T, hi: T) {
+            for v in x {
+                *v = v.clamp(lo, hi);
+            }
+        }
+
+        /// `x[i] = x[i].clamp(lo[i], hi[i])`.
+        #[inline]
+        pub fn clamp_each<T: Scalar>(x: &mut [T], lo: &[T], hi: &[T]) {
+strict_assert_eq!(x.len(), lo.len(), "bounds dim mismatch");
+strict_assert_eq!(x.len(), hi.len(), "bounds dim mismatch");
+            for ((v, &l), &h) in x.iter_mut().zip(lo).zip(hi) {
+                *v = v.clamp(l, h);
+            }
+        }
+
+        /// `out[i] = a[i] - b[i]`.
+        #[inline]
+        pub fn sub_into<T: Scalar>(out: &mut [T], a: &[T], b: &[T]) {
+strict_assert_eq!(out.len(), a.len(), "dim mismatch");
+strict_assert_eq!(out.len(), b.len(), "dim mismatch");
+            for ((o, a), b) in out.iter_mut().zip(a).zip(b) {
+                *o = *a - *b;
+            }
+        }
+
+        /// `y[i] = a * x[i] + y[i]`.
+        #[inline]
+        pub fn axpy<T: Scalar>(y: &mut [T], a: T, x: &[T]) {
+strict_assert_eq!(y.len(), x.len(), "dim mismatch");
+            for (y, x) in y.iter_mut().zip(x) {
+                *y = a.mul_add(*x, *y);
+            }
+        }
+
+        /// Squared Euclidean distance between `a` and `b`.
+        #[inline]
+        #[must_use]
+        pub fn squared_distance<T: Scalar>(a: &[T], b: &[T]) -> T {
+strict_assert_eq!(a.len(), b.len(), "dim mismatch");
+            a.iter().zip(b).map(|(&a, &b)| (a - b) * (a - b)).sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Comprimento que não é múltiplo da largura dos blocos
+    fn data() -> (Vec<f64>, Vec<f64>) {
+        let a: Vec<f64> = (0..11).map(|i| f64::from(i).mul_add(0.5, -2.0)).collect();
+        let b: Vec<f64> = (0..11).map(|i| f64::from(i).mul_add(-0.25, 1.0)).collect();
+        (a, b)
+    }
+
+    #[test]
+    fn kernels_match_scalar_loops() {
+        let (a, b) = data();
+
+        let mut x = a.clone();
+        clamp(&mut x, -1.0, 1.0);
+        assert!(
+            x.iter()
+                .zip(&a)
+                .all(|(x, a)| (x - a.clamp(-1.0, 1.0)).abs() < 1e-15)
+        );
+
+        let mut x = a.clone();
+        clamp_each(&mut x, &b, &[2.0; 11]);
+        assert!(
+            x.iter()
+                .zip(&a)
+                .zip(&b)
+                .all(|((x, a), b)| (x - a.clamp(*b, 2.0)).abs() < 1e-15)
+        );
+
+        let mut out = vec![0.0; 11];
+        sub_into(&mut out, &a, &b);
+        assert!(
+            out.iter()
+                .zip(&a)
+                .zip(&b)
+                .all(|((o, a), b)| (o - (a - b)).abs() < 1e-15)
+        );
+
+        let mut y = b.clone();
+        axpy(&mut y, 0.5, &a);
+        assert!(
+            y.iter()
+                .zip(&a)
+                .zip(&b)
+                .all(|((y, a), b)| (y - 0.5f64.mul_add(*a, *b)).abs() < 1e-12)
+        );
+
+        let expected: f64 = a.iter().zip(&b).map(|(a, b)| (a - b).powi(2)).sum();
+        assert!((squared_distance(&a, &b) - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,129 @@
+/// Transfer function mapping a continuous coordinate to the probability of a `1` bit, as in
+/// binary HHO (BHHO). A bit is set when its probability reaches `0.5`, so decoding stays
+/// deterministic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Transfer {
+    /// The coordinate itself, for positions searched in `[0, 1]`.
+    #[default]
+    Threshold,
+
+    /// S-shaped `1 / (1 + e⁻ˣ)`: set for `x >= 0`.
+    Sigmoid,
+
+    /// V-shaped `|tanh x|`: set for `|x| >= atanh 0.5 ≈ 0.55`.
+    VShaped,
+}
+
+impl Transfer {
+    /// Probability of a `1` bit for the coordinate `x`.
+    #[must_use]
+    pub fn probability(self, x: f64) -> f64 {
+        match self {
+            Self::Threshold => x,
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::VShaped => x.tanh().abs(),
+        }
+    }
+
+    #[must_use]
+    pub fn bit(self, x: f64) -> bool {
+        self.probability(x) >= 0.5
+    }
+
+    /// Indices of the set bits of `x`, in increasing order.
+    #[must_use]
+    pub fn ones(self, x: &[f64]) -> Vec<usize> {
+        (0..x.len()).filter(|&i| self.bit(x[i])).collect()
+    }
+}
+
+/// How a group of bits spells an unsigned integer, most significant bit first.
+///
+/// With [`BitCode::Gray`], consecutive integers differ in a single bit, so the search reaches
+/// a neighboring value by flipping one bit instead of several (e.g. `3 → 4` is `011 → 100` in
+/// base-2 but `010 → 110` in Gray).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BitCode {
+    /// Plain base-2.
+    #[default]
+    Binary,
+
+    /// Reflected binary Gray code.
+    Gray,
+}
+
+impl BitCode {
+    /// The `width` bits spelling `value` (only its lowest `width` bits are kept).
+    #[must_use]
+    pub fn encode(self, value: u64, width: usize) -> Vec<bool> {
+        let word = match self {
+            Self::Binary => value,
+            Self::Gray => value ^ (value >> 1),
+        };
+        (0..width)
+            .rev()
+            .map(|i| i < 64 && (word >> i) & 1 == 1)
+            .collect()
+    }
+
+    /// The integer spelled by `bits` (at most 64 of them).
+    #[must_use]
+    pub fn decode(self, bits: &[bool]) -> u64 {
+        let word = bits.iter().fold(0u64, |acc, &b| (acc << 1) | u64::from(b));
+        match self {
+            Self::Binary => word,
+            Self::Gray => {
+                // Desfaz o XOR com todos os prefixos deslocados
+                let mut value = word;
+                let mut shift = word >> 1;
+                while shift != 0 {
+                    value ^= shift;
+                    shift >>= 1;
+                }
+                value
+            }
+        }
+    }
+}
+
+/// Bits needed to spell every integer in `0..=max`.
+#[must_use]
+pub const fn bits_for(max: u64) -> usize {
+    (u64::BITS - max.leading_zeros()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_follow_the_transfer_shape() {
+        let x = [-1.0, -0.2, 0.0, 0.3, 0.6, 2.0];
+        assert_eq!(Transfer::Threshold.ones(&x), vec![4, 5]);
+        assert_eq!(Transfer::Sigmoid.ones(&x), vec![2, 3, 4, 5]);
+        assert_eq!(Transfer::VShaped.ones(&x), vec![0, 4, 5]);
+        assert!((Transfer::Sigmoid.probability(0.0) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn gray_neighbors_differ_in_one_bit() {
+        assert_eq!(bits_for(0), 0);
+        assert_eq!(bits_for(7), 3);
+        assert_eq!(bits_for(8), 4);
+        assert_eq!(BitCode::Binary.encode(6, 4), vec![false, true, true, false]);
+        assert_eq!(BitCode::Gray.encode(6, 4), vec![false, true, false, true]);
+        for code in [BitCode::Binary, BitCode::Gray] {
+            for v in 0..32 {
+                assert_eq!(code.decode(&code.encode(v, 5)), v);
+            }
+        }
+        for v in 0..31 {
+            let (a, b) = (BitCode::Gray.encode(v, 5), BitCode::Gray.encode(v + 1, 5));
+            assert_eq!(a.iter().zip(&b).filter(|(x, y)| x != y).count(), 1);
+        }
+    }
+}
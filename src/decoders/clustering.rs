@@ -0,0 +1,156 @@
+use thiserror::Error;
+
+use crate::core::{Bounds, Decoder, DecoderError, bounds::BoundsError, vecops};
+
+#[derive(Debug, Error)]
+pub enum ClusteringError {
+    #[error("Empty dataset or no clusters")]
+    Empty,
+
+    #[error("Ragged dataset: point {point} has {len} features, expected {features}")]
+    Ragged {
+        point: usize,
+        len: usize,
+        features: usize,
+    },
+}
+
+/// k-means objective over a fixed dataset.
+///
+/// The position holds `k` centroids, laid out one after the other, and the fitness is the
+/// within-cluster sum of squares: the squared distance from every point to its nearest
+/// centroid, summed.
+#[derive(Debug, Clone)]
+pub struct Clustering {
+    data: Vec<Vec<f64>>,
+    k: usize,
+    features: usize,
+}
+
+impl Clustering {
+    /// # Errors
+    ///
+    /// [`ClusteringError::Empty`] if `data` is empty or `k == 0`, [`ClusteringError::Ragged`]
+    /// if the points do not all have the same number of features.
+    pub fn new(data: Vec<Vec<f64>>, k: usize) -> Result<Self, ClusteringError> {
+        let features = data.first().map_or(0, Vec::len);
+        if features == 0 || k == 0 {
+            return Err(ClusteringError::Empty);
+        }
+        if let Some((point, p)) = data.iter().enumerate().find(|(_, p)| p.len() != features) {
+            return Err(ClusteringError::Ragged {
+                point,
+                len: p.len(),
+                features,
+            });
+        }
+        Ok(Self { data, k, features })
+    }
+
+    #[must_use]
+    pub const fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Number of features per point.
+    #[must_use]
+    pub const fn features(&self) -> usize {
+        self.features
+    }
+
+    /// Search dimension, `k · features`.
+    #[must_use]
+    pub const fn dim(&self) -> usize {
+        self.k * self.features
+    }
+
+    /// The bounding box of the data, repeated for each centroid.
+    ///
+    /// # Errors
+    ///
+    /// Never in practice; the box of a non-empty dataset is valid.
+    pub fn bounds(&self) -> Result<Bounds, BoundsError> {
+        let (lo, hi): (Vec<f64>, Vec<f64>) = (0..self.features)
+            .map(|j| {
+                self.data
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                        (lo.min(p[j]), hi.max(p[j]))
+                    })
+            })
+            .unzip();
+        Bounds::per_dim(lo.repeat(self.k), hi.repeat(self.k))
+    }
+
+    /// Index of the nearest centroid of each point.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have [`Clustering::dim`] values.
+    pub fn assign(&self, x: &[f64]) -> Result<Vec<usize>, DecoderError> {
+        self.check_dim(x)?;
+        Ok(self.data.iter().map(|p| self.nearest(x, p).0).collect())
+    }
+
+    const fn check_dim(&self, x: &[f64]) -> Result<(), DecoderError> {
+        let expected = self.dim();
+        if x.len() == expected {
+            Ok(())
+        } else {
+            Err(DecoderError::InvalidDimension {
+                expected,
+                received: x.len(),
+            })
+        }
+    }
+
+    /// Centróide mais próximo de `point` e a distância quadrada até ele
+    fn nearest(&self, x: &[f64], point: &[f64]) -> (usize, f64) {
+        x.chunks_exact(self.features)
+            .map(|c| vecops::squared_distance(c, point))
+            .enumerate()
+            .fold(
+                (0, f64::INFINITY),
+                |best, (i, d)| if d < best.1 { (i, d) } else { best },
+            )
+    }
+}
+
+impl Decoder for Clustering {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        self.check_dim(solution)?;
+        Ok(self.data.iter().map(|p| self.nearest(solution, p).1).sum())
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.dim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_the_within_cluster_sum_of_squares() {
+        let data = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 2.0],
+            vec![10.0, 0.0],
+            vec![10.0, 2.0],
+        ];
+        let clustering = Clustering::new(data, 2).unwrap();
+        assert_eq!(clustering.dim(), 4);
+        let bounds = clustering.bounds().unwrap();
+        assert!((bounds.hi_at(2) - 10.0).abs() < 1e-12 && (bounds.hi_at(3) - 2.0).abs() < 1e-12);
+
+        let centroids = [10.0, 1.0, 0.0, 1.0];
+        assert_eq!(clustering.assign(&centroids).unwrap(), vec![1, 1, 0, 0]);
+        assert!((clustering.decode(&centroids).unwrap() - 4.0).abs() < 1e-12);
+        assert!(clustering.decode(&[0.0; 3]).is_err());
+        assert!(matches!(
+            Clustering::new(vec![vec![0.0], vec![0.0, 1.0]], 1),
+            Err(ClusteringError::Ragged { point: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,336 @@
+//! Domination problems on a [`Graph`], decoded from positions in `[0, 1]ⁿ`.
+//!
+//! Coordinate `i` belongs to vertex `i`: it is compared against thresholds to read a label,
+//! infeasible labelings are repaired greedily (preferring the vertices with the largest
+//! coordinates) and redundant vertices are then dropped, so every position maps to a valid
+//! solution whose weight is the fitness. Search in [`unit_bounds`].
+
+use crate::{
+    core::{Bounds, Decoder, DecoderError, bounds::BoundsError},
+    decoders::graph::{Graph, GraphError},
+};
+
+/// The box `[0, 1]ⁿ` matching a graph of order `n`.
+///
+/// # Errors
+///
+/// [`BoundsError::InvalidDim`] for the empty graph.
+pub fn unit_bounds(graph: &Graph) -> Result<Bounds, BoundsError> {
+    Bounds::uniform(0.0, 1.0, graph.order())
+}
+
+/// Minimum dominating set: every vertex is in the set or adjacent to it.
+///
+/// Vertex `i` is picked when `x[i] >= threshold` (`0.5` by default).
+#[derive(Debug, Clone)]
+pub struct DominatingSet {
+    graph: Graph,
+    threshold: f64,
+}
+
+impl DominatingSet {
+    #[must_use]
+    pub const fn new(graph: Graph) -> Self {
+        Self {
+            graph,
+            threshold: 0.5,
+        }
+    }
+
+    #[must_use]
+    pub const fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[must_use]
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// The repaired dominating set encoded by `x`, in increasing order.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one value per vertex.
+    pub fn solution(&self, x: &[f64]) -> Result<Vec<usize>, DecoderError> {
+        check_dim(&self.graph, x)?;
+        Ok(covering_set(&self.graph, x, self.threshold, true))
+    }
+}
+
+impl Decoder for DominatingSet {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(weight(self.solution(solution)?.len()))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.graph.order())
+    }
+}
+
+/// Minimum total dominating set: every vertex, chosen or not, has a neighbor in the set.
+///
+/// Vertex `i` is picked when `x[i] >= threshold` (`0.5` by default).
+#[derive(Debug, Clone)]
+pub struct TotalDominatingSet {
+    graph: Graph,
+    threshold: f64,
+}
+
+impl TotalDominatingSet {
+    /// # Errors
+    ///
+    /// [`GraphError::IsolatedVertex`] if some vertex has no neighbor, since then no total
+    /// dominating set exists.
+    pub fn new(graph: Graph) -> Result<Self, GraphError> {
+        if let Some(v) = (0..graph.order()).find(|&v| graph.degree(v) == 0) {
+            return Err(GraphError::IsolatedVertex(v));
+        }
+        Ok(Self {
+            graph,
+            threshold: 0.5,
+        })
+    }
+
+    #[must_use]
+    pub const fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[must_use]
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// The repaired total dominating set encoded by `x`, in increasing order.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one value per vertex.
+    pub fn solution(&self, x: &[f64]) -> Result<Vec<usize>, DecoderError> {
+        check_dim(&self.graph, x)?;
+        Ok(covering_set(&self.graph, x, self.threshold, false))
+    }
+}
+
+impl Decoder for TotalDominatingSet {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(weight(self.solution(solution)?.len()))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.graph.order())
+    }
+}
+
+/// Minimum Roman domination: labels `f(v) ∈ {0, 1, 2}` such that every vertex labeled `0`
+/// has a neighbor labeled `2`, minimizing `Σ f(v)`.
+///
+/// Vertex `i` gets `0` below the first threshold, `1` below the second and `2` above
+/// (`1/3` and `2/3` by default).
+#[derive(Debug, Clone)]
+pub struct RomanDomination {
+    graph: Graph,
+    thresholds: (f64, f64),
+}
+
+impl RomanDomination {
+    #[must_use]
+    pub const fn new(graph: Graph) -> Self {
+        Self {
+            graph,
+            thresholds: (1.0 / 3.0, 2.0 / 3.0),
+        }
+    }
+
+    #[must_use]
+    pub const fn thresholds(mut self, one: f64, two: f64) -> Self {
+        self.thresholds = (one, two);
+        self
+    }
+
+    #[must_use]
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// The repaired Roman dominating function encoded by `x`, one label per vertex.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one value per vertex.
+    pub fn solution(&self, x: &[f64]) -> Result<Vec<u8>, DecoderError> {
+        check_dim(&self.graph, x)?;
+        let graph = &self.graph;
+        let (one, two) = self.thresholds;
+        let mut labels: Vec<u8> = x
+            .iter()
+            .map(|&v| u8::from(v >= one) + u8::from(v >= two))
+            .collect();
+
+        // Reparo: um vértice 0 sem vizinho 2 vira 1, ou promove a 2 o vizinho mais forte
+        for v in 0..graph.order() {
+            if labels[v] == 0 && !graph.neighbors(v).iter().any(|&u| labels[u] == 2) {
+                let u = strongest(graph.closed(v), x);
+                labels[u] = if u == v { 1 } else { 2 };
+            }
+        }
+
+        // Um vértice 1 com vizinho 2 já está protegido e pode cair para 0
+        for v in 0..graph.order() {
+            if labels[v] == 1 && graph.neighbors(v).iter().any(|&u| labels[u] == 2) {
+                labels[v] = 0;
+            }
+        }
+        Ok(labels)
+    }
+}
+
+impl Decoder for RomanDomination {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let labels = self.solution(solution)?;
+        Ok(weight(labels.iter().map(|&l| usize::from(l)).sum()))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.graph.order())
+    }
+}
+
+const fn check_dim(graph: &Graph, x: &[f64]) -> Result<(), DecoderError> {
+    if x.len() == graph.order() {
+        Ok(())
+    } else {
+        Err(DecoderError::InvalidDimension {
+            expected: graph.order(),
+            received: x.len(),
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn weight(w: usize) -> f64 {
+    w as f64
+}
+
+/// Vértice de maior coordenada (o primeiro, em caso de empate)
+fn strongest(vertices: impl Iterator<Item = usize>, x: &[f64]) -> usize {
+    vertices
+        .reduce(|best, u| if x[u] > x[best] { u } else { best })
+        .unwrap_or_default()
+}
+
+/// Conjunto dominante (`closed`) ou dominante total (vizinhança aberta) lido de `x`,
+/// reparado e sem vértices redundantes
+fn covering_set(graph: &Graph, x: &[f64], threshold: f64, closed: bool) -> Vec<usize> {
+    let n = graph.order();
+    let dominators = |v: usize| -> Vec<usize> {
+        if closed {
+            graph.closed(v).collect()
+        } else {
+            graph.neighbors(v).to_vec()
+        }
+    };
+
+    let mut chosen: Vec<bool> = x.iter().map(|&v| v >= threshold).collect();
+    // cover[v] = quantos escolhidos dominam v
+    let mut cover = vec![0usize; n];
+    for u in (0..n).filter(|&u| chosen[u]) {
+        for w in dominators(u) {
+            cover[w] += 1;
+        }
+    }
+
+    // Reparo guloso: cada vértice descoberto escolhe o dominador de maior coordenada
+    for v in 0..n {
+        if cover[v] == 0 {
+            let u = strongest(dominators(v).into_iter(), x);
+            chosen[u] = true;
+            for w in dominators(u) {
+                cover[w] += 1;
+            }
+        }
+    }
+
+    // Poda: remove, dos mais fracos aos mais fortes, os que só cobrem vértices já cobertos
+    let mut order: Vec<usize> = (0..n).filter(|&u| chosen[u]).collect();
+    order.sort_by(|&a, &b| x[a].total_cmp(&x[b]));
+    for u in order {
+        let targets = dominators(u);
+        if targets.iter().all(|&w| cover[w] >= 2) {
+            chosen[u] = false;
+            for w in targets {
+                cover[w] -= 1;
+            }
+        }
+    }
+
+    (0..n).filter(|&u| chosen[u]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Caminho 0 - 1 - 2 - 3 - 4
+    fn path() -> Graph {
+        Graph::from_edges(5, [(0, 1), (1, 2), (2, 3), (3, 4)]).unwrap()
+    }
+
+    fn is_dominating(g: &Graph, set: &[usize], closed: bool) -> bool {
+        (0..g.order())
+            .all(|v| (closed && set.contains(&v)) || g.neighbors(v).iter().any(|u| set.contains(u)))
+    }
+
+    #[test]
+    fn repairs_to_feasible_sets() {
+        let g = path();
+        let ds = DominatingSet::new(g.clone());
+        let tds = TotalDominatingSet::new(g.clone()).unwrap();
+        for x in [[0.0; 5], [1.0; 5], [0.9, 0.1, 0.2, 0.8, 0.3]] {
+            let set = ds.solution(&x).unwrap();
+            assert!(is_dominating(&g, &set, true), "{x:?} -> {set:?}");
+            let set = tds.solution(&x).unwrap();
+            assert!(is_dominating(&g, &set, false), "{x:?} -> {set:?}");
+        }
+        // γ(P5) = 2, γt(P5) = 3
+        let x = [0.0, 1.0, 0.0, 0.0, 1.0];
+        assert!((ds.decode(&[0.0, 1.0, 0.0, 1.0, 0.0]).unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(ds.solution(&x).unwrap(), vec![1, 4]);
+        assert!((tds.decode(&[0.0, 1.0, 1.0, 1.0, 0.0]).unwrap() - 3.0).abs() < 1e-12);
+        assert!(matches!(
+            ds.decode(&[0.0; 4]),
+            Err(DecoderError::InvalidDimension {
+                expected: 5,
+                received: 4
+            })
+        ));
+        assert!(matches!(
+            TotalDominatingSet::new(Graph::new(2)),
+            Err(GraphError::IsolatedVertex(0))
+        ));
+    }
+
+    #[test]
+    fn roman_labels_are_feasible_and_trimmed() {
+        let g = path();
+        let rd = RomanDomination::new(g.clone());
+        for x in [[0.0; 5], [0.5; 5], [1.0; 5], [0.0, 0.9, 0.0, 0.0, 0.9]] {
+            let labels = rd.solution(&x).unwrap();
+            for v in 0..g.order() {
+                assert!(
+                    labels[v] > 0 || g.neighbors(v).iter().any(|&u| labels[u] == 2),
+                    "{x:?} -> {labels:?}"
+                );
+            }
+        }
+        // γR(P5) = 4
+        assert_eq!(
+            rd.solution(&[0.0, 0.9, 0.0, 0.9, 0.0]).unwrap(),
+            vec![0, 2, 0, 2, 0]
+        );
+        assert!((rd.decode(&[0.5; 5]).unwrap() - 5.0).abs() < 1e-12);
+    }
+}
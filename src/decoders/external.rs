@@ -0,0 +1,309 @@
+use std::{
+    ffi::OsString,
+    fs,
+    io::{ErrorKind, Read, Write},
+    path::PathBuf,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{Decoder, DecoderError},
+    decoders::limiter::Limiter,
+};
+
+/// Argumento substituído pelo caminho do arquivo temporário
+const PLACEHOLDER: &str = "{}";
+
+/// Intervalo entre verificações do processo enquanto há timeout
+const POLL: Duration = Duration::from_millis(5);
+
+/// How an [`ExternalDecoder`] hands the candidate to the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Input {
+    /// One line of whitespace-separated numbers on stdin.
+    #[default]
+    Stdin,
+
+    /// The same line in a fresh temporary file, removed after the call. Its path replaces
+    /// every `{}` argument, or is appended to the arguments if there is none.
+    File,
+}
+
+/// Evaluates each candidate by running an external program, e.g. a black-box simulator.
+///
+/// The candidate is written with full precision as whitespace-separated numbers (see
+/// [`Input`]) and the fitness is the first token the program prints on stdout. A failed
+/// launch, a non-zero exit status, an unparsable output or an expired timeout is reported as
+/// [`DecoderError::External`]; a program still running at its timeout is killed.
+///
+/// The decoder is `Sync`, so parallel optimizers may call it from several threads;
+/// [`ExternalDecoder::max_concurrency`] caps how many programs run at once.
+#[derive(Debug)]
+pub struct ExternalDecoder {
+    program: OsString,
+    args: Vec<OsString>,
+    input: Input,
+    timeout: Option<Duration>,
+    max_concurrency: usize,
+    running: Limiter,
+}
+
+impl ExternalDecoder {
+    /// Runs `program` without arguments, on stdin, without timeout or concurrency cap.
+    #[must_use]
+    pub fn new(program: impl Into<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            input: Input::Stdin,
+            timeout: None,
+            max_concurrency: 0,
+            running: Limiter::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    #[must_use]
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub const fn input(mut self, input: Input) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Kills the program and fails the evaluation after `timeout`.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// At most `n` programs run at the same time; `0` (the default) means no limit.
+    #[must_use]
+    pub const fn max_concurrency(mut self, n: usize) -> Self {
+        self.max_concurrency = n;
+        self
+    }
+
+    /// Executa o programa com `line` como entrada e devolve o stdout
+    fn call(&self, line: &str) -> Result<Vec<u8>, String> {
+        let _permit = self.running.acquire(self.max_concurrency);
+        let mut command = Command::new(&self.program);
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        let file = match self.input {
+            Input::Stdin => {
+                command.args(&self.args).stdin(Stdio::piped());
+                None
+            }
+            Input::File => {
+                let file = TempFile::write(line).map_err(|e| e.to_string())?;
+                let path = file.0.as_os_str();
+                let mut substituted = false;
+                for arg in &self.args {
+                    if arg == PLACEHOLDER {
+                        command.arg(path);
+                        substituted = true;
+                    } else {
+                        command.arg(arg);
+                    }
+                }
+                if !substituted {
+                    command.arg(path);
+                }
+                Some(file)
+            }
+        };
+        let child = command
+            .spawn()
+            .map_err(|e| format!("cannot run {}: {e}", self.program.to_string_lossy()))?;
+        let output = self.wait(child, line);
+        drop(file);
+        output
+    }
+
+    /// Espera o processo respeitando o timeout. O stdout é lido e o stdin escrito em threads
+    /// próprias, já abertas quando o prazo começa a contar: um programa que responde antes de
+    /// terminar de ler não trava nos dois pipes, e no timeout as duas terminam com o processo
+    fn wait(&self, mut child: Child, line: &str) -> Result<Vec<u8>, String> {
+        let mut stdout = child.stdout.take().ok_or("stdout not captured")?;
+        let stdin = child.stdin.take();
+        thread::scope(|scope| {
+            let reader = scope.spawn(move || {
+                let mut buf = Vec::new();
+                stdout.read_to_end(&mut buf).map(|_| buf)
+            });
+            let writer = stdin.map(|mut stdin| scope.spawn(move || writeln!(stdin, "{line}")));
+            let status = self.status(&mut child);
+            let written = writer.map(|w| w.join().map_err(|_| "stdin writer panicked"));
+            let stdout = reader.join().map_err(|_| "stdout reader panicked");
+            let status = status?;
+            match written.transpose()? {
+                // O programa pode sair sem ler a entrada; o status decide
+                Some(Err(e)) if e.kind() != ErrorKind::BrokenPipe => {
+                    return Err(format!("cannot write the candidate: {e}"));
+                }
+                _ => {}
+            }
+            let stdout = stdout?.map_err(|e| e.to_string())?;
+            if status.success() {
+                Ok(stdout)
+            } else {
+                Err(format!("exited with {status}"))
+            }
+        })
+    }
+
+    /// Status de saída do processo, que é morto se passar do timeout
+    fn status(&self, child: &mut Child) -> Result<ExitStatus, String> {
+        let Some(timeout) = self.timeout else {
+            return child.wait().map_err(|e| e.to_string());
+        };
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+                return Ok(status);
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {timeout:?}"));
+            }
+            thread::sleep(POLL);
+        }
+    }
+}
+
+impl Clone for ExternalDecoder {
+    fn clone(&self) -> Self {
+        Self {
+            program: self.program.clone(),
+            args: self.args.clone(),
+            input: self.input,
+            timeout: self.timeout,
+            max_concurrency: self.max_concurrency,
+            running: Limiter::default(),
+        }
+    }
+}
+
+impl Decoder for ExternalDecoder {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let line: Vec<String> = solution.iter().map(f64::to_string).collect();
+        let stdout = self.call(&line.join(" ")).map_err(DecoderError::External)?;
+        let stdout = String::from_utf8_lossy(&stdout);
+        let token = stdout
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| DecoderError::External("empty output".to_owned()))?;
+        token
+            .parse()
+            .map_err(|_| DecoderError::External(format!("cannot parse fitness `{token}`")))
+    }
+}
+
+/// Arquivo temporário com o candidato, removido ao sair de escopo
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn write(line: &str) -> std::io::Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let name = format!(
+            "kambo-hho-{}-{}.txt",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, format!("{line}\n"))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Soma dos quadrados dos números lidos
+    const AWK_SPHERE: &str = "{ s = 0; for (i = 1; i <= NF; i++) s += $i * $i; print s }";
+
+    #[test]
+    fn stdin_and_file_inputs() {
+        let stdin = ExternalDecoder::new("awk").arg(AWK_SPHERE);
+        assert!((stdin.decode(&[1.0, 2.0, 0.5]).unwrap() - 5.25).abs() < 1e-12);
+
+        let file = ExternalDecoder::new("awk")
+            .args([AWK_SPHERE, PLACEHOLDER])
+            .input(Input::File)
+            .max_concurrency(1);
+        assert!((file.decode(&[3.0]).unwrap() - 9.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn large_candidates_do_not_fill_both_pipes() {
+        // `cat` devolve a entrada enquanto lê: os dois pipes enchem se ninguém ler o stdout
+        let echo = ExternalDecoder::new("cat").timeout(Duration::from_secs(10));
+        let mut big = vec![0.25; 100_000];
+        big[0] = 1.5;
+        assert!((echo.decode(&big).unwrap() - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn failures_are_external_errors() {
+        let slow = ExternalDecoder::new("sleep")
+            .arg("5")
+            .timeout(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(matches!(
+            slow.decode(&[0.0]),
+            Err(DecoderError::External(_))
+        ));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        // Um candidato maior que o buffer do pipe, para um programa que nunca lê a entrada
+        let big = vec![0.5; 100_000];
+        let start = Instant::now();
+        assert!(matches!(slow.decode(&big), Err(DecoderError::External(_))));
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        let failing = ExternalDecoder::new("sh").args(["-c", "exit 3"]);
+        assert!(matches!(
+            failing.decode(&[0.0]),
+            Err(DecoderError::External(_))
+        ));
+        let garbage = ExternalDecoder::new("echo").arg("nan?");
+        assert!(matches!(
+            garbage.decode(&[0.0]),
+            Err(DecoderError::External(_))
+        ));
+        let missing = ExternalDecoder::new("/nonexistent/evaluator");
+        assert!(matches!(
+            missing.decode(&[0.0]),
+            Err(DecoderError::External(_))
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    core::{Decoder, DecoderError},
+    decoders::{binary::Transfer, genome::BinaryGenome},
+};
+
+/// Wrapper feature selection: picks a subset of features, scores it with a user closure
+/// (typically a classifier's validation accuracy, in `[0, 1]`) and minimizes
+///
+/// ```text
+/// α · (1 − score) + (1 − α) · |subset| / n_features
+/// ```
+///
+/// with `α = 0.99` by default, so accuracy dominates and size breaks ties. Coordinate `i`
+/// selects feature `i` through the [`Transfer`] function. An empty subset is repaired to the
+/// single feature with the largest coordinate.
+#[derive(Debug, Clone)]
+pub struct FeatureSelection<F> {
+    n_features: usize,
+    score: F,
+    transfer: Transfer,
+    alpha: f64,
+}
+
+impl<F: Fn(&[usize]) -> f64> FeatureSelection<F> {
+    #[must_use]
+    pub const fn new(n_features: usize, score: F) -> Self {
+        Self {
+            n_features,
+            score,
+            transfer: Transfer::Threshold,
+            alpha: 0.99,
+        }
+    }
+
+    #[must_use]
+    pub const fn transfer(mut self, transfer: Transfer) -> Self {
+        self.transfer = transfer;
+        self
+    }
+
+    /// Weight of the score against the subset size, in `[0, 1]`.
+    #[must_use]
+    pub const fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    #[must_use]
+    pub const fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    /// The (non-empty) feature subset encoded by `x`, in increasing order.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one value per feature.
+    pub fn subset(&self, x: &[f64]) -> Result<Vec<usize>, DecoderError> {
+        if x.len() != self.n_features {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.n_features,
+                received: x.len(),
+            });
+        }
+        let mut subset = BinaryGenome::with_transfer(x, self.transfer).ones();
+        if subset.is_empty() {
+            let strongest = (0..x.len()).reduce(|best, i| if x[i] > x[best] { i } else { best });
+            subset.extend(strongest);
+        }
+        Ok(subset)
+    }
+}
+
+impl<F: Fn(&[usize]) -> f64> Decoder for FeatureSelection<F> {
+    #[allow(clippy::cast_precision_loss)]
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let subset = self.subset(solution)?;
+        let score = (self.score)(&subset);
+        let ratio = subset.len() as f64 / self.n_features as f64;
+        Ok(self.alpha.mul_add(1.0 - score, (1.0 - self.alpha) * ratio))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.n_features)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trades_score_for_size() {
+        let fs = FeatureSelection::new(4, |s: &[usize]| if s.contains(&1) { 1.0 } else { 0.5 })
+            .alpha(0.9);
+        // Só a feature 1: 0.9 · 0 + 0.1 · 1/4
+        assert!((fs.decode(&[0.0, 0.9, 0.1, 0.0]).unwrap() - 0.025).abs() < 1e-12);
+        // Todas: 0.9 · 0 + 0.1 · 1
+        assert!((fs.decode(&[1.0; 4]).unwrap() - 0.1).abs() < 1e-12);
+        // Vazio vira a feature mais forte (2): 0.9 · 0.5 + 0.1 · 1/4
+        assert_eq!(fs.subset(&[0.0, 0.1, 0.3, 0.2]).unwrap(), vec![2]);
+        assert!((fs.decode(&[0.0, 0.1, 0.3, 0.2]).unwrap() - 0.475).abs() < 1e-12);
+        assert!(fs.decode(&[0.0; 3]).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::decoders::{
+    binary::{BitCode, Transfer},
+    permutation::random_keys,
+};
+
+/// A typed solution representation read from the continuous search vector.
+///
+/// [`Genome::from_position`] is how a decoder interprets a position and
+/// [`Genome::to_position`] gives back a position that reads as the same genome, e.g. to seed
+/// the population with known solutions.
+pub trait Genome: Sized {
+    /// Number of genes.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The genome encoded by the position `x`, one gene per coordinate.
+    fn from_position(x: &[f64]) -> Self;
+
+    /// A position that [`Genome::from_position`] maps back to this genome.
+    fn to_position(&self) -> Vec<f64>;
+}
+
+/// Real-valued genes, taken as they are.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealGenome(pub Vec<f64>);
+
+impl Genome for RealGenome {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn from_position(x: &[f64]) -> Self {
+        Self(x.to_vec())
+    }
+
+    fn to_position(&self) -> Vec<f64> {
+        self.0.clone()
+    }
+}
+
+/// Bits, read with [`Transfer::Threshold`] (`x >= 0.5`) unless another transfer function is
+/// given to [`BinaryGenome::with_transfer`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryGenome(pub Vec<bool>);
+
+impl BinaryGenome {
+    #[must_use]
+    pub fn with_transfer(x: &[f64], transfer: Transfer) -> Self {
+        Self(x.iter().map(|&v| transfer.bit(v)).collect())
+    }
+
+    /// Indices of the set bits, in increasing order.
+    #[must_use]
+    pub fn ones(&self) -> Vec<usize> {
+        (0..self.0.len()).filter(|&i| self.0[i]).collect()
+    }
+
+    /// Reads consecutive groups of `width` bits as unsigned integers with `code`; trailing
+    /// bits that do not fill a group are ignored.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is zero.
+    #[must_use]
+    pub fn integers(&self, width: usize, code: BitCode) -> Vec<u64> {
+        self.0.chunks_exact(width).map(|g| code.decode(g)).collect()
+    }
+
+    /// The genome spelling each of `values` in `width` bits with `code`.
+    #[must_use]
+    pub fn from_integers(values: &[u64], width: usize, code: BitCode) -> Self {
+        Self(values.iter().flat_map(|&v| code.encode(v, width)).collect())
+    }
+}
+
+impl Genome for BinaryGenome {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn from_position(x: &[f64]) -> Self {
+        Self::with_transfer(x, Transfer::Threshold)
+    }
+
+    fn to_position(&self) -> Vec<f64> {
+        self.0.iter().map(|&b| f64::from(u8::from(b))).collect()
+    }
+}
+
+/// An ordering of `0..n`, read with random keys (see [`random_keys`]).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermutationGenome(pub Vec<usize>);
+
+impl Genome for PermutationGenome {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn from_position(x: &[f64]) -> Self {
+        Self(random_keys(x))
+    }
+
+    /// Keys evenly spaced in `(0, 1)`, increasing along the permutation.
+    ///
+    /// # Panics
+    ///
+    /// If the genome is not a permutation of `0..len`.
+    #[allow(clippy::cast_precision_loss)]
+    fn to_position(&self) -> Vec<f64> {
+        let n = self.0.len() as f64;
+        let mut keys = vec![f64::NAN; self.0.len()];
+        for (rank, &item) in self.0.iter().enumerate() {
+            keys[item] = (rank as f64 + 0.5) / n;
+        }
+        assert!(keys.iter().all(|k| !k.is_nan()), "not a permutation");
+        keys
+    }
+}
+
+/// Integer genes, read by rounding each coordinate to the nearest integer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntegerGenome(pub Vec<i64>);
+
+impl Genome for IntegerGenome {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_position(x: &[f64]) -> Self {
+        Self(x.iter().map(|v| v.round() as i64).collect())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn to_position(&self) -> Vec<f64> {
+        self.0.iter().map(|&v| v as f64).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trips<G: Genome + PartialEq + std::fmt::Debug>(genome: &G) {
+        assert_eq!(&G::from_position(&genome.to_position()), genome);
+    }
+
+    #[test]
+    fn positions_read_back_as_the_same_genome() {
+        let x = [0.7, -1.4, 0.2, 2.5];
+        assert_eq!(RealGenome::from_position(&x).0, x.to_vec());
+        assert_eq!(
+            BinaryGenome::from_position(&x).0,
+            vec![true, false, false, true]
+        );
+        assert_eq!(
+            BinaryGenome::with_transfer(&x, Transfer::Sigmoid).ones(),
+            vec![0, 2, 3]
+        );
+        assert_eq!(PermutationGenome::from_position(&x).0, vec![1, 2, 0, 3]);
+        assert_eq!(IntegerGenome::from_position(&x).0, vec![1, -1, 0, 3]);
+
+        round_trips(&RealGenome(x.to_vec()));
+        round_trips(&BinaryGenome(vec![true, false, true]));
+        round_trips(&PermutationGenome(vec![2, 0, 3, 1]));
+        round_trips(&IntegerGenome(vec![-3, 0, 7]));
+        assert!(PermutationGenome(Vec::new()).is_empty());
+
+        let bits = BinaryGenome::from_integers(&[5, 2], 3, BitCode::Gray);
+        assert_eq!(bits.0, vec![true, true, true, false, true, true]);
+        assert_eq!(bits.integers(3, BitCode::Gray), vec![5, 2]);
+    }
+}
@@ -0,0 +1,114 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GraphError {
+    #[error("Vertex out of range: vertex {vertex} in a graph of order {order}")]
+    VertexOutOfRange { vertex: usize, order: usize },
+
+    #[error("Isolated vertex {0}: the graph has no total dominating set")]
+    IsolatedVertex(usize),
+}
+
+/// Simple undirected graph on the vertices `0..order`, stored as adjacency lists.
+///
+/// Loops and repeated edges are ignored, so every neighbor list is a set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graph {
+    adj: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Edgeless graph with `order` vertices.
+    #[must_use]
+    pub fn new(order: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); order],
+        }
+    }
+
+    /// Graph with `order` vertices and the given edges.
+    ///
+    /// # Errors
+    ///
+    /// [`GraphError::VertexOutOfRange`] if an edge has an endpoint `>= order`.
+    pub fn from_edges<I>(order: usize, edges: I) -> Result<Self, GraphError>
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut graph = Self::new(order);
+        for (u, v) in edges {
+            graph.add_edge(u, v)?;
+        }
+        Ok(graph)
+    }
+
+    /// Adds the edge `{u, v}`.
+    ///
+    /// # Errors
+    ///
+    /// [`GraphError::VertexOutOfRange`] if `u` or `v` is not a vertex.
+    pub fn add_edge(&mut self, u: usize, v: usize) -> Result<(), GraphError> {
+        let order = self.order();
+        if let Some(&vertex) = [u, v].iter().find(|&&w| w >= order) {
+            return Err(GraphError::VertexOutOfRange { vertex, order });
+        }
+        if u != v && !self.adj[u].contains(&v) {
+            self.adj[u].push(v);
+            self.adj[v].push(u);
+        }
+        Ok(())
+    }
+
+    /// Number of vertices.
+    #[must_use]
+    pub const fn order(&self) -> usize {
+        self.adj.len()
+    }
+
+    /// Number of edges.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.adj.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    /// Open neighborhood of `v`.
+    ///
+    /// # Panics
+    ///
+    /// If `v` is not a vertex.
+    #[must_use]
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    #[must_use]
+    pub fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Closed neighborhood de `v`: o próprio `v` seguido dos vizinhos
+    pub fn closed(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(v).chain(self.adj[v].iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_loops_and_repeated_edges() {
+        let g = Graph::from_edges(3, [(0, 1), (1, 0), (1, 1), (1, 2)]).unwrap();
+        assert_eq!((g.order(), g.size()), (3, 2));
+        assert_eq!(g.neighbors(1), &[0, 2]);
+        assert_eq!(g.closed(0).collect::<Vec<_>>(), vec![0, 1]);
+        assert!(matches!(
+            Graph::from_edges(2, [(0, 2)]),
+            Err(GraphError::VertexOutOfRange {
+                vertex: 2,
+                order: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+use thiserror::Error;
+
+use crate::{
+    core::{Decoder, DecoderError},
+    decoders::{binary::Transfer, genome::BinaryGenome},
+};
+
+#[derive(Debug, Error)]
+pub enum KnapsackError {
+    #[error("Length mismatch: {weights} weights but {values} values")]
+    LengthMismatch { weights: usize, values: usize },
+
+    #[error("Invalid weight at i={i}: {weight} (weights must be positive)")]
+    InvalidWeight { i: usize, weight: f64 },
+}
+
+/// 0/1 knapsack: item `i` is packed when its coordinate is set by the [`Transfer`] function.
+///
+/// An overweight selection is repaired by dropping the packed items with the lowest
+/// value/weight ratio until it fits, then greedily packing the unpacked items with the
+/// highest ratio that still fit. The fitness is the total value, so run it under
+/// [`crate::core::Maximization`].
+#[derive(Debug, Clone)]
+pub struct Knapsack {
+    weights: Vec<f64>,
+    values: Vec<f64>,
+    capacity: f64,
+    transfer: Transfer,
+    /// Itens do pior para o melhor valor/peso
+    by_ratio: Vec<usize>,
+}
+
+impl Knapsack {
+    /// # Errors
+    ///
+    /// [`KnapsackError::LengthMismatch`] if `weights` and `values` differ in length,
+    /// [`KnapsackError::InvalidWeight`] if a weight is not positive.
+    pub fn new(weights: Vec<f64>, values: Vec<f64>, capacity: f64) -> Result<Self, KnapsackError> {
+        if weights.len() != values.len() {
+            return Err(KnapsackError::LengthMismatch {
+                weights: weights.len(),
+                values: values.len(),
+            });
+        }
+        if let Some((i, &weight)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, w)| w.is_nan() || **w <= 0.0)
+        {
+            return Err(KnapsackError::InvalidWeight { i, weight });
+        }
+        let mut by_ratio: Vec<usize> = (0..weights.len()).collect();
+        by_ratio.sort_by(|&a, &b| (values[a] / weights[a]).total_cmp(&(values[b] / weights[b])));
+        Ok(Self {
+            weights,
+            values,
+            capacity,
+            transfer: Transfer::Threshold,
+            by_ratio,
+        })
+    }
+
+    #[must_use]
+    pub const fn transfer(mut self, transfer: Transfer) -> Self {
+        self.transfer = transfer;
+        self
+    }
+
+    /// Number of items.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Whether each item is packed in the repaired selection encoded by `x`.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one value per item.
+    pub fn selection(&self, x: &[f64]) -> Result<Vec<bool>, DecoderError> {
+        if x.len() != self.len() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.len(),
+                received: x.len(),
+            });
+        }
+        let BinaryGenome(mut packed) = BinaryGenome::with_transfer(x, self.transfer);
+        let mut load: f64 = (0..x.len())
+            .filter(|&i| packed[i])
+            .map(|i| self.weights[i])
+            .sum();
+
+        // Remove os piores itens até caber
+        for &i in &self.by_ratio {
+            if load <= self.capacity {
+                break;
+            }
+            if packed[i] {
+                packed[i] = false;
+                load -= self.weights[i];
+            }
+        }
+
+        // Completa com os melhores itens que ainda cabem
+        for &i in self.by_ratio.iter().rev() {
+            if !packed[i] && load + self.weights[i] <= self.capacity {
+                packed[i] = true;
+                load += self.weights[i];
+            }
+        }
+        Ok(packed)
+    }
+}
+
+impl Decoder for Knapsack {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let packed = self.selection(solution)?;
+        Ok((0..packed.len())
+            .filter(|&i| packed[i])
+            .map(|i| self.values[i])
+            .sum())
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repairs_overweight_selections() {
+        // Razões valor/peso: 3, 1, 2, 0.5
+        let ks = Knapsack::new(vec![2.0, 3.0, 4.0, 2.0], vec![6.0, 3.0, 8.0, 1.0], 6.0).unwrap();
+        // Tudo escolhido (peso 11): saem 3 e 1, sobra {0, 2} com valor 14
+        assert_eq!(
+            ks.selection(&[1.0; 4]).unwrap(),
+            vec![true, false, true, false]
+        );
+        assert!((ks.decode(&[1.0; 4]).unwrap() - 14.0).abs() < 1e-12);
+        // Só o item 3 (peso 2): completa com o item 0, o único que ainda cabe
+        assert_eq!(
+            ks.selection(&[0.0, 0.0, 0.0, 1.0]).unwrap(),
+            vec![true, false, false, true]
+        );
+        assert!(ks.decode(&[0.0; 3]).is_err());
+        assert!(matches!(
+            Knapsack::new(vec![1.0, 0.0], vec![1.0, 1.0], 1.0),
+            Err(KnapsackError::InvalidWeight { i: 1, .. })
+        ));
+    }
+}
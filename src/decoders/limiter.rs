@@ -0,0 +1,39 @@
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// Contador de chamadas em andamento, para limitar quantas rodam ao mesmo tempo
+#[derive(Debug, Default)]
+pub struct Limiter {
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl Limiter {
+    /// Reserva uma vaga, bloqueando enquanto `max` chamadas estiverem em andamento
+    /// (`0` = sem limite)
+    pub fn acquire(&self, max: usize) -> Permit<'_> {
+        let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        while max > 0 && *running >= max {
+            running = self
+                .freed
+                .wait(running)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *running += 1;
+        drop(running);
+        Permit(self)
+    }
+}
+
+/// Vaga reservada, liberada ao sair de escopo
+pub struct Permit<'a>(&'a Limiter);
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        *self
+            .0
+            .running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) -= 1;
+        self.0.freed.notify_one();
+    }
+}
@@ -0,0 +1,218 @@
+use crate::core::{Bounds, Decoder, DecoderError, bounds::BoundsError};
+
+/// Activation applied element-wise after a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Activation {
+    #[default]
+    Tanh,
+    Sigmoid,
+    Relu,
+    Identity,
+}
+
+impl Activation {
+    #[must_use]
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            Self::Tanh => x.tanh(),
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Relu => x.max(0.0),
+            Self::Identity => x,
+        }
+    }
+}
+
+/// Fully connected feed-forward topology, e.g. `Mlp::new(vec![2, 4, 1])` for 2 inputs, one
+/// hidden layer of 4 neurons and 1 output.
+///
+/// The flat weight vector lists the layers in order; within a layer, each neuron contributes
+/// its bias followed by one weight per input, so a layer from `a` to `b` neurons takes
+/// `(a + 1) · b` values (see [`Mlp::dim`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mlp {
+    layers: Vec<usize>,
+    hidden: Activation,
+    output: Activation,
+}
+
+impl Mlp {
+    /// Layer sizes from input to output, with `tanh` hidden layers and a linear output.
+    ///
+    /// # Panics
+    ///
+    /// If there are fewer than two layers or a layer is empty.
+    #[must_use]
+    pub fn new(layers: Vec<usize>) -> Self {
+        assert!(layers.len() >= 2, "an MLP needs input and output layers");
+        assert!(layers.iter().all(|&n| n > 0), "layers must not be empty");
+        Self {
+            layers,
+            hidden: Activation::Tanh,
+            output: Activation::Identity,
+        }
+    }
+
+    #[must_use]
+    pub const fn hidden(mut self, activation: Activation) -> Self {
+        self.hidden = activation;
+        self
+    }
+
+    #[must_use]
+    pub const fn output(mut self, activation: Activation) -> Self {
+        self.output = activation;
+        self
+    }
+
+    #[must_use]
+    pub fn layers(&self) -> &[usize] {
+        &self.layers
+    }
+
+    /// Number of weights and biases, i.e. the search dimension.
+    #[must_use]
+    pub fn dim(&self) -> usize {
+        self.layers.windows(2).map(|w| (w[0] + 1) * w[1]).sum()
+    }
+
+    /// Glorot-style box: each weight of a layer from `a` to `b` neurons lies in
+    /// `±scale · √(6 / (a + b))`, with `scale = 1.0` for the usual initialization range and
+    /// larger values to let the search reach bigger weights.
+    ///
+    /// # Errors
+    ///
+    /// [`BoundsError::InvalidInterval`] if `scale` is negative.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bounds(&self, scale: f64) -> Result<Bounds, BoundsError> {
+        let hi: Vec<f64> = self
+            .layers
+            .windows(2)
+            .flat_map(|w| {
+                let limit = scale * (6.0 / (w[0] + w[1]) as f64).sqrt();
+                std::iter::repeat_n(limit, (w[0] + 1) * w[1])
+            })
+            .collect();
+        Bounds::per_dim(hi.iter().map(|h| -h).collect(), hi)
+    }
+
+    /// Runs `input` through the network with the given `weights`.
+    ///
+    /// # Panics
+    ///
+    /// If `weights` has fewer than [`Mlp::dim`] values or `input` does not match the input
+    /// layer.
+    #[must_use]
+    pub fn forward(&self, weights: &[f64], input: &[f64]) -> Vec<f64> {
+        assert_eq!(input.len(), self.layers[0], "input size");
+        let last = self.layers.len() - 2;
+        let mut offset = 0;
+        let mut activations = input.to_vec();
+        for (l, w) in self.layers.windows(2).enumerate() {
+            let act = if l == last { self.output } else { self.hidden };
+            activations = weights[offset..offset + (w[0] + 1) * w[1]]
+                .chunks_exact(w[0] + 1)
+                .map(|neuron| {
+                    let sum = neuron[1..]
+                        .iter()
+                        .zip(&activations)
+                        .fold(neuron[0], |acc, (wi, a)| wi.mul_add(*a, acc));
+                    act.apply(sum)
+                })
+                .collect();
+            offset += (w[0] + 1) * w[1];
+        }
+        activations
+    }
+}
+
+/// A [`Mlp`] with concrete weights, handed to the loss of an [`MlpDecoder`].
+#[derive(Debug, Clone, Copy)]
+pub struct Network<'a> {
+    pub mlp: &'a Mlp,
+    pub weights: &'a [f64],
+}
+
+impl Network<'_> {
+    /// See [`Mlp::forward`].
+    #[must_use]
+    pub fn forward(&self, input: &[f64]) -> Vec<f64> {
+        self.mlp.forward(self.weights, input)
+    }
+}
+
+/// Trains the weights of an [`Mlp`]: the position is the flat weight vector and the fitness is
+/// whatever the `loss` closure computes with the [`Network`], e.g. the mean squared error
+/// over a training set.
+#[derive(Debug, Clone)]
+pub struct MlpDecoder<F> {
+    mlp: Mlp,
+    loss: F,
+}
+
+impl<F: Fn(&Network<'_>) -> f64> MlpDecoder<F> {
+    #[must_use]
+    pub const fn new(mlp: Mlp, loss: F) -> Self {
+        Self { mlp, loss }
+    }
+
+    #[must_use]
+    pub const fn mlp(&self) -> &Mlp {
+        &self.mlp
+    }
+}
+
+impl<F: Fn(&Network<'_>) -> f64> Decoder for MlpDecoder<F> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        if solution.len() != self.mlp.dim() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.mlp.dim(),
+                received: solution.len(),
+            });
+        }
+        Ok((self.loss)(&Network {
+            mlp: &self.mlp,
+            weights: solution,
+        }))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.mlp.dim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward_follows_the_weight_layout() {
+        let mlp = Mlp::new(vec![2, 2, 1]).hidden(Activation::Relu);
+        assert_eq!(mlp.dim(), 3 * 2 + 3);
+        assert_eq!(mlp.bounds(1.0).unwrap().dim(), mlp.dim());
+        assert!((mlp.bounds(1.0).unwrap().hi_at(0) - 1.5f64.sqrt()).abs() < 1e-12);
+
+        // XOR: h1 = relu(x + y), h2 = relu(x + y − 1), saída h1 − 2 h2
+        let weights = [0.0, 1.0, 1.0, -1.0, 1.0, 1.0, 0.0, 1.0, -2.0];
+        for (x, y, xor) in [
+            (0.0, 0.0, 0.0),
+            (0.0, 1.0, 1.0),
+            (1.0, 0.0, 1.0),
+            (1.0, 1.0, 0.0),
+        ] {
+            let out = mlp.forward(&weights, &[x, y]);
+            assert!((out[0] - xor).abs() < 1e-12);
+        }
+
+        let data = [([0.0, 1.0], 1.0), ([1.0, 1.0], 0.0)];
+        let decoder = MlpDecoder::new(mlp, |net: &Network<'_>| {
+            data.iter()
+                .map(|(x, y)| (net.forward(x)[0] - y).powi(2))
+                .sum()
+        });
+        assert!(decoder.decode(&weights).unwrap().abs() < 1e-12);
+        assert!(decoder.decode(&[0.0; 4]).is_err());
+    }
+}
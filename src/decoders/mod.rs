@@ -0,0 +1,34 @@
+//! Ready-made [`crate::core::Decoder`] implementations.
+
+pub mod binary;
+pub mod clustering;
+#[cfg(feature = "graphs")]
+pub mod domination;
+pub mod external;
+pub mod feature_selection;
+pub mod genome;
+#[cfg(feature = "graphs")]
+pub mod graph;
+pub mod knapsack;
+mod limiter;
+pub mod mlp;
+pub mod permutation;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod tsp;
+
+pub use binary::{BitCode, Transfer};
+pub use clustering::{Clustering, ClusteringError};
+#[cfg(feature = "graphs")]
+pub use domination::{DominatingSet, RomanDomination, TotalDominatingSet};
+pub use external::{ExternalDecoder, Input};
+pub use feature_selection::FeatureSelection;
+pub use genome::{BinaryGenome, Genome, IntegerGenome, PermutationGenome, RealGenome};
+#[cfg(feature = "graphs")]
+pub use graph::{Graph, GraphError};
+pub use knapsack::{Knapsack, KnapsackError};
+pub use mlp::{Activation, Mlp, MlpDecoder, Network};
+pub use permutation::random_keys;
+#[cfg(feature = "remote")]
+pub use remote::RemoteDecoder;
+pub use tsp::{Tsp, TspError};
@@ -0,0 +1,19 @@
+/// Random-keys decoding: the permutation that sorts `x` in increasing order, i.e. the item
+/// with the smallest key comes first. Ties keep index order and NaN keys sort last.
+#[must_use]
+pub fn random_keys(x: &[f64]) -> Vec<usize> {
+    let mut perm: Vec<usize> = (0..x.len()).collect();
+    perm.sort_by(|&a, &b| x[a].total_cmp(&x[b]));
+    perm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_by_key() {
+        assert_eq!(random_keys(&[0.7, 0.1, 0.4, 0.1]), vec![1, 3, 2, 0]);
+        assert!(random_keys(&[]).is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Condvar, Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{Decoder, DecoderError},
+    decoders::limiter::Limiter,
+};
+
+/// Evaluates candidates on a remote service speaking JSON over HTTP.
+///
+/// Each request is a `POST` of `{"candidates": [[x₀, x₁, …], …]}` to the endpoint, answered
+/// with `{"fitness": [f₀, f₁, …]}` in the same order (`null` marks a candidate the service
+/// could not evaluate). Only plain `http://` endpoints are supported; put a TLS-terminating
+/// proxy in front of the service if needed.
+///
+/// Concurrent [`Decoder::decode`] calls, e.g. from [`crate::hho::Hho::run_parallel`], are
+/// coalesced: calls arriving within [`RemoteDecoder::linger`] of each other travel in one
+/// request of at most [`RemoteDecoder::max_batch`] candidates, so the cost of a round trip is
+/// shared. Failed requests are retried with exponential backoff, and
+/// [`RemoteDecoder::max_in_flight`] caps how many requests are outstanding at once.
+#[derive(Debug)]
+pub struct RemoteDecoder {
+    host: String,
+    port: u16,
+    path: String,
+    max_batch: usize,
+    linger: Duration,
+    max_in_flight: usize,
+    retries: usize,
+    backoff: Duration,
+    timeout: Option<Duration>,
+    queue: Mutex<Queue>,
+    in_flight: Limiter,
+}
+
+/// Candidatos à espera de um lote
+#[derive(Debug, Default)]
+struct Queue {
+    jobs: VecDeque<Arc<Job>>,
+    /// Alguma chamada já está juntando o próximo lote
+    leader: bool,
+}
+
+/// Um candidato e o lugar onde sua fitness é entregue
+#[derive(Debug)]
+struct Job {
+    x: Vec<f64>,
+    result: Mutex<Option<Result<f64, String>>>,
+    done: Condvar,
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    candidates: Vec<&'a [f64]>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    fitness: Vec<Option<f64>>,
+}
+
+impl RemoteDecoder {
+    /// Client of the service at `url` (`http://host[:port][/path]`), sending batches of up to
+    /// 64 candidates after a 2 ms linger, with two retries from a 100 ms backoff and no
+    /// timeout or in-flight cap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] if `url` is not a plain HTTP URL.
+    pub fn new(url: &str) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("invalid URL {url}"));
+        let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+        let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            path: path.to_owned(),
+            max_batch: 64,
+            linger: Duration::from_millis(2),
+            max_in_flight: 0,
+            retries: 2,
+            backoff: Duration::from_millis(100),
+            timeout: None,
+            queue: Mutex::default(),
+            in_flight: Limiter::default(),
+        })
+    }
+
+    /// Largest number of candidates per request (at least 1).
+    #[must_use]
+    pub fn max_batch(mut self, n: usize) -> Self {
+        self.max_batch = n.max(1);
+        self
+    }
+
+    /// How long the first call of a batch waits for others to join it.
+    #[must_use]
+    pub const fn linger(mut self, linger: Duration) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// At most `n` requests are outstanding at the same time; `0` means no limit.
+    #[must_use]
+    pub const fn max_in_flight(mut self, n: usize) -> Self {
+        self.max_in_flight = n;
+        self
+    }
+
+    /// Retries a failed request up to `retries` times, waiting `backoff` before the first
+    /// retry and doubling it for each following one.
+    #[must_use]
+    pub const fn retries(mut self, retries: usize, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// Connect, read and write timeout of each request.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Evaluates `candidates` in one request (with retries), bypassing the coalescing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecoderError::External`] if every attempt fails.
+    pub fn evaluate_batch(&self, candidates: &[&[f64]]) -> Result<Vec<Option<f64>>, DecoderError> {
+        self.send(candidates).map_err(DecoderError::External)
+    }
+
+    /// Envia o lote com novas tentativas e devolve uma fitness por candidato
+    fn send(&self, candidates: &[&[f64]]) -> Result<Vec<Option<f64>>, String> {
+        let body = serde_json::to_vec(&Request {
+            candidates: candidates.to_vec(),
+        })
+        .map_err(|e| e.to_string())?;
+        let _permit = self.in_flight.acquire(self.max_in_flight);
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let outcome = self.post(&body).and_then(|response| {
+                let response: Response =
+                    serde_json::from_slice(&response).map_err(|e| e.to_string())?;
+                if response.fitness.len() == candidates.len() {
+                    Ok(response.fitness)
+                } else {
+                    Err(format!(
+                        "expected {} fitness values, got {}",
+                        candidates.len(),
+                        response.fitness.len()
+                    ))
+                }
+            });
+            match outcome {
+                Err(e) if attempt < self.retries => {
+                    log_retry(&e);
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
+    /// Um `POST` HTTP/1.1 com `Connection: close`; devolve o corpo de uma resposta 2xx
+    fn post(&self, body: &[u8]) -> Result<Vec<u8>, String> {
+        let addr = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| e.to_string())?
+            .next()
+            .ok_or_else(|| format!("cannot resolve {}", self.host))?;
+        let mut stream = self
+            .timeout
+            .map_or_else(
+                || TcpStream::connect(addr),
+                |t| TcpStream::connect_timeout(&addr, t),
+            )
+            .map_err(|e| e.to_string())?;
+        stream
+            .set_read_timeout(self.timeout)
+            .and_then(|()| stream.set_write_timeout(self.timeout))
+            .map_err(|e| e.to_string())?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            self.host,
+            self.port,
+            body.len()
+        )
+        .and_then(|()| stream.write_all(body))
+        .map_err(|e| e.to_string())?;
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .map_err(|e| e.to_string())?;
+        parse_response(&response)
+    }
+
+    /// Junta candidatos de chamadas simultâneas por até `linger` e envia o lote
+    fn lead(&self) {
+        thread::sleep(self.linger);
+        let batch: Vec<Arc<Job>> = {
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            let n = queue.jobs.len().min(self.max_batch);
+            let batch = queue.jobs.drain(..n).collect();
+            queue.leader = false;
+            batch
+        };
+        if batch.is_empty() {
+            return;
+        }
+        let xs: Vec<&[f64]> = batch.iter().map(|job| job.x.as_slice()).collect();
+        let results = self.send(&xs);
+        for (k, job) in batch.iter().enumerate() {
+            let result = match &results {
+                Ok(fitness) => fitness[k].ok_or_else(|| "service returned null".to_owned()),
+                Err(e) => Err(e.clone()),
+            };
+            *job.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(result);
+            job.done.notify_all();
+        }
+    }
+}
+
+impl Decoder for RemoteDecoder {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let job = Arc::new(Job {
+            x: solution.to_vec(),
+            result: Mutex::new(None),
+            done: Condvar::new(),
+        });
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .jobs
+            .push_back(Arc::clone(&job));
+        loop {
+            let lead = {
+                let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+                let lead = !queue.leader && !queue.jobs.is_empty();
+                queue.leader |= lead;
+                lead
+            };
+            if lead {
+                self.lead();
+            }
+            // Acorda periodicamente para liderar um lote se ninguém mais o fizer
+            let result = job
+                .done
+                .wait_timeout_while(
+                    job.result.lock().unwrap_or_else(PoisonError::into_inner),
+                    self.linger.max(Duration::from_millis(1)),
+                    |r| r.is_none(),
+                )
+                .unwrap_or_else(PoisonError::into_inner)
+                .0
+                .take();
+            if let Some(result) = result {
+                return result.map_err(DecoderError::External);
+            }
+        }
+    }
+}
+
+/// Separa status, cabeçalhos e corpo (com ou sem `chunked`) de uma resposta HTTP/1.1
+fn parse_response(response: &[u8]) -> Result<Vec<u8>, String> {
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("malformed HTTP response")?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let body = &response[split + 4..];
+    let mut lines = head.lines();
+    let status: u16 = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or("malformed HTTP status line")?;
+    if !(200..300).contains(&status) {
+        return Err(format!("HTTP status {status}"));
+    }
+    let chunked = lines.any(|l| {
+        l.split_once(':').is_some_and(|(k, v)| {
+            k.trim().eq_ignore_ascii_case("transfer-encoding")
+                && v.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    if chunked {
+        dechunk(body)
+    } else {
+        Ok(body.to_vec())
+    }
+}
+
+/// Decodifica um corpo `Transfer-Encoding: chunked`
+fn dechunk(mut body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    loop {
+        let eol = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or("malformed chunk")?;
+        let size = String::from_utf8_lossy(&body[..eol]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| "malformed chunk size")?;
+        if size == 0 {
+            return Ok(out);
+        }
+        let chunk = body.get(eol + 2..eol + 2 + size).ok_or("truncated chunk")?;
+        out.extend_from_slice(chunk);
+        body = body.get(eol + 4 + size..).ok_or("truncated chunk")?;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "log")] {
+        fn log_retry(error: &str) {
+            log::warn!("remote evaluation failed, retrying: {error}");
+        }
+    } else {
+        const fn log_retry(_: &str) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::BufRead, io::BufReader, net::TcpListener};
+
+    use super::*;
+
+    /// Servidor que soma os quadrados; falha nas primeiras `failures` requisições e registra
+    /// o tamanho de cada lote
+    fn serve(failures: usize) -> (String, Arc<Mutex<Vec<usize>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/eval", listener.local_addr().unwrap());
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&batches);
+        thread::spawn(move || {
+            for (k, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                if k < failures {
+                    write!(stream, "HTTP/1.1 503 Busy\r\nContent-Length: 0\r\n\r\n").unwrap();
+                    continue;
+                }
+                let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                let fitness: Vec<f64> = value["candidates"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|x| {
+                        x.as_array()
+                            .unwrap()
+                            .iter()
+                            .map(|v| v.as_f64().unwrap().powi(2))
+                            .sum()
+                    })
+                    .collect();
+                log.lock().unwrap().push(fitness.len());
+                let reply = serde_json::json!({ "fitness": fitness }).to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Transfer-Encoding: chunked\r\n\r\n{:x}\r\n{reply}\r\n0\r\n\r\n",
+                    reply.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, batches)
+    }
+
+    #[test]
+    fn coalesces_concurrent_calls_and_retries() {
+        let (url, batches) = serve(1);
+        let decoder = RemoteDecoder::new(&url)
+            .unwrap()
+            .linger(Duration::from_millis(50))
+            .retries(1, Duration::from_millis(1))
+            .max_in_flight(1);
+        let results: Vec<f64> = thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let decoder = &decoder;
+                    s.spawn(move || decoder.decode(&[f64::from(i), 1.0]).unwrap())
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(results, vec![1.0, 2.0, 5.0, 10.0]);
+        let batches = batches.lock().unwrap().clone();
+        assert_eq!(batches.iter().sum::<usize>(), 4);
+        assert!(batches.len() < 4, "batches = {batches:?}");
+    }
+
+    #[test]
+    fn reports_exhausted_retries_and_bad_urls() {
+        let (url, _) = serve(usize::MAX);
+        let decoder = RemoteDecoder::new(&url)
+            .unwrap()
+            .retries(1, Duration::from_millis(1));
+        assert!(matches!(
+            decoder.decode(&[1.0]),
+            Err(DecoderError::External(e)) if e.contains("503")
+        ));
+        assert!(RemoteDecoder::new("https://example.com").is_err());
+        assert!(RemoteDecoder::new("http://:80/x").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use thiserror::Error;
+
+use crate::{
+    core::{Decoder, DecoderError},
+    decoders::genome::{Genome, PermutationGenome},
+};
+
+#[derive(Debug, Error)]
+pub enum TspError {
+    #[error("Empty distance matrix")]
+    Empty,
+
+    #[error("Distance matrix is not square: row {row} has {len} entries, expected {order}")]
+    NotSquare {
+        row: usize,
+        len: usize,
+        order: usize,
+    },
+}
+
+/// Traveling salesman over a distance matrix, decoded with random keys: the cities are
+/// visited in increasing order of their coordinates and the fitness is the length of the
+/// closed tour.
+///
+/// With [`Tsp::two_opt`] the decoded tour is first improved by 2-opt moves until none
+/// shortens it. The move gains assume a symmetric matrix.
+#[derive(Debug, Clone)]
+pub struct Tsp {
+    dist: Vec<Vec<f64>>,
+    two_opt: bool,
+}
+
+impl Tsp {
+    /// `dist[i][j]` is the cost of going from city `i` to city `j`.
+    ///
+    /// # Errors
+    ///
+    /// [`TspError::Empty`] or [`TspError::NotSquare`] for a malformed matrix.
+    pub fn new(dist: Vec<Vec<f64>>) -> Result<Self, TspError> {
+        let order = dist.len();
+        if order == 0 {
+            return Err(TspError::Empty);
+        }
+        if let Some((row, r)) = dist.iter().enumerate().find(|(_, r)| r.len() != order) {
+            return Err(TspError::NotSquare {
+                row,
+                len: r.len(),
+                order,
+            });
+        }
+        Ok(Self {
+            dist,
+            two_opt: false,
+        })
+    }
+
+    /// Euclidean distances between the given points.
+    ///
+    /// # Errors
+    ///
+    /// [`TspError::Empty`] if there are no points.
+    pub fn euclidean(points: &[(f64, f64)]) -> Result<Self, TspError> {
+        Self::new(
+            points
+                .iter()
+                .map(|&(xa, ya)| {
+                    points
+                        .iter()
+                        .map(|&(xb, yb)| (xa - xb).hypot(ya - yb))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Improves every decoded tour with 2-opt before measuring it.
+    #[must_use]
+    pub const fn two_opt(mut self, enabled: bool) -> Self {
+        self.two_opt = enabled;
+        self
+    }
+
+    /// Number of cities.
+    #[must_use]
+    pub const fn order(&self) -> usize {
+        self.dist.len()
+    }
+
+    /// The tour encoded by `x` (after 2-opt, if enabled), starting at any city.
+    ///
+    /// # Errors
+    ///
+    /// [`DecoderError::InvalidDimension`] if `x` does not have one key per city.
+    pub fn tour(&self, x: &[f64]) -> Result<Vec<usize>, DecoderError> {
+        if x.len() != self.order() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.order(),
+                received: x.len(),
+            });
+        }
+        let PermutationGenome(mut tour) = PermutationGenome::from_position(x);
+        if self.two_opt {
+            self.improve(&mut tour);
+        }
+        Ok(tour)
+    }
+
+    /// Length of the closed `tour`.
+    ///
+    /// # Panics
+    ///
+    /// If `tour` holds a city `>= order`.
+    #[must_use]
+    pub fn length(&self, tour: &[usize]) -> f64 {
+        let next = tour.iter().skip(1).chain(tour.first());
+        tour.iter().zip(next).map(|(&a, &b)| self.dist[a][b]).sum()
+    }
+
+    /// 2-opt com primeira melhora: inverte `tour[i+1..=j]` enquanto isso encurta o ciclo
+    fn improve(&self, tour: &mut [usize]) {
+        let len = tour.len();
+        let dist = |from: usize, to: usize| self.dist[from][to];
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..len.saturating_sub(2) {
+                for j in i + 2..len {
+                    // Troca as arestas (u1, u2) e (v1, v2) por (u1, v1) e (u2, v2)
+                    let (u1, u2) = (tour[i], tour[i + 1]);
+                    let (v1, v2) = (tour[j], tour[(j + 1) % len]);
+                    if u1 == v2 {
+                        continue;
+                    }
+                    if dist(u1, v1) + dist(u2, v2) < dist(u1, u2) + dist(v1, v2) - 1e-12 {
+                        tour[i + 1..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Decoder for Tsp {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(self.length(&self.tour(solution)?))
+    }
+
+    fn expected_dim(&self) -> Option<usize> {
+        Some(self.order())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quadrado unitário: o ciclo ótimo tem comprimento 4
+    fn square() -> Tsp {
+        Tsp::euclidean(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]).unwrap()
+    }
+
+    #[test]
+    fn decodes_keys_into_tours() {
+        let tsp = square();
+        assert_eq!(tsp.tour(&[0.1, 0.2, 0.3, 0.4]).unwrap(), vec![0, 1, 2, 3]);
+        assert!((tsp.decode(&[0.1, 0.2, 0.3, 0.4]).unwrap() - 4.0).abs() < 1e-12);
+        // 0 → 2 → 1 → 3 cruza as diagonais
+        let crossing = [0.1, 0.3, 0.2, 0.4];
+        let expected = 2.0f64.mul_add(2.0f64.sqrt(), 2.0);
+        assert!((tsp.decode(&crossing).unwrap() - expected).abs() < 1e-12);
+        let tsp = tsp.two_opt(true);
+        assert!((tsp.decode(&crossing).unwrap() - 4.0).abs() < 1e-12);
+        assert!(tsp.decode(&[0.0; 3]).is_err());
+        assert!(matches!(
+            Tsp::new(vec![vec![0.0, 1.0], vec![1.0]]),
+            Err(TspError::NotSquare {
+                row: 1,
+                len: 1,
+                order: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,447 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    core::{Bounds, Budget, Decoder, Direction, Optimizer, Report, ReportSet, SeedStreams},
+    experiments::hypothesis::{self, Friedman, TestResult},
+    hho::HhoError,
+};
+
+/// A benchmark problem: a decoder and the box it is searched in.
+#[derive(Clone)]
+pub struct Problem {
+    pub name: String,
+    pub bounds: Bounds,
+    pub decoder: Arc<dyn Decoder + Send + Sync>,
+}
+
+impl Problem {
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        bounds: Bounds,
+        decoder: impl Decoder + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            bounds,
+            decoder: Arc::new(decoder),
+        }
+    }
+}
+
+impl fmt::Debug for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Problem")
+            .field("name", &self.name)
+            .field("bounds", &self.bounds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builds a fresh optimizer for the bounds of a problem and the seed of a replication.
+pub type OptimizerFactory = dyn Fn(&Bounds, u64) -> Box<dyn Optimizer + Send> + Send + Sync;
+
+/// An optimizer configuration under comparison.
+#[derive(Clone)]
+pub struct Algorithm {
+    pub name: String,
+    factory: Arc<OptimizerFactory>,
+}
+
+impl Algorithm {
+    /// Configuration named `name`, built by `factory` for each run.
+    ///
+    /// The factory receives the seed of the replication; seedable optimizers (e.g.
+    /// [`crate::hho::HhoBuilder::seed`]) should use it so the experiment is reproducible.
+    #[must_use]
+    pub fn new<F>(name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&Bounds, u64) -> Box<dyn Optimizer + Send> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            factory: Arc::new(factory),
+        }
+    }
+}
+
+impl fmt::Debug for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Algorithm")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs every [`Algorithm`] on every [`Problem`] `replications` times with the same budget.
+///
+/// Replication `k` is seeded with [`SeedStreams::replication`] of the experiment seed for
+/// every algorithm and problem, so the algorithms are compared on common random numbers. With
+/// the `rayon` feature all runs are spread over the global thread pool, each run staying on
+/// one thread.
+#[derive(Debug, Clone)]
+pub struct Experiment {
+    pub problems: Vec<Problem>,
+    pub algorithms: Vec<Algorithm>,
+    pub replications: usize,
+    pub budget: Budget,
+    pub seed: u64,
+}
+
+impl Experiment {
+    /// Empty experiment with 30 replications and seed 0.
+    #[must_use]
+    pub const fn new(budget: Budget) -> Self {
+        Self {
+            problems: Vec::new(),
+            algorithms: Vec::new(),
+            replications: 30,
+            budget,
+            seed: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn problem(mut self, problem: Problem) -> Self {
+        self.problems.push(problem);
+        self
+    }
+
+    #[must_use]
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithms.push(algorithm);
+        self
+    }
+
+    #[must_use]
+    pub const fn replications(mut self, replications: usize) -> Self {
+        self.replications = replications;
+        self
+    }
+
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the whole grid and groups the reports by (problem, algorithm), in declaration
+    /// order.
+    ///
+    /// A run stopped by its decoder counts with its partial report, whose stop reason is
+    /// [`crate::core::TerminationReason::Error`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a run that could not start, e.g. an invalid configuration.
+    pub fn run(&self) -> Result<Results, HhoError> {
+        let reports = run_all(&self.grid(), |&(p, a, k)| self.run_one(p, a, k));
+        Ok(self.group(reports.into_iter().collect::<Result<_, _>>()?))
+    }
+
+    /// Execuções da grade como (problema, algoritmo, réplica), na ordem das células
+    pub(super) fn grid(&self) -> Vec<(usize, usize, usize)> {
+        (0..self.problems.len())
+            .flat_map(|p| {
+                (0..self.algorithms.len())
+                    .flat_map(move |a| (0..self.replications).map(move |k| (p, a, k)))
+            })
+            .collect()
+    }
+
+    /// Agrupa por célula os relatórios dados na ordem de [`Experiment::grid`]
+    pub(super) fn group(&self, reports: Vec<Report>) -> Results {
+        let mut reports = reports.into_iter();
+        let mut cells = Vec::with_capacity(self.problems.len() * self.algorithms.len());
+        for problem in &self.problems {
+            for algorithm in &self.algorithms {
+                let runs = reports.by_ref().take(self.replications).collect();
+                cells.push(Cell {
+                    problem: problem.name.clone(),
+                    algorithm: algorithm.name.clone(),
+                    reports: ReportSet::new(runs),
+                });
+            }
+        }
+        Results { cells }
+    }
+
+    /// Semente da réplica `k`
+    pub(super) const fn seed_of(&self, k: usize) -> u64 {
+        SeedStreams::new(self.seed).replication(k as u64)
+    }
+
+    /// Hash da configuração do algoritmo `a` montado para a réplica `k` do problema `p`
+    #[cfg(feature = "serde")]
+    pub(super) fn config_hash_of(&self, p: usize, a: usize, k: usize) -> Option<u64> {
+        (self.algorithms[a].factory)(&self.problems[p].bounds, self.seed_of(k)).config_hash()
+    }
+
+    /// Réplica `k` do algoritmo `a` no problema `p`
+    pub(super) fn run_one(&self, p: usize, a: usize, k: usize) -> Result<Report, HhoError> {
+        let problem = &self.problems[p];
+        let seed = self.seed_of(k);
+        let mut optimizer = (self.algorithms[a].factory)(&problem.bounds, seed);
+        let mut report = partial(optimizer.run(problem.decoder.as_ref(), self.budget))?;
+        report.seed.get_or_insert(seed);
+        report
+            .metadata
+            .problem
+            .get_or_insert_with(|| problem.name.clone());
+        Ok(report)
+    }
+}
+
+/// Relatório de uma execução; a que o decoder interrompeu conta com o relatório parcial
+pub(crate) fn partial(outcome: Result<Report, HhoError>) -> Result<Report, HhoError> {
+    match outcome {
+        Err(e) if e.report().is_some() => Ok(e.into_report()),
+        outcome => outcome,
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        /// Executa as tarefas em paralelo, devolvendo os resultados na ordem das tarefas
+        pub(crate) fn run_all<T, R, F>(tasks: &[T], f: F) -> Vec<R>
+        where
+            T: Sync,
+            R: Send,
+            F: Fn(&T) -> R + Send + Sync,
+        {
+            use rayon::prelude::*;
+
+            tasks.par_iter().map(f).collect()
+        }
+    } else {
+        /// Executa as tarefas em ordem
+        pub(crate) fn run_all<T, R, F>(tasks: &[T], f: F) -> Vec<R>
+        where
+            F: Fn(&T) -> R,
+        {
+            tasks.iter().map(f).collect()
+        }
+    }
+}
+
+/// Reports of one (problem, algorithm) pair.
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub problem: String,
+    pub algorithm: String,
+    pub reports: ReportSet,
+}
+
+/// Outcome of an [`Experiment`], one [`Cell`] per (problem, algorithm) pair.
+#[derive(Debug, Clone, Default)]
+pub struct Results {
+    pub cells: Vec<Cell>,
+}
+
+impl Results {
+    /// Reports of `algorithm` on `problem`.
+    #[must_use]
+    pub fn get(&self, problem: &str, algorithm: &str) -> Option<&ReportSet> {
+        self.cells
+            .iter()
+            .find(|c| c.problem == problem && c.algorithm == algorithm)
+            .map(|c| &c.reports)
+    }
+
+    /// Algorithms run on `problem`, in declaration order.
+    fn cells_of<'a>(&'a self, problem: &'a str) -> impl Iterator<Item = &'a Cell> {
+        self.cells.iter().filter(move |c| c.problem == problem)
+    }
+
+    /// Wilcoxon signed-rank test of the best fitness of algorithms `a` and `b` on `problem`,
+    /// pairing their runs by replication. `None` if either cell is missing or empty.
+    #[must_use]
+    pub fn wilcoxon(&self, problem: &str, a: &str, b: &str) -> Option<TestResult> {
+        let (a, b) = (self.get(problem, a)?, self.get(problem, b)?);
+        hypothesis::wilcoxon(&best_fitness(a), &best_fitness(b))
+    }
+
+    /// Wilcoxon tests of every pair of algorithms on `problem`, with Holm-adjusted p-values
+    /// over the whole family of pairs.
+    #[must_use]
+    pub fn pairwise_wilcoxon(&self, problem: &str) -> Vec<Comparison> {
+        let cells: Vec<&Cell> = self.cells_of(problem).collect();
+        let mut comparisons = Vec::new();
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                let test =
+                    hypothesis::wilcoxon(&best_fitness(&a.reports), &best_fitness(&b.reports));
+                if let Some(test) = test {
+                    comparisons.push(Comparison {
+                        a: a.algorithm.clone(),
+                        b: b.algorithm.clone(),
+                        test,
+                        adjusted_p: test.p_value,
+                    });
+                }
+            }
+        }
+        let p: Vec<f64> = comparisons.iter().map(|c| c.test.p_value).collect();
+        for (c, adjusted) in comparisons.iter_mut().zip(hypothesis::holm(&p)) {
+            c.adjusted_p = adjusted;
+        }
+        comparisons
+    }
+
+    /// Friedman test of all algorithms on `problem`, with the replications as blocks. The mean
+    /// ranks follow the algorithms' declaration order.
+    #[must_use]
+    pub fn friedman(&self, problem: &str) -> Option<Friedman> {
+        let cells: Vec<&Cell> = self.cells_of(problem).collect();
+        let direction = cells
+            .iter()
+            .find_map(|c| c.reports.reports.first())
+            .map_or_else(Direction::default, |r| r.direction);
+        let samples: Vec<Vec<f64>> = cells.iter().map(|c| best_fitness(&c.reports)).collect();
+        let samples: Vec<&[f64]> = samples.iter().map(Vec::as_slice).collect();
+        hypothesis::friedman(&samples, direction)
+    }
+
+    /// Writes the results as a tidy CSV to `path` (see [`Results::write_csv`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut out)?;
+        out.flush()
+    }
+
+    /// Writes one row per run, with the columns `problem`, `algorithm`, `run`, `seed`,
+    /// `best_fitness`, `evals`, `iters`, `seconds` and `stop_reason`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "problem,algorithm,run,seed,best_fitness,evals,iters,seconds,stop_reason"
+        )?;
+        for cell in &self.cells {
+            let (problem, algorithm) = (csv_field(&cell.problem), csv_field(&cell.algorithm));
+            for (k, r) in cell.reports.reports.iter().enumerate() {
+                let seed = r.seed.map(|s| s.to_string()).unwrap_or_default();
+                let seconds = r
+                    .duration
+                    .map(|d| d.as_secs_f64().to_string())
+                    .unwrap_or_default();
+                let reason = r.stop_reason.map_or("", |r| r.as_str());
+                writeln!(
+                    out,
+                    "{problem},{algorithm},{k},{seed},{},{},{},{seconds},{}",
+                    r.best_fitness,
+                    r.evals,
+                    r.iters,
+                    csv_field(reason)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wilcoxon test of two algorithms on one problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub a: String,
+    pub b: String,
+    pub test: TestResult,
+    /// Holm-adjusted p-value within the family of comparisons it was computed with.
+    pub adjusted_p: f64,
+}
+
+/// Melhor fitness de cada execução, na ordem das réplicas
+fn best_fitness(set: &ReportSet) -> Vec<f64> {
+    set.reports.iter().map(|r| r.best_fitness).collect()
+}
+
+/// Campo CSV, entre aspas quando contém vírgula, aspas ou quebra de linha
+pub(crate) fn csv_field(s: &str) -> Cow<'_, str> {
+    if s.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, hho::HhoBuilder};
+
+    fn hho(name: &str, pop_size: usize) -> Algorithm {
+        Algorithm::new(name, move |bounds, seed| {
+            Box::new(
+                HhoBuilder::new(bounds.clone())
+                    .pop_size(pop_size)
+                    .seed(seed)
+                    .build(),
+            )
+        })
+    }
+
+    #[test]
+    fn runs_the_grid_and_writes_tidy_csv() {
+        let experiment = Experiment::new(Budget::Iterations(20))
+            .problem(Problem::new(
+                "sphere, 2d",
+                Bounds::uniform(-5.0, 5.0, 2).unwrap(),
+                Sphere,
+            ))
+            .problem(Problem::new(
+                "sphere-4d",
+                Bounds::uniform(-5.0, 5.0, 4).unwrap(),
+                Sphere,
+            ))
+            .algorithm(hho("hho-10", 10))
+            .algorithm(hho("hho-20", 20))
+            .replications(3)
+            .seed(5);
+        let results = experiment.run().unwrap();
+
+        assert_eq!(results.cells.len(), 4);
+        let cell = results.get("sphere-4d", "hho-20").unwrap();
+        assert_eq!(cell.len(), 3);
+        assert!(cell.reports.iter().all(|r| r.iters == 20));
+        // Mesma semente por réplica em todas as células
+        let seeds =
+            |c: &Cell| -> Vec<Option<u64>> { c.reports.reports.iter().map(|r| r.seed).collect() };
+        assert_eq!(seeds(&results.cells[0]), seeds(&results.cells[3]));
+        // Reprodutível
+        let again = experiment.run().unwrap();
+        assert_eq!(
+            again.get("sphere-4d", "hho-20").unwrap().reports[2].best_position,
+            cell.reports[2].best_position
+        );
+
+        let mut csv = Vec::new();
+        results.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 4 * 3);
+        assert!(lines[1].starts_with("\"sphere, 2d\",hho-10,0,"));
+        assert!(lines[12].starts_with("sphere-4d,hho-20,2,"));
+        assert!(lines[12].ends_with(",max_iters"));
+
+        let pairs = results.pairwise_wilcoxon("sphere-4d");
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0].adjusted_p >= pairs[0].test.p_value);
+        let friedman = results.friedman("sphere-4d").unwrap();
+        assert_eq!(friedman.mean_ranks.len(), 2);
+        assert!(results.wilcoxon("sphere-4d", "hho-10", "nope").is_none());
+    }
+}
@@ -0,0 +1,305 @@
+//! Nonparametric tests over the best fitness of replicated runs.
+//!
+//! [`wilcoxon`] compares two algorithms run for run, [`friedman`] compares any number of them
+//! and [`holm`] corrects the p-values of several comparisons. Runs are paired by replication
+//! index, which in an [`crate::experiments::Experiment`] means they share the seed.
+
+use std::f64::consts::SQRT_2;
+
+use crate::{core::Direction, hho::levy::ln_gamma};
+
+/// Maior amostra (sem empates) tratada com a distribuição exata de Wilcoxon
+const EXACT_MAX: usize = 30;
+
+/// Statistic and two-sided p-value of a test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// Outcome of a Friedman test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Friedman {
+    /// Tie-corrected chi-squared statistic with `k - 1` degrees of freedom.
+    pub statistic: f64,
+    pub p_value: f64,
+    /// Mean rank of each sample over the blocks; rank 1 is the best fitness.
+    pub mean_ranks: Vec<f64>,
+}
+
+/// Wilcoxon signed-rank test of the paired samples `a` and `b`.
+///
+/// The statistic is the smaller of the positive and negative rank sums; zero differences are
+/// dropped. The p-value is exact for up to 30 pairs without tied differences and otherwise
+/// uses the normal approximation with tie and continuity corrections. Returns `None` if the
+/// samples are empty or of different lengths.
+#[must_use]
+pub fn wilcoxon(a: &[f64], b: &[f64]) -> Option<TestResult> {
+    if a.is_empty() || a.len() != b.len() {
+        return None;
+    }
+    let diffs: Vec<f64> = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| x - y)
+        .filter(|d| *d != 0.0)
+        .collect();
+    let n = diffs.len();
+    if n == 0 {
+        return Some(TestResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        });
+    }
+    let magnitudes: Vec<f64> = diffs.iter().map(|d| d.abs()).collect();
+    let (ranks, ties) = average_ranks(&magnitudes);
+    let plus: f64 = diffs
+        .iter()
+        .zip(&ranks)
+        .filter(|(d, _)| **d > 0.0)
+        .map(|(_, r)| r)
+        .sum();
+    #[allow(clippy::cast_precision_loss)]
+    let nf = n as f64;
+    let total = nf * (nf + 1.0) / 2.0;
+    let statistic = plus.min(total - plus);
+
+    let p_value = if n <= EXACT_MAX && ties.is_empty() {
+        exact_wilcoxon(n, statistic)
+    } else {
+        let correction: f64 = ties.iter().map(|&t| t.powi(3) - t).sum::<f64>() / 48.0;
+        let var = nf * (nf + 1.0) * 2.0f64.mul_add(nf, 1.0) / 24.0 - correction;
+        if var <= 0.0 {
+            1.0
+        } else {
+            let z = ((total / 2.0 - statistic) - 0.5).max(0.0) / var.sqrt();
+            erfc(z / SQRT_2)
+        }
+    };
+    Some(TestResult {
+        statistic,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// `2 · P(W ≤ w)` sob a hipótese nula, contando os subconjuntos de `1..=n` por soma
+fn exact_wilcoxon(n: usize, w: f64) -> f64 {
+    let max = n * (n + 1) / 2;
+    let mut counts = vec![0.0f64; max + 1];
+    counts[0] = 1.0;
+    for k in 1..=n {
+        for s in (k..=max).rev() {
+            counts[s] += counts[s - k];
+        }
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let w = w.floor() as usize;
+    let tail: f64 = counts[..=w.min(max)].iter().sum();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let p = 2.0 * tail / 2f64.powi(n as i32);
+    p
+}
+
+/// Friedman test of `samples`, one per algorithm, whose `i`-th values form block `i`.
+///
+/// Values are ranked within each block (average ranks for ties) so that rank 1 is the best
+/// fitness in `direction`. Returns `None` with fewer than two samples, without blocks or if
+/// the samples have different lengths.
+#[must_use]
+pub fn friedman(samples: &[&[f64]], direction: Direction) -> Option<Friedman> {
+    let k = samples.len();
+    let n = samples.first().map_or(0, |s| s.len());
+    if k < 2 || n == 0 || samples.iter().any(|s| s.len() != n) {
+        return None;
+    }
+    let mut rank_sums = vec![0.0; k];
+    let mut tie_sum = 0.0;
+    let mut block = vec![0.0; k];
+    for i in 0..n {
+        for (b, s) in block.iter_mut().zip(samples) {
+            *b = match direction {
+                Direction::Minimize => s[i],
+                Direction::Maximize => -s[i],
+            };
+        }
+        let (ranks, ties) = average_ranks(&block);
+        for (sum, r) in rank_sums.iter_mut().zip(&ranks) {
+            *sum += r;
+        }
+        tie_sum += ties.iter().map(|&t| t.powi(3) - t).sum::<f64>();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let (nf, kf) = (n as f64, k as f64);
+    let squares: f64 = rank_sums.iter().map(|r| r * r).sum();
+    let q = (12.0 / (nf * kf * (kf + 1.0))).mul_add(squares, -3.0 * nf * (kf + 1.0));
+    let denominator = 1.0 - tie_sum / nf.mul_add(kf.powi(3), -nf * kf);
+    // Todos os blocos empatados: nenhuma evidência de diferença
+    let (statistic, p_value) = if denominator <= 0.0 {
+        (0.0, 1.0)
+    } else {
+        let statistic = (q / denominator).max(0.0);
+        (statistic, gamma_q((kf - 1.0) / 2.0, statistic / 2.0))
+    };
+    Some(Friedman {
+        statistic,
+        p_value,
+        mean_ranks: rank_sums.iter().map(|r| r / nf).collect(),
+    })
+}
+
+/// Holm–Bonferroni adjusted p-values, in the order of `p_values`.
+///
+/// Compare each adjusted value with the family-wise significance level directly.
+#[must_use]
+pub fn holm(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+    let mut adjusted = vec![0.0; m];
+    let mut running = 0.0f64;
+    for (rank, &i) in order.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let factor = (m - rank) as f64;
+        running = running.max((p_values[i] * factor).min(1.0));
+        adjusted[i] = running;
+    }
+    adjusted
+}
+
+/// Postos médios de `values` (1 = menor) e o tamanho de cada grupo de empatados
+fn average_ranks(values: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut ties = Vec::new();
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        // Empate é igualdade exata (0.0 e -0.0 incluídos)
+        #[allow(clippy::float_cmp)]
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        if end - start > 1 {
+            #[allow(clippy::cast_precision_loss)]
+            ties.push((end - start) as f64);
+        }
+        start = end;
+    }
+    (ranks, ties)
+}
+
+/// Função gama incompleta regularizada superior `Q(a, x)` (Numerical Recipes, 6.2)
+#[allow(clippy::many_single_char_names)]
+fn gamma_q(a: f64, x: f64) -> f64 {
+    const EPS: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefactor = (a.mul_add(x.ln(), -x) - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Série de P(a, x)
+        let (mut ap, mut del) = (a, 1.0 / a);
+        let mut sum = del;
+        for _ in 0..1000 {
+            ap += 1.0;
+            del *= x / ap;
+            sum += del;
+            if del.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        sum.mul_add(-prefactor, 1.0).max(0.0)
+    } else {
+        // Fração contínua de Q(a, x) pelo método de Lentz
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let i = f64::from(i);
+            let an = -i * (i - a);
+            b += 2.0;
+            d = an.mul_add(d, b);
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let del = d * c;
+            h *= del;
+            if (del - 1.0).abs() < EPS {
+                break;
+            }
+        }
+        prefactor * h
+    }
+}
+
+/// Função erro complementar para `x ≥ 0`, via `erfc(x) = Q(1/2, x²)`
+fn erfc(x: f64) -> f64 {
+    gamma_q(0.5, x * x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wilcoxon_exact_and_approximate() {
+        let a: Vec<f64> = (1..=10).map(f64::from).collect();
+        let zeros = vec![0.0; 10];
+        let r = wilcoxon(&a, &zeros).unwrap();
+        assert!(r.statistic.abs() < 1e-12);
+        assert!((r.p_value - 2.0 / 1024.0).abs() < 1e-12);
+
+        // Empates forçam a aproximação normal; amostras iguais não têm diferença
+        let b = [1.0, 1.0, 2.0, 2.0, 3.0, -1.0];
+        let r = wilcoxon(&b, &[0.0; 6]).unwrap();
+        assert!((r.statistic - 2.0).abs() < 1e-12);
+        assert!(r.p_value > 0.05 && r.p_value < 0.1, "p = {}", r.p_value);
+        assert!((wilcoxon(&a, &a).unwrap().p_value - 1.0).abs() < 1e-12);
+        assert!(wilcoxon(&a, &b).is_none());
+    }
+
+    #[test]
+    fn friedman_on_a_consistent_ordering() {
+        let best = [1.0, 2.0, 1.5, 0.5];
+        let middle = [2.0, 3.0, 2.5, 1.0];
+        let worst = [3.0, 4.0, 3.5, 2.0];
+        let r = friedman(&[&worst, &best, &middle], Direction::Minimize).unwrap();
+
+        assert!((r.statistic - 8.0).abs() < 1e-12);
+        // Qui-quadrado com 2 graus de liberdade: P(X > 8) = e^-4
+        assert!((r.p_value - (-4.0f64).exp()).abs() < 1e-10);
+        assert_eq!(r.mean_ranks, vec![3.0, 1.0, 2.0]);
+
+        let r = friedman(&[&worst, &best], Direction::Maximize).unwrap();
+        assert_eq!(r.mean_ranks, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn holm_is_monotone_and_capped() {
+        let adjusted = holm(&[0.01, 0.04, 0.03, 0.9]);
+        let expected = [0.04, 0.09, 0.09, 0.9];
+        assert!(
+            adjusted
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| (a - e).abs() < 1e-12)
+        );
+        assert!((erfc(0.0) - 1.0).abs() < 1e-12);
+        assert!((erfc(1.0) - 0.157_299_207_050_285_1).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,240 @@
+//! Crash-consistent record of the finished runs of an [`Experiment`] (feature `serde`).
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::Report,
+    experiments::{Experiment, Results, experiment::run_all},
+};
+
+/// Uma linha do manifesto: uma execução terminada
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    problem: String,
+    algorithm: String,
+    /// Ausente nos manifestos antigos, cujas execuções são refeitas
+    #[serde(default)]
+    config_hash: Option<u64>,
+    seed: u64,
+    report: Report,
+}
+
+/// Execução identificada por problema, algoritmo, hash da configuração e semente
+type Key = (String, String, Option<u64>, u64);
+
+/// Execuções terminadas, por [`Key`]
+type Done = HashMap<Key, Report>;
+
+impl Experiment {
+    /// Like [`Experiment::run`], recording every finished run in the manifest at `path` and
+    /// skipping the runs already recorded there.
+    ///
+    /// The manifest is a JSON Lines file, one run per line, appended and synced to disk as
+    /// soon as each run ends, so a campaign killed at any point picks up where it stopped
+    /// when launched again with the same path. Runs are matched by problem name, algorithm
+    /// name, [`Optimizer::config_hash`](crate::core::Optimizer::config_hash) and seed, so an
+    /// algorithm whose settings changed under the same name runs again instead of reusing
+    /// stale results. A line cut short by the crash is dropped, and lines of other
+    /// experiments or that cannot be read are ignored, so their runs are done again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or written, and the error of a run
+    /// that could not start (see [`Experiment::run`]) as an [`io::Error`] of kind `Other`.
+    pub fn run_resumable(&self, path: impl AsRef<Path>) -> io::Result<Results> {
+        let path = path.as_ref();
+        let mut done = load(path)?;
+        let grid = self.grid();
+        let hashes: HashMap<_, _> = grid
+            .iter()
+            .map(|&(p, a, k)| ((p, a, k), self.config_hash_of(p, a, k)))
+            .collect();
+        let key = |p: usize, a: usize, k: usize| -> Key {
+            (
+                self.problems[p].name.clone(),
+                self.algorithms[a].name.clone(),
+                hashes[&(p, a, k)],
+                self.seed_of(k),
+            )
+        };
+        let todo: Vec<(usize, usize, usize)> = grid
+            .iter()
+            .copied()
+            .filter(|&(p, a, k)| !done.contains_key(&key(p, a, k)))
+            .collect();
+
+        let file = Mutex::new(OpenOptions::new().create(true).append(true).open(path)?);
+        let fresh = run_all(&todo, |&(p, a, k)| -> io::Result<Report> {
+            let (problem, algorithm, config_hash, seed) = key(p, a, k);
+            let entry = Entry {
+                problem,
+                algorithm,
+                config_hash,
+                seed,
+                report: self.run_one(p, a, k).map_err(io::Error::other)?,
+            };
+            let mut line = serde_json::to_string(&entry)?;
+            line.push('\n');
+            // Uma linha por escrita, já no disco quando a execução conta como feita
+            {
+                let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+                file.write_all(line.as_bytes())?;
+                file.sync_data()?;
+            }
+            Ok(entry.report)
+        });
+        for (&(p, a, k), report) in todo.iter().zip(fresh) {
+            done.insert(key(p, a, k), report?);
+        }
+
+        let reports = grid
+            .iter()
+            .map(|&(p, a, k)| done.remove(&key(p, a, k)).unwrap_or_else(Report::empty))
+            .collect();
+        Ok(self.group(reports))
+    }
+}
+
+/// Lê o manifesto em `path`, cortando a última linha se ela ficou pela metade
+fn load(path: &Path) -> io::Result<Done> {
+    let mut done = Done::new();
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(done),
+        Err(e) => return Err(e),
+    };
+    let mut complete = 0;
+    for line in text.split_inclusive('\n') {
+        if !line.ends_with('\n') {
+            break;
+        }
+        complete += line.len();
+        if let Ok(entry) = serde_json::from_str::<Entry>(line) {
+            done.insert(
+                (
+                    entry.problem,
+                    entry.algorithm,
+                    entry.config_hash,
+                    entry.seed,
+                ),
+                entry.report,
+            );
+        }
+    }
+    if complete < text.len() {
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_len(complete as u64)?;
+    }
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        benchmarks::Function::Sphere,
+        core::{Bounds, Budget, Decoder, Optimizer},
+        experiments::{Algorithm, Problem},
+        hho::{Hho, HhoBuilder, HhoError},
+    };
+
+    /// Execuções iniciadas
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    /// HHO que conta as execuções; a fábrica também é chamada só para ler o hash
+    struct Counted(Hho);
+
+    impl Optimizer for Counted {
+        fn name(&self) -> &'static str {
+            "hho"
+        }
+
+        fn run(&mut self, decoder: &dyn Decoder, budget: Budget) -> Result<Report, HhoError> {
+            RUNS.fetch_add(1, Ordering::Relaxed);
+            Optimizer::run(&mut self.0, decoder, budget)
+        }
+
+        fn config_hash(&self) -> Option<u64> {
+            Optimizer::config_hash(&self.0)
+        }
+    }
+
+    fn campaign(pop_size: usize) -> Experiment {
+        Experiment::new(Budget::Iterations(10))
+            .problem(Problem::new(
+                "sphere",
+                Bounds::uniform(-5.0, 5.0, 3).unwrap(),
+                Sphere,
+            ))
+            .algorithm(Algorithm::new("hho", move |bounds, seed| {
+                Box::new(Counted(
+                    HhoBuilder::new(bounds.clone())
+                        .pop_size(pop_size)
+                        .seed(seed)
+                        .build(),
+                ))
+            }))
+            .replications(4)
+            .seed(2)
+    }
+
+    #[test]
+    fn a_killed_campaign_resumes_where_it_stopped() {
+        let path =
+            std::env::temp_dir().join(format!("kambo-manifest-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let experiment = campaign(5);
+
+        let full = experiment.run_resumable(&path).unwrap();
+        assert_eq!(RUNS.swap(0, Ordering::Relaxed), 4);
+        // Relançar não refaz nada
+        let again = experiment.run_resumable(&path).unwrap();
+        assert_eq!(RUNS.swap(0, Ordering::Relaxed), 0);
+
+        // Morte no meio da escrita da terceira execução: só ela e a quarta são refeitas
+        let text = fs::read_to_string(&path).unwrap();
+        let cut: usize = text.lines().take(2).map(|l| l.len() + 1).sum::<usize>() + 10;
+        fs::write(&path, &text[..cut]).unwrap();
+        let resumed = experiment.run_resumable(&path).unwrap();
+        assert_eq!(RUNS.swap(0, Ordering::Relaxed), 2);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        // Outra configuração com o mesmo nome não reaproveita as execuções gravadas
+        let changed = campaign(6).run_resumable(&path).unwrap();
+        assert_eq!(RUNS.swap(0, Ordering::Relaxed), 4);
+        let (old, new) = (
+            full.get("sphere", "hho").unwrap(),
+            changed.get("sphere", "hho").unwrap(),
+        );
+        assert!(
+            new.reports
+                .iter()
+                .all(|r| r.config_hash != old.reports[0].config_hash)
+        );
+
+        for results in [&again, &resumed] {
+            let (a, b) = (
+                full.get("sphere", "hho").unwrap(),
+                results.get("sphere", "hho").unwrap(),
+            );
+            assert_eq!(a.len(), b.len());
+            for (x, y) in a.reports.iter().zip(&b.reports) {
+                assert_eq!(x.best_position, y.best_position);
+                assert_eq!(x.seed, y.seed);
+            }
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,15 @@
+//! Experiment harness: every algorithm on every problem, replicated, as a tidy table.
+
+pub mod experiment;
+pub mod hypothesis;
+#[cfg(feature = "serde")]
+pub mod manifest;
+pub mod registry;
+pub mod sweep;
+pub mod tuner;
+
+pub use experiment::{Algorithm, Cell, Comparison, Experiment, Problem, Results};
+pub use hypothesis::{Friedman, TestResult, friedman, holm, wilcoxon};
+pub use registry::{ProblemArgs, ProblemFactory, ProblemRegistry, RegistryError};
+pub use sweep::{Design, Knob, Sweep, SweepRow, SweepTable};
+pub use tuner::{Configuration, SearchSpace, Strategy, Trial, Tuner, Tuning};
@@ -0,0 +1,301 @@
+//! Problems instantiated by name, e.g. `"rastrigin(dim=30)"`, for the CLI and configuration
+//! files.
+
+use std::{collections::BTreeMap, fmt, str::FromStr, sync::Arc};
+
+use thiserror::Error;
+
+use crate::{
+    benchmarks::{Engineering, Function},
+    core::{Bounds, bounds::BoundsError},
+    experiments::Problem,
+};
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("Invalid problem spec `{spec}`: {reason}")]
+    Syntax { spec: String, reason: String },
+
+    #[error("Unknown problem `{0}`")]
+    Unknown(String),
+
+    #[error("Problem `{problem}` has no argument `{key}`")]
+    UnknownArgument { problem: String, key: String },
+
+    #[error("Problem `{problem}` needs the argument `{key}`")]
+    MissingArgument { problem: String, key: String },
+
+    #[error("Invalid value `{value}` for argument `{key}`")]
+    InvalidArgument { key: String, value: String },
+
+    #[error(transparent)]
+    Bounds(#[from] BoundsError),
+
+    #[error("Invalid problem: {0}")]
+    Invalid(String),
+}
+
+/// A parsed problem spec: a name and `key=value` arguments, written
+/// `name(key=value, ...)` or just `name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProblemArgs {
+    name: String,
+    args: Vec<(String, String)>,
+}
+
+impl ProblemArgs {
+    /// Parses `spec`. Names and keys are lowercased; values may be quoted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::Syntax`] on a malformed spec or a repeated key.
+    pub fn parse(spec: &str) -> Result<Self, RegistryError> {
+        let fail = |reason: &str| RegistryError::Syntax {
+            spec: spec.to_owned(),
+            reason: reason.to_owned(),
+        };
+        let trimmed = spec.trim();
+        let (name, rest) = match trimmed.split_once('(') {
+            Some((name, rest)) => (
+                name,
+                Some(
+                    rest.strip_suffix(')')
+                        .ok_or_else(|| fail("missing closing parenthesis"))?,
+                ),
+            ),
+            None => (trimmed, None),
+        };
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty() {
+            return Err(fail("empty name"));
+        }
+        let mut args: Vec<(String, String)> = Vec::new();
+        for item in rest.unwrap_or_default().split(',').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| fail(&format!("expected `key=value`, found `{item}`")))?;
+            let key = key.trim().to_ascii_lowercase();
+            if args.iter().any(|(k, _)| *k == key) {
+                return Err(fail(&format!("repeated argument `{key}`")));
+            }
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|&q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+                .unwrap_or(value);
+            args.push((key, value.to_owned()));
+        }
+        Ok(Self { name, args })
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Argument `key` parsed as a `T`, or `None` if absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::InvalidArgument`] if the value does not parse.
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<Option<T>, RegistryError> {
+        let Some((_, value)) = self.args.iter().find(|(k, _)| k == key) else {
+            return Ok(None);
+        };
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| RegistryError::InvalidArgument {
+                key: key.to_owned(),
+                value: value.clone(),
+            })
+    }
+
+    /// Argument `key` parsed as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::MissingArgument`] if it is absent and
+    /// [`RegistryError::InvalidArgument`] if it does not parse.
+    pub fn require<T: FromStr>(&self, key: &str) -> Result<T, RegistryError> {
+        self.get(key)?
+            .ok_or_else(|| RegistryError::MissingArgument {
+                problem: self.name.clone(),
+                key: key.to_owned(),
+            })
+    }
+}
+
+/// Canonical form: `name` or `name(key=value, ...)` in the order written.
+impl fmt::Display for ProblemArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if !self.args.is_empty() {
+            let args: Vec<String> = self.args.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            write!(f, "({})", args.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a [`Problem`] from its parsed arguments, see [`ProblemRegistry::register`].
+pub type ProblemFactory = dyn Fn(&ProblemArgs) -> Result<Problem, RegistryError> + Send + Sync;
+
+/// Fábrica e argumentos aceitos de um problema registrado
+#[derive(Clone)]
+struct Entry {
+    keys: Vec<String>,
+    factory: Arc<ProblemFactory>,
+}
+
+/// Problems by name, instantiated from specs such as `"rastrigin(dim=30)"`.
+///
+/// [`ProblemRegistry::new`] knows every [`Function`], with a required `dim` and optional
+/// `lower` and `upper` replacing its domain, and every [`Engineering`] problem, without
+/// arguments. Downstream crates add their own with [`ProblemRegistry::register`].
+#[derive(Clone)]
+pub struct ProblemRegistry {
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Default for ProblemRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ProblemRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries.keys()).finish()
+    }
+}
+
+impl ProblemRegistry {
+    /// Registry of the built-in problems.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for f in Function::ALL {
+            registry.register(f.name(), &["dim", "lower", "upper"], move |args| {
+                let (lo, hi) = f.domain();
+                let bounds = Bounds::uniform(
+                    args.get("lower")?.unwrap_or(lo),
+                    args.get("upper")?.unwrap_or(hi),
+                    args.require("dim")?,
+                )?;
+                Ok(Problem::new(f.name(), bounds, f))
+            });
+        }
+        for p in Engineering::ALL {
+            registry.register(p.name(), &[], move |_| {
+                Ok(Problem::new(p.name(), p.bounds()?, p))
+            });
+        }
+        registry
+    }
+
+    /// Registry without any problem.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `factory` under `name` (case-insensitive), accepting the arguments `keys`;
+    /// a problem already registered under that name is replaced.
+    pub fn register(
+        &mut self,
+        name: &str,
+        keys: &[&str],
+        factory: impl Fn(&ProblemArgs) -> Result<Problem, RegistryError> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.entries.insert(
+            name.to_ascii_lowercase(),
+            Entry {
+                keys: keys.iter().map(|k| k.to_ascii_lowercase()).collect(),
+                factory: Arc::new(factory),
+            },
+        );
+        self
+    }
+
+    /// Registered names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Problem of `spec`, named after its canonical form (see [`ProblemArgs`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RegistryError`] if `spec` does not parse, names no registered problem,
+    /// passes an argument the problem does not accept, or the factory fails.
+    pub fn create(&self, spec: &str) -> Result<Problem, RegistryError> {
+        let args = ProblemArgs::parse(spec)?;
+        let entry = self
+            .entries
+            .get(&args.name)
+            .ok_or_else(|| RegistryError::Unknown(args.name.clone()))?;
+        if let Some((key, _)) = args.args.iter().find(|(k, _)| !entry.keys.contains(k)) {
+            return Err(RegistryError::UnknownArgument {
+                problem: args.name.clone(),
+                key: key.clone(),
+            });
+        }
+        let mut problem = (entry.factory)(&args)?;
+        problem.name = args.to_string();
+        Ok(problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Decoder;
+
+    #[test]
+    fn specs_name_registered_problems() {
+        let mut registry = ProblemRegistry::new();
+        let problem = registry.create(" Rastrigin( dim = 30 ) ").unwrap();
+        assert_eq!(problem.name, "rastrigin(dim=30)");
+        assert_eq!(problem.bounds.dim(), 30);
+        assert!((problem.bounds.upper()[0] - 5.12).abs() < 1e-12);
+        let narrow = registry
+            .create("sphere(dim=2, lower=-1, upper='1')")
+            .unwrap();
+        assert_eq!(*narrow.bounds.lower(), [-1.0, -1.0]);
+        assert_eq!(registry.create("spring").unwrap().bounds.dim(), 3);
+
+        for (spec, check) in [
+            ("nope", "Unknown"),
+            ("sphere", "Missing"),
+            ("sphere(dim=x)", "Invalid"),
+            ("sphere(dim=2, dimm=3)", "UnknownArgument"),
+            ("sphere(dim=2", "Syntax"),
+        ] {
+            let e = registry.create(spec).unwrap_err();
+            assert!(format!("{e:?}").starts_with(check), "{spec}: {e:?}");
+        }
+
+        registry.register("shifted", &["offset"], |args| {
+            let offset: f64 = args.get("offset")?.unwrap_or(1.0);
+            let bounds = Bounds::uniform(-5.0, 5.0, 2)?;
+            Ok(Problem::new("", bounds, Shifted(offset)))
+        });
+        let shifted = registry.create("shifted(offset=2)").unwrap();
+        assert!(shifted.decoder.decode(&[2.0, 2.0]).unwrap() < 1e-12);
+        assert!(registry.names().any(|n| n == "shifted"));
+    }
+
+    struct Shifted(f64);
+
+    impl Decoder for Shifted {
+        fn decode(&self, x: &[f64]) -> Result<f64, crate::core::DecoderError> {
+            Ok(x.iter().map(|v| (v - self.0).powi(2)).sum())
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Grid and random sweeps over the HHO builder settings, as a long-format table.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use rand::Rng;
+
+use crate::{
+    core::{Budget, Optimizer, Report, SeedStreams},
+    experiments::{
+        Problem,
+        experiment::{csv_field, partial, run_all},
+    },
+    hho::{HhoBuilder, HhoError, Params},
+};
+
+/// A builder setting varied by a [`Sweep`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Knob {
+    /// [`HhoBuilder::pop_size`], rounded to the nearest integer (at least 1).
+    PopSize,
+    /// [`Params::energy_decay`].
+    EnergyDecay,
+    /// [`Params::dive_scale`].
+    DiveScale,
+    /// [`Params::levy_beta`].
+    LevyBeta,
+    /// [`HhoBuilder::momentum`].
+    Momentum,
+}
+
+impl Knob {
+    /// Column name in the CSV output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PopSize => "pop_size",
+            Self::EnergyDecay => "energy_decay",
+            Self::DiveScale => "dive_scale",
+            Self::LevyBeta => "levy_beta",
+            Self::Momentum => "momentum",
+        }
+    }
+}
+
+/// How a [`Sweep`] picks its configurations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Design {
+    /// Every combination of the listed values.
+    Grid,
+    /// `samples` configurations, each knob drawn uniformly between its smallest and largest
+    /// listed value.
+    Random { samples: usize },
+}
+
+/// Runs the HHO under several settings on a set of (minimization) problems.
+///
+/// Every configuration is run `replications` times on every problem, replication `k` with the
+/// seed [`SeedStreams::replication`] of the sweep seed, so all configurations see the same
+/// seeds. Settings without a knob keep the builder defaults. With the `rayon` feature the runs
+/// are spread over the global thread pool.
+///
+/// Unlike the [`crate::experiments::Tuner`] nothing is searched adaptively: the sweep only
+/// tabulates, which is usually enough to pick a setting and shows how sensitive it is.
+#[derive(Debug, Clone)]
+pub struct Sweep {
+    pub design: Design,
+    pub knobs: Vec<(Knob, Vec<f64>)>,
+    pub problems: Vec<Problem>,
+    pub budget: Budget,
+    pub replications: usize,
+    pub seed: u64,
+}
+
+impl Sweep {
+    /// Full factorial sweep with 10 replications and seed 0.
+    #[must_use]
+    pub const fn grid(budget: Budget) -> Self {
+        Self::new(Design::Grid, budget)
+    }
+
+    /// Sweep of `samples` random configurations with 10 replications and seed 0.
+    #[must_use]
+    pub const fn random(samples: usize, budget: Budget) -> Self {
+        Self::new(Design::Random { samples }, budget)
+    }
+
+    const fn new(design: Design, budget: Budget) -> Self {
+        Self {
+            design,
+            knobs: Vec::new(),
+            problems: Vec::new(),
+            budget,
+            replications: 10,
+            seed: 0,
+        }
+    }
+
+    /// Varies `knob` over `values` (grid) or over their range (random). A knob given twice
+    /// keeps the last values.
+    #[must_use]
+    pub fn knob(mut self, knob: Knob, values: impl Into<Vec<f64>>) -> Self {
+        self.knobs.retain(|(k, _)| *k != knob);
+        self.knobs.push((knob, values.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn problem(mut self, problem: Problem) -> Self {
+        self.problems.push(problem);
+        self
+    }
+
+    #[must_use]
+    pub const fn replications(mut self, replications: usize) -> Self {
+        self.replications = replications;
+        self
+    }
+
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Values of the knobs for every configuration, in the order the knobs were added.
+    ///
+    /// The grid varies the last knob fastest. A knob without values empties the grid.
+    #[must_use]
+    pub fn configurations(&self) -> Vec<Vec<f64>> {
+        match self.design {
+            Design::Grid => self
+                .knobs
+                .iter()
+                .fold(vec![Vec::new()], |acc, (_, values)| {
+                    acc.iter()
+                        .flat_map(|head| {
+                            values.iter().map(move |&v| {
+                                let mut row = head.clone();
+                                row.push(v);
+                                row
+                            })
+                        })
+                        .collect()
+                }),
+            Design::Random { samples } => {
+                let ranges: Option<Vec<(f64, f64)>> = self
+                    .knobs
+                    .iter()
+                    .map(|(_, values)| {
+                        let lo = values.iter().copied().reduce(f64::min)?;
+                        let hi = values.iter().copied().reduce(f64::max)?;
+                        Some((lo, hi))
+                    })
+                    .collect();
+                let Some(ranges) = ranges else {
+                    return Vec::new();
+                };
+                let mut rng = SeedStreams::new(self.seed).init();
+                (0..samples)
+                    .map(|_| {
+                        ranges
+                            .iter()
+                            .map(|&(lo, hi)| rng.random::<f64>().mul_add(hi - lo, lo))
+                            .collect()
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Runs every configuration and returns one row per run, grouped by configuration, then
+    /// problem, then replication.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::experiments::Experiment::run`].
+    pub fn run(&self) -> Result<SweepTable, HhoError> {
+        let configurations = self.configurations();
+        let tasks: Vec<(usize, usize, usize)> = (0..configurations.len())
+            .flat_map(|c| {
+                (0..self.problems.len())
+                    .flat_map(move |p| (0..self.replications).map(move |k| (c, p, k)))
+            })
+            .collect();
+        let reports = run_all(&tasks, |&(c, p, k)| {
+            self.run_one(&configurations[c], &self.problems[p], k)
+        });
+        let rows = tasks
+            .iter()
+            .zip(reports)
+            .map(|(&(c, p, k), report)| {
+                Ok(SweepRow {
+                    configuration: c,
+                    values: configurations[c].clone(),
+                    problem: self.problems[p].name.clone(),
+                    run: k,
+                    report: report?,
+                })
+            })
+            .collect::<Result<_, HhoError>>()?;
+        Ok(SweepTable {
+            knobs: self.knobs.iter().map(|(k, _)| *k).collect(),
+            rows,
+        })
+    }
+
+    /// Réplica `k` da configuração `values` no problema
+    fn run_one(&self, values: &[f64], problem: &Problem, k: usize) -> Result<Report, HhoError> {
+        let seed = SeedStreams::new(self.seed).replication(k as u64);
+        let mut builder = HhoBuilder::new(problem.bounds.clone()).seed(seed);
+        let mut params = Params::default();
+        for (&(knob, _), &v) in self.knobs.iter().zip(values) {
+            match knob {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Knob::PopSize => builder = builder.pop_size((v.round() as usize).max(1)),
+                Knob::EnergyDecay => params.energy_decay = v,
+                Knob::DiveScale => params.dive_scale = v,
+                Knob::LevyBeta => params.levy_beta = v,
+                Knob::Momentum => builder = builder.momentum(v),
+            }
+        }
+        let mut hho = builder.params(params.clamped()).build();
+        let mut report = partial(Optimizer::run(
+            &mut hho,
+            problem.decoder.as_ref(),
+            self.budget,
+        ))?;
+        report.seed.get_or_insert(seed);
+        Ok(report)
+    }
+}
+
+/// One run of a [`Sweep`].
+#[derive(Debug, Clone)]
+pub struct SweepRow {
+    /// Index of the configuration in [`Sweep::configurations`].
+    pub configuration: usize,
+    /// Value of each knob, in the order of [`SweepTable::knobs`].
+    pub values: Vec<f64>,
+    pub problem: String,
+    /// Replication index.
+    pub run: usize,
+    pub report: Report,
+}
+
+/// Outcome of a [`Sweep`]: one [`SweepRow`] per run.
+#[derive(Debug, Clone, Default)]
+pub struct SweepTable {
+    pub knobs: Vec<Knob>,
+    pub rows: Vec<SweepRow>,
+}
+
+impl SweepTable {
+    /// Mean best fitness of each configuration over all its runs, by configuration index.
+    #[must_use]
+    pub fn mean_best_fitness(&self) -> Vec<f64> {
+        let n = self
+            .rows
+            .iter()
+            .map(|r| r.configuration + 1)
+            .max()
+            .unwrap_or(0);
+        let mut sums = vec![(0.0, 0u32); n];
+        for row in &self.rows {
+            let (sum, count) = &mut sums[row.configuration];
+            *sum += row.report.best_fitness;
+            *count += 1;
+        }
+        sums.iter()
+            .map(|&(sum, count)| sum / f64::from(count.max(1)))
+            .collect()
+    }
+
+    /// Knob values of the configuration with the best mean best fitness, in the direction of
+    /// the runs.
+    #[must_use]
+    pub fn best(&self) -> Option<&[f64]> {
+        let direction = self.rows.first()?.report.direction;
+        let means = self.mean_best_fitness();
+        let best = (0..means.len()).reduce(|a, b| {
+            if direction.better(means[b], means[a]) {
+                b
+            } else {
+                a
+            }
+        })?;
+        self.rows
+            .iter()
+            .find(|r| r.configuration == best)
+            .map(|r| r.values.as_slice())
+    }
+
+    /// Writes the table as CSV to `path` (see [`SweepTable::write_csv`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut out)?;
+        out.flush()
+    }
+
+    /// Writes one row per run, with the columns `configuration`, one per knob, then
+    /// `problem`, `run`, `seed`, `best_fitness`, `evals`, `iters` and `seconds`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "configuration")?;
+        for knob in &self.knobs {
+            write!(out, ",{}", knob.as_str())?;
+        }
+        writeln!(out, ",problem,run,seed,best_fitness,evals,iters,seconds")?;
+        for row in &self.rows {
+            write!(out, "{}", row.configuration)?;
+            for v in &row.values {
+                write!(out, ",{v}")?;
+            }
+            let r = &row.report;
+            let seed = r.seed.map(|s| s.to_string()).unwrap_or_default();
+            let seconds = r
+                .duration
+                .map(|d| d.as_secs_f64().to_string())
+                .unwrap_or_default();
+            writeln!(
+                out,
+                ",{},{},{seed},{},{},{},{seconds}",
+                csv_field(&row.problem),
+                row.run,
+                r.best_fitness,
+                r.evals,
+                r.iters
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, core::Bounds};
+
+    fn sphere() -> Problem {
+        Problem::new("sphere", Bounds::uniform(-5.0, 5.0, 3).unwrap(), Sphere)
+    }
+
+    #[test]
+    fn grid_runs_every_combination_with_shared_seeds() {
+        let sweep = Sweep::grid(Budget::Iterations(10))
+            .knob(Knob::PopSize, [5.0, 10.0])
+            .knob(Knob::LevyBeta, [1.2, 1.5, 1.8])
+            .problem(sphere())
+            .replications(2)
+            .seed(4);
+        assert_eq!(sweep.configurations().len(), 6);
+        assert_eq!(sweep.configurations()[1], vec![5.0, 1.5]);
+
+        let table = sweep.run().unwrap();
+        assert_eq!(table.rows.len(), 12);
+        // As configurações 0-2 têm 5 falcões e as 3-5, 10
+        assert!(table.rows[3].report.evals < table.rows[9].report.evals);
+        // Mesma semente por réplica em todas as configurações
+        assert_eq!(table.rows[1].report.seed, table.rows[11].report.seed);
+        assert_eq!(table.mean_best_fitness().len(), 6);
+        assert!(table.best().is_some());
+
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "configuration,pop_size,levy_beta,problem,run,seed,best_fitness,evals,iters,seconds"
+            )
+        );
+        assert!(lines.next().unwrap().starts_with("0,5,1.2,sphere,0,"));
+    }
+
+    #[test]
+    fn random_draws_within_the_ranges() {
+        let sweep = Sweep::random(5, Budget::Iterations(5))
+            .knob(Knob::EnergyDecay, [0.5, 2.0])
+            .knob(Knob::Momentum, [0.0, 0.3])
+            .problem(sphere())
+            .replications(1);
+        let configurations = sweep.configurations();
+        assert_eq!(configurations.len(), 5);
+        assert!(
+            configurations
+                .iter()
+                .all(|c| (0.5..=2.0).contains(&c[0]) && (0.0..=0.3).contains(&c[1]))
+        );
+        assert_eq!(sweep.configurations(), configurations);
+        assert_eq!(sweep.run().unwrap().rows.len(), 5);
+    }
+}
@@ -0,0 +1,290 @@
+//! Meta-optimization of the HHO settings over a family of problems.
+
+use std::sync::{Mutex, PoisonError};
+
+use rand::Rng;
+
+use crate::{
+    core::{Bounds, Budget, Decoder, DecoderError, SeedStreams},
+    experiments::{Problem, experiment::run_all},
+    hho::{HhoBuilder, Params},
+};
+
+/// HHO settings explored by the [`Tuner`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Configuration {
+    pub pop_size: usize,
+    pub params: Params,
+}
+
+/// Ranges searched by the [`Tuner`]. `dive_scale` is searched on a log scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchSpace {
+    pub pop_size: (usize, usize),
+    pub energy_decay: (f64, f64),
+    pub dive_scale: (f64, f64),
+    pub levy_beta: (f64, f64),
+}
+
+impl Default for SearchSpace {
+    /// Populations of 10 to 100 hawks and the admissible range of every [`Params`] field.
+    fn default() -> Self {
+        Self {
+            pop_size: (10, 100),
+            energy_decay: Params::ENERGY_DECAY_RANGE,
+            dive_scale: Params::DIVE_SCALE_RANGE,
+            levy_beta: Params::LEVY_BETA_RANGE,
+        }
+    }
+}
+
+impl SearchSpace {
+    /// Dimensão do espaço unitário de busca
+    const DIM: usize = 4;
+
+    /// Configuration at the point `u` of the unit hypercube `[0, 1]^4`.
+    #[must_use]
+    pub fn configuration(&self, u: &[f64]) -> Configuration {
+        let lerp = |(lo, hi): (f64, f64), t: f64| t.clamp(0.0, 1.0).mul_add(hi - lo, lo);
+        let (lo, hi) = self.pop_size;
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let pop_size = lerp((lo as f64, hi as f64), u[0]).round() as usize;
+        let (d_lo, d_hi) = self.dive_scale;
+        Configuration {
+            pop_size: pop_size.max(1),
+            params: Params {
+                energy_decay: lerp(self.energy_decay, u[1]),
+                dive_scale: lerp((d_lo.ln(), d_hi.ln()), u[2]).exp(),
+                levy_beta: lerp(self.levy_beta, u[3]),
+            }
+            .clamped(),
+        }
+    }
+}
+
+/// How the [`Tuner`] explores the [`SearchSpace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// `samples` uniformly random configurations, scored in parallel with the `rayon` feature.
+    Random { samples: usize },
+
+    /// HHO itself over the unit hypercube of the search space, with `pop_size` hawks for
+    /// `iters` iterations.
+    Hho { pop_size: usize, iters: usize },
+}
+
+/// A configuration and its score (lower is better).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trial {
+    pub configuration: Configuration,
+    pub score: f64,
+}
+
+/// Outcome of [`Tuner::tune`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuning {
+    pub best: Trial,
+    /// Every configuration scored, in the order they were tried.
+    pub trials: Vec<Trial>,
+}
+
+/// Searches the HHO configuration that does best on a family of (minimization) problems.
+///
+/// A configuration is scored by running it `replications` times on every problem with the
+/// inner `budget` and averaging the best fitness over all those runs, so the problems should
+/// have comparable fitness scales. Every configuration sees the same seeds, which makes the
+/// scores directly comparable. Runs that fail score `+∞`.
+#[derive(Debug, Clone)]
+pub struct Tuner {
+    pub problems: Vec<Problem>,
+    pub space: SearchSpace,
+    pub strategy: Strategy,
+    pub budget: Budget,
+    pub replications: usize,
+    pub seed: u64,
+}
+
+impl Tuner {
+    /// Random search of 50 configurations, 5 replications per problem and seed 0.
+    #[must_use]
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            problems: Vec::new(),
+            space: SearchSpace::default(),
+            strategy: Strategy::Random { samples: 50 },
+            budget,
+            replications: 5,
+            seed: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn problem(mut self, problem: Problem) -> Self {
+        self.problems.push(problem);
+        self
+    }
+
+    #[must_use]
+    pub const fn space(mut self, space: SearchSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    #[must_use]
+    pub const fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    #[must_use]
+    pub const fn replications(mut self, replications: usize) -> Self {
+        self.replications = replications;
+        self
+    }
+
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the search. Returns `None` if there is no problem or no configuration was scored.
+    #[must_use]
+    pub fn tune(&self) -> Option<Tuning> {
+        if self.problems.is_empty() {
+            return None;
+        }
+        let streams = SeedStreams::new(self.seed);
+        let trials = match self.strategy {
+            Strategy::Random { samples } => {
+                let mut rng = streams.init();
+                let points: Vec<[f64; SearchSpace::DIM]> =
+                    (0..samples).map(|_| rng.random()).collect();
+                run_all(&points, |u| self.trial(u))
+            }
+            Strategy::Hho { pop_size, iters } => {
+                let meta = Meta {
+                    tuner: self,
+                    trials: Mutex::new(Vec::new()),
+                };
+                let bounds = Bounds::uniform(0.0, 1.0, SearchSpace::DIM).ok()?;
+                let hho = HhoBuilder::new(bounds)
+                    .pop_size(pop_size)
+                    .max_iters(iters)
+                    .seed(streams.replication(u64::MAX))
+                    .build();
+                hho.run(&meta).ok()?;
+                meta.trials
+                    .into_inner()
+                    .unwrap_or_else(PoisonError::into_inner)
+            }
+        };
+        let best = *trials.iter().min_by(|a, b| a.score.total_cmp(&b.score))?;
+        Some(Tuning { best, trials })
+    }
+
+    /// Pontua a configuração do ponto `u`
+    fn trial(&self, u: &[f64]) -> Trial {
+        let configuration = self.space.configuration(u);
+        Trial {
+            configuration,
+            score: self.score(configuration),
+        }
+    }
+
+    /// Média da melhor fitness de todas as execuções internas
+    fn score(&self, configuration: Configuration) -> f64 {
+        let streams = SeedStreams::new(self.seed);
+        let mut total = 0.0;
+        let mut runs = 0u32;
+        for problem in &self.problems {
+            for k in 0..self.replications {
+                let hho = HhoBuilder::new(problem.bounds.clone())
+                    .pop_size(configuration.pop_size)
+                    .params(configuration.params)
+                    .seed(streams.replication(k as u64))
+                    .build();
+                match hho.run_with_budget(problem.decoder.as_ref(), self.budget) {
+                    Ok(report) => total += report.best_fitness,
+                    Err(_) => return f64::INFINITY,
+                }
+                runs += 1;
+            }
+        }
+        total / f64::from(runs).max(1.0)
+    }
+}
+
+/// Objetivo da meta-otimização: a pontuação da configuração de cada ponto
+struct Meta<'a> {
+    tuner: &'a Tuner,
+    trials: Mutex<Vec<Trial>>,
+}
+
+impl Decoder for Meta<'_> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let trial = self.tuner.trial(solution);
+        self.trials
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(trial);
+        Ok(trial.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::Function::Sphere;
+
+    fn tuner(strategy: Strategy) -> Tuner {
+        Tuner::new(Budget::Evaluations(200))
+            .problem(Problem::new(
+                "sphere",
+                Bounds::uniform(-5.0, 5.0, 3).unwrap(),
+                Sphere,
+            ))
+            .space(SearchSpace {
+                pop_size: (5, 20),
+                ..SearchSpace::default()
+            })
+            .strategy(strategy)
+            .replications(2)
+            .seed(3)
+    }
+
+    #[test]
+    fn random_search_keeps_the_best_trial() {
+        let tuning = tuner(Strategy::Random { samples: 6 }).tune().unwrap();
+
+        assert_eq!(tuning.trials.len(), 6);
+        assert!(tuning.trials.iter().all(|t| t.score >= tuning.best.score));
+        assert!((5..=20).contains(&tuning.best.configuration.pop_size));
+        // Reprodutível
+        assert_eq!(
+            tuner(Strategy::Random { samples: 6 }).tune().unwrap(),
+            tuning
+        );
+    }
+
+    #[test]
+    fn hho_drives_the_search() {
+        let tuning = tuner(Strategy::Hho {
+            pop_size: 4,
+            iters: 2,
+        })
+        .tune()
+        .unwrap();
+
+        assert!(tuning.trials.len() >= 4);
+        assert!(tuning.trials.iter().all(|t| t.score >= tuning.best.score));
+        let space = SearchSpace::default();
+        let low = space.configuration(&[0.0; 4]);
+        assert_eq!(low.pop_size, 10);
+        assert!((low.params.dive_scale - Params::DIVE_SCALE_RANGE.0).abs() < 1e-12);
+    }
+}
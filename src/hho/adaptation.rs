@@ -0,0 +1,161 @@
+use rand::Rng;
+
+use crate::hho::{Params, levy::standard_normal};
+
+/// Controls how the [`Params`] used by each hawk are chosen.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Adaptation {
+    /// Every hawk uses the configured parameters for the whole run.
+    #[default]
+    Off,
+
+    /// SHADE-style success history: each hawk samples its parameters around one of
+    /// `memory_size` historical means, and the means move toward the values that produced
+    /// improvements.
+    SuccessHistory { memory_size: usize },
+}
+
+impl Adaptation {
+    /// Builds the memory for this strategy, if any, seeded with `initial`.
+    #[must_use]
+    pub fn history(self, initial: Params) -> Option<SuccessHistory> {
+        match self {
+            Self::Off => None,
+            Self::SuccessHistory { memory_size } => Some(SuccessHistory::new(memory_size, initial)),
+        }
+    }
+}
+
+/// Circular memory of successful parameter means (one slot per entry, as in SHADE).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuccessHistory {
+    memory: Vec<Params>,
+    cursor: usize,
+    successes: Vec<(Params, f64)>,
+}
+
+impl SuccessHistory {
+    /// Desvio padrão relativo usado na amostragem de cada parâmetro
+    const SPREAD: f64 = 0.1;
+
+    /// Creates a memory of `memory_size` slots (at least one), all set to `initial`.
+    #[must_use]
+    pub fn new(memory_size: usize, initial: Params) -> Self {
+        Self {
+            memory: vec![initial; memory_size.max(1)],
+            cursor: 0,
+            successes: Vec::new(),
+        }
+    }
+
+    /// Samples a parameter set around a randomly chosen memory slot.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Params {
+        let m = self.memory[rng.random_range(0..self.memory.len())];
+        let (dl, dh) = Params::ENERGY_DECAY_RANGE;
+        let (sl, sh) = Params::DIVE_SCALE_RANGE;
+        let (bl, bh) = Params::LEVY_BETA_RANGE;
+        Params {
+            energy_decay: Self::SPREAD.mul_add((dh - dl) * standard_normal(rng), m.energy_decay),
+            dive_scale: Self::SPREAD.mul_add((sh - sl) * standard_normal(rng), m.dive_scale),
+            levy_beta: Self::SPREAD.mul_add((bh - bl) * standard_normal(rng), m.levy_beta),
+        }
+        .clamped()
+    }
+
+    /// Records that `params` improved a hawk's fitness by `improvement`. Improvements that
+    /// are not finite and positive carry no weight on the scale of the others and are skipped.
+    pub fn record(&mut self, params: Params, improvement: f64) {
+        if improvement.is_finite() && improvement > 0.0 {
+            self.successes.push((params, improvement));
+        }
+    }
+
+    /// Closes the current iteration: writes the improvement-weighted means of the successful
+    /// parameters into the next memory slot. Does nothing if no hawk improved.
+    pub fn update(&mut self) {
+        if self.successes.is_empty() {
+            return;
+        }
+        let total: f64 = self.successes.iter().map(|(_, w)| w).sum();
+        let mut decay = 0.0;
+        let mut beta = 0.0;
+        let (mut scale_sq, mut scale) = (0.0, 0.0);
+        for (p, w) in &self.successes {
+            let w = w / total;
+            decay += w * p.energy_decay;
+            beta += w * p.levy_beta;
+            // Média de Lehmer para a escala, como o F do SHADE
+            scale_sq += w * p.dive_scale * p.dive_scale;
+            scale += w * p.dive_scale;
+        }
+        self.memory[self.cursor] = Params {
+            energy_decay: decay,
+            dive_scale: scale_sq / scale,
+            levy_beta: beta,
+        }
+        .clamped();
+        self.cursor = (self.cursor + 1) % self.memory.len();
+        self.successes.clear();
+    }
+
+    /// Component-wise mean of the memory slots.
+    #[must_use]
+    pub fn mean(&self) -> Params {
+        #[allow(clippy::cast_precision_loss)]
+        let n = self.memory.len() as f64;
+        let sum = self.memory.iter().fold((0.0, 0.0, 0.0), |acc, p| {
+            (
+                acc.0 + p.energy_decay,
+                acc.1 + p.dive_scale,
+                acc.2 + p.levy_beta,
+            )
+        });
+        Params {
+            energy_decay: sum.0 / n,
+            dive_scale: sum.1 / n,
+            levy_beta: sum.2 / n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_moves_memory_toward_successes() {
+        let mut h = SuccessHistory::new(2, Params::default());
+        let good = Params {
+            energy_decay: 2.0,
+            dive_scale: 3.0,
+            levy_beta: 1.0,
+        };
+        h.record(good, 5.0);
+        h.update();
+
+        assert_eq!(h.memory[0], good);
+        assert_eq!(h.memory[1], Params::default());
+        assert_eq!(h.cursor, 1);
+
+        // Sem sucessos a memória não muda
+        h.update();
+        assert_eq!(h.cursor, 1);
+
+        // Melhoras sem peso (nulas, negativas, infinitas ou NaN) não contam como sucesso
+        let bad = Params {
+            energy_decay: 0.5,
+            ..good
+        };
+        for improvement in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            h.record(bad, improvement);
+        }
+        h.update();
+        assert_eq!(h.cursor, 1);
+        h.record(bad, f64::NAN);
+        h.record(good, 1e-12);
+        h.update();
+        assert_eq!(h.memory[1], good);
+    }
+}
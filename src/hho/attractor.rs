@@ -0,0 +1,37 @@
+use crate::core::{Objective, Population, utils::top_k_indices};
+
+/// Point the hawks besiege when `|E| < 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Attractor {
+    /// The single best hawk found so far (original HHO).
+    #[default]
+    Rabbit,
+
+    /// Centroid of the `k` best hawks of the current population. Less sensitive to a lucky
+    /// outlier rabbit, which reduces premature convergence.
+    EliteCentroid { k: usize },
+}
+
+/// Writes into `out` the centroid of the `k` best hawks (at least one, at most all).
+pub fn elite_centroid<O: Objective>(
+    positions: &Population,
+    fitness: &[f64],
+    k: usize,
+    out: &mut [f64],
+) {
+    crate::core::utils::strict_assert_eq!(positions.len(), fitness.len(), "len mismatch");
+    let elites = top_k_indices::<O>(fitness, k.max(1));
+
+    out.fill(0.0);
+    for &i in &elites {
+        for (o, x) in out.iter_mut().zip(&positions[i]) {
+            *o += x;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let k = elites.len().max(1) as f64;
+    for o in out {
+        *o /= k;
+    }
+}
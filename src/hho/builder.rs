@@ -0,0 +1,606 @@
+use std::{
+    marker::PhantomData,
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+
+#[cfg(feature = "serde")]
+use crate::hho::CheckpointPolicy;
+use crate::{
+    core::{
+        Bounds, CurveSampling, Distance, FitnessScaling, HallOfFame, Minimization, Objective,
+        Report, RngKind,
+    },
+    hho::{
+        Adaptation, Attractor, ConfigError, Dedup, DiveReplay, DiveShape, Dynamic, Exploitation,
+        Fidelity, Hho, Immigrants, JumpStrength, Niching, NonFinite, OrthogonalLearning, Parameter,
+        Params, PartnerSelection, Preset, RankSteps, Schedule, Settings, UpdateMode, Zoom,
+    },
+    init::{Initializer, Prior, PriorInitializer, RandomInitializer, WarmStart},
+    local_search::{LocalRefiner, RefinerSchedule},
+    observer::{EarlyStop, IterationContext, MutObserver, Observer, ProgressBar, SharedObserver},
+    termination::{CancellationToken, Criterion},
+};
+
+/// Builder for [`Hho`].
+///
+/// Only the [`Bounds`] are mandatory; every other setting starts from the values of the
+/// original paper (`pop_size = 30`, `max_iters = 500`, [`Params::default`]).
+#[derive(Debug, Clone)]
+pub struct HhoBuilder<O = Minimization, I = RandomInitializer> {
+    bounds: Bounds,
+    initializer: I,
+    settings: Settings,
+    objective: PhantomData<fn() -> O>,
+}
+
+impl HhoBuilder {
+    #[must_use]
+    pub fn new(bounds: Bounds) -> Self {
+        Self {
+            bounds,
+            initializer: RandomInitializer,
+            settings: Settings::default(),
+            objective: PhantomData,
+        }
+    }
+}
+
+impl<O, I> HhoBuilder<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Changes the optimization direction (e.g. [`crate::core::Maximization`]).
+    #[must_use]
+    pub fn objective<O2: Objective>(self) -> HhoBuilder<O2, I> {
+        HhoBuilder {
+            bounds: self.bounds,
+            initializer: self.initializer,
+            settings: self.settings,
+            objective: PhantomData,
+        }
+    }
+
+    /// Replaces the strategy used to create the initial population.
+    #[must_use]
+    pub fn initializer<I2: Initializer>(self, initializer: I2) -> HhoBuilder<O, I2> {
+        HhoBuilder {
+            bounds: self.bounds,
+            initializer,
+            settings: self.settings,
+            objective: PhantomData,
+        }
+    }
+
+    /// Starts from the outcome of a previous run, see [`WarmStart`]; chain it with
+    /// [`HhoBuilder::continue_counters`] to carry on its iteration and evaluation counts.
+    #[must_use]
+    pub fn warm_start(self, report: &Report) -> HhoBuilder<O, WarmStart> {
+        self.initializer(WarmStart::from_report(report))
+    }
+
+    /// Draws the initial population from one [`Prior`] per dimension, see
+    /// [`PriorInitializer`].
+    #[must_use]
+    pub fn initial_priors(self, priors: Vec<Prior>) -> HhoBuilder<O, PriorInitializer> {
+        self.initializer(PriorInitializer::new(priors))
+    }
+
+    /// Starts the iteration and evaluation counters at those of `report`, so the iteration
+    /// limit, the evaluation budget and the schedule of `E` continue where that run stopped
+    /// instead of starting over.
+    #[must_use]
+    pub const fn continue_counters(mut self, report: &Report) -> Self {
+        self.settings.counters = Some((report.iters, report.evals));
+        self
+    }
+
+    #[must_use]
+    pub const fn pop_size(mut self, pop_size: usize) -> Self {
+        self.settings.pop_size = pop_size;
+        self
+    }
+
+    #[must_use]
+    pub const fn max_iters(mut self, max_iters: usize) -> Self {
+        self.settings.max_iters = max_iters;
+        self
+    }
+
+    /// Applies a [`Preset`] for the kind of problem at hand; later calls override it.
+    #[must_use]
+    pub fn preset(self, preset: Preset) -> Self {
+        preset.apply(self)
+    }
+
+    /// Sets the (initial) update coefficients.
+    #[must_use]
+    pub const fn params(mut self, params: Params) -> Self {
+        self.settings.params = params;
+        self
+    }
+
+    /// Enables online adaptation of the [`Params`], see [`Adaptation`].
+    #[must_use]
+    pub const fn adaptation(mut self, adaptation: Adaptation) -> Self {
+        self.settings.adaptation = adaptation;
+        self
+    }
+
+    /// Selects how exploring hawks pick the family member they perch on.
+    #[must_use]
+    pub const fn partner_selection(mut self, selection: PartnerSelection) -> Self {
+        self.settings.partner_selection = selection;
+        self
+    }
+
+    /// Enables a niching mode so the swarm keeps several optima, see [`Niching`].
+    #[must_use]
+    pub const fn niching(mut self, niching: Niching) -> Self {
+        self.settings.niching = niching;
+        self
+    }
+
+    /// Enables support for time-varying objectives, see [`Dynamic`].
+    #[must_use]
+    pub fn dynamic(mut self, dynamic: Dynamic) -> Self {
+        self.settings.dynamic = dynamic;
+        self
+    }
+
+    /// Keeps the `capacity` best distinct solutions ever evaluated (points closer than
+    /// `min_distance` count as duplicates) and returns them in [`crate::core::Report`].
+    #[must_use]
+    pub fn hall_of_fame(mut self, capacity: usize, min_distance: f64) -> Self {
+        self.settings.hall_of_fame = Some(HallOfFame::new(capacity, min_distance));
+        self
+    }
+
+    /// Records every full-fidelity evaluation of the run, with its fitness, into
+    /// [`crate::core::Report::evaluations`], an [`crate::core::EvaluationArchive`] answering
+    /// nearest-neighbour queries under the run's distance.
+    #[must_use]
+    pub const fn record_evaluations(mut self) -> Self {
+        self.settings.record_evaluations = true;
+        self
+    }
+
+    /// Chooses which iterations enter the convergence curve, see [`CurveSampling`].
+    #[must_use]
+    pub const fn curve_sampling(mut self, sampling: CurveSampling) -> Self {
+        self.settings.curve_sampling = sampling;
+        self
+    }
+
+    /// Includes the final position and fitness of every hawk in
+    /// [`crate::core::Report::population`], e.g. to seed a follow-up run or compute custom
+    /// statistics.
+    #[must_use]
+    pub const fn keep_population(mut self) -> Self {
+        self.settings.keep_population = true;
+        self
+    }
+
+    /// Caps the memory of the records of the report (the per-iteration series, the improvement
+    /// log, the trajectory, the evaluation archive, the hall of fame and the final population)
+    /// at about `bytes`.
+    ///
+    /// When a record would exceed it, the run scales the records down instead of growing
+    /// without bound: the evaluation archive stops growing, then the trajectory and the
+    /// curves, with the improvement log, keep one point in two, as often as needed, and as a last resort the archive is
+    /// dropped; the final population is only copied if it fits. Each step is noted in
+    /// [`crate::core::Report::degradations`].
+    #[must_use]
+    pub const fn memory_budget(mut self, bytes: usize) -> Self {
+        self.settings.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Records the rabbit's position every `every` iterations (`1` records all of them) into
+    /// [`crate::core::Report::trajectory`], e.g. to animate the search path or spot
+    /// oscillation.
+    #[must_use]
+    pub const fn record_trajectory(mut self, every: usize) -> Self {
+        self.settings.trajectory = Some(every);
+        self
+    }
+
+    /// Records how many clusters the population forms at each recorded iteration, on a grid of
+    /// `cells` cells per dimension, into [`crate::core::Report::clusters`] and
+    /// [`crate::core::Report::occupancy_entropy`] (see [`crate::metrics::Grid`]).
+    ///
+    /// [`crate::core::Report::collapse_iteration`] then tells when the population fell into
+    /// a single cluster. Counting clusters costs O(N²·D) per recorded iteration.
+    #[must_use]
+    pub const fn record_clusters(mut self, cells: usize) -> Self {
+        self.settings.clusters = Some(cells);
+        self
+    }
+
+    /// Selects the update rule used in the exploitation phase, see [`Exploitation`].
+    #[must_use]
+    pub const fn exploitation(mut self, exploitation: Exploitation) -> Self {
+        self.settings.exploitation = exploitation;
+        self
+    }
+
+    /// Rescales the fitness before the adaptive mechanisms weigh improvements with it: the
+    /// credit of [`Exploitation::Adaptive`] and the success history of
+    /// [`Adaptation::SuccessHistory`]. The scaling is fitted to the population at the start
+    /// of each iteration; comparisons, the report and the stopping rules keep the raw values.
+    #[must_use]
+    pub const fn fitness_scaling(mut self, scaling: FitnessScaling) -> Self {
+        self.settings.fitness_scaling = scaling;
+        self
+    }
+
+    /// Enables the orthogonal learning step on the rabbit, see [`OrthogonalLearning`].
+    #[must_use]
+    pub const fn orthogonal_learning(mut self, ol: OrthogonalLearning) -> Self {
+        self.settings.orthogonal_learning = Some(ol);
+        self
+    }
+
+    /// Runs a local refiner around the rabbit every iteration, keeping the trials that
+    /// improve it: a compass sweep with [`LocalRefiner::Pattern`], or a few chaotic trials
+    /// with [`LocalRefiner::Chaotic`], whose radius shrinks linearly with the progress of the
+    /// run. [`LocalRefiner::from_name`] picks one by name.
+    #[must_use]
+    pub const fn local_refiner(mut self, refiner: LocalRefiner) -> Self {
+        self.settings.local_refiner = Some(refiner);
+        self
+    }
+
+    /// Caps the evaluations of the [`HhoBuilder::local_refiner`] to a share of each
+    /// iteration, tapered over the run, instead of its full size every iteration, so the
+    /// refiner does not starve the global search on a tight budget. See
+    /// [`RefinerSchedule`]; [`crate::core::Report::refiner_evals`] counts what it spent.
+    #[must_use]
+    pub const fn refiner_schedule(mut self, schedule: RefinerSchedule) -> Self {
+        self.settings.refiner_schedule = Some(schedule);
+        self
+    }
+
+    /// Re-evaluates the rabbit every `every` iterations (one evaluation each) and keeps the
+    /// running mean of its samples as its fitness. Meant for noisy objectives, where the first
+    /// sample of a point may be lucky and would otherwise block every genuine improvement.
+    #[must_use]
+    pub const fn reevaluate_rabbit(mut self, every: usize) -> Self {
+        self.settings.reevaluate = Some(every);
+        self
+    }
+
+    /// Lets each coordinate of a besiege or dive candidate change with probability `p` only,
+    /// the others keeping the hawk's current value, like the crossover rate of differential
+    /// evolution; one random coordinate always changes. In high dimensions moving every
+    /// coordinate at once tends to undo the good ones a hawk already has, and `p` around
+    /// `10 / dim` keeps them. Exploration moves and the non-classic exploitation rules are
+    /// unaffected.
+    #[must_use]
+    pub const fn update_probability(mut self, p: f64) -> Self {
+        self.settings.update_probability = Some(p);
+        self
+    }
+
+    /// Scales the step of every hawk by its fitness rank, see [`RankSteps`].
+    #[must_use]
+    pub const fn rank_steps(mut self, steps: RankSteps) -> Self {
+        self.settings.rank_steps = Some(steps);
+        self
+    }
+
+    /// Drives `parameter` with `schedule` over the run instead of a fixed value, e.g. a
+    /// momentum fading out or an immigrant fraction growing as the population converges. A
+    /// later schedule of the same parameter replaces the earlier one; values are clamped to
+    /// the range of the parameter. With success-history adaptation the history, not the
+    /// schedule, picks [`Params`].
+    #[must_use]
+    pub fn schedule(mut self, parameter: Parameter, schedule: Schedule) -> Self {
+        self.settings.schedules.push((parameter, schedule));
+        self
+    }
+
+    /// Inertia weight of the per-hawk momentum term: each candidate position is shifted by
+    /// `momentum` times the hawk's previous displacement. `0.0` (the default) is classic HHO.
+    #[must_use]
+    pub const fn momentum(mut self, momentum: f64) -> Self {
+        self.settings.momentum = momentum;
+        self
+    }
+
+    /// Distance used to compare hawks by niching, the duplicate purge and the hall of fame;
+    /// Euclidean by default. [`crate::core::Normalized`] weighs all dimensions equally when
+    /// their ranges differ widely.
+    #[must_use]
+    pub fn distance(mut self, distance: impl Distance + 'static) -> Self {
+        self.settings.distance = Arc::new(distance);
+        self
+    }
+
+    /// Purges duplicate hawks after every iteration, see [`Dedup`].
+    #[must_use]
+    pub const fn dedup(mut self, dedup: Dedup) -> Self {
+        self.settings.dedup = Some(dedup);
+        self
+    }
+
+    /// Replaces the worst hawks with random immigrants after every iteration, see
+    /// [`Immigrants`].
+    #[must_use]
+    pub const fn immigrants(mut self, immigrants: Immigrants) -> Self {
+        self.settings.immigrants = Some(immigrants);
+        self
+    }
+
+    /// Shrinks the search box around the rabbit on stagnation, see [`Zoom`].
+    #[must_use]
+    pub const fn zoom(mut self, zoom: Zoom) -> Self {
+        self.settings.zoom = Some(zoom);
+        self
+    }
+
+    /// Screens greedy moves with the decoder's low-fidelity mode before evaluating them
+    /// exactly, see [`Fidelity`].
+    #[must_use]
+    pub const fn fidelity(mut self, fidelity: Fidelity) -> Self {
+        self.settings.fidelity = Some(fidelity);
+        self
+    }
+
+    /// Replays the displacements of successful dives for other hawks, see [`DiveReplay`].
+    #[must_use]
+    pub const fn dive_replay(mut self, replay: DiveReplay) -> Self {
+        self.settings.dive_replay = Some(replay);
+        self
+    }
+
+    /// Shapes the Lévy steps of the dives by the population spread, see [`DiveShape`].
+    #[must_use]
+    pub const fn dive_shape(mut self, shape: DiveShape) -> Self {
+        self.settings.dive_shape = shape;
+        self
+    }
+
+    /// Evaluates the reflection of every rapid dive candidate about the hawk as well, and
+    /// keeps the better of the two. Costs up to one more evaluation per dive candidate,
+    /// charged to the budget like any other.
+    #[must_use]
+    pub const fn mirror_dives(mut self) -> Self {
+        self.settings.mirror_dives = true;
+        self
+    }
+
+    /// Gives every evaluation of an iteration the same [`crate::core::EvalContext::seed`]
+    /// (common random numbers). The seed depends only on the seed of the run and the
+    /// iteration, so two configurations run with the same seed also see the same random
+    /// numbers at matched iterations, which removes most of the simulation noise from their
+    /// comparison.
+    #[must_use]
+    pub const fn common_random_numbers(mut self) -> Self {
+        self.settings.common_random_numbers = true;
+        self
+    }
+
+    /// What to do when the decoder returns a NaN or infinite fitness, see [`NonFinite`]. The
+    /// default keeps the value.
+    #[must_use]
+    pub const fn non_finite(mut self, policy: NonFinite) -> Self {
+        self.settings.non_finite = policy;
+        self
+    }
+
+    /// Maintains a [`crate::hho::HawkMemory`] during the run (personal best, iterations
+    /// without improvement, age and origin of every hawk), readable from
+    /// [`crate::observer::IterationContext::hawks`] and [`crate::hho::Run::hawks`].
+    #[must_use]
+    pub const fn track_hawks(mut self) -> Self {
+        self.settings.track_hawks = true;
+        self
+    }
+
+    /// Selects how candidate positions replace the current ones, see [`UpdateMode`].
+    #[must_use]
+    pub const fn update_mode(mut self, mode: UpdateMode) -> Self {
+        self.settings.update_mode = mode;
+        self
+    }
+
+    /// Sets the distribution of the jump strength `J`, see [`JumpStrength`].
+    #[must_use]
+    pub const fn jump_strength(mut self, jump: JumpStrength) -> Self {
+        self.settings.jump_strength = jump;
+        self
+    }
+
+    /// Selects the point besieged in the exploitation phase, see [`Attractor`].
+    ///
+    /// Ignored when niching is enabled, since each species then besieges its own seed.
+    #[must_use]
+    pub const fn attractor(mut self, attractor: Attractor) -> Self {
+        self.settings.attractor = attractor;
+        self
+    }
+
+    /// Adds a stopping rule checked after every iteration, on top of the iteration budget.
+    /// Combine several rules with [`crate::termination::Any`] / [`crate::termination::All`].
+    #[must_use]
+    pub fn termination(mut self, criterion: impl Criterion + 'static) -> Self {
+        self.settings.termination = Some(Arc::new(criterion));
+        self
+    }
+
+    /// Registers an observer. Several observers may be registered; they are notified in
+    /// registration order.
+    #[must_use]
+    pub fn observer(self, observer: impl Observer + 'static) -> Self {
+        self.shared_observer(Arc::new(Mutex::new(observer)))
+    }
+
+    /// Registers an observer the caller keeps a handle to, e.g. to read collected data back
+    /// after the run.
+    #[must_use]
+    pub fn shared_observer(mut self, observer: SharedObserver) -> Self {
+        self.settings.observers.0.push(observer);
+        self
+    }
+
+    /// Saves the complete run state to `path` every `every` iterations, so that an
+    /// interrupted run can be continued with [`Hho::resume`].
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn checkpoint(mut self, path: impl Into<PathBuf>, every: usize) -> Self {
+        self.settings.checkpoint = Some(CheckpointPolicy {
+            path: path.into(),
+            every,
+            interval: None,
+            rotate: false,
+        });
+        self
+    }
+
+    /// Saves the run state every `every` iterations and whenever `interval` has passed since
+    /// the previous save, alternating between the two files of
+    /// [`CheckpointPolicy::slots`]. Meant for unattended long runs: the older file is still
+    /// there if the newest is lost. Continue with [`Hho::resume_rolling`] on the same `path`.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn rolling_checkpoint(
+        mut self,
+        path: impl Into<PathBuf>,
+        every: usize,
+        interval: Duration,
+    ) -> Self {
+        self.settings.checkpoint = Some(CheckpointPolicy {
+            path: path.into(),
+            every,
+            interval: Some(interval),
+            rotate: true,
+        });
+        self
+    }
+
+    /// Fixes the master seed. Every random stream of the run (initialization, main loop) is
+    /// derived from it, so two runs with the same seed and settings are bit-for-bit identical.
+    /// Without it a fresh seed is drawn per run; either way the seed is recorded in
+    /// [`crate::core::Report::seed`].
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Names the problem in the [`crate::core::RunMetadata`] of the reports.
+    #[must_use]
+    pub fn problem_name(mut self, name: impl Into<String>) -> Self {
+        self.settings.problem_name = Some(name.into());
+        self
+    }
+
+    /// Adds `key = value` to the tags of the [`crate::core::RunMetadata`] of the reports, e.g.
+    /// the commit of the calling code or the dataset; a later tag replaces one with the same
+    /// key. Tags do not change [`Hho::config_hash`].
+    #[must_use]
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Generator behind every random stream of the run, `ChaCha12` by default. The same seed
+    /// gives different runs with different generators.
+    #[must_use]
+    pub const fn rng(mut self, kind: RngKind) -> Self {
+        self.settings.rng = kind;
+        self
+    }
+
+    /// Lets `token` stop the run from another thread; the run then returns the best solution
+    /// found so far with the stop reason [`crate::core::TerminationReason::Cancelled`].
+    #[must_use]
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.settings.cancellation = Some(token);
+        self
+    }
+
+    /// Registers an observer with mutable access to the population (see
+    /// [`crate::observer::IterationContextMut`] for the invariants it must keep).
+    #[must_use]
+    pub fn mut_observer(mut self, observer: impl MutObserver + 'static) -> Self {
+        self.settings
+            .mut_observers
+            .0
+            .push(Arc::new(Mutex::new(observer)));
+        self
+    }
+
+    /// Caps the latency between observer notifications: each iteration's hawks are updated
+    /// in batches sized, from the measured cost per hawk, to take about `target`, and
+    /// [`Observer::on_batch`] is called after each batch. Most useful with parallel evaluation
+    /// ([`Hho::run_parallel`]) on expensive objectives.
+    #[must_use]
+    pub const fn batch_latency(mut self, target: Duration) -> Self {
+        self.settings.batch_latency = Some(target);
+        self
+    }
+
+    /// Makes [`Hho::run_parallel`] reproducible bit for bit whatever the number of threads and
+    /// their scheduling. Hawks are already updated from their own random streams and merged in
+    /// index order; in this mode each hawk of a sweep also gets a fixed share of the remaining
+    /// evaluation budget instead of racing the others for it, so which evaluations happen when
+    /// the budget runs out mid-sweep no longer depends on which thread got there first.
+    ///
+    /// Only an evaluation budget is affected: runs stopped by wall-clock time or cancellation
+    /// stay timing-dependent, and [`HhoBuilder::batch_latency`], which sizes batches from
+    /// measured times, is rejected.
+    #[must_use]
+    pub const fn deterministic_parallel(mut self) -> Self {
+        self.settings.deterministic_parallel = true;
+        self
+    }
+
+    /// Shows a progress bar with the current best and an ETA on standard error, see
+    /// [`ProgressBar`].
+    #[must_use]
+    pub fn progress_bar(self) -> Self {
+        self.observer(ProgressBar::stderr())
+    }
+
+    /// Registers a closure called after every iteration; returning [`ControlFlow::Break`]
+    /// stops the run (reason [`crate::core::TerminationReason::EarlyStop`]). Coexists with
+    /// the termination criteria.
+    #[must_use]
+    pub fn early_stop<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&IterationContext<'_>) -> ControlFlow<()> + Send + 'static,
+    {
+        self.settings.early_stop = Some(EarlyStop(Arc::new(Mutex::new(f))));
+        self
+    }
+
+    /// Builds the optimizer without checking the settings; they are checked when a run
+    /// starts. See [`HhoBuilder::try_build`] to catch mistakes up front.
+    #[must_use]
+    pub fn build(self) -> Hho<O, I> {
+        Hho {
+            bounds: self.bounds,
+            initializer: self.initializer,
+            settings: self.settings,
+            objective: PhantomData,
+        }
+    }
+
+    /// Builds the optimizer after checking every setting against the others.
+    ///
+    /// # Errors
+    ///
+    /// A [`ConfigError`] listing all the problems found, see [`Hho::check`].
+    pub fn try_build(self) -> Result<Hho<O, I>, ConfigError> {
+        let hho = self.build();
+        hho.check()?;
+        Ok(hho)
+    }
+}
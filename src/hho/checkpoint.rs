@@ -0,0 +1,138 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    core::StreamRng,
+    hho::{SuccessHistory, optimizer::State},
+};
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Checkpoint I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Invalid checkpoint file: {0}")]
+    Format(#[from] serde_json::Error),
+
+    #[error("Checkpoint does not match the optimizer: dim={dim}, pop_size={pop_size}")]
+    Mismatch { dim: usize, pop_size: usize },
+}
+
+/// Where and how often the run state is saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointPolicy {
+    pub path: PathBuf,
+    /// Iterações entre duas gravações (ao menos 1)
+    pub every: usize,
+    /// Also saves once this much wall-clock time has passed since the previous save.
+    pub interval: Option<Duration>,
+    /// Saves alternately to the two [`CheckpointPolicy::slots`] instead of `path`, so the
+    /// previous checkpoint survives whatever happens to the newest one.
+    pub rotate: bool,
+}
+
+impl CheckpointPolicy {
+    /// Files a rotating policy writes in turn: `path` with `.0` and `.1` appended.
+    #[must_use]
+    pub fn slots(&self) -> [PathBuf; 2] {
+        slots(&self.path)
+    }
+
+    /// Se uma gravação é devida na iteração `iter`, `since` depois da anterior
+    pub(crate) fn due(&self, iter: usize, since: Duration) -> bool {
+        iter.is_multiple_of(self.every.max(1)) || self.interval.is_some_and(|t| since >= t)
+    }
+
+    /// Arquivo da `k`-ésima gravação
+    pub(crate) fn target(&self, k: usize) -> PathBuf {
+        if self.rotate {
+            let [a, b] = self.slots();
+            if k.is_multiple_of(2) { a } else { b }
+        } else {
+            self.path.clone()
+        }
+    }
+}
+
+/// Os dois arquivos de um checkpoint rotativo em `path`
+fn slots(path: &Path) -> [PathBuf; 2] {
+    ["0", "1"].map(|k| {
+        let mut name = OsString::from(path.as_os_str());
+        name.push(".");
+        name.push(k);
+        PathBuf::from(name)
+    })
+}
+
+/// Complete state of a run: population, rabbit, counters, adaptation memory and RNG.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Checkpoint {
+    pub dim: usize,
+    pub pop_size: usize,
+    pub state: State,
+    pub history: Option<SuccessHistory>,
+    pub rng: StreamRng,
+}
+
+/// Mesmo layout de [`Checkpoint`], mas emprestado, para gravar sem clonar o estado
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    dim: usize,
+    pop_size: usize,
+    state: &'a State,
+    history: Option<&'a SuccessHistory>,
+    rng: &'a StreamRng,
+}
+
+impl Checkpoint {
+    /// Writes the run state to `path` through a temporary file, so a run killed mid-write
+    /// never leaves a truncated checkpoint behind.
+    pub fn save(
+        path: &Path,
+        state: &State,
+        history: Option<&SuccessHistory>,
+        rng: &StreamRng,
+    ) -> Result<(), CheckpointError> {
+        let snapshot = Snapshot {
+            dim: state.rabbit.len(),
+            pop_size: state.positions.len(),
+            state,
+            history,
+            rng,
+        };
+        // `.tmp` vai depois do nome inteiro, sem tocar num `<stem>.tmp` vizinho; o conteúdo
+        // chega ao disco antes do rename, que só então publica o arquivo
+        let mut tmp = OsString::from(path.as_os_str());
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(&snapshot)?)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Loads the most advanced readable slot of the rotating checkpoint at `path`, or the
+    /// error of the first slot if neither can be read.
+    pub fn load_latest(path: &Path) -> Result<Self, CheckpointError> {
+        let [a, b] = slots(path);
+        match (Self::load(&a), Self::load(&b)) {
+            (Ok(a), Ok(b)) => Ok(if b.state.iter > a.state.iter { b } else { a }),
+            (Ok(c), Err(_)) | (Err(_), Ok(c)) => Ok(c),
+            (Err(e), Err(_)) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,676 @@
+//! Declarative optimizer setup, (de)serialized with serde.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    mem::size_of,
+    ops::RangeInclusive,
+    path::Path,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{Bounds, CurveSampling, FitnessScaling, Objective, Policy, RngKind},
+    experiments::{Problem, ProblemRegistry, RegistryError},
+    hho::{
+        Adaptation, Attractor, Dedup, DiveReplay, DiveShape, Exploitation, Fidelity, Hho,
+        HhoBuilder, HhoError, Immigrants, JumpStrength, Niching, NonFinite, OrthogonalLearning,
+        Parameter, Params, PartnerSelection, PhaseCounts, RankSteps, Schedule, UpdateMode, Zoom,
+    },
+    local_search::{LocalRefiner, RefinerSchedule},
+    termination::{Any, MaxEvals, MaxIters, MaxStall, MaxTime, TargetFitness},
+};
+
+/// Complete setup of an [`Hho`], the declarative counterpart of [`HhoBuilder`].
+///
+/// Only `bounds` is mandatory; every omitted field takes the builder default. Observers,
+/// cancellation, checkpoints and dynamic-objective support need code and are left to the
+/// builder, see [`Config::builder`].
+///
+/// ```json
+/// {
+///   "bounds": { "lower": -5.0, "upper": 5.0, "dim": 10 },
+///   "pop_size": 40,
+///   "params": { "energy_decay": 1.5, "dive_scale": 1.0, "levy_beta": 1.5 },
+///   "termination": [{ "max_stall": 50 }, { "max_seconds": 2.5 }],
+///   "reporting": { "progress_bar": true }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub bounds: BoundsConfig,
+    /// Problem to optimize as a spec such as `"rastrigin(dim=30)"`, see [`Config::problem`].
+    #[serde(default)]
+    pub problem: Option<String>,
+    #[serde(default)]
+    pub initializer: InitializerConfig,
+    #[serde(default = "default_pop_size")]
+    pub pop_size: usize,
+    #[serde(default = "default_max_iters")]
+    pub max_iters: usize,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// See [`HhoBuilder::tag`].
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub rng: RngKind,
+    #[serde(default)]
+    pub params: Params,
+    #[serde(default)]
+    pub adaptation: Adaptation,
+    #[serde(default)]
+    pub partner_selection: PartnerSelection,
+    #[serde(default)]
+    pub niching: Niching,
+    #[serde(default)]
+    pub exploitation: Exploitation,
+    #[serde(default)]
+    pub fitness_scaling: FitnessScaling,
+    #[serde(default)]
+    pub orthogonal_learning: Option<OrthogonalLearning>,
+    /// See [`HhoBuilder::local_refiner`].
+    #[serde(default)]
+    pub local_refiner: Option<LocalRefiner>,
+    /// See [`HhoBuilder::refiner_schedule`].
+    #[serde(default)]
+    pub refiner_schedule: Option<RefinerSchedule>,
+    /// See [`HhoBuilder::reevaluate_rabbit`].
+    #[serde(default)]
+    pub reevaluate_rabbit: Option<usize>,
+    #[serde(default)]
+    pub dedup: Option<Dedup>,
+    #[serde(default)]
+    pub immigrants: Option<Immigrants>,
+    #[serde(default)]
+    pub zoom: Option<Zoom>,
+    #[serde(default)]
+    pub fidelity: Option<Fidelity>,
+    #[serde(default)]
+    pub dive_replay: Option<DiveReplay>,
+    #[serde(default)]
+    pub dive_shape: DiveShape,
+    /// See [`HhoBuilder::mirror_dives`].
+    #[serde(default)]
+    pub mirror_dives: bool,
+    /// See [`HhoBuilder::common_random_numbers`].
+    #[serde(default)]
+    pub common_random_numbers: bool,
+    /// See [`HhoBuilder::deterministic_parallel`].
+    #[serde(default)]
+    pub deterministic_parallel: bool,
+    /// See [`HhoBuilder::non_finite`].
+    #[serde(default)]
+    pub non_finite: NonFinite,
+    #[serde(default)]
+    pub momentum: f64,
+    /// See [`HhoBuilder::update_probability`].
+    #[serde(default)]
+    pub update_probability: Option<f64>,
+    #[serde(default)]
+    pub rank_steps: Option<RankSteps>,
+    /// See [`HhoBuilder::schedule`]; closures cannot be written here.
+    #[serde(default)]
+    pub schedules: Vec<(Parameter, Schedule)>,
+    #[serde(default)]
+    pub update_mode: UpdateMode,
+    #[serde(default)]
+    pub jump_strength: JumpStrength,
+    #[serde(default)]
+    pub attractor: Attractor,
+    /// Stopping rules on top of `max_iters`; the run stops as soon as any of them fires.
+    #[serde(default)]
+    pub termination: Vec<Termination>,
+    #[serde(default)]
+    pub reporting: Reporting,
+}
+
+const fn default_pop_size() -> usize {
+    30
+}
+
+const fn default_max_iters() -> usize {
+    500
+}
+
+/// The search box. `lower` and `upper` are each a number or one number per dimension; with
+/// two numbers `dim` is required.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoundsConfig {
+    pub lower: Limit,
+    pub upper: Limit,
+    #[serde(default)]
+    pub dim: Option<usize>,
+    #[serde(default)]
+    pub policy: Policy,
+}
+
+/// A limit shared by every dimension or given per dimension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Limit {
+    Uniform(f64),
+    PerDim(Vec<f64>),
+}
+
+impl BoundsConfig {
+    /// Builds the [`Bounds`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if the dimension is missing or inconsistent, and
+    /// [`HhoError::Bounds`] if the limits are invalid.
+    pub fn bounds(&self) -> Result<Bounds, HhoError> {
+        let len = |l: &Limit| match l {
+            Limit::Uniform(_) => None,
+            Limit::PerDim(v) => Some(v.len()),
+        };
+        let dims = [len(&self.lower), len(&self.upper), self.dim];
+        let dim =
+            dims.iter().flatten().next().copied().ok_or_else(|| {
+                HhoError::Config("bounds need `dim` or per-dimension limits".into())
+            })?;
+        if dims.iter().flatten().any(|&d| d != dim) {
+            return Err(HhoError::Config(format!(
+                "bounds have inconsistent dimensions {dims:?}"
+            )));
+        }
+        let bounds = match (&self.lower, &self.upper) {
+            (Limit::Uniform(lo), Limit::Uniform(hi)) => Bounds::uniform(*lo, *hi, dim)?,
+            (lo, hi) => {
+                let expand = |l: &Limit| match l {
+                    Limit::Uniform(v) => vec![*v; dim],
+                    Limit::PerDim(v) => v.clone(),
+                };
+                Bounds::per_dim(expand(lo), expand(hi))?
+            }
+        };
+        Ok(bounds.with_policy(self.policy))
+    }
+}
+
+/// Strategy used to create the initial population.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InitializerConfig {
+    /// [`crate::init::RandomInitializer`].
+    #[default]
+    Random,
+}
+
+/// A stopping rule, see [`crate::termination`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Termination {
+    MaxIters(usize),
+    MaxEvals(usize),
+    TargetFitness {
+        target: f64,
+        tolerance: f64,
+    },
+    MaxStall(usize),
+    /// Wall-clock limit in seconds.
+    MaxSeconds(f64),
+}
+
+/// What the run prints and records in the [`crate::core::Report`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Reporting {
+    /// See [`HhoBuilder::progress_bar`].
+    pub progress_bar: bool,
+    /// See [`HhoBuilder::keep_population`].
+    pub keep_population: bool,
+    /// See [`HhoBuilder::record_trajectory`].
+    pub trajectory: Option<usize>,
+    /// Cells per dimension, see [`HhoBuilder::record_clusters`].
+    pub clusters: Option<usize>,
+    /// `(capacity, min_distance)`, see [`HhoBuilder::hall_of_fame`].
+    pub hall_of_fame: Option<(usize, f64)>,
+    /// See [`HhoBuilder::record_evaluations`].
+    pub evaluations: bool,
+    pub curve_sampling: CurveSampling,
+    /// Bytes, see [`HhoBuilder::memory_budget`].
+    pub memory_budget: Option<usize>,
+}
+
+impl Config {
+    /// Reads a JSON configuration from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid configuration.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Writes the configuration as pretty-printed JSON to `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        out.flush()
+    }
+
+    /// Problem named by [`Config::problem`](Config#structfield.problem) in `registry`, or
+    /// `None` if the configuration names none. The run still searches `bounds`.
+    ///
+    /// # Errors
+    ///
+    /// See [`ProblemRegistry::create`].
+    pub fn problem(&self, registry: &ProblemRegistry) -> Result<Option<Problem>, RegistryError> {
+        self.problem
+            .as_deref()
+            .map(|spec| registry.create(spec))
+            .transpose()
+    }
+
+    /// Builder holding this configuration, to add what only code can express (observers,
+    /// cancellation, ...) before building.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds or a stopping rule are invalid.
+    pub fn builder(&self) -> Result<HhoBuilder, HhoError> {
+        let mut builder = HhoBuilder::new(self.bounds.bounds()?)
+            .pop_size(self.pop_size)
+            .max_iters(self.max_iters)
+            .rng(self.rng)
+            .params(self.params)
+            .adaptation(self.adaptation)
+            .partner_selection(self.partner_selection)
+            .niching(self.niching)
+            .exploitation(self.exploitation)
+            .fitness_scaling(self.fitness_scaling)
+            .momentum(self.momentum)
+            .update_mode(self.update_mode)
+            .jump_strength(self.jump_strength)
+            .attractor(self.attractor)
+            .curve_sampling(self.reporting.curve_sampling);
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(problem) = &self.problem {
+            builder = builder.problem_name(problem.as_str());
+        }
+        for (key, value) in &self.tags {
+            builder = builder.tag(key.as_str(), value.as_str());
+        }
+        if let Some(ol) = self.orthogonal_learning {
+            builder = builder.orthogonal_learning(ol);
+        }
+        if let Some(p) = self.update_probability {
+            builder = builder.update_probability(p);
+        }
+        if let Some(steps) = self.rank_steps {
+            builder = builder.rank_steps(steps);
+        }
+        if let Some(refiner) = self.local_refiner {
+            builder = builder.local_refiner(refiner);
+        }
+        if let Some(schedule) = self.refiner_schedule {
+            builder = builder.refiner_schedule(schedule);
+        }
+        if let Some(every) = self.reevaluate_rabbit {
+            builder = builder.reevaluate_rabbit(every);
+        }
+        if let Some(dedup) = self.dedup {
+            builder = builder.dedup(dedup);
+        }
+        if let Some(immigrants) = self.immigrants {
+            builder = builder.immigrants(immigrants);
+        }
+        if let Some(zoom) = self.zoom {
+            builder = builder.zoom(zoom);
+        }
+        if let Some(fidelity) = self.fidelity {
+            builder = builder.fidelity(fidelity);
+        }
+        if let Some(replay) = self.dive_replay {
+            builder = builder.dive_replay(replay);
+        }
+        builder = builder.dive_shape(self.dive_shape);
+        if self.mirror_dives {
+            builder = builder.mirror_dives();
+        }
+        if self.common_random_numbers {
+            builder = builder.common_random_numbers();
+        }
+        if self.deterministic_parallel {
+            builder = builder.deterministic_parallel();
+        }
+        builder = builder.non_finite(self.non_finite);
+        for (parameter, schedule) in &self.schedules {
+            builder = builder.schedule(*parameter, schedule.clone());
+        }
+        if let Some(any) = self.stopping_rule()? {
+            builder = builder.termination(any);
+        }
+        let reporting = &self.reporting;
+        if reporting.progress_bar {
+            builder = builder.progress_bar();
+        }
+        if reporting.keep_population {
+            builder = builder.keep_population();
+        }
+        if let Some(every) = reporting.trajectory {
+            builder = builder.record_trajectory(every);
+        }
+        if let Some(cells) = reporting.clusters {
+            builder = builder.record_clusters(cells);
+        }
+        if let Some((capacity, min_distance)) = reporting.hall_of_fame {
+            builder = builder.hall_of_fame(capacity, min_distance);
+        }
+        if reporting.evaluations {
+            builder = builder.record_evaluations();
+        }
+        if let Some(bytes) = reporting.memory_budget {
+            builder = builder.memory_budget(bytes);
+        }
+        Ok(builder)
+    }
+
+    /// Regras de parada combinadas, ou `None` se a lista for vazia
+    fn stopping_rule(&self) -> Result<Option<Any>, HhoError> {
+        if self.termination.is_empty() {
+            return Ok(None);
+        }
+        let mut any = Any::new();
+        for rule in &self.termination {
+            any = match *rule {
+                Termination::MaxIters(n) => any.or(MaxIters(n)),
+                Termination::MaxEvals(n) => any.or(MaxEvals(n)),
+                Termination::TargetFitness { target, tolerance } => {
+                    any.or(TargetFitness { target, tolerance })
+                }
+                Termination::MaxStall(n) => any.or(MaxStall(n)),
+                Termination::MaxSeconds(s) => {
+                    let limit = Duration::try_from_secs_f64(s)
+                        .map_err(|e| HhoError::Config(format!("invalid time limit {s}: {e}")))?;
+                    any.or(MaxTime(limit))
+                }
+            };
+        }
+        Ok(Some(any))
+    }
+
+    /// Iterations the run goes through unless a target, stall or time rule stops it first.
+    fn iteration_limit(&self) -> usize {
+        self.termination
+            .iter()
+            .filter_map(|rule| match *rule {
+                Termination::MaxIters(n) => Some(n),
+                _ => None,
+            })
+            .fold(self.max_iters, usize::min)
+    }
+
+    /// Range of decoder calls the configured run makes, computed without running it.
+    ///
+    /// Counts the initial population, one or two moves per hawk and iteration (a failed
+    /// soft or hard dive tries a second point; mirrored dives double both), the evaluations of each move under
+    /// [`UpdateMode::BlockGreedy`], orthogonal learning, the local refiner, rabbit re-evaluations, random
+    /// immigrants and, for the upper end, a duplicate purge re-evaluating all but one hawk
+    /// every iteration. With [`Fidelity`] screening the moves are counted in full-fidelity
+    /// equivalents, from every move screened out to every move screened and then evaluated.
+    /// Both ends assume the run reaches its iteration limit and are capped by a `max_evals`
+    /// rule; a target, stall or time rule may stop it sooner.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds are invalid.
+    pub fn estimated_evals(&self) -> Result<RangeInclusive<usize>, HhoError> {
+        let dim = self.bounds.bounds()?.dim();
+        let (pop, iters) = (self.pop_size, self.iteration_limit());
+        let per_move = match self.update_mode {
+            UpdateMode::FullVector => 1..=1,
+            UpdateMode::BlockGreedy { block_size } => 1..=dim.div_ceil(block_size.max(1)),
+        };
+        // Um mergulho espelhado avalia até dois pontos por candidato
+        let dives = if self.mirror_dives { 4 } else { 2 };
+        let moves = match self.exploitation {
+            Exploitation::Classic | Exploitation::Adaptive { .. } => 1..=dives,
+            Exploitation::Quantum { .. }
+            | Exploitation::Differential { .. }
+            | Exploitation::SineCosine { .. } => 1..=1,
+        };
+        let ol = self.orthogonal_learning.map_or(0, |ol| {
+            let runs = iters.checked_div(ol.every).unwrap_or(0);
+            runs * ((ol.factors + 1).next_power_of_two() + 1)
+        });
+        let ol = ol.saturating_add(
+            self.reevaluate_rabbit
+                .map_or(0, |every| iters.checked_div(every).unwrap_or(0)),
+        );
+        // Uma varredura da busca de padrões pula os passos cortados pelos limites, e um
+        // cronograma pode não liberar nenhuma avaliação
+        let refine = self.local_refiner.map_or((0, 0), |refiner| {
+            let most = iters.saturating_mul(refiner.evals_per_iter(dim));
+            match refiner {
+                LocalRefiner::Chaotic(_) if self.refiner_schedule.is_none() => (most, most),
+                _ => (0, most),
+            }
+        });
+        let dedup = if self.dedup.is_some() {
+            pop.saturating_sub(1)
+        } else {
+            0
+        };
+        let (screen_lo, screen_hi) = self.fidelity.map_or((1.0, 1.0), |f| (f.cost, 1.0 + f.cost));
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let total = |per_hawk: usize, screen: f64, extra: usize, refine: usize| {
+            let sweeps = iters.saturating_mul(pop.saturating_mul(per_hawk)) as f64 * screen;
+            let sweeps = if screen < 1.0 {
+                sweeps.floor()
+            } else {
+                sweeps.ceil()
+            };
+            pop.saturating_add(sweeps as usize)
+                .saturating_add(iters.saturating_mul(extra))
+                .saturating_add(ol)
+                .saturating_add(refine)
+        };
+        let immigrants = self.immigrants.map_or(0, |im| im.count(pop));
+        let lower = total(
+            moves.start() * per_move.start(),
+            screen_lo,
+            immigrants,
+            refine.0,
+        );
+        let upper = total(
+            moves.end() * per_move.end(),
+            screen_hi,
+            dedup + immigrants,
+            refine.1,
+        );
+        let cap = self
+            .termination
+            .iter()
+            .filter_map(|rule| match *rule {
+                Termination::MaxEvals(n) => Some(n),
+                _ => None,
+            })
+            .fold(usize::MAX, usize::min);
+        Ok(lower.min(cap)..=upper.min(cap))
+    }
+
+    /// Approximate peak memory of the configured run in bytes: the population and its working
+    /// copies, plus everything the [`Reporting`] settings keep in the report (per-iteration
+    /// series, trajectory, population snapshot, hall of fame and, at the upper end of
+    /// [`Config::estimated_evals`], the evaluation archive). Decoder memory is not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds are invalid.
+    pub fn estimated_memory(&self) -> Result<usize, HhoError> {
+        const F64: usize = size_of::<f64>();
+        const VEC: usize = size_of::<Vec<f64>>();
+        let dim = self.bounds.bounds()?.dim();
+        let (pop, iters) = (self.pop_size, self.iteration_limit());
+        let point = dim * F64;
+        let solution = point + F64 + VEC;
+
+        // Posições, fitness, retrato da varredura paralela e, se ligados, velocidades e
+        // sementes das espécies
+        let mut copies = 2;
+        if self.momentum != 0.0 {
+            copies += 1;
+        }
+        if self.niching != Niching::Off {
+            copies += 1;
+        }
+        // Falcão corrente, média, elite, coelho e os buffers de candidatos
+        let mut state = copies * pop * point + 3 * pop * F64 + 9 * point;
+        if let Some(replay) = self.dive_replay {
+            state += replay.capacity * point;
+        }
+        state += match self.dive_shape {
+            DiveShape::Isotropic => 0,
+            DiveShape::Diagonal => point,
+            DiveShape::Covariance => dim * point,
+        };
+
+        let reporting = &self.reporting;
+        let points = match reporting.curve_sampling {
+            CurveSampling::Reservoir { size } => size.min(iters),
+            sampling => (1..=iters).filter(|&t| sampling.records(t)).count(),
+        };
+        // Curva, iterações, avaliações, diversidade, energia, fases e, se ligados, agrupamentos
+        let mut series = points * (3 * F64 + 2 * size_of::<usize>() + size_of::<PhaseCounts>());
+        if reporting.clusters.is_some() {
+            series += points * (F64 + size_of::<usize>());
+        }
+        let trajectory = reporting
+            .trajectory
+            .map_or(0, |every| iters / every.max(1) * (point + VEC));
+        let population = if reporting.keep_population {
+            pop * solution
+        } else {
+            0
+        };
+        let hall_of_fame = reporting
+            .hall_of_fame
+            .map_or(0, |(capacity, _)| capacity * solution);
+        // Cada avaliação guardada mais o seu nó na árvore de vizinhança
+        let evaluations = if reporting.evaluations {
+            self.estimated_evals()?.end() * (solution + 4 * F64)
+        } else {
+            0
+        };
+        Ok(state + series + trajectory + population + hall_of_fame + evaluations)
+    }
+}
+
+impl<O: Objective> Hho<O> {
+    /// Optimizer described by `config`, see [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds or a stopping rule are invalid.
+    pub fn from_config(config: &Config) -> Result<Self, HhoError> {
+        Ok(config.builder()?.objective::<O>().build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        benchmarks::Function::Sphere,
+        core::{Minimization, TerminationReason},
+    };
+
+    #[test]
+    fn builds_the_optimizer_from_json() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "bounds": { "lower": [-5.0, -1.0, -1.0], "upper": 5.0 },
+                "pop_size": 12,
+                "seed": 7,
+                "jump_strength": { "Constant": 1.0 },
+                "termination": [{ "max_evals": 120 }],
+                "reporting": { "keep_population": true }
+            }"#,
+        )
+        .unwrap();
+        let bounds = config.bounds.bounds().unwrap();
+        assert_eq!(bounds.dim(), 3);
+        assert!((bounds.hi_at(1) - 5.0).abs() < 1e-12);
+
+        let hho = Hho::<Minimization>::from_config(&config).unwrap();
+        let report = hho.run(&Sphere).unwrap();
+        assert_eq!(report.stop_reason, Some(TerminationReason::MaxEvals));
+        assert_eq!(report.population.len(), 12);
+        assert_eq!(report.seed, Some(7));
+
+        // Ida e volta pelo JSON
+        let json = serde_json::to_string(&config).unwrap();
+        let again: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(again.termination, config.termination);
+        assert_eq!(again.bounds, config.bounds);
+
+        let registry = ProblemRegistry::new();
+        assert!(config.problem(&registry).unwrap().is_none());
+        let named: Config = serde_json::from_str(
+            r#"{ "bounds": { "lower": -5.0, "upper": 5.0, "dim": 3 }, "problem": "griewank(dim=3)" }"#,
+        )
+        .unwrap();
+        let problem = named.problem(&registry).unwrap().unwrap();
+        assert_eq!(problem.name, "griewank(dim=3)");
+        let hho = Hho::<Minimization>::from_config(&named).unwrap();
+        assert!(hho.run(problem.decoder.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn estimates_the_cost_before_running() {
+        let mut config: Config = serde_json::from_str(
+            r#"{
+                "bounds": { "lower": -1.0, "upper": 1.0, "dim": 4 },
+                "pop_size": 10,
+                "max_iters": 50,
+                "seed": 3
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.estimated_evals().unwrap(), 510..=1010);
+
+        let hho = Hho::<Minimization>::from_config(&config).unwrap();
+        let report = hho.run(&Sphere).unwrap();
+        assert!(config.estimated_evals().unwrap().contains(&report.evals));
+
+        config.update_mode = UpdateMode::BlockGreedy { block_size: 2 };
+        config.termination = vec![Termination::MaxEvals(1500)];
+        assert_eq!(config.estimated_evals().unwrap(), 510..=1500);
+
+        let light = config.estimated_memory().unwrap();
+        config.reporting.keep_population = true;
+        config.reporting.trajectory = Some(1);
+        assert!(config.estimated_memory().unwrap() > light + 50 * 4 * 8);
+    }
+
+    #[test]
+    fn rejects_incomplete_bounds() {
+        let config: Config =
+            serde_json::from_str(r#"{ "bounds": { "lower": 0.0, "upper": 1.0 } }"#).unwrap();
+        assert!(matches!(config.builder(), Err(HhoError::Config(_))));
+        let config: Config = serde_json::from_str(
+            r#"{ "bounds": { "lower": [0.0, 0.0], "upper": [1.0], "dim": 2 } }"#,
+        )
+        .unwrap();
+        assert!(matches!(config.builder(), Err(HhoError::Config(_))));
+        assert!(serde_json::from_str::<Config>(r#"{ "bounds": {}, "typo": 1 }"#).is_err());
+    }
+}
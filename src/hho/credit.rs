@@ -0,0 +1,285 @@
+use rand::Rng;
+
+use crate::hho::Phase;
+
+/// How often one update operator improved the hawk it moved, and by how much.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorRecord {
+    /// Hawk updates made with the operator.
+    pub uses: usize,
+    /// Updates that left the hawk strictly better than before.
+    pub improvements: usize,
+    /// Sum of the fitness improvements, in absolute value.
+    pub gain: f64,
+}
+
+impl OperatorRecord {
+    const ZERO: Self = Self {
+        uses: 0,
+        improvements: 0,
+        gain: 0.0,
+    };
+
+    /// Fraction of the uses that improved the hawk, `0` if the operator was never used.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn success_rate(&self) -> f64 {
+        if self.uses == 0 {
+            0.0
+        } else {
+            self.improvements as f64 / self.uses as f64
+        }
+    }
+
+    /// Mean improvement per use (failed uses count as zero).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_gain(&self) -> f64 {
+        if self.uses == 0 {
+            0.0
+        } else {
+            self.gain / self.uses as f64
+        }
+    }
+}
+
+/// Success statistics of every update operator over a run, one [`OperatorRecord`] per
+/// [`Phase`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorStats {
+    records: [OperatorRecord; Phase::ALL.len()],
+}
+
+impl OperatorStats {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            records: [OperatorRecord::ZERO; Phase::ALL.len()],
+        }
+    }
+
+    #[must_use]
+    pub const fn get(&self, phase: Phase) -> &OperatorRecord {
+        &self.records[phase.index()]
+    }
+
+    /// Every operator with its record, in [`Phase::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Phase, &OperatorRecord)> {
+        Phase::ALL.into_iter().zip(&self.records)
+    }
+
+    /// Soma os registros de `other` aos deste
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (r, o) in self.records.iter_mut().zip(&other.records) {
+            r.uses += o.uses;
+            r.improvements += o.improvements;
+            r.gain += o.gain;
+        }
+    }
+
+    /// Registra um uso de `phase`, que melhorou o falcão em `gain` (se melhorou)
+    pub(crate) fn record(&mut self, phase: Phase, gain: Option<f64>) {
+        let r = &mut self.records[phase.index()];
+        r.uses += 1;
+        if let Some(g) = gain {
+            r.improvements += 1;
+            r.gain += g;
+        }
+    }
+}
+
+/// Rule turning the rewards collected by a set of operators into the choice of the next one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Selection {
+    /// Probability matching: the quality of an operator is an exponential moving average of
+    /// its rewards with rate `alpha`, and each one is drawn with probability
+    /// `p_min + (1 - K·p_min)·qᵢ / Σq` among `K` operators, so none is ever starved.
+    ProbabilityMatching { p_min: f64, alpha: f64 },
+
+    /// UCB1: picks the operator maximizing its mean reward plus `c·√(ln N / nᵢ)`, trying
+    /// every operator once first.
+    Ucb { c: f64 },
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self::ProbabilityMatching {
+            p_min: 0.05,
+            alpha: 0.3,
+        }
+    }
+}
+
+/// Credit assignment over a fixed set of operators: collects the reward of each use and
+/// chooses the next operator with a [`Selection`] rule.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Credit {
+    selection: Selection,
+    /// Qualidade de cada operador (média móvel ou média simples, conforme a regra)
+    quality: Vec<f64>,
+    uses: Vec<usize>,
+}
+
+impl Credit {
+    /// Credit for `operators` operators (at least one), all starting with quality `0`.
+    #[must_use]
+    pub fn new(operators: usize, selection: Selection) -> Self {
+        let n = operators.max(1);
+        Self {
+            selection,
+            quality: vec![0.0; n],
+            uses: vec![0; n],
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.quality.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.quality.is_empty()
+    }
+
+    /// Current quality estimate of operator `k`.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is out of range.
+    #[must_use]
+    pub fn quality(&self, k: usize) -> f64 {
+        self.quality[k]
+    }
+
+    /// Times operator `k` was rewarded.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is out of range.
+    #[must_use]
+    pub fn uses(&self, k: usize) -> usize {
+        self.uses[k]
+    }
+
+    /// Selection probability of each operator; under [`Selection::Ucb`] all the mass is on
+    /// the operator [`Credit::select`] would pick.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn probabilities(&self) -> Vec<f64> {
+        let n = self.len();
+        match self.selection {
+            Selection::ProbabilityMatching { p_min, .. } => {
+                let total: f64 = self.quality.iter().sum();
+                if total > 0.0 {
+                    let share = p_min.mul_add(-(n as f64), 1.0);
+                    self.quality
+                        .iter()
+                        .map(|q| share.mul_add(q / total, p_min))
+                        .collect()
+                } else {
+                    vec![1.0 / n as f64; n]
+                }
+            }
+            Selection::Ucb { c } => {
+                let best = self.ucb_best(c);
+                (0..n).map(|k| if k == best { 1.0 } else { 0.0 }).collect()
+            }
+        }
+    }
+
+    /// Draws the next operator.
+    pub fn select<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        match self.selection {
+            Selection::Ucb { c } => self.ucb_best(c),
+            Selection::ProbabilityMatching { .. } => {
+                let p = self.probabilities();
+                let mut u: f64 = rng.random();
+                for (k, &pk) in p.iter().enumerate() {
+                    if u < pk {
+                        return k;
+                    }
+                    u -= pk;
+                }
+                p.len() - 1
+            }
+        }
+    }
+
+    /// Credits operator `k` with `reward` (non-negative; non-finite rewards count as `0`).
+    ///
+    /// # Panics
+    ///
+    /// If `k` is out of range.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn reward(&mut self, k: usize, reward: f64) {
+        let r = if reward.is_finite() {
+            reward.max(0.0)
+        } else {
+            0.0
+        };
+        self.uses[k] += 1;
+        let rate = match self.selection {
+            Selection::ProbabilityMatching { alpha, .. } => alpha,
+            Selection::Ucb { .. } => 1.0 / self.uses[k] as f64,
+        };
+        self.quality[k] += rate * (r - self.quality[k]);
+    }
+
+    /// Operador de maior índice UCB1; os ainda não usados vêm primeiro
+    #[allow(clippy::cast_precision_loss)]
+    fn ucb_best(&self, c: f64) -> usize {
+        if let Some(k) = self.uses.iter().position(|&n| n == 0) {
+            return k;
+        }
+        let total = self.uses.iter().sum::<usize>() as f64;
+        let score =
+            |k: usize| c.mul_add((total.ln() / self.uses[k] as f64).sqrt(), self.quality[k]);
+        (0..self.len())
+            .max_by(|&a, &b| score(a).total_cmp(&score(b)))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+
+    #[test]
+    fn rewarded_operators_are_chosen_more_often() {
+        let mut stats = OperatorStats::new();
+        stats.record(Phase::SoftBesiege, Some(2.0));
+        stats.record(Phase::SoftBesiege, None);
+        let soft = stats.get(Phase::SoftBesiege);
+        assert!((soft.success_rate() - 0.5).abs() < 1e-12);
+        assert!((soft.mean_gain() - 1.0).abs() < 1e-12);
+        assert_eq!(stats.get(Phase::Quantum).uses, 0);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        let mut pm = Credit::new(3, Selection::default());
+        for _ in 0..20 {
+            pm.reward(1, 1.0);
+            pm.reward(2, 0.1);
+        }
+        let p = pm.probabilities();
+        assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((p[0] - 0.05).abs() < 1e-12 && p[1] > 0.8);
+        let picks = (0..1000).filter(|_| pm.select(&mut rng) == 1).count();
+        assert!(picks > 800, "picks = {picks}");
+
+        let mut ucb = Credit::new(2, Selection::Ucb { c: 0.1 });
+        assert_eq!(ucb.select(&mut rng), 0);
+        ucb.reward(0, 0.0);
+        assert_eq!(ucb.select(&mut rng), 1);
+        ucb.reward(1, 1.0);
+        assert_eq!(ucb.select(&mut rng), 1);
+    }
+}
@@ -0,0 +1,113 @@
+use rand::Rng;
+
+use crate::core::{Bounds, Distance, Objective, Population, utils::argsort_by_fitness};
+
+/// Where a purged duplicate or a random immigrant (see [`crate::hho::Immigrants`]) is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Refill {
+    /// A uniform random point of the bounds.
+    #[default]
+    Random,
+
+    /// A random point between the center of the bounds and the opposite `lo + hi - x` of the
+    /// replaced hawk (quasi-opposition), so clones of one hawk do not land on the same point.
+    Opposition,
+}
+
+/// Duplicate purge run after every iteration.
+///
+/// Hawks are scanned from best to worst; one lying within `epsilon` of a better hawk that
+/// was kept (see [`crate::hho::HhoBuilder::distance`]) is a clone, and is moved with `refill`
+/// and re-evaluated (one evaluation each). The number of purged hawks ends up in [`crate::core::Report::duplicates_purged`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dedup {
+    pub epsilon: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refill: Refill,
+}
+
+impl Default for Dedup {
+    fn default() -> Self {
+        Self {
+            epsilon: 1e-8,
+            refill: Refill::Random,
+        }
+    }
+}
+
+impl Refill {
+    /// Move `x` para um novo ponto dentro dos limites
+    pub(crate) fn apply<R: Rng>(self, bounds: &Bounds, x: &mut [f64], rng: &mut R) {
+        match self {
+            Self::Random => bounds.fill_random(x, rng),
+            Self::Opposition => {
+                for (j, v) in x.iter_mut().enumerate() {
+                    let center = bounds.lo_at(j).midpoint(bounds.hi_at(j));
+                    let opposite = bounds.lo_at(j) + bounds.hi_at(j) - *v;
+                    *v = rng.random::<f64>().mul_add(opposite - center, center);
+                }
+            }
+        }
+        bounds.project_slice(x);
+    }
+}
+
+impl Dedup {
+    /// Falcões a menos de `epsilon` de um falcão melhor, em ordem crescente
+    pub(crate) fn duplicates<O: Objective>(
+        &self,
+        positions: &Population,
+        fitness: &[f64],
+        distance: &dyn Distance,
+    ) -> Vec<usize> {
+        // Do melhor para o pior, cada falcão é comparado só com os que ficaram
+        let mut kept: Vec<usize> = Vec::with_capacity(positions.len());
+        let mut duplicates = Vec::new();
+        for i in argsort_by_fitness::<O>(fitness) {
+            let clone = kept
+                .iter()
+                .any(|&k| distance.distance(&positions[i], &positions[k]) <= self.epsilon);
+            if clone {
+                duplicates.push(i);
+            } else {
+                kept.push(i);
+            }
+        }
+        duplicates.sort_unstable();
+        duplicates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::core::{Minimization, Norm};
+
+    #[test]
+    fn keeps_the_best_of_each_group() {
+        let positions =
+            Population::from_rows(&[[1.0, 1.0], [1.0 + 1e-12, 1.0], [3.0, 0.0], [1.0, 1.0]])
+                .unwrap();
+        let dedup = Dedup::default();
+        let duplicates =
+            dedup.duplicates::<Minimization>(&positions, &[2.0, 1.0, 5.0, 3.0], &Norm::Euclidean);
+        assert_eq!(duplicates, vec![0, 3]);
+
+        let bounds = Bounds::uniform(0.0, 4.0, 2).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(7);
+        let opposition = Dedup {
+            refill: Refill::Opposition,
+            ..dedup
+        };
+        let mut x = [3.0, 0.0];
+        opposition.refill.apply(&bounds, &mut x, &mut rng);
+        // Entre o centro (2, 2) e o oposto (1, 4)
+        assert!((1.0..=2.0).contains(&x[0]) && (2.0..=4.0).contains(&x[1]));
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crate::core::Population;
+
+/// Handle used to tell a running optimizer that the fitness landscape has changed.
+///
+/// Cloning the handle is cheap; every clone refers to the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeSignal(Arc<AtomicBool>);
+
+impl ChangeSignal {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flags a change. The optimizer reacts at the start of its next iteration.
+    pub fn notify(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Consome o sinal, retornando se havia uma mudança pendente
+    pub(crate) fn take(&self) -> bool {
+        self.0.swap(false, Ordering::AcqRel)
+    }
+}
+
+/// How a change of the objective is detected.
+#[derive(Debug, Clone, Default)]
+pub enum ChangeDetection {
+    /// The objective is assumed static.
+    #[default]
+    Off,
+
+    /// Watches `count` fixed points: the rabbit and the first `count - 1` hawks at the time
+    /// they are placed. Every iteration re-evaluates them (costs `count` evaluations) and
+    /// declares a change when the raw value of the decoder at one of them moved by more than
+    /// [`Dynamic::tolerance`].
+    ///
+    /// The values compared are what the decoder returns, before any [`crate::hho::NonFinite`]
+    /// policy, so the stored fitness of the hawks (sanitized, averaged by
+    /// [`crate::hho::HhoBuilder::reevaluate_rabbit`], ...) plays no part. The points are
+    /// placed on the first iteration, and again after a change or when the penalty
+    /// coefficient of the decoder moved (see [`crate::core::Decoder::penalty_coefficient`]),
+    /// which changes its values without the landscape changing; those iterations only
+    /// measure.
+    Sentinels { count: usize },
+
+    /// Changes are reported by the user through a [`ChangeSignal`].
+    Manual(ChangeSignal),
+}
+
+/// Support for time-varying objectives.
+#[derive(Debug, Clone)]
+pub struct Dynamic {
+    pub detection: ChangeDetection,
+
+    /// Fraction of the population (the worst hawks) re-sampled uniformly after a change,
+    /// in `[0, 1]`. Frozen and fixed dimensions keep their value.
+    pub rerandomize: f64,
+
+    /// Largest move of a sentinel value that is not a change, relative to the magnitude of
+    /// the value (absolute below 1), for noisy objectives; `1e-9` by default.
+    pub tolerance: f64,
+}
+
+impl Default for Dynamic {
+    fn default() -> Self {
+        Self {
+            detection: ChangeDetection::Off,
+            rerandomize: 0.0,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+impl Dynamic {
+    /// Evaluations a detection plus the response may cost in one iteration for a population
+    /// of `n` hawks.
+    pub(crate) const fn max_cost(&self, n: usize) -> usize {
+        match &self.detection {
+            ChangeDetection::Off => 0,
+            ChangeDetection::Manual(_) => n,
+            ChangeDetection::Sentinels { count } => *count + n,
+        }
+    }
+
+    /// Falcões re-sorteados após uma mudança numa população de `n`
+    pub(crate) fn rerandomized(&self, n: usize) -> usize {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let k = (self.rerandomize.clamp(0.0, 1.0) * n as f64).round() as usize;
+        k.min(n)
+    }
+
+    /// Se o valor de uma sentinela passou de `before` a `now` sem mudança da paisagem
+    pub(crate) fn unchanged(&self, before: f64, now: f64) -> bool {
+        if before.is_nan() || now.is_nan() {
+            return before.is_nan() && now.is_nan();
+        }
+        before.to_bits() == now.to_bits()
+            || (now - before).abs() <= self.tolerance * before.abs().max(now.abs()).max(1.0)
+    }
+}
+
+/// Pontos fixos reavaliados pela detecção por sentinelas
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Sentinels {
+    pub positions: Population,
+    /// Valores crus do decodificador em `positions`
+    pub raw: Vec<f64>,
+    /// Coeficiente de penalidade do decodificador quando `raw` foi medido
+    pub penalty: Option<f64>,
+}
@@ -0,0 +1,83 @@
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use crate::hho::CheckpointError;
+use crate::{
+    core::{DecoderError, Report, TerminationReason, bounds::BoundsError},
+    init::InitError,
+};
+
+/// Every problem found in the settings of an optimizer, see [`crate::hho::Hho::check`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{}", .problems.join("; "))]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+/// Everything that can make a run fail, returned by [`crate::hho::Hho::run`] and friends.
+#[derive(Debug, Error)]
+pub enum HhoError {
+    #[error(transparent)]
+    Bounds(#[from] BoundsError),
+
+    #[error("initialization failed: {0}")]
+    Init(#[from] InitError),
+
+    /// The optimizer was built with settings it cannot run with.
+    #[error("invalid configuration: {0}")]
+    Config(String),
+
+    /// The decoder needs another dimension than the bounds have, see
+    /// [`crate::core::Decoder::expected_dim`].
+    #[error("invalid configuration: decoder expects {expected} dimensions, bounds have {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    /// The decoder returned an error. `report` describes the run up to the failed call, as
+    /// with [`crate::core::TerminationReason::Error`].
+    #[error("decoder failed after {} evaluations: {source}", report.evals)]
+    Decoder {
+        source: DecoderError,
+        report: Box<Report>,
+    },
+
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Checkpoint(#[from] CheckpointError),
+
+    /// A checkpoint could not be written during a run, which ended it. `report` describes the
+    /// run up to then.
+    #[cfg(feature = "serde")]
+    #[error("checkpoint write failed after {} iterations: {source}", report.iters)]
+    CheckpointWrite {
+        source: CheckpointError,
+        report: Box<Report>,
+    },
+}
+
+impl HhoError {
+    /// The partial report of a run stopped by a decoder error or a failed checkpoint write.
+    #[must_use]
+    pub fn report(&self) -> Option<&Report> {
+        match self {
+            Self::Decoder { report, .. } => Some(report),
+            #[cfg(feature = "serde")]
+            Self::CheckpointWrite { report, .. } => Some(report),
+            _ => None,
+        }
+    }
+
+    /// The report of the run that failed, with [`TerminationReason::Error`] as stop reason;
+    /// an empty report with that reason when the run never started.
+    #[must_use]
+    pub fn into_report(self) -> Report {
+        match self {
+            Self::Decoder { report, .. } => *report,
+            #[cfg(feature = "serde")]
+            Self::CheckpointWrite { report, .. } => *report,
+            _ => Report {
+                stop_reason: Some(TerminationReason::Error),
+                ..Report::empty()
+            },
+        }
+    }
+}
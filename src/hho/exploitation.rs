@@ -0,0 +1,183 @@
+use std::f64::consts::TAU;
+
+use rand::Rng;
+
+use crate::hho::{Phase, Selection};
+
+/// Update rule used by hawks in the exploitation phase (`|E| < 1`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Exploitation {
+    /// The four besiege branches of the original HHO (soft/hard, with or without dives).
+    #[default]
+    Classic,
+
+    /// Quantum-behaved update (delta potential well around a local attractor between the hawk
+    /// and the rabbit, with characteristic length driven by the distance to the mean best).
+    ///
+    /// Since HHO keeps no personal bests, a hawk only moves when the quantum candidate
+    /// improves on it. The contraction–expansion coefficient decreases linearly from `alpha_start` to
+    /// `alpha_end` over the run; `1.0 → 0.5` is the usual choice.
+    Quantum { alpha_start: f64, alpha_end: f64 },
+
+    /// Differential evolution step (`DE/current-to-best/1/bin`): the mutant
+    /// `x + f·(rabbit - x) + f·(a - b)`, with `a` and `b` random hawks, is crossed over with
+    /// the hawk at rate `cr` and replaces it only if it improves.
+    Differential { f: f64, cr: f64 },
+
+    /// Sine cosine algorithm step: `x + r₁·sin(r₂)·|r₃·rabbit - x|` (or with `cos`, at
+    /// random) per dimension, with `r₁` decreasing linearly from `a` to `0` over the run.
+    SineCosine { a: f64 },
+
+    /// Ensemble of the rules above: each exploiting hawk draws one of classic, quantum,
+    /// differential and sine cosine (with their default settings) through a [`crate::hho::Credit`]
+    /// fed with the relative improvement each rule obtained, so the rules that work on the
+    /// problem at hand are used more often.
+    Adaptive { selection: Selection },
+}
+
+impl Exploitation {
+    /// Quantum variant with the customary `α: 1.0 → 0.5` schedule.
+    #[must_use]
+    pub const fn quantum() -> Self {
+        Self::Quantum {
+            alpha_start: 1.0,
+            alpha_end: 0.5,
+        }
+    }
+
+    /// Differential variant with `f = 0.5`, `cr = 0.9`.
+    #[must_use]
+    pub const fn differential() -> Self {
+        Self::Differential { f: 0.5, cr: 0.9 }
+    }
+
+    /// Sine cosine variant with the customary `a = 2`.
+    #[must_use]
+    pub const fn sine_cosine() -> Self {
+        Self::SineCosine { a: 2.0 }
+    }
+
+    /// Adaptive variant with the default [`Selection`].
+    #[must_use]
+    pub fn adaptive() -> Self {
+        Self::Adaptive {
+            selection: Selection::default(),
+        }
+    }
+
+    /// Problema nos parâmetros da regra, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        match *self {
+            Self::Differential { f, cr } if !(f > 0.0 && (0.0..=1.0).contains(&cr)) => Some(
+                format!("differential step needs f = {f} > 0 and cr = {cr} in [0, 1]"),
+            ),
+            Self::SineCosine { a } if !(a.is_finite() && a > 0.0) => Some(format!(
+                "sine cosine amplitude a = {a} must be finite and positive"
+            )),
+            Self::Adaptive {
+                selection: Selection::ProbabilityMatching { p_min, alpha },
+            } if !((0.0..0.25).contains(&p_min) && alpha > 0.0 && alpha <= 1.0) => Some(format!(
+                "probability matching needs p_min = {p_min} in [0, 1/4) and alpha = {alpha} \
+                 in (0, 1]"
+            )),
+            Self::Adaptive {
+                selection: Selection::Ucb { c },
+            } if !(c.is_finite() && c >= 0.0) => Some(format!(
+                "UCB exploration c = {c} must be finite and non-negative"
+            )),
+            _ => None,
+        }
+    }
+
+    /// Regras entre as quais a variante adaptativa escolhe
+    pub(crate) const ARMS: [Self; 4] = [
+        Self::Classic,
+        Self::quantum(),
+        Self::differential(),
+        Self::sine_cosine(),
+    ];
+
+    /// Regra (índice em `ARMS`) que produziu um movimento da fase `phase`
+    pub(crate) const fn arm(phase: Phase) -> Option<usize> {
+        match phase {
+            Phase::Exploration => None,
+            Phase::SoftBesiege | Phase::HardBesiege | Phase::SoftDives | Phase::HardDives => {
+                Some(0)
+            }
+            Phase::Quantum => Some(1),
+            Phase::Differential => Some(2),
+            Phase::SineCosine => Some(3),
+        }
+    }
+}
+
+/// Quantum-behaved position update written into `out`.
+///
+/// For each dimension, `p = φ·x + (1 - φ)·rabbit` with `φ ~ U(0, 1)`, and
+/// `out = p ± α·|mbest - x|·ln(1/u)` with `u ~ U(0, 1)` and the sign chosen at random.
+pub fn quantum_step<R: Rng + ?Sized>(
+    x: &[f64],
+    rabbit: &[f64],
+    mbest: &[f64],
+    alpha: f64,
+    rng: &mut R,
+    out: &mut [f64],
+) {
+    for j in 0..out.len() {
+        let phi: f64 = rng.random();
+        let p = phi.mul_add(x[j] - rabbit[j], rabbit[j]);
+        // 1 - U evita ln(1/0)
+        let u: f64 = 1.0 - rng.random::<f64>();
+        let step = alpha * (mbest[j] - x[j]).abs() * (1.0 / u).ln();
+        out[j] = if rng.random::<bool>() {
+            p + step
+        } else {
+            p - step
+        };
+    }
+}
+
+/// `DE/current-to-best/1/bin` mutant of `x` written into `out`; `a` and `b` are the
+/// difference vectors.
+#[allow(clippy::too_many_arguments)]
+pub fn differential_step<R: Rng + ?Sized>(
+    x: &[f64],
+    rabbit: &[f64],
+    a: &[f64],
+    b: &[f64],
+    f: f64,
+    cr: f64,
+    rng: &mut R,
+    out: &mut [f64],
+) {
+    // Ao menos uma coordenada vem sempre do mutante
+    let forced = rng.random_range(0..out.len());
+    for j in 0..out.len() {
+        out[j] = if j == forced || rng.random::<f64>() < cr {
+            f.mul_add(rabbit[j] - x[j] + a[j] - b[j], x[j])
+        } else {
+            x[j]
+        };
+    }
+}
+
+/// Sine cosine position update written into `out`, with amplitude `r1`.
+pub fn sine_cosine_step<R: Rng + ?Sized>(
+    x: &[f64],
+    rabbit: &[f64],
+    r1: f64,
+    rng: &mut R,
+    out: &mut [f64],
+) {
+    for j in 0..out.len() {
+        let r2 = TAU * rng.random::<f64>();
+        let r3 = 2.0 * rng.random::<f64>();
+        let wave = if rng.random::<bool>() {
+            r2.sin()
+        } else {
+            r2.cos()
+        };
+        out[j] = (r1 * wave).mul_add(r3.mul_add(rabbit[j], -x[j]).abs(), x[j]);
+    }
+}
@@ -0,0 +1,39 @@
+/// Low-fidelity screening of greedy moves, for decoders with a cheap approximate mode (see
+/// [`crate::core::Decoder::decode_at`]).
+///
+/// Every candidate that the hawk would only take if it improves (progressive dives, quantum
+/// and differential moves) is first evaluated at fidelity `level`; only when that value beats
+/// the hawk's current fitness is it evaluated again at full fidelity, and the hawk moves on
+/// the full-fidelity value. Unconditional moves, initialization and every other evaluation
+/// stay at full fidelity, so all the fitness values the run keeps are comparable.
+///
+/// The budget is counted in full-fidelity equivalents: a screening costs `cost` evaluations,
+/// and [`crate::core::Report::evals`] adds them up (the number of screenings itself ends up in
+/// [`crate::core::Report::screened`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fidelity {
+    /// Fidelity passed to the decoder when screening, in `(0, 1)`.
+    pub level: f64,
+    /// Cost of a screening relative to a full evaluation, in `(0, 1]`.
+    pub cost: f64,
+}
+
+impl Default for Fidelity {
+    fn default() -> Self {
+        Self {
+            level: 0.1,
+            cost: 0.1,
+        }
+    }
+}
+
+impl Fidelity {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self { level, cost } = *self;
+        (!(level > 0.0 && level < 1.0 && cost > 0.0 && cost <= 1.0)).then(|| {
+            format!("fidelity needs level = {level} in (0, 1) and cost = {cost} in (0, 1]")
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use crate::hho::Refill;
+
+/// Random immigrants, a classic way to keep diversity up on long runs.
+///
+/// After every iteration the worst `fraction` of the hawks is replaced by fresh individuals
+/// placed with `refill` (one evaluation each).
+/// The best hawk is never replaced. The number of immigrants of each iteration ends up in
+/// [`crate::hho::PhaseCounts::immigrants`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Immigrants {
+    /// Fraction of the population replaced, in `(0, 1)`.
+    pub fraction: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refill: Refill,
+}
+
+impl Default for Immigrants {
+    fn default() -> Self {
+        Self {
+            fraction: 0.1,
+            refill: Refill::Random,
+        }
+    }
+}
+
+impl Immigrants {
+    /// Falcões substituídos numa população de `n`, nunca todos
+    #[must_use]
+    pub fn count(&self, n: usize) -> usize {
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let k = (self.fraction * n as f64).round() as usize;
+        k.min(n.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_a_rounded_share_but_never_everyone() {
+        let immigrants = Immigrants::default();
+        assert_eq!(immigrants.count(30), 3);
+        assert_eq!(immigrants.count(4), 0);
+        let all = Immigrants {
+            fraction: 1.0,
+            ..immigrants
+        };
+        assert_eq!(all.count(5), 4);
+    }
+}
@@ -0,0 +1,90 @@
+use rand::Rng;
+
+use crate::hho::levy::standard_normal;
+
+/// Distribution of the rabbit's jump strength `J` used in the besiege phases.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JumpStrength {
+    /// `J = 2(1 - r)`, `r ~ U(0, 1)` (original HHO).
+    #[default]
+    Uniform,
+
+    /// `J = 2·B` with `B ~ Beta(alpha, beta)`, keeping the `[0, 2]` support but reshaping the
+    /// density.
+    Beta { alpha: f64, beta: f64 },
+
+    /// Fixed jump strength.
+    Constant(f64),
+
+    /// Deterministic linear decay from `start` to `end` over the run.
+    Decaying { start: f64, end: f64 },
+}
+
+impl JumpStrength {
+    /// Samples `J` at the given run `progress` (`t/T`, in `[0, 1]`).
+    pub fn sample<R: Rng + ?Sized>(&self, progress: f64, rng: &mut R) -> f64 {
+        match *self {
+            Self::Uniform => 2.0 * (1.0 - rng.random::<f64>()),
+            Self::Beta { alpha, beta } => 2.0 * sample_beta(alpha, beta, rng),
+            Self::Constant(j) => j,
+            Self::Decaying { start, end } => (end - start).mul_add(progress, start),
+        }
+    }
+}
+
+/// Gamma(`shape`, 1) variate (Marsaglia & Tsang, 2000).
+#[allow(clippy::many_single_char_names)]
+pub fn sample_gamma<R: Rng + ?Sized>(shape: f64, rng: &mut R) -> f64 {
+    if shape < 1.0 {
+        // Gamma(a) = Gamma(a + 1) · U^(1/a)
+        let u: f64 = 1.0 - rng.random::<f64>();
+        return sample_gamma(shape + 1.0, rng) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = c.mul_add(x, 1.0);
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u: f64 = 1.0 - rng.random::<f64>();
+        if u.ln() < (0.5 * x).mul_add(x, d - d * v + d * v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+/// Beta(`alpha`, `beta`) variate as the ratio of two gamma variates.
+pub fn sample_beta<R: Rng + ?Sized>(alpha: f64, beta: f64, rng: &mut R) -> f64 {
+    let x = sample_gamma(alpha, rng);
+    let y = sample_gamma(beta, rng);
+    if x + y > 0.0 { x / (x + y) } else { 0.5 }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn beta_sample_mean_matches() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 20_000;
+        let mean = (0..n).map(|_| sample_beta(2.0, 5.0, &mut rng)).sum::<f64>() / f64::from(n);
+        assert!((mean - 2.0 / 7.0).abs() < 0.01, "mean = {mean}");
+    }
+
+    #[test]
+    fn decaying_interpolates() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let j = JumpStrength::Decaying {
+            start: 2.0,
+            end: 0.0,
+        };
+        assert!((j.sample(0.25, &mut rng) - 1.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,76 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+/// Coeficientes de Lanczos (g = 7, n = 9)
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Gamma function via the Lanczos approximation (reflection formula for `x < 0.5`).
+#[must_use]
+pub fn gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        return PI / ((PI * x).sin() * gamma(1.0 - x));
+    }
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    let t = x + 7.5;
+    for (i, &c) in LANCZOS.iter().enumerate().skip(1) {
+        #[allow(clippy::cast_precision_loss)]
+        let k = i as f64;
+        a += c / (x + k);
+    }
+    (2.0 * PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * a
+}
+
+/// Natural logarithm of the gamma function for `x > 0`, with the same Lanczos approximation
+/// as [`gamma`] but without overflowing for large `x`.
+#[must_use]
+pub fn ln_gamma(x: f64) -> f64 {
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    let t = x + 7.5;
+    for (i, &c) in LANCZOS.iter().enumerate().skip(1) {
+        #[allow(clippy::cast_precision_loss)]
+        let k = i as f64;
+        a += c / (x + k);
+    }
+    (x + 0.5).mul_add(t.ln(), 0.5 * (2.0 * PI).ln()) - t + a.ln()
+}
+
+/// Samples a standard normal variate using the Box–Muller transform.
+pub fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // 1 - U evita ln(0)
+    let u1: f64 = 1.0 - rng.random::<f64>();
+    let u2: f64 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// Scale `σ_u` of Mantegna's algorithm for a given `β`.
+#[must_use]
+pub fn mantegna_sigma(beta: f64) -> f64 {
+    let num = gamma(1.0 + beta) * (PI * beta / 2.0).sin();
+    let den = gamma(f64::midpoint(1.0, beta)) * beta * ((beta - 1.0) / 2.0).exp2();
+    (num / den).powf(1.0 / beta)
+}
+
+/// Fills `out` with a Lévy flight step (Mantegna's algorithm), as used by the rapid dives.
+///
+/// Each component is `0.01 · u / |v|^(1/β)` with `u ~ N(0, σ²)` and `v ~ N(0, 1)`.
+pub fn levy_flight<R: Rng + ?Sized>(rng: &mut R, beta: f64, out: &mut [f64]) {
+    let sigma = mantegna_sigma(beta);
+    for o in out {
+        let u = standard_normal(rng) * sigma;
+        let v = standard_normal(rng);
+        *o = 0.01 * u / v.abs().powf(1.0 / beta);
+    }
+}
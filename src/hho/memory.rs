@@ -0,0 +1,155 @@
+use crate::core::{Objective, Population};
+
+/// How a hawk got its current position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Origin {
+    /// Sampled by the initializer.
+    #[default]
+    Init,
+
+    /// Re-sampled after a landscape change (see [`crate::hho::Dynamic`]) or as a purged
+    /// duplicate (see [`crate::hho::Dedup`]).
+    Restart,
+
+    /// Put in place by [`crate::hho::Run::inject`].
+    Injected,
+
+    /// A random immigrant replacing one of the worst hawks (see [`crate::hho::Immigrants`]).
+    Immigrant,
+}
+
+/// Personal best, iterations without improvement, age and origin of every hawk.
+///
+/// Maintained by the main loop when enabled with [`crate::hho::HhoBuilder::track_hawks`] and
+/// indexed like the population.
+///
+/// A hawk's record starts over whenever it is replaced (restart or injection); its age then
+/// counts the iterations since the replacement.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HawkMemory {
+    best: Population,
+    best_fitness: Vec<f64>,
+    stagnation: Vec<usize>,
+    age: Vec<usize>,
+    origin: Vec<Origin>,
+}
+
+impl HawkMemory {
+    /// Memória de uma população recém-inicializada
+    pub(crate) fn new(positions: &Population, fitness: &[f64]) -> Self {
+        let n = positions.len();
+        Self {
+            best: positions.clone(),
+            best_fitness: fitness.to_vec(),
+            stagnation: vec![0; n],
+            age: vec![0; n],
+            origin: vec![Origin::Init; n],
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.best_fitness.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.best_fitness.is_empty()
+    }
+
+    /// Best position hawk `i` has visited and its fitness.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of range.
+    #[must_use]
+    pub fn personal_best(&self, i: usize) -> (&[f64], f64) {
+        (&self.best[i], self.best_fitness[i])
+    }
+
+    /// Iterations since hawk `i` last improved its personal best.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of range.
+    #[must_use]
+    pub fn stagnation(&self, i: usize) -> usize {
+        self.stagnation[i]
+    }
+
+    /// Iterations hawk `i` has lived through since it was initialized or replaced.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of range.
+    #[must_use]
+    pub fn age(&self, i: usize) -> usize {
+        self.age[i]
+    }
+
+    /// # Panics
+    ///
+    /// If `i` is out of range.
+    #[must_use]
+    pub fn origin(&self, i: usize) -> Origin {
+        self.origin[i]
+    }
+
+    /// Fecha uma iteração: envelhece os falcões e atualiza o melhor pessoal de cada um
+    pub(crate) fn update<O: Objective>(&mut self, positions: &Population, fitness: &[f64]) {
+        for (i, (x, &f)) in positions.iter().zip(fitness).enumerate() {
+            self.age[i] += 1;
+            if O::better(f, self.best_fitness[i]) {
+                self.best[i].copy_from_slice(x);
+                self.best_fitness[i] = f;
+                self.stagnation[i] = 0;
+            } else {
+                self.stagnation[i] += 1;
+            }
+        }
+    }
+
+    /// Recomeça o registro do falcão `i`, que passou a ocupar `x`
+    pub(crate) fn reset(&mut self, i: usize, x: &[f64], f: f64, origin: Origin) {
+        self.best[i].copy_from_slice(x);
+        self.best_fitness[i] = f;
+        self.stagnation[i] = 0;
+        self.age[i] = 0;
+        self.origin[i] = origin;
+    }
+
+    /// Descarta os melhores pessoais após uma mudança da paisagem: as fitness antigas não
+    /// valem mais, então cada falcão parte da posição atual, mantendo idade e origem
+    pub(crate) fn rebase(&mut self, positions: &Population, fitness: &[f64]) {
+        self.best.clone_from(positions);
+        self.best_fitness.copy_from_slice(fitness);
+        self.stagnation.fill(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    #[test]
+    fn tracks_personal_bests_and_stagnation() {
+        let mut positions = Population::from_rows(&[[1.0], [2.0]]).unwrap();
+        let mut memory = HawkMemory::new(&positions, &[1.0, 4.0]);
+
+        positions[0][0] = 3.0;
+        positions[1][0] = 0.5;
+        memory.update::<Minimization>(&positions, &[9.0, 0.25]);
+        assert_eq!(memory.personal_best(0).0, &[1.0]);
+        assert_eq!((memory.stagnation(0), memory.stagnation(1)), (1, 0));
+        assert_eq!(memory.personal_best(1).0, &[0.5]);
+
+        memory.reset(0, &[0.0], 0.0, Origin::Injected);
+        assert_eq!((memory.age(0), memory.age(1)), (0, 1));
+        assert_eq!(memory.origin(0), Origin::Injected);
+        assert_eq!(memory.origin(1), Origin::Init);
+    }
+}
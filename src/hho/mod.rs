@@ -0,0 +1,76 @@
+pub mod adaptation;
+pub mod attractor;
+pub mod blockwise;
+pub mod builder;
+#[cfg(feature = "serde")]
+pub mod checkpoint;
+#[cfg(feature = "serde")]
+pub mod config;
+pub mod credit;
+pub mod dedup;
+pub mod dynamic;
+pub mod error;
+pub mod exploitation;
+pub mod fidelity;
+pub mod immigrants;
+pub mod jump;
+pub mod levy;
+pub mod memory;
+pub mod niching;
+pub mod non_finite;
+pub mod optimizer;
+pub mod orthogonal;
+pub mod paired;
+pub mod params;
+pub mod phase;
+pub mod pooled;
+pub mod preset;
+pub mod replay;
+#[cfg(feature = "rayon")]
+pub mod replication;
+pub mod schedule;
+pub mod selection;
+pub mod shape;
+pub mod stepper;
+pub mod steps;
+pub mod suite;
+pub mod sweep;
+pub mod two_phase;
+pub mod zoom;
+
+pub use adaptation::{Adaptation, SuccessHistory};
+pub use attractor::Attractor;
+pub use blockwise::UpdateMode;
+pub use builder::HhoBuilder;
+#[cfg(feature = "serde")]
+pub use checkpoint::{CheckpointError, CheckpointPolicy};
+#[cfg(feature = "serde")]
+pub use config::{BoundsConfig, Config, InitializerConfig, Limit, Reporting, Termination};
+pub use credit::{Credit, OperatorRecord, OperatorStats, Selection};
+pub use dedup::{Dedup, Refill};
+pub use dynamic::{ChangeDetection, ChangeSignal, Dynamic};
+pub use error::{ConfigError, HhoError};
+pub use exploitation::Exploitation;
+pub use fidelity::Fidelity;
+pub use immigrants::Immigrants;
+pub use jump::JumpStrength;
+pub use memory::{HawkMemory, Origin};
+pub use niching::{LeaderAssignment, Niching};
+pub use non_finite::NonFinite;
+pub use optimizer::Hho;
+pub(crate) use optimizer::Settings;
+pub use orthogonal::OrthogonalLearning;
+pub use paired::PairedComparison;
+pub use params::Params;
+pub use phase::{Phase, PhaseCounts};
+pub use pooled::Plateau;
+pub use preset::Preset;
+pub use replay::{Displacements, DiveReplay};
+pub use schedule::{Parameter, Schedule};
+pub use selection::PartnerSelection;
+pub use shape::DiveShape;
+pub use stepper::{PollStatus, Run};
+pub use steps::RankSteps;
+pub use suite::ProblemRef;
+pub use two_phase::TwoPhase;
+pub use zoom::Zoom;
@@ -0,0 +1,211 @@
+use crate::core::{Distance, Objective, Population, report::Niche, utils::argsort_by_fitness};
+
+/// Niching strategy used to keep several sub-swarms alive at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Niching {
+    /// A single swarm chasing the global rabbit (original HHO).
+    #[default]
+    Off,
+
+    /// Speciation by distance: hawks are scanned from best to worst and each one joins the
+    /// first species whose seed lies within `radius` (see [`crate::hho::HhoBuilder::distance`]),
+    /// otherwise it founds a new species. Every hawk then besieges its own species seed instead of the global rabbit.
+    Speciation { radius: f64 },
+
+    /// Multiple rabbits: the `k` best hawks all lead, and every other hawk besieges the
+    /// leader given by `assignment`. The population converges toward up to `k` attractors
+    /// at once, a lighter way than islands to keep it from collapsing on the first optimum
+    /// it finds.
+    Leaders {
+        k: usize,
+        assignment: LeaderAssignment,
+    },
+}
+
+/// How the hawks are shared among the leaders of [`Niching::Leaders`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LeaderAssignment {
+    /// Each hawk follows the nearest leader (see [`crate::hho::HhoBuilder::distance`]), so
+    /// the groups are regions of the search space.
+    #[default]
+    Nearest,
+
+    /// Hawks are dealt to the leaders in turn, from best to worst, so every leader gets a
+    /// group of about the same size and quality wherever it lies.
+    Rank,
+}
+
+impl Niching {
+    /// Problema nos parâmetros para uma população de `pop_size`, se houver
+    pub(crate) fn problem(&self, pop_size: usize) -> Option<String> {
+        match *self {
+            Self::Speciation { radius } if !(radius.is_finite() && radius > 0.0) => Some(format!(
+                "speciation radius = {radius} must be finite and positive"
+            )),
+            Self::Leaders { k, .. } if !(1..=pop_size).contains(&k) => Some(format!(
+                "leader count = {k} must be between 1 and pop_size = {pop_size}"
+            )),
+            _ => None,
+        }
+    }
+
+    /// Divide os falcões em grupos (ver [`speciate`] e [`lead`]); vazio sem nichos
+    pub(crate) fn group<O: Objective>(
+        self,
+        positions: &Population,
+        fitness: &[f64],
+        distance: &dyn Distance,
+        species: &mut Vec<usize>,
+    ) -> Vec<usize> {
+        match self {
+            Self::Off => Vec::new(),
+            Self::Speciation { radius } => {
+                speciate::<O>(positions, fitness, radius, distance, species)
+            }
+            Self::Leaders { k, assignment } => {
+                lead::<O>(positions, fitness, k, assignment, distance, species)
+            }
+        }
+    }
+}
+
+/// Assigns every hawk to a species and returns the indices of the species seeds, best first.
+///
+/// After the call `species[i]` is the position in the returned vector of the seed that hawk
+/// `i` belongs to.
+pub fn speciate<O: Objective>(
+    positions: &Population,
+    fitness: &[f64],
+    radius: f64,
+    distance: &dyn Distance,
+    species: &mut Vec<usize>,
+) -> Vec<usize> {
+    crate::core::utils::strict_assert_eq!(positions.len(), fitness.len(), "len mismatch");
+    let order = argsort_by_fitness::<O>(fitness);
+
+    let mut seeds: Vec<usize> = Vec::new();
+    species.clear();
+    species.resize(fitness.len(), 0);
+    for &i in &order {
+        let found = seeds
+            .iter()
+            .position(|&s| distance.distance(&positions[i], &positions[s]) <= radius);
+        species[i] = found.unwrap_or_else(|| {
+            seeds.push(i);
+            seeds.len() - 1
+        });
+    }
+    seeds
+}
+
+/// Picks the `k` best hawks as leaders (all of them if there are fewer) and assigns every
+/// hawk to one of them, returning the leader indices best first.
+///
+/// After the call `species[i]` is the position in the returned vector of the leader that
+/// hawk `i` follows; each leader follows itself.
+pub fn lead<O: Objective>(
+    positions: &Population,
+    fitness: &[f64],
+    k: usize,
+    assignment: LeaderAssignment,
+    distance: &dyn Distance,
+    species: &mut Vec<usize>,
+) -> Vec<usize> {
+    crate::core::utils::strict_assert_eq!(positions.len(), fitness.len(), "len mismatch");
+    let order = argsort_by_fitness::<O>(fitness);
+    let leaders: Vec<usize> = order.iter().copied().take(k.max(1)).collect();
+
+    species.clear();
+    species.resize(fitness.len(), 0);
+    for (rank, &i) in order.iter().enumerate() {
+        species[i] = match assignment {
+            _ if rank < leaders.len() => rank,
+            LeaderAssignment::Rank => rank % leaders.len(),
+            LeaderAssignment::Nearest => {
+                let d = |l: usize| distance.distance(&positions[i], &positions[l]);
+                (0..leaders.len())
+                    .min_by(|&a, &b| d(leaders[a]).total_cmp(&d(leaders[b])))
+                    .unwrap_or(0)
+            }
+        };
+    }
+    leaders
+}
+
+/// Builds the per-niche summary reported at the end of a run.
+#[must_use]
+pub fn niches<O: Objective>(
+    positions: &Population,
+    fitness: &[f64],
+    radius: f64,
+    distance: &dyn Distance,
+) -> Vec<Niche> {
+    summarize::<O>(Niching::Speciation { radius }, positions, fitness, distance)
+}
+
+/// Resumo por nicho do agrupamento de `niching`, do melhor para o pior
+pub(crate) fn summarize<O: Objective>(
+    niching: Niching,
+    positions: &Population,
+    fitness: &[f64],
+    distance: &dyn Distance,
+) -> Vec<Niche> {
+    let mut species = Vec::new();
+    let seeds = niching.group::<O>(positions, fitness, distance, &mut species);
+    if seeds.is_empty() {
+        return Vec::new();
+    }
+    let mut out: Vec<Niche> = seeds
+        .iter()
+        .map(|&s| Niche {
+            position: positions[s].to_vec(),
+            fitness: fitness[s],
+            size: 0,
+        })
+        .collect();
+    for &k in &species {
+        out[k].size += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Minimization, Norm};
+
+    #[test]
+    fn speciation_groups_by_radius() {
+        let positions = Population::from_flat(vec![0.0, 0.1, 5.0, 5.2, 0.05], 1).unwrap();
+        let fitness = vec![1.0, 2.0, 0.5, 3.0, 4.0];
+        let mut species = Vec::new();
+        let seeds =
+            speciate::<Minimization>(&positions, &fitness, 0.5, &Norm::Euclidean, &mut species);
+
+        assert_eq!(seeds, vec![2, 0]);
+        assert_eq!(species, vec![1, 1, 0, 0, 1]);
+
+        // Líderes: os dois melhores (2 e 0); os demais seguem o mais próximo ou a vez
+        let leaders = lead::<Minimization>(
+            &positions,
+            &fitness,
+            2,
+            LeaderAssignment::Nearest,
+            &Norm::Euclidean,
+            &mut species,
+        );
+        assert_eq!(leaders, vec![2, 0]);
+        assert_eq!(species, vec![1, 1, 0, 0, 1]);
+        lead::<Minimization>(
+            &positions,
+            &fitness,
+            2,
+            LeaderAssignment::Rank,
+            &Norm::Euclidean,
+            &mut species,
+        );
+        assert_eq!(species, vec![1, 0, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::core::DecoderError;
+
+/// What the HHO optimizer does with a NaN or infinite fitness returned by the decoder.
+///
+/// Whatever the policy, such values are counted in [`crate::core::Report::non_finite`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NonFinite {
+    /// Keeps the value: an infinite fitness is a common penalty, but a NaN compares as
+    /// neither better nor worse than anything and may linger in the population.
+    #[default]
+    Keep,
+    /// Replaces the value with the worst fitness of the objective, so the point loses every
+    /// comparison.
+    Worst,
+    /// Stops the run with [`DecoderError::NonFiniteFitness`], naming the position.
+    Abort,
+}
+
+impl NonFinite {
+    /// Aplica a política a `f`, a fitness de `x`, se ela não for finita; `worst` é a pior
+    /// fitness do objetivo
+    pub(crate) fn apply(self, worst: f64, x: &[f64], f: f64) -> Result<f64, DecoderError> {
+        if f.is_finite() {
+            return Ok(f);
+        }
+        match self {
+            Self::Keep => Ok(f),
+            Self::Worst => Ok(worst),
+            Self::Abort => Err(DecoderError::NonFiniteFitness {
+                value: f,
+                position: x.to_vec(),
+            }),
+        }
+    }
+}
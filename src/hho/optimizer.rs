@@ -0,0 +1,3614 @@
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc, time::Duration};
+
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+use rand::Rng;
+
+#[cfg(feature = "serde")]
+use crate::hho::{CheckpointError, CheckpointPolicy, checkpoint::Checkpoint};
+use crate::{
+    core::{
+        Bounds, Budget, CurveSampling, Decoder, DecoderError, Degradation, Distance, EvalContext,
+        EvaluationArchive, FitnessScaling, Improvement, Minimization, Norm, Objective, Optimizer,
+        Population, Report, RngKind, Scaler, SeedStreams, StreamRng,
+        archive::HallOfFame,
+        clock::Instant,
+        utils::{
+            argsort_by_fitness, best_index_with, dimension_wise_diversity_masked, top_k_indices,
+        },
+    },
+    hho::{
+        Adaptation, Attractor, ChangeDetection, ConfigError, Credit, Dedup, Displacements,
+        DiveReplay, DiveShape, Dynamic, Exploitation, Fidelity, HawkMemory, HhoError, Immigrants,
+        JumpStrength, Niching, NonFinite, OperatorStats, Origin, OrthogonalLearning, Parameter,
+        Params, PartnerSelection, RankSteps, Refill, Run, Schedule, SuccessHistory, UpdateMode,
+        Zoom,
+        attractor::elite_centroid,
+        dynamic::Sentinels,
+        orthogonal::orthogonal_combine,
+        phase::{Phase, PhaseCounts},
+        selection::fdb_scores,
+        sweep::{Buffers, DecoderRef},
+    },
+    init::{InitError, Initializer, RandomInitializer},
+    local_search::{ChaoticSearch, LocalRefiner, PatternSearch, RefinerSchedule},
+    metrics::{Grid, distance::centroid_into},
+    observer::{EarlyStop, IterationContext, MutObservers, Observers},
+    termination::{CancellationToken, Criterion, Status},
+};
+
+/// Harris Hawks Optimization.
+///
+/// Build it with [`crate::hho::HhoBuilder`] and call [`Hho::run`] with a [`Decoder`].
+#[derive(Debug, Clone)]
+pub struct Hho<O = Minimization, I = RandomInitializer> {
+    pub(crate) bounds: Bounds,
+    pub(crate) initializer: I,
+    pub(crate) settings: Settings,
+    pub(crate) objective: PhantomData<fn() -> O>,
+}
+
+/// Configurações que não dependem dos parâmetros genéricos do otimizador
+#[allow(clippy::struct_excessive_bools)] // chaves independentes do construtor
+#[derive(Debug, Clone)]
+pub(crate) struct Settings {
+    pub pop_size: usize,
+    pub max_iters: usize,
+    pub params: Params,
+    pub adaptation: Adaptation,
+    pub partner_selection: PartnerSelection,
+    pub niching: Niching,
+    pub dynamic: Dynamic,
+    pub dedup: Option<Dedup>,
+    pub immigrants: Option<Immigrants>,
+    pub zoom: Option<Zoom>,
+    /// Triagem dos movimentos gulosos em baixa fidelidade
+    pub fidelity: Option<Fidelity>,
+    /// Memória de deslocamentos dos mergulhos bem-sucedidos
+    pub dive_replay: Option<DiveReplay>,
+    pub dive_shape: DiveShape,
+    /// Avalia também o reflexo de cada candidato de mergulho
+    pub mirror_dives: bool,
+    /// Iterações e avaliações herdadas de uma execução anterior
+    pub counters: Option<(usize, usize)>,
+    /// Distância usada por nichos, remoção de duplicatas e hall da fama
+    pub distance: Arc<dyn Distance>,
+    /// Arquivo vazio usado como modelo para cada execução
+    pub hall_of_fame: Option<HallOfFame>,
+    /// Guarda todas as avaliações num [`EvaluationArchive`]
+    pub record_evaluations: bool,
+    pub curve_sampling: CurveSampling,
+    /// Copia a população final para o relatório
+    pub keep_population: bool,
+    /// Memória máxima, em bytes, dos registros do relatório
+    pub memory_budget: Option<usize>,
+    /// Células por dimensão da grade de agrupamentos (`None` não registra)
+    pub clusters: Option<usize>,
+    /// Intervalo, em iterações, entre registros da trajetória do coelho
+    pub trajectory: Option<usize>,
+    pub exploitation: Exploitation,
+    /// Escala da fitness usada pelos mecanismos adaptativos
+    pub fitness_scaling: FitnessScaling,
+    pub orthogonal_learning: Option<OrthogonalLearning>,
+    /// Refinador local em torno do coelho a cada iteração
+    pub local_refiner: Option<LocalRefiner>,
+    /// Parcela das avaliações do refinador local (`None` gasta o tamanho dele toda iteração)
+    pub refiner_schedule: Option<RefinerSchedule>,
+    /// Intervalo, em iterações, entre reavaliações do coelho (objetivos ruidosos)
+    pub reevaluate: Option<usize>,
+    pub momentum: f64,
+    /// Probabilidade de cada coordenada mudar nos cercos e mergulhos (`None` muda todas)
+    pub update_probability: Option<f64>,
+    /// Passos escalados pelo ranking de cada falcão
+    pub rank_steps: Option<RankSteps>,
+    /// O que fazer com uma fitness não finita
+    pub non_finite: NonFinite,
+    /// Agendas de parâmetros numéricos; a última de cada parâmetro vale
+    pub schedules: Vec<(Parameter, Schedule)>,
+    /// Mantém a memória por falcão (melhor pessoal, estagnação, idade, origem)
+    pub track_hawks: bool,
+    /// Mesma semente de decodificador para todas as avaliações de uma iteração
+    pub common_random_numbers: bool,
+    pub update_mode: UpdateMode,
+    pub jump_strength: JumpStrength,
+    pub attractor: Attractor,
+    pub termination: Option<Arc<dyn Criterion>>,
+    pub observers: Observers,
+    pub mut_observers: MutObservers,
+    pub early_stop: Option<EarlyStop>,
+    pub cancellation: Option<CancellationToken>,
+    #[cfg(feature = "serde")]
+    pub checkpoint: Option<CheckpointPolicy>,
+    /// Semente mestra; `None` sorteia uma nova a cada execução
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+    /// Duração alvo de um lote de falcões; `None` atualiza a população inteira de uma vez
+    pub batch_latency: Option<Duration>,
+    /// Reparte o orçamento restante entre os falcões de uma varredura paralela em cotas fixas
+    pub deterministic_parallel: bool,
+    /// Nome do problema e etiquetas copiados para os metadados do relatório
+    pub problem_name: Option<String>,
+    pub tags: BTreeMap<String, String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            pop_size: 30,
+            max_iters: 500,
+            params: Params::default(),
+            adaptation: Adaptation::Off,
+            partner_selection: PartnerSelection::Uniform,
+            niching: Niching::Off,
+            dynamic: Dynamic::default(),
+            dedup: None,
+            immigrants: None,
+            zoom: None,
+            fidelity: None,
+            dive_replay: None,
+            dive_shape: DiveShape::Isotropic,
+            mirror_dives: false,
+            counters: None,
+            distance: Arc::new(Norm::Euclidean),
+            hall_of_fame: None,
+            record_evaluations: false,
+            curve_sampling: CurveSampling::All,
+            keep_population: false,
+            memory_budget: None,
+            clusters: None,
+            trajectory: None,
+            exploitation: Exploitation::Classic,
+            fitness_scaling: FitnessScaling::Raw,
+            orthogonal_learning: None,
+            local_refiner: None,
+            refiner_schedule: None,
+            reevaluate: None,
+            momentum: 0.0,
+            update_probability: None,
+            rank_steps: None,
+            non_finite: NonFinite::Keep,
+            schedules: Vec::new(),
+            track_hawks: false,
+            common_random_numbers: false,
+            update_mode: UpdateMode::FullVector,
+            jump_strength: JumpStrength::Uniform,
+            attractor: Attractor::Rabbit,
+            termination: None,
+            observers: Observers::default(),
+            mut_observers: MutObservers::default(),
+            early_stop: None,
+            cancellation: None,
+            #[cfg(feature = "serde")]
+            checkpoint: None,
+            seed: None,
+            rng: RngKind::ChaCha12,
+            batch_latency: None,
+            deterministic_parallel: false,
+            problem_name: None,
+            tags: BTreeMap::new(),
+        }
+    }
+}
+
+/// Estado mutável de uma execução
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct State {
+    pub positions: Population,
+    pub fitness: Vec<f64>,
+    pub rabbit: Vec<f64>,
+    pub rabbit_fit: f64,
+    /// Amostras cuja média é `rabbit_fit` (1 até a primeira reavaliação)
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub rabbit_samples: usize,
+    pub iter: usize,
+    pub evals: usize,
+    pub curve: Vec<f64>,
+    /// Iteração (a partir de 1) de cada ponto de `curve`
+    pub curve_iters: Vec<usize>,
+    /// Avaliações gastas em cada ponto de `curve`
+    pub curve_evals: Vec<usize>,
+    /// Posições do coelho registradas (vazio quando a trajetória está desligada)
+    pub trajectory: Vec<Vec<f64>>,
+    /// Diversidade por dimensão da população em cada ponto de `curve`
+    pub diversity: Vec<f64>,
+    /// Ramos tomados pelos falcões na última iteração
+    pub phases: PhaseCounts,
+    /// Média de |E| na última iteração
+    pub energy: f64,
+    /// `energy` e `phases` em cada ponto de `curve`
+    pub energy_history: Vec<f64>,
+    pub phase_history: Vec<PhaseCounts>,
+    /// Coeficiente de penalidade do decodificador na última iteração, se ele informar um
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub penalty: Option<f64>,
+    /// `penalty` em cada ponto de `curve` (vazio quando o decodificador não informa)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub penalty_history: Vec<f64>,
+    /// Grade dos agrupamentos, quando registrados
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid: Option<Grid>,
+    /// Agrupamentos e entropia de ocupação em cada ponto de `curve` (vazios sem `grid`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub clusters: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub occupancy_entropy: Vec<f64>,
+    /// Usos e melhoras de cada operador desde o início
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub operators: OperatorStats,
+    /// Escala da fitness ajustada à população no início da iteração (`None` sem escala)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scaler: Option<Scaler>,
+    /// Crédito das regras de aproveitamento (só com `Exploitation::Adaptive`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub credit: Option<Credit>,
+    /// Duração de cada iteração registrada
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub iteration_times: Vec<Duration>,
+    /// Duração da última iteração
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub iter_time: Duration,
+    /// Tempo gasto no decodificador, somado entre as threads
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eval_time: Duration,
+    pub changes: usize,
+    /// Pontos vigiados pela detecção por sentinelas (`None` até serem posicionados)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sentinels: Option<Sentinels>,
+    /// Falcões duplicados substituídos até agora
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub purged: usize,
+    /// Avaliações de triagem em baixa fidelidade
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub screened: usize,
+    /// Avaliações gastas pelo refinador local
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refined: usize,
+    /// Avaliações do refinador local já merecidas e ainda não gastas (só com cronograma)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refine_credit: f64,
+    /// Custo das triagens ainda não convertido numa avaliação completa, em `[0, 1)`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub screen_debt: f64,
+    pub hall_of_fame: Option<HallOfFame>,
+    /// Todas as avaliações completas (`None` quando o registro está desligado)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evaluations: Option<EvaluationArchive>,
+    /// Último deslocamento de cada falcão (vazio quando o momento está desligado)
+    pub velocity: Population,
+    /// Deslocamentos dos mergulhos que melhoraram (`None` sem `DiveReplay`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub displacements: Option<Displacements>,
+    /// Metadados por falcão (`None` quando o rastreamento está desligado)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub memory: Option<HawkMemory>,
+    pub budget: Budget,
+    /// Iterações desde a última melhora do coelho
+    pub stall: usize,
+    /// Escala da caixa de busca em torno do coelho (`None` quando o zoom está desligado)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zoom: Option<f64>,
+    /// Tempo acumulado até o último ponto de retomada
+    pub spent: Duration,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
+    pub clock: Instant,
+    /// Dimensões congeladas pela API passo a passo (vazio quando nenhuma está)
+    pub frozen: Vec<bool>,
+    /// Dimensões fixas dos limites, fora da diversidade (vazio quando nenhuma é)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fixed: Vec<bool>,
+    /// Primeiro erro do decodificador; nenhuma avaliação é feita depois dele
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub error: Option<DecoderError>,
+    /// Coluna de trabalho do cálculo da diversidade
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub column: Vec<f64>,
+    pub seed: u64,
+    /// Estado do mapa logístico da busca caótica (vazio até a primeira)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chaos: Vec<f64>,
+    /// Passos da busca de padrões, mantidos entre iterações (vazios até a primeira)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub steps: Vec<f64>,
+    /// Um ponto a cada `curve_stride` iterações amostradas entra nas curvas (1 sem limite
+    /// de memória)
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub curve_stride: usize,
+    /// Idem para a trajetória
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub trajectory_stride: usize,
+    /// O registro de avaliações parou de crescer por falta de memória
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub archive_capped: bool,
+    /// Registros reduzidos para caber no orçamento de memória
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub degradations: Vec<Degradation>,
+    /// Melhoras estritas da melhor fitness já avaliada
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub improvements: Vec<Improvement>,
+    /// Números aleatórios comuns: a semente do decodificador só depende da iteração
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub common: bool,
+    /// Política para fitness não finitas
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sanitize: NonFinite,
+    /// Fitness não finitas devolvidas pelo decodificador
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub non_finite: usize,
+}
+
+#[cfg(feature = "serde")]
+const fn one() -> usize {
+    1
+}
+
+/// Descarta um elemento em dois, mantendo sempre o último
+fn halve<T>(v: &mut Vec<T>) {
+    let n = v.len();
+    let mut k = 0;
+    v.retain(|_| {
+        k += 1;
+        (n - k).is_multiple_of(2)
+    });
+}
+
+impl State {
+    /// Wall-clock time of the run so far, across resumes.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.spent + self.clock.elapsed()
+    }
+
+    /// Folds the running clock into `spent`, so a checkpoint carries the time used so far.
+    #[cfg(feature = "serde")]
+    pub fn rebase_clock(&mut self) {
+        self.spent = self.elapsed();
+        self.clock = Instant::now();
+    }
+
+    /// Records iteration `t` if `sampling` keeps it; `last` forces the final iteration in.
+    pub fn sample(&mut self, t: usize, sampling: CurveSampling, last: bool) {
+        if let CurveSampling::Reservoir { size } = sampling {
+            let size = size.max(1);
+            if self.curve.len() >= size {
+                // Algoritmo R: o ponto t entra com probabilidade size/t, no lugar de um
+                // sorteado; a última iteração sempre entra
+                let pool = if last { size } else { t };
+                #[allow(clippy::cast_possible_truncation)]
+                let slot = (SeedStreams::new(self.seed).mix(t as u64) % pool as u64) as usize;
+                if slot >= size {
+                    return;
+                }
+                self.evict(slot);
+            }
+        } else if !last && (!sampling.records(t) || !t.is_multiple_of(self.curve_stride)) {
+            return;
+        }
+        self.record(t);
+    }
+
+    /// Bytes ocupados pelas curvas e pelo registro de melhoras, pela trajetória, pelo registro
+    /// de avaliações e pelo hall da fama
+    fn footprint(&self) -> [usize; 4] {
+        const F64: usize = size_of::<f64>();
+        const VEC: usize = size_of::<Vec<f64>>();
+        let point = 6 * F64 + size_of::<PhaseCounts>() + size_of::<Duration>();
+        let clusters = self.clusters.len() * (size_of::<usize>() + F64);
+        let log = self.improvements.len() * size_of::<Improvement>();
+        let dim = self.rabbit.len();
+        let archive = self.evaluations.as_ref().map_or(0, EvaluationArchive::len);
+        let fame = self.hall_of_fame.as_ref().map_or(0, |h| h.entries().len());
+        [
+            self.curve.len() * point + clusters + log,
+            self.trajectory.len() * (dim * F64 + VEC),
+            archive * (dim * F64 + F64 + VEC),
+            fame * (dim * F64 + F64 + VEC),
+        ]
+    }
+
+    /// Total de [`State::footprint`]
+    pub fn recorded_bytes(&self) -> usize {
+        self.footprint().iter().sum()
+    }
+
+    /// Reduz os registros até caberem em `budget` bytes: o registro de avaliações para de
+    /// crescer, a trajetória e as curvas, com o registro de melhoras, perdem um ponto em dois,
+    /// a maior delas primeiro, e, se não bastar, o registro de avaliações é descartado
+    pub fn fit_memory(&mut self, budget: usize) {
+        if self.recorded_bytes() <= budget {
+            return;
+        }
+        let iteration = self.iter + 1;
+        if !self.archive_capped
+            && let Some(archive) = &self.evaluations
+        {
+            self.archive_capped = true;
+            self.degradations.push(Degradation::EvaluationsCapped {
+                iteration,
+                kept: archive.len(),
+            });
+        }
+        let strides = (self.curve_stride, self.trajectory_stride);
+        let mut log_thinned = false;
+        while self.recorded_bytes() > budget {
+            let [curves, trajectory, ..] = self.footprint();
+            let shrinkable = self.curve.len() > 1 || self.improvements.len() > 1;
+            if self.trajectory.len() > 1 && (trajectory >= curves || !shrinkable) {
+                halve(&mut self.trajectory);
+                self.trajectory_stride *= 2;
+            } else if shrinkable {
+                // O registro de melhoras não tem passo: só perde um ponto em dois, ficando a última
+                log_thinned |= self.improvements.len() > 1;
+                halve(&mut self.improvements);
+                if self.curve.len() > 1 {
+                    halve(&mut self.curve);
+                    halve(&mut self.curve_iters);
+                    halve(&mut self.curve_evals);
+                    halve(&mut self.diversity);
+                    halve(&mut self.energy_history);
+                    halve(&mut self.phase_history);
+                    halve(&mut self.iteration_times);
+                    halve(&mut self.penalty_history);
+                    halve(&mut self.clusters);
+                    halve(&mut self.occupancy_entropy);
+                    self.curve_stride *= 2;
+                }
+            } else {
+                break;
+            }
+        }
+        if self.trajectory_stride != strides.1 {
+            self.degradations.push(Degradation::TrajectoryThinned {
+                iteration,
+                stride: self.trajectory_stride,
+            });
+        }
+        if self.curve_stride != strides.0 || log_thinned {
+            self.degradations.push(Degradation::CurvesThinned {
+                iteration,
+                stride: self.curve_stride,
+            });
+        }
+        if self.recorded_bytes() > budget && self.evaluations.take().is_some() {
+            self.degradations
+                .push(Degradation::EvaluationsDropped { iteration });
+        }
+    }
+
+    /// Drops the `k`-th recorded point of every curve.
+    fn evict(&mut self, k: usize) {
+        self.curve.remove(k);
+        self.curve_iters.remove(k);
+        self.curve_evals.remove(k);
+        self.diversity.remove(k);
+        self.energy_history.remove(k);
+        self.phase_history.remove(k);
+        self.iteration_times.remove(k);
+        if k < self.penalty_history.len() {
+            self.penalty_history.remove(k);
+        }
+        if k < self.clusters.len() {
+            self.clusters.remove(k);
+            self.occupancy_entropy.remove(k);
+        }
+    }
+
+    /// Appends the current best, evaluation count, diversity, escape energy, phase counts,
+    /// iteration time, penalty coefficient and clusters to the curves, as iteration `t`.
+    fn record(&mut self, t: usize) {
+        self.curve.push(self.rabbit_fit);
+        self.curve_iters.push(t);
+        self.curve_evals.push(self.evals);
+        self.energy_history.push(self.energy);
+        self.phase_history.push(self.phases);
+        self.iteration_times.push(self.iter_time);
+        if let Some(p) = self.penalty {
+            self.penalty_history.push(p);
+        }
+        let d = dimension_wise_diversity_masked(&self.positions, &self.fixed, &mut self.column);
+        self.diversity.push(d);
+        if let Some(grid) = &self.grid {
+            let occupancy = grid.occupancy(&self.positions);
+            self.clusters.push(occupancy.clusters);
+            self.occupancy_entropy.push(occupancy.entropy);
+        }
+    }
+
+    /// Read-only view handed to the observers.
+    pub fn context(&self) -> IterationContext<'_> {
+        let elapsed = self.elapsed();
+        IterationContext {
+            iter: self.iter,
+            evals: self.evals,
+            best_fitness: self.rabbit_fit,
+            best_position: &self.rabbit,
+            positions: &self.positions,
+            fitness: &self.fitness,
+            stall_iters: self.stall,
+            elapsed,
+            energy: self.energy,
+            phases: self.phases,
+            progress: self.budget.progress(self.iter, self.evals, elapsed),
+            hawks: self.memory.as_ref(),
+        }
+    }
+
+    /// Snapshot handed to the termination criteria.
+    pub fn status<O: Objective>(&self) -> Status {
+        let elapsed = self.elapsed();
+        Status {
+            iter: self.iter,
+            evals: self.evals,
+            best_fitness: self.rabbit_fit,
+            stall_iters: self.stall,
+            elapsed,
+            better: O::better,
+        }
+    }
+
+    /// Fraction of the budget already used (`t/T` for iteration budgets).
+    #[inline]
+    pub fn progress(&self) -> f64 {
+        self.budget.progress(self.iter, self.evals, self.elapsed())
+    }
+
+    /// Evaluations left in an evaluation budget (`usize::MAX` for other budgets, `0` after a
+    /// decoder failure).
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        if self.failed() {
+            return 0;
+        }
+        match self.budget {
+            Budget::Evaluations(n) => n.saturating_sub(self.evals),
+            _ => usize::MAX,
+        }
+    }
+
+    /// Makes hawk `i` the rabbit.
+    pub fn promote(&mut self, i: usize) {
+        self.rabbit.clear();
+        self.rabbit.extend_from_slice(&self.positions[i]);
+        self.rabbit_fit = self.fitness[i];
+        self.rabbit_samples = 1;
+    }
+
+    /// Evaluates the rabbit once more and folds the sample into the running mean of its
+    /// fitness, so a lucky draw of a noisy objective does not keep it on top for good.
+    pub fn reevaluate_rabbit<O: Objective>(&mut self, decoder: &dyn Decoder) {
+        let rabbit = std::mem::take(&mut self.rabbit);
+        if let Some(f) = self.evaluate::<O>(decoder, &rabbit) {
+            self.rabbit_samples += 1;
+            #[allow(clippy::cast_precision_loss)]
+            let n = self.rabbit_samples as f64;
+            self.rabbit_fit += (f - self.rabbit_fit) / n;
+        }
+        self.rabbit = rabbit;
+    }
+
+    /// Records a hawk move of `phase` from fitness `old` to `new` in the operator statistics
+    /// and, under adaptive exploitation, credits the rule that made it with the relative
+    /// improvement. Returns the improvement, if any, measured on the scaled fitness when a
+    /// [`FitnessScaling`] is set.
+    pub fn credit_move<O: Objective>(&mut self, phase: Phase, old: f64, new: f64) -> Option<f64> {
+        let gain = O::better(new, old).then(|| (old - new).abs());
+        self.operators.record(phase, gain);
+        // Com escala, o ganho já é adimensional e não precisa ser relativo a `old`
+        let scaled = self
+            .scaler
+            .as_ref()
+            .map_or(gain, |s| gain.map(|_| (s.apply(old) - s.apply(new)).abs()));
+        if let (Some(credit), Some(k)) = (&mut self.credit, Exploitation::arm(phase)) {
+            let reward = match (&self.scaler, scaled) {
+                (_, None) => 0.0,
+                (Some(_), Some(g)) => g.min(1.0),
+                (None, Some(g)) => (g / old.abs().max(f64::MIN_POSITIVE)).min(1.0),
+            };
+            credit.reward(k, reward);
+        }
+        scaled
+    }
+
+    /// Promotes the best hawk if it beats the rabbit, or if the rabbit is `NaN`.
+    pub fn refresh_rabbit<O: Objective>(&mut self) {
+        if let Some(b) = best_index_with::<O>(&self.fitness)
+            && (O::better(self.fitness[b], self.rabbit_fit) || self.rabbit_fit.is_nan())
+            && !self.fitness[b].is_nan()
+        {
+            self.promote(b);
+            // Avaliações em lote não passam por `evaluate`
+            self.improve::<O>(self.rabbit_fit);
+        }
+    }
+
+    /// Registra `f` se supera a última melhora, com a contagem de avaliações atual
+    fn improve<O: Objective>(&mut self, f: f64) {
+        let last = self.improvements.last().map(|e| e.fitness);
+        if !f.is_nan() && last.is_none_or(|l| O::better(f, l)) {
+            self.improvements.push(Improvement {
+                iteration: self.iter + 1,
+                evaluation: self.evals,
+                fitness: f,
+                delta: last.map_or(0.0, |l| (l - f).abs()),
+            });
+        }
+    }
+
+    #[inline]
+    pub const fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Records a decoder error; only the first one is kept.
+    pub fn fail(&mut self, error: DecoderError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Evaluates `x`, counting the evaluation and offering the point to the hall of fame.
+    ///
+    /// Every decoder call of the main loop goes through here. Returns `None`, without calling
+    /// the decoder, once an evaluation budget is spent, and `None` (flagging the state as
+    /// failed, see [`State::fail`]) when the decoder returns an error.
+    pub fn evaluate<O: Objective>(&mut self, decoder: &dyn Decoder, x: &[f64]) -> Option<f64> {
+        self.evaluate_raw::<O>(decoder, x).map(|(_, f)| f)
+    }
+
+    /// Like [`State::evaluate`], also returning the value of the decoder before the
+    /// [`NonFinite`] policy: `(raw, fitness)`.
+    pub fn evaluate_raw<O: Objective>(
+        &mut self,
+        decoder: &dyn Decoder,
+        x: &[f64],
+    ) -> Option<(f64, f64)> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        let ctx = EvalContext {
+            iteration: self.iter,
+            evaluation: self.evals,
+            progress: self.progress(),
+            seed: SeedStreams::new(self.seed).evaluation(
+                self.iter as u64,
+                u64::MAX,
+                self.evals as u64,
+                self.common,
+            ),
+        };
+        let started = Instant::now();
+        let result = decoder.decode_with(x, &ctx);
+        self.eval_time += started.elapsed();
+        let raw = match result {
+            Ok(f) => f,
+            Err(e) => {
+                self.fail(e);
+                return None;
+            }
+        };
+        self.evals += 1;
+        if !raw.is_finite() {
+            self.non_finite += 1;
+        }
+        let f = match self.sanitize.apply(O::worst(), x, raw) {
+            Ok(f) => f,
+            Err(e) => {
+                self.fail(e);
+                return None;
+            }
+        };
+        if let Some(hof) = &mut self.hall_of_fame {
+            hof.offer::<O>(x, f);
+        }
+        if let Some(archive) = &mut self.evaluations
+            && !self.archive_capped
+        {
+            archive.push(x, f);
+        }
+        self.improve::<O>(f);
+        Some((raw, f))
+    }
+}
+
+/// Scratch buffers reused by every hawk update.
+#[derive(Debug)]
+pub(crate) struct Scratch {
+    /// Cópia do falcão em atualização na varredura sequencial
+    pub x: Vec<f64>,
+    pub mean: Vec<f64>,
+    pub buffers: Buffers,
+    /// Retrato da população lido pela varredura paralela
+    #[cfg(feature = "rayon")]
+    pub snapshot: Population,
+    pub partner_weights: Vec<f64>,
+    /// Sementes das espécies (cópias, pois as posições mudam durante a varredura)
+    pub niche_seeds: Population,
+    pub species: Vec<usize>,
+    pub elite: Vec<f64>,
+    /// Fator que molda os voos de Lévy dos mergulhos (vazio com passos isotrópicos)
+    pub shape: Vec<f64>,
+    /// Fator do passo de cada falcão pelo seu ranking (vazio sem [`RankSteps`])
+    pub step_factors: Vec<f64>,
+    /// Tempo medido por falcão no último lote, usado para dimensionar o próximo
+    pub per_hawk: Option<Duration>,
+    /// Caixa reduzida em torno do coelho, quando o zoom está ativo e abaixo da escala 1
+    pub window: Option<Bounds>,
+}
+
+impl Scratch {
+    pub fn new(dim: usize, pop_size: usize) -> Self {
+        Self {
+            x: vec![0.0; dim],
+            mean: vec![0.0; dim],
+            buffers: Buffers::new(dim),
+            #[cfg(feature = "rayon")]
+            snapshot: Population::new(dim),
+            partner_weights: Vec::with_capacity(pop_size),
+            niche_seeds: Population::new(dim),
+            species: Vec::with_capacity(pop_size),
+            elite: vec![0.0; dim],
+            shape: Vec::new(),
+            step_factors: Vec::new(),
+            per_hawk: None,
+            window: None,
+        }
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    #[inline]
+    #[must_use]
+    pub const fn bounds(&self) -> &Bounds {
+        &self.bounds
+    }
+
+    /// Fingerprint of the bounds and settings of this optimizer, recorded in
+    /// [`Report::config_hash`]: two optimizers built the same way share it whatever their
+    /// seed, observers, cancellation token, checkpoints, problem name or tags. The hash is stable for a given
+    /// version of the crate only.
+    #[must_use]
+    pub fn config_hash(&self) -> u64 {
+        let mut settings = self.settings.clone();
+        settings.seed = None;
+        settings.counters = None;
+        settings.observers = Observers::default();
+        settings.mut_observers = MutObservers::default();
+        settings.early_stop = None;
+        settings.cancellation = None;
+        settings.problem_name = None;
+        settings.tags.clear();
+        #[cfg(feature = "serde")]
+        {
+            settings.checkpoint = None;
+        }
+        // FNV-1a, que não depende da versão da biblioteca padrão
+        format!("{:?}{settings:?}", self.bounds)
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |h, b| {
+                (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn pop_size(&self) -> usize {
+        self.settings.pop_size
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn max_iters(&self) -> usize {
+        self.settings.max_iters
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn params(&self) -> Params {
+        self.settings.params
+    }
+
+    /// Runs the optimizer for `max_iters` iterations and returns the best solution found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HhoError`] if the configuration is invalid, if the initializer rejects the
+    /// population size or if the decoder fails; in the last case the error carries the report
+    /// of the run up to the failure.
+    pub fn run(&self, decoder: &dyn Decoder) -> Result<Report, HhoError> {
+        self.run_with_budget(decoder, Budget::Iterations(self.settings.max_iters))
+    }
+
+    /// Runs the optimizer until `budget` is exhausted. The escape energy schedule follows the
+    /// fraction of the budget used, so evaluation and wall-clock budgets are handled naturally.
+    ///
+    /// With [`Budget::WallClock`] the run stops before an iteration that would overshoot the
+    /// deadline, judged by the slowest iteration so far; [`Report::duration`] and
+    /// [`Report::iters`] tell what was actually done.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::run`], plus [`HhoError::CheckpointWrite`] if a checkpoint cannot be
+    /// written.
+    pub fn run_with_budget(
+        &self,
+        decoder: &dyn Decoder,
+        budget: Budget,
+    ) -> Result<Report, HhoError> {
+        self.start_with_budget(decoder, budget)?.try_complete()
+    }
+
+    /// Like [`Hho::run`], but the hawks of each iteration are updated and evaluated in
+    /// parallel. Each hawk draws from its own random stream (see [`SeedStreams::task`]), so
+    /// a seeded run gives the same result whatever the number of threads.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::run`].
+    #[cfg(feature = "rayon")]
+    pub fn run_parallel(&self, decoder: &(dyn Decoder + Sync)) -> Result<Report, HhoError> {
+        self.run_parallel_with_budget(decoder, Budget::Iterations(self.settings.max_iters))
+    }
+
+    /// Like [`Hho::run_with_budget`], with the hawks of each iteration updated and evaluated
+    /// in parallel; see [`Hho::run_parallel`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::run_with_budget`].
+    #[cfg(feature = "rayon")]
+    pub fn run_parallel_with_budget(
+        &self,
+        decoder: &(dyn Decoder + Sync),
+        budget: Budget,
+    ) -> Result<Report, HhoError> {
+        self.start_parallel_with_budget(decoder, budget)?
+            .try_complete()
+    }
+
+    /// Uma execução sequencial completa com a semente `seed`
+    pub(crate) fn run_seeded(&self, decoder: &dyn Decoder, seed: u64) -> Result<Report, HhoError> {
+        let budget = Budget::Iterations(self.settings.max_iters);
+        let (state, history, rng) = self.fresh_from(decoder, budget, SeedStreams::new(seed))?;
+        Run::new(self, DecoderRef::Local(decoder), state, history, rng).try_complete()
+    }
+
+    /// Continues the run saved in the checkpoint at `path` (see
+    /// [`crate::hho::HhoBuilder::checkpoint`]) with the population, counters, budget and RNG
+    /// state it had when saved.
+    ///
+    /// `self` must be configured as the optimizer that wrote the checkpoint; only the dimension
+    /// and population size are verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Checkpoint`] if the file cannot be read or parsed, if it was written
+    /// by an optimizer with a different dimension or population size, or if a later checkpoint
+    /// cannot be written, and [`HhoError::DimensionMismatch`] if `decoder` needs another
+    /// dimension. Otherwise fails like [`Hho::run`].
+    #[cfg(feature = "serde")]
+    pub fn resume(
+        &self,
+        path: impl AsRef<Path>,
+        decoder: &dyn Decoder,
+    ) -> Result<Report, HhoError> {
+        self.validate()?;
+        self.resume_from(Checkpoint::load(path.as_ref())?, decoder)
+    }
+
+    /// Like [`Hho::resume`], for the checkpoint written by
+    /// [`crate::hho::HhoBuilder::rolling_checkpoint`] at `path`: continues from the most
+    /// advanced of its two files that can still be read.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::resume`]; the read error is the one of the first file when neither can
+    /// be read.
+    #[cfg(feature = "serde")]
+    pub fn resume_rolling(
+        &self,
+        path: impl AsRef<Path>,
+        decoder: &dyn Decoder,
+    ) -> Result<Report, HhoError> {
+        self.validate()?;
+        self.resume_from(Checkpoint::load_latest(path.as_ref())?, decoder)
+    }
+
+    /// Retoma a execução salva em `checkpoint`
+    #[cfg(feature = "serde")]
+    fn resume_from(
+        &self,
+        checkpoint: Checkpoint,
+        decoder: &dyn Decoder,
+    ) -> Result<Report, HhoError> {
+        self.check_dim(decoder)?;
+        if checkpoint.dim != self.bounds.dim() || checkpoint.pop_size != self.settings.pop_size {
+            return Err(CheckpointError::Mismatch {
+                dim: checkpoint.dim,
+                pop_size: checkpoint.pop_size,
+            }
+            .into());
+        }
+        Run::new(
+            self,
+            DecoderRef::Local(decoder),
+            checkpoint.state,
+            checkpoint.history,
+            checkpoint.rng,
+        )
+        .try_complete()
+    }
+
+    /// Checks that `decoder` accepts the dimension of the bounds, when it declares one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::DimensionMismatch`] if [`Decoder::expected_dim`] differs from the
+    /// dimension of the bounds.
+    pub fn check_dim(&self, decoder: &dyn Decoder) -> Result<(), HhoError> {
+        match decoder.expected_dim() {
+            Some(expected) if expected != self.bounds.dim() => Err(HhoError::DimensionMismatch {
+                expected,
+                actual: self.bounds.dim(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the settings the builder cannot check on its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Init`] for an empty population and otherwise [`HhoError::Config`]
+    /// describing every invalid setting, see [`Hho::check`].
+    pub fn validate(&self) -> Result<(), HhoError> {
+        // Uma população vazia segue sendo um erro de inicialização
+        if self.settings.pop_size == 0 {
+            return Err(InitError::InvalidPopSize(0).into());
+        }
+        self.check().map_err(|e| HhoError::Config(e.to_string()))
+    }
+
+    /// Checks every setting, including the ones that only conflict with each other (e.g. an
+    /// elite count larger than the population).
+    ///
+    /// # Errors
+    ///
+    /// A [`ConfigError`] listing all the problems found, not just the first one.
+    pub fn check(&self) -> Result<(), ConfigError> {
+        let settings = &self.settings;
+        let pop_size = settings.pop_size;
+        let mut problems = Vec::new();
+        if self.bounds.dim() == 0 {
+            problems.push("bounds have no dimensions".to_owned());
+        }
+        if pop_size == 0 {
+            problems.push("pop_size must be at least 1".to_owned());
+        }
+        let p = settings.params;
+        for (name, value, (lo, hi)) in [
+            ("energy_decay", p.energy_decay, Params::ENERGY_DECAY_RANGE),
+            ("dive_scale", p.dive_scale, Params::DIVE_SCALE_RANGE),
+            ("levy_beta", p.levy_beta, Params::LEVY_BETA_RANGE),
+        ] {
+            if !(lo..=hi).contains(&value) {
+                problems.push(format!("{name} = {value} is outside [{lo}, {hi}]"));
+            }
+        }
+        let momentum = settings.momentum;
+        if !momentum.is_finite() || momentum < 0.0 {
+            problems.push(format!(
+                "momentum = {momentum} must be finite and non-negative"
+            ));
+        }
+        problems.extend(
+            settings
+                .update_probability
+                .filter(|p| p.is_nan() || *p <= 0.0 || *p > 1.0)
+                .map(|p| format!("update probability = {p} must be in (0, 1]")),
+        );
+        if let Some(problem) = settings.niching.problem(settings.pop_size) {
+            problems.push(problem);
+        }
+        if let Some(Dedup { epsilon, .. }) = settings.dedup
+            && !(epsilon.is_finite() && epsilon > 0.0)
+        {
+            problems.push(format!(
+                "dedup epsilon = {epsilon} must be finite and positive"
+            ));
+        }
+        if let Some(problem) = settings.exploitation.problem() {
+            problems.push(problem);
+        }
+        if let Some(Immigrants { fraction, .. }) = settings.immigrants
+            && !(fraction > 0.0 && fraction < 1.0)
+        {
+            problems.push(format!("immigrant fraction = {fraction} must be in (0, 1)"));
+        }
+        problems.extend(settings.zoom.map(|z| z.problems()).unwrap_or_default());
+        problems.extend(settings.fidelity.and_then(|f| f.problem()));
+        problems.extend(settings.dive_replay.and_then(|r| r.problem()));
+        if let Attractor::EliteCentroid { k } = settings.attractor
+            && !(1..=pop_size).contains(&k)
+        {
+            problems.push(format!(
+                "elite centroid of {k} hawks needs 1 to pop_size = {pop_size} elites"
+            ));
+        }
+        if let Some(ol) = settings.orthogonal_learning {
+            if !(1..=pop_size).contains(&ol.elites) {
+                problems.push(format!(
+                    "orthogonal learning with {} elites needs 1 to pop_size = {pop_size}",
+                    ol.elites
+                ));
+            }
+            if ol.factors == 0 {
+                problems.push("orthogonal learning needs at least one factor".to_owned());
+            }
+        }
+        problems.extend(settings.local_refiner.and_then(|r| r.problem()));
+        if let Some(schedule) = settings.refiner_schedule {
+            problems.extend(schedule.problem());
+            if settings.local_refiner.is_none() {
+                problems.push("a refiner schedule needs a local refiner".to_owned());
+            }
+        }
+        problems.extend(settings.rank_steps.and_then(|r| r.problem()));
+        problems.extend(settings.schedule_problems());
+        if settings.reevaluate == Some(0) {
+            problems.push("rabbit re-evaluation interval must be at least 1".to_owned());
+        }
+        if let ChangeDetection::Sentinels { count } = settings.dynamic.detection
+            && count > pop_size
+        {
+            problems.push(format!("{count} sentinels exceed pop_size = {pop_size}"));
+        }
+        let rerandomize = settings.dynamic.rerandomize;
+        if !(0.0..=1.0).contains(&rerandomize) {
+            problems.push(format!("rerandomize = {rerandomize} is outside [0, 1]"));
+        }
+        if settings.deterministic_parallel && settings.batch_latency.is_some() {
+            problems.push("deterministic parallel mode cannot size batches by latency".to_owned());
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { problems })
+        }
+    }
+
+    /// Estado inicial, memória de adaptação e RNG de uma nova execução
+    pub(crate) fn fresh(
+        &self,
+        decoder: &dyn Decoder,
+        budget: Budget,
+    ) -> Result<(State, Option<SuccessHistory>, StreamRng), HhoError> {
+        let streams = self
+            .settings
+            .seed
+            .map_or_else(SeedStreams::from_entropy, SeedStreams::new);
+        self.fresh_from(decoder, budget, streams)
+    }
+
+    /// Like [`Hho::fresh`], with the master seed of `streams` instead of the configured one
+    /// (the generator is always the configured one).
+    pub(crate) fn fresh_from(
+        &self,
+        decoder: &dyn Decoder,
+        budget: Budget,
+        streams: SeedStreams,
+    ) -> Result<(State, Option<SuccessHistory>, StreamRng), HhoError> {
+        self.validate()?;
+        self.check_dim(decoder)?;
+        let streams = streams.with_kind(self.settings.rng);
+        let state = self.init_state(decoder, budget, streams)?;
+        #[cfg(feature = "log")]
+        log::debug!(
+            "initialized {} hawks with {} evaluations in {:?} (seed {}), best = {}",
+            state.positions.len(),
+            state.evals,
+            state.elapsed(),
+            state.seed,
+            state.rabbit_fit
+        );
+        let history = self.settings.adaptation.history(self.settings.params);
+        Ok((state, history, streams.iterations()))
+    }
+
+    pub(crate) fn init_state(
+        &self,
+        decoder: &dyn Decoder,
+        budget: Budget,
+        streams: SeedStreams,
+    ) -> Result<State, InitError> {
+        let clock = Instant::now();
+        let positions = self.initializer.initialize(
+            self.settings.pop_size,
+            &self.bounds,
+            &mut streams.init(),
+        )?;
+        let n = positions.len();
+        let (iter, evals) = self.settings.counters.unwrap_or_default();
+        let mut state = State {
+            rabbit: Vec::new(),
+            rabbit_fit: O::worst(),
+            rabbit_samples: 1,
+            evals,
+            positions,
+            fitness: Vec::with_capacity(n),
+            iter,
+            curve: Vec::new(),
+            curve_iters: Vec::new(),
+            curve_evals: Vec::new(),
+            trajectory: Vec::new(),
+            diversity: Vec::new(),
+            phases: PhaseCounts::default(),
+            operators: OperatorStats::new(),
+            scaler: None,
+            credit: match self.settings.exploitation {
+                Exploitation::Adaptive { selection } => {
+                    Some(Credit::new(Exploitation::ARMS.len(), selection))
+                }
+                _ => None,
+            },
+            energy: 0.0,
+            energy_history: Vec::new(),
+            phase_history: Vec::new(),
+            penalty: None,
+            penalty_history: Vec::new(),
+            grid: self
+                .settings
+                .clusters
+                .map(|cells| Grid::new(&self.bounds, cells)),
+            clusters: Vec::new(),
+            occupancy_entropy: Vec::new(),
+            iteration_times: Vec::new(),
+            iter_time: Duration::ZERO,
+            eval_time: Duration::ZERO,
+            budget,
+            stall: 0,
+            zoom: self.settings.zoom.map(|_| 1.0),
+            seed: streams.seed(),
+            chaos: Vec::new(),
+            steps: Vec::new(),
+            curve_stride: 1,
+            trajectory_stride: 1,
+            archive_capped: false,
+            degradations: Vec::new(),
+            improvements: Vec::new(),
+            common: self.settings.common_random_numbers,
+            sanitize: self.settings.non_finite,
+            non_finite: 0,
+            spent: Duration::ZERO,
+            clock,
+            frozen: Vec::new(),
+            fixed: self.bounds.fixed_mask(),
+            error: None,
+            column: Vec::with_capacity(n),
+            changes: 0,
+            sentinels: None,
+            purged: 0,
+            screened: 0,
+            refined: 0,
+            refine_credit: 0.0,
+            screen_debt: 0.0,
+            hall_of_fame: self.settings.hall_of_fame.clone(),
+            evaluations: self
+                .settings
+                .record_evaluations
+                .then(|| EvaluationArchive::new().with_distance(self.settings.distance.clone())),
+            velocity: self.velocity(n),
+            displacements: self
+                .settings
+                .dive_replay
+                .map(|r| Displacements::new(r.capacity, self.bounds.dim())),
+            memory: None,
+        };
+        // A inicialização também consome o orçamento; falcões sem avaliação ficam com a pior
+        // fitness e são os primeiros a serem substituídos
+        let positions = std::mem::take(&mut state.positions);
+        for x in &positions {
+            let f = state.evaluate::<O>(decoder, x).unwrap_or_else(O::worst);
+            state.fitness.push(f);
+        }
+        state.positions = positions;
+        for event in &mut state.improvements {
+            event.iteration = iter;
+        }
+        if self.settings.track_hawks {
+            state.memory = Some(HawkMemory::new(&state.positions, &state.fitness));
+        }
+        // Um inicializador que não devolve nenhum falcão deixa a execução sem coelho
+        let b = best_index_with::<O>(&state.fitness).ok_or(InitError::InvalidPopSize(n))?;
+        state.promote(b);
+        Ok(state)
+    }
+
+    /// Velocidades dos `n` falcões, vazias quando o momento nunca é usado
+    fn velocity(&self, n: usize) -> Population {
+        if self.settings.momentum > 0.0
+            || self.settings.scheduled(Parameter::Momentum, 0.0).is_some()
+        {
+            Population::zeros(n, self.bounds.dim())
+        } else {
+            Population::new(self.bounds.dim())
+        }
+    }
+
+    /// Escape energy `E = 2·E0·(1 - t/T)^decay`, with `E0 ~ U(-1, 1)`.
+    pub(crate) fn energy<R: Rng>(progress: f64, params: Params, rng: &mut R) -> f64 {
+        let e0 = rng.random::<f64>().mul_add(2.0, -1.0);
+        2.0 * e0 * (1.0 - progress).powf(params.energy_decay)
+    }
+
+    pub(crate) fn iterate<R: Rng>(
+        &self,
+        state: &mut State,
+        mut history: Option<&mut SuccessHistory>,
+        scratch: &mut Scratch,
+        decoder: DecoderRef<'_>,
+        rng: &mut R,
+    ) {
+        let (started, spent) = (Instant::now(), state.evals);
+        self.follow_landscape(state, decoder.get(), rng);
+        if state.failed() {
+            return;
+        }
+
+        let before = state.rabbit_fit;
+        if self.settings.fitness_scaling != FitnessScaling::Raw {
+            state.scaler = Some(Scaler::fit(self.settings.fitness_scaling, &state.fitness));
+        }
+        centroid_into(&state.positions, &mut scratch.mean);
+        if self.settings.partner_selection == PartnerSelection::FitnessDistanceBalance {
+            fdb_scores::<O>(
+                &state.positions,
+                &state.fitness,
+                &state.rabbit,
+                &mut scratch.partner_weights,
+            );
+        }
+        if let Attractor::EliteCentroid { k } = self.settings.attractor {
+            elite_centroid::<O>(&state.positions, &state.fitness, k, &mut scratch.elite);
+        }
+        self.settings
+            .dive_shape
+            .factor::<O>(&state.positions, &state.fitness, &mut scratch.shape);
+        if let Some(steps) = self.settings.rank_steps {
+            steps.factors::<O>(&state.fitness, &mut scratch.step_factors);
+        }
+        if self.settings.niching != Niching::Off {
+            let seeds = self.settings.niching.group::<O>(
+                &state.positions,
+                &state.fitness,
+                &*self.settings.distance,
+                &mut scratch.species,
+            );
+            scratch.niche_seeds.clear();
+            for &s in &seeds {
+                // As sementes têm a dimensão da população
+                let _ = scratch.niche_seeds.push(&state.positions[s]);
+            }
+        }
+
+        scratch.window = state
+            .zoom
+            .filter(|&scale| scale < 1.0)
+            .map(|scale| Zoom::window(&self.bounds, &state.rabbit, scale));
+        self.sweep(state, history.as_deref_mut(), scratch, decoder, rng);
+        if let Some(h) = history {
+            h.update();
+        }
+        state.refresh_rabbit::<O>();
+        let purged = self.purge_duplicates(state, decoder.get(), rng);
+        let immigrants = self.admit_immigrants(state, decoder.get(), rng);
+        if let Some(memory) = &mut state.memory {
+            memory.update::<O>(&state.positions, &state.fitness);
+            for i in purged {
+                memory.reset(i, &state.positions[i], state.fitness[i], Origin::Restart);
+            }
+            for i in immigrants {
+                memory.reset(i, &state.positions[i], state.fitness[i], Origin::Immigrant);
+            }
+        }
+        if let Some(ol) = self.settings.orthogonal_learning
+            && ol.every > 0
+            && (state.iter + 1).is_multiple_of(ol.every)
+            && state.remaining() > 0
+        {
+            Self::orthogonal_learning(state, ol, decoder.get(), rng);
+        }
+        if let Some(refiner) = self.settings.local_refiner {
+            self.refine_rabbit(state, refiner, state.evals - spent, decoder.get());
+        }
+        if let Some(every) = self.settings.reevaluate
+            && every > 0
+            && (state.iter + 1).is_multiple_of(every)
+        {
+            state.reevaluate_rabbit::<O>(decoder.get());
+        }
+        if O::better(state.rabbit_fit, before) {
+            state.stall = 0;
+        } else {
+            state.stall += 1;
+        }
+        if let (Some(zoom), Some(scale)) = (self.settings.zoom, state.zoom) {
+            state.zoom = Some(zoom.rescale(scale, state.stall));
+        }
+        state.iter_time = started.elapsed();
+        // O coeficiente registrado é o que valeu durante a iteração, antes do ajuste
+        state.penalty = decoder.get().penalty_coefficient();
+        decoder.get().end_iteration(state.iter + 1);
+        state.sample(state.iter + 1, self.settings.curve_sampling, false);
+        if let Some(k) = self.settings.trajectory
+            && (state.iter + 1).is_multiple_of(k.max(1).saturating_mul(state.trajectory_stride))
+        {
+            state.trajectory.push(state.rabbit.clone());
+        }
+        if let Some(budget) = self.settings.memory_budget {
+            state.fit_memory(budget);
+        }
+        state.iter += 1;
+    }
+
+    /// Detects a landscape change and, if there was one, responds to it: re-samples the
+    /// worst hawks, re-evaluates the others, moves the rabbit and clears the stale hall of
+    /// fame and hawk memories. Every evaluation goes through [`State::evaluate`]; a decoder
+    /// error is left in the state.
+    fn follow_landscape<R: Rng>(&self, state: &mut State, decoder: &dyn Decoder, rng: &mut R) {
+        let dynamic = &self.settings.dynamic;
+        // A detecção só roda se o orçamento cobre também a reavaliação da população
+        let affordable = state.remaining() >= dynamic.max_cost(state.positions.len());
+        let changed = affordable
+            && match &dynamic.detection {
+                ChangeDetection::Off => false,
+                ChangeDetection::Manual(signal) => signal.take(),
+                ChangeDetection::Sentinels { count } => {
+                    Self::probe_sentinels(state, *count, dynamic, decoder)
+                }
+            };
+        if !changed || state.failed() {
+            return;
+        }
+        // Os valores guardados são da paisagem antiga
+        if let Some(hof) = &mut state.hall_of_fame {
+            hof.clear();
+        }
+        state.sentinels = None;
+        let n = state.positions.len();
+        // Do pior para o melhor, na ordem inversa do ranking
+        let mut worst = argsort_by_fitness::<O>(&state.fitness);
+        worst.reverse();
+        worst.truncate(dynamic.rerandomized(n));
+        let restarted = self.replace(state, worst, Refill::Random, decoder, rng);
+        let positions = std::mem::take(&mut state.positions);
+        for (i, x) in positions.iter().enumerate() {
+            if restarted.contains(&i) {
+                continue;
+            }
+            let Some(f) = state.evaluate::<O>(decoder, x) else {
+                break;
+            };
+            state.fitness[i] = f;
+        }
+        state.positions = positions;
+        if let Some(memory) = &mut state.memory {
+            memory.rebase(&state.positions, &state.fitness);
+            for i in restarted {
+                memory.reset(i, &state.positions[i], state.fitness[i], Origin::Restart);
+            }
+        }
+        // O coelho antigo pode não valer mais nada na nova paisagem
+        if let Some(b) = best_index_with::<O>(&state.fitness) {
+            state.promote(b);
+        }
+        state.changes += 1;
+        #[cfg(feature = "log")]
+        log::debug!("landscape change detected at iteration {}", state.iter + 1);
+    }
+
+    /// Reavalia as sentinelas e diz se o valor cru de alguma se moveu além da tolerância.
+    /// Na primeira vez, ou se o coeficiente de penalidade do decodificador mudou, só as
+    /// (re)posiciona: o coelho e os primeiros `count - 1` falcões
+    fn probe_sentinels(
+        state: &mut State,
+        count: usize,
+        dynamic: &Dynamic,
+        decoder: &dyn Decoder,
+    ) -> bool {
+        if count == 0 {
+            return false;
+        }
+        let penalty = decoder.penalty_coefficient();
+        let mut sentinels = match state.sentinels.take() {
+            Some(s) if s.penalty.map(f64::to_bits) == penalty.map(f64::to_bits) => s,
+            _ => {
+                let mut positions = Population::new(state.rabbit.len());
+                for x in std::iter::once(&state.rabbit[..]).chain(state.positions.iter()) {
+                    if positions.len() == count {
+                        break;
+                    }
+                    let _ = positions.push(x);
+                }
+                Sentinels {
+                    raw: Vec::with_capacity(positions.len()),
+                    positions,
+                    penalty,
+                }
+            }
+        };
+        let placing = sentinels.raw.is_empty();
+        let mut changed = false;
+        for (i, x) in sentinels.positions.iter().enumerate() {
+            let Some((raw, _)) = state.evaluate_raw::<O>(decoder, x) else {
+                return false;
+            };
+            if placing {
+                sentinels.raw.push(raw);
+            } else if !dynamic.unchanged(sentinels.raw[i], raw) {
+                changed = true;
+                break;
+            }
+        }
+        state.sentinels = (!changed).then_some(sentinels);
+        changed
+    }
+
+    /// Moves the hawks that duplicate a better one, see [`Dedup`]. Returns the moved hawks,
+    /// which may be fewer than the duplicates once the budget runs out.
+    fn purge_duplicates<R: Rng>(
+        &self,
+        state: &mut State,
+        decoder: &dyn Decoder,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let Some(dedup) = self.settings.dedup else {
+            return Vec::new();
+        };
+        let duplicates =
+            dedup.duplicates::<O>(&state.positions, &state.fitness, &*self.settings.distance);
+        let duplicates = self.replace(state, duplicates, dedup.refill, decoder, rng);
+        state.purged += duplicates.len();
+        duplicates
+    }
+
+    /// Replaces the worst hawks with random immigrants, see [`Immigrants`]. Returns the
+    /// replaced hawks.
+    fn admit_immigrants<R: Rng>(
+        &self,
+        state: &mut State,
+        decoder: &dyn Decoder,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let Some(mut immigrants) = self.settings.immigrants else {
+            return Vec::new();
+        };
+        if let Some(fraction) = self
+            .settings
+            .scheduled(Parameter::ImmigrantFraction, state.progress())
+        {
+            immigrants.fraction = fraction;
+        }
+        let k = immigrants.count(state.positions.len());
+        let mut worst = argsort_by_fitness::<O>(&state.fitness);
+        let worst = worst.split_off(worst.len() - k);
+        let replaced = self.replace(state, worst, immigrants.refill, decoder, rng);
+        state.phases.immigrants = replaced.len();
+        replaced
+    }
+
+    /// Moves each hawk of `hawks` to a point placed by `refill` (keeping frozen dimensions)
+    /// and evaluates it. Stops when the budget runs out, returning the hawks moved.
+    fn replace<R: Rng>(
+        &self,
+        state: &mut State,
+        mut hawks: Vec<usize>,
+        refill: Refill,
+        decoder: &dyn Decoder,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let mut x = vec![0.0; self.bounds.dim()];
+        for (k, &i) in hawks.iter().enumerate() {
+            x.copy_from_slice(&state.positions[i]);
+            refill.apply(&self.bounds, &mut x, rng);
+            for (j, _) in state.frozen.iter().enumerate().filter(|(_, f)| **f) {
+                x[j] = state.positions[i][j];
+            }
+            let Some(f) = state.evaluate::<O>(decoder, &x) else {
+                hawks.truncate(k);
+                break;
+            };
+            state.positions[i].copy_from_slice(&x);
+            state.fitness[i] = f;
+            if let Some(v) = state.velocity.get_mut(i) {
+                v.fill(0.0);
+            }
+        }
+        hawks
+    }
+
+    /// Combines the rabbit with a random elite through an orthogonal design, keeping the
+    /// predicted combination if it beats the rabbit.
+    fn orthogonal_learning<R: Rng>(
+        state: &mut State,
+        ol: OrthogonalLearning,
+        decoder: &dyn Decoder,
+        rng: &mut R,
+    ) {
+        let elites = top_k_indices::<O>(&state.fitness, ol.elites.max(1));
+        if elites.is_empty() {
+            return;
+        }
+        let k = elites[rng.random_range(0..elites.len())];
+
+        let rabbit = state.rabbit.clone();
+        let elite = state.positions[k].to_vec();
+        let (x, f) = orthogonal_combine::<O, _>(&rabbit, &elite, ol.factors, |x| {
+            state.evaluate::<O>(decoder, x).unwrap_or_else(O::worst)
+        });
+        if O::better(f, state.rabbit_fit) {
+            state.rabbit = x;
+            state.rabbit_fit = f;
+            state.rabbit_samples = 1;
+        }
+    }
+
+    /// Roda o refinador local em torno do coelho, depois de o resto da iteração gastar
+    /// `spent` avaliações: o tamanho inteiro dele, ou o crédito merecido com um cronograma
+    fn refine_rabbit(
+        &self,
+        state: &mut State,
+        refiner: LocalRefiner,
+        spent: usize,
+        decoder: &dyn Decoder,
+    ) {
+        let most = refiner.evals_per_iter(self.bounds.dim());
+        let evals = self.settings.refiner_schedule.map_or(most, |schedule| {
+            #[allow(clippy::cast_precision_loss)]
+            let cap = most as f64;
+            let credit = state.refine_credit + schedule.credit(spent, state.progress());
+            state.refine_credit = credit.min(cap);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let evals = state.refine_credit as usize;
+            evals
+        });
+        let before = state.evals;
+        match refiner {
+            LocalRefiner::Pattern(search) => self.pattern_search(state, search, evals, decoder),
+            LocalRefiner::Chaotic(search) => self.chaotic_search(state, search, evals, decoder),
+        }
+        let used = state.evals - before;
+        state.refined += used;
+        if self.settings.refiner_schedule.is_some() {
+            #[allow(clippy::cast_precision_loss)]
+            let used = used as f64;
+            state.refine_credit = (state.refine_credit - used).max(0.0);
+        }
+    }
+
+    /// Uma varredura da busca de padrões a partir do coelho, com os passos da anterior;
+    /// passos esgotados recomeçam do inicial
+    fn pattern_search(
+        &self,
+        state: &mut State,
+        search: PatternSearch,
+        evals: usize,
+        decoder: &dyn Decoder,
+    ) {
+        let mut steps = std::mem::take(&mut state.steps);
+        if steps.len() != self.bounds.dim() || search.exhausted(&self.bounds, &steps) {
+            steps = search.initial_steps(&self.bounds);
+        }
+        let (mut x, mut f) = (state.rabbit.clone(), state.rabbit_fit);
+        search.sweep::<O>(&self.bounds, &mut x, &mut f, &mut steps, evals, |y| {
+            state.evaluate::<O>(decoder, y)
+        });
+        state.steps = steps;
+        if O::better(f, state.rabbit_fit) {
+            state.rabbit = x;
+            state.rabbit_fit = f;
+            state.rabbit_samples = 1;
+        }
+    }
+
+    /// Tenta `evals` perturbações caóticas em torno do coelho, com raio decrescente com o
+    /// progresso, e guarda as que o melhoram
+    fn chaotic_search(
+        &self,
+        state: &mut State,
+        cls: ChaoticSearch,
+        evals: usize,
+        decoder: &dyn Decoder,
+    ) {
+        if state.chaos.len() != state.rabbit.len() {
+            state.chaos = ChaoticSearch::seed_map(state.rabbit.len());
+        }
+        let radius = cls.radius * (1.0 - state.progress());
+        let mut trial = vec![0.0; state.rabbit.len()];
+        for _ in 0..evals {
+            if state.remaining() == 0 {
+                break;
+            }
+            cls.perturb(
+                &self.bounds,
+                &state.rabbit,
+                radius,
+                &mut state.chaos,
+                &mut trial,
+            );
+            if let Some(f) = state.evaluate::<O>(decoder, &trial)
+                && O::better(f, state.rabbit_fit)
+            {
+                state.rabbit.copy_from_slice(&trial);
+                state.rabbit_fit = f;
+                state.rabbit_samples = 1;
+            }
+        }
+    }
+}
+
+impl<O, I> Optimizer for Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    fn name(&self) -> &'static str {
+        "hho"
+    }
+
+    /// Same as [`Hho::run_with_budget`].
+    fn run(&mut self, decoder: &dyn Decoder, budget: Budget) -> Result<Report, HhoError> {
+        self.run_with_budget(decoder, budget)
+    }
+
+    /// Same as [`Hho::config_hash`].
+    fn config_hash(&self) -> Option<u64> {
+        Some(Self::config_hash(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        benchmarks::Function::{self, Sphere},
+        core::{DecoderError, Direction, Maximization, TerminationReason},
+        hho::{
+            Adaptation, Attractor, Dedup, Exploitation, HhoBuilder, Immigrants, LeaderAssignment,
+            PartnerSelection, Refill, Selection, Zoom,
+        },
+        termination::TargetFitness,
+    };
+
+    struct NegSphere;
+
+    impl Decoder for NegSphere {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(-solution.iter().map(|x| x * x).sum::<f64>())
+        }
+    }
+
+    #[test]
+    fn minimizes_sphere() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(3)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        assert_eq!(report.iters, 200);
+        assert_eq!(report.convergence_curve.len(), 200);
+        assert!(report.convergence_curve.windows(2).all(|w| w[1] <= w[0]));
+        // A população se concentra ao redor do ótimo
+        assert_eq!(report.diversity.len(), 200);
+        assert!(report.exploitation()[199] > 99.0);
+    }
+
+    #[test]
+    fn maximizes_negated_sphere() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .objective::<Maximization>()
+            .pop_size(20)
+            .max_iters(200)
+            .seed(3)
+            .build();
+        let report = hho.run(&NegSphere).unwrap();
+
+        assert!(
+            report.best_fitness > -1e-6,
+            "best = {}",
+            report.best_fitness
+        );
+    }
+
+    /// `-f` de uma função de teste
+    struct Negated(Function);
+
+    impl Decoder for Negated {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(-self.0.eval(solution))
+        }
+    }
+
+    /// Variante `k` da matriz de configurações, para qualquer direção
+    fn variant<O: Objective>(builder: HhoBuilder<O>, k: usize, target: f64) -> HhoBuilder<O> {
+        let builder = builder
+            .pop_size(12)
+            .max_iters(40)
+            .seed(11)
+            .hall_of_fame(3, 0.0);
+        match k {
+            0 => builder,
+            1 => builder
+                .partner_selection(PartnerSelection::FitnessDistanceBalance)
+                .adaptation(Adaptation::SuccessHistory { memory_size: 5 })
+                .attractor(Attractor::EliteCentroid { k: 3 }),
+            2 => builder
+                .exploitation(Exploitation::Adaptive {
+                    selection: Selection::default(),
+                })
+                .dedup(Dedup::default())
+                .immigrants(Immigrants::default())
+                .zoom(Zoom::default()),
+            _ => builder.termination(TargetFitness {
+                target,
+                tolerance: 0.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn both_directions_mirror_each_other_over_the_benchmarks() {
+        for function in Function::ALL {
+            let bounds = function.bounds(4).unwrap();
+            for k in 0..4 {
+                // Um alvo atingido no meio da execução, para exercitar a parada
+                let target = HhoBuilder::new(bounds.clone())
+                    .pop_size(12)
+                    .max_iters(10)
+                    .seed(11)
+                    .build()
+                    .run(&function)
+                    .unwrap()
+                    .best_fitness;
+                let min = variant(HhoBuilder::new(bounds.clone()), k, target)
+                    .build()
+                    .run(&function)
+                    .unwrap();
+                let max = variant(
+                    HhoBuilder::new(bounds.clone()).objective::<Maximization>(),
+                    k,
+                    -target,
+                )
+                .build()
+                .run(&Negated(function))
+                .unwrap();
+
+                let case = format!("{function:?} variant {k}");
+                assert_eq!(max.direction, Direction::Maximize, "{case}");
+                assert_eq!(min.best_position, max.best_position, "{case}");
+                assert!(
+                    (min.best_fitness + max.best_fitness).abs() < 1e-12,
+                    "{case}"
+                );
+                assert_eq!(min.iters, max.iters, "{case}");
+                assert_eq!(min.evals, max.evals, "{case}");
+                assert_eq!(min.stop_reason, max.stop_reason, "{case}");
+                if k == 3 {
+                    assert_eq!(
+                        max.stop_reason,
+                        Some(TerminationReason::TargetReached),
+                        "{case}"
+                    );
+                }
+                assert!(
+                    min.convergence_curve
+                        .iter()
+                        .zip(&max.convergence_curve)
+                        .all(|(a, b)| (a + b).abs() < 1e-12),
+                    "{case}"
+                );
+                assert!(
+                    min.hall_of_fame
+                        .iter()
+                        .zip(&max.hall_of_fame)
+                        .all(|(a, b)| a.position == b.position),
+                    "{case}"
+                );
+                assert!(max.is_better_than(&Report::empty_for::<Maximization>()));
+            }
+        }
+    }
+
+    #[test]
+    fn dive_replay_helps_on_rosenbrock() {
+        let run = |replay: Option<DiveReplay>, seed: u64| {
+            let mut builder = HhoBuilder::new(Function::Rosenbrock.bounds(10).unwrap())
+                .pop_size(20)
+                .max_iters(300)
+                .seed(seed);
+            if let Some(replay) = replay {
+                builder = builder.dive_replay(replay);
+            }
+            builder
+                .build()
+                .run(&Function::Rosenbrock)
+                .unwrap()
+                .best_fitness
+        };
+        let (mut plain, mut replayed) = (0.0, 0.0);
+        for seed in 0..8 {
+            plain += run(None, seed);
+            replayed += run(Some(DiveReplay::default()), seed);
+        }
+        assert!(replayed < plain, "plain = {plain}, replayed = {replayed}");
+    }
+
+    #[test]
+    fn shaped_dives_converge_on_rosenbrock() {
+        for shape in [DiveShape::Diagonal, DiveShape::Covariance] {
+            for seed in 0..4 {
+                let report = HhoBuilder::new(Function::Rosenbrock.bounds(10).unwrap())
+                    .pop_size(20)
+                    .max_iters(500)
+                    .dive_shape(shape)
+                    .seed(seed)
+                    .build()
+                    .run(&Function::Rosenbrock)
+                    .unwrap();
+                assert!(
+                    report.best_fitness < 0.05,
+                    "{shape:?}, seed {seed}: best = {}",
+                    report.best_fitness
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mirrored_dives_spend_budget_on_reflections() {
+        let build = |mirror: bool| {
+            let builder = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(100)
+                .seed(4);
+            if mirror {
+                builder.mirror_dives()
+            } else {
+                builder
+            }
+            .build()
+        };
+        let plain = build(false).run(&Sphere).unwrap();
+        let mirrored = build(true).run(&Sphere).unwrap();
+        assert!(mirrored.evals > plain.evals);
+        assert!(
+            mirrored.best_fitness < 1e-6,
+            "best = {}",
+            mirrored.best_fitness
+        );
+
+        let capped = build(true)
+            .run_with_budget(&Sphere, Budget::Evaluations(1000))
+            .unwrap();
+        assert!(capped.evals <= 1000);
+    }
+
+    #[test]
+    fn success_history_adaptation_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(3)
+            .adaptation(Adaptation::SuccessHistory { memory_size: 5 })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn hall_of_fame_is_reported_best_first() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(3)
+            .hall_of_fame(5, 0.1)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        let hof = &report.hall_of_fame;
+        assert_eq!(hof.len(), 5);
+        assert!((hof[0].fitness - report.best_fitness).abs() < f64::EPSILON);
+        assert!(hof.windows(2).all(|w| w[0].fitness <= w[1].fitness));
+    }
+
+    #[test]
+    fn every_evaluation_is_recorded() {
+        let report = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(3)
+            .record_evaluations()
+            .build()
+            .run(&Sphere)
+            .unwrap();
+
+        let archive = report.evaluations.as_ref().unwrap();
+        assert_eq!(archive.len(), report.evals);
+        let (nearest, d) = archive.nearest(&report.best_position).unwrap();
+        assert!(d.abs() < f64::EPSILON);
+        assert!((nearest.fitness - report.best_fitness).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn records_degrade_to_fit_the_memory_budget() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+                .pop_size(10)
+                .max_iters(400)
+                .seed(6)
+                .record_evaluations()
+                .record_trajectory(1)
+                .keep_population()
+        };
+        let full = builder().build().run(&Sphere).unwrap();
+        let lean = builder()
+            .memory_budget(16_000)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        // A busca é a mesma, só os registros encolhem
+        assert_eq!(lean.best_fitness.to_bits(), full.best_fitness.to_bits());
+        assert!(full.degradations.is_empty());
+        assert!(matches!(
+            lean.degradations[0],
+            Degradation::EvaluationsCapped { .. }
+        ));
+        assert!(
+            lean.degradations
+                .iter()
+                .any(|d| matches!(d, Degradation::CurvesThinned { .. }))
+        );
+        let kept = lean.evaluations.as_ref().map_or(0, EvaluationArchive::len);
+        assert!(kept < full.evals);
+        assert!(lean.trajectory.len() < full.trajectory.len() / 2);
+        assert!(lean.convergence_curve.len() < full.convergence_curve.len() / 2);
+        assert_eq!(lean.curve_iters.last(), Some(&400));
+        assert!(lean.curve_iters.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(lean.convergence_curve.len(), lean.diversity.len());
+        assert!(
+            lean.degradations
+                .iter()
+                .any(|d| matches!(d, Degradation::TrajectoryThinned { .. }))
+        );
+    }
+
+    #[test]
+    fn clusters_show_when_the_population_collapses() {
+        let report = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(3)
+            .record_clusters(8)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert_eq!(report.clusters.len(), report.curve_iters.len());
+        assert_eq!(report.occupancy_entropy.len(), report.curve_iters.len());
+        assert!(report.clusters[0] > 1);
+        let collapse = report.collapse_iteration().unwrap();
+        assert!(collapse > 1 && collapse < 200);
+        let k = report
+            .curve_iters
+            .iter()
+            .position(|&t| t == collapse)
+            .unwrap();
+        assert!(report.occupancy_entropy[k] < report.occupancy_entropy[0]);
+    }
+
+    #[test]
+    fn times_iterations_and_evaluations() {
+        struct Slow;
+
+        impl Decoder for Slow {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                std::thread::sleep(Duration::from_micros(200));
+                Sphere.decode(solution)
+            }
+        }
+
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(5)
+            .max_iters(10)
+            .seed(1)
+            .build();
+        let report = hho.run(&Slow).unwrap();
+        assert_eq!(report.iteration_times.len(), report.convergence_curve.len());
+        // Cada iteração avalia ao menos os 5 falcões
+        assert!(
+            report
+                .iteration_times
+                .iter()
+                .all(|&t| t >= Duration::from_millis(1))
+        );
+        // Ao menos 5 + 10·5 avaliações de 200 µs
+        assert!(report.eval_time >= Duration::from_millis(11));
+        assert!(report.eval_time <= report.duration.unwrap());
+        assert!(report.overhead().is_some());
+    }
+
+    #[test]
+    fn sampled_curve_keeps_the_last_iteration() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(3)
+            .curve_sampling(CurveSampling::Every(7))
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.curve_iters, vec![7, 14, 21, 28, 35, 42, 49, 50]);
+        assert_eq!(report.curve_evals.len(), report.convergence_curve.len());
+        assert_eq!(
+            report.best_at_evals(report.evals),
+            Some(report.best_fitness)
+        );
+    }
+
+    #[test]
+    fn population_snapshot_is_reported() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(12)
+            .max_iters(20)
+            .seed(4)
+            .keep_population()
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.population.len(), 12);
+        assert!(report.population.iter().all(|s| s.position.len() == 2));
+        assert!(
+            report
+                .population
+                .iter()
+                .any(|s| (s.fitness - report.best_fitness).abs() < f64::EPSILON)
+        );
+    }
+
+    #[test]
+    fn energy_and_phases_are_recorded() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(10)
+            .max_iters(100)
+            .seed(5)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.energy.len(), 100);
+        assert_eq!(report.phases.len(), 100);
+        assert!(report.phases.iter().all(|p| p.total() == 10));
+        // A energia decai: o início explora, o fim só cerca
+        assert!(report.energy[0] > report.energy[99]);
+        assert_eq!(report.phases[99].exploration, 0);
+
+        let ops = &report.operators;
+        assert_eq!(ops.iter().map(|(_, r)| r.uses).sum::<usize>(), 100 * 10);
+        let exploration = ops.get(Phase::Exploration);
+        assert!(exploration.uses > 0 && exploration.improvements <= exploration.uses);
+        assert!(ops.iter().any(|(_, r)| r.gain > 0.0));
+    }
+
+    #[test]
+    fn reservoir_sampling_bounds_the_report() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(5)
+            .max_iters(2_000)
+            .curve_sampling(CurveSampling::Reservoir { size: 16 })
+            .seed(3)
+            .build();
+        let a = hho.run(&Sphere).unwrap();
+        let b = hho.run(&Sphere).unwrap();
+
+        assert_eq!(a.convergence_curve.len(), 16);
+        assert_eq!(a.diversity.len(), 16);
+        assert_eq!(a.curve_iters.last(), Some(&2_000));
+        assert!(a.curve_iters.windows(2).all(|w| w[0] < w[1]));
+        // A amostra se espalha pela execução inteira
+        assert!(a.curve_iters[8] > 200);
+        assert_eq!(a.curve_iters, b.curve_iters);
+    }
+
+    #[test]
+    fn trajectory_records_every_kth_rabbit() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(2)
+            .record_trajectory(10)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.trajectory.len(), 5);
+        assert_eq!(report.trajectory.last(), Some(&report.best_position));
+    }
+
+    #[test]
+    fn quantum_exploitation_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(4)
+            .exploitation(Exploitation::quantum())
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn adaptive_exploitation_uses_every_rule() {
+        let run = |exploitation| {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(200)
+                .seed(2)
+                .exploitation(exploitation)
+                .build()
+                .run(&Sphere)
+                .unwrap()
+        };
+        for exploitation in [Exploitation::differential(), Exploitation::sine_cosine()] {
+            let report = run(exploitation);
+            assert!(report.best_fitness < 1e-3, "best = {}", report.best_fitness);
+        }
+
+        let report = run(Exploitation::adaptive());
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        let ops = &report.operators;
+        for phase in [Phase::Quantum, Phase::Differential, Phase::SineCosine] {
+            assert!(ops.get(phase).uses > 0, "{phase:?} never used");
+        }
+        assert!(ops.get(Phase::SoftBesiege).uses + ops.get(Phase::HardDives).uses > 0);
+    }
+
+    #[test]
+    fn fitness_scaling_makes_adaptive_runs_scale_free() {
+        struct Scaled(f64);
+
+        impl Decoder for Scaled {
+            fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+                Ok(self.0 * x.iter().map(|v| v * v).sum::<f64>())
+            }
+        }
+
+        let run = |scaling, k| {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(100)
+                .seed(9)
+                .exploitation(Exploitation::adaptive())
+                .adaptation(Adaptation::SuccessHistory { memory_size: 5 })
+                .fitness_scaling(scaling)
+                .build()
+                .run(&Scaled(k))
+                .unwrap()
+        };
+        // Potências de 2 mudam a escala sem arredondamento, então as execuções coincidem
+        for scaling in [FitnessScaling::ZScore, FitnessScaling::Rank] {
+            let (small, large) = (run(scaling, 2f64.powi(-30)), run(scaling, 2f64.powi(40)));
+            assert_eq!(small.best_position, large.best_position, "{scaling:?}");
+            assert!(small.best_fitness < 1e-12);
+        }
+        assert!(run(FitnessScaling::Log, 1.0).best_fitness < 1e-6);
+    }
+
+    #[test]
+    fn chaotic_search_polishes_the_rabbit() {
+        let run = |search: Option<ChaoticSearch>, iters| {
+            let mut builder = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(10)
+                .max_iters(iters)
+                .seed(8);
+            if let Some(search) = search {
+                builder = builder.local_refiner(LocalRefiner::Chaotic(search));
+            }
+            builder.build().run(&Sphere).unwrap()
+        };
+        // A busca não usa o gerador: a primeira varredura é a mesma, mais as tentativas
+        let search = ChaoticSearch {
+            evals: 30,
+            ..ChaoticSearch::default()
+        };
+        let (plain, chaotic) = (run(None, 1), run(Some(search), 1));
+        assert_eq!(chaotic.evals, plain.evals + search.evals);
+        assert!(chaotic.best_fitness < plain.best_fitness);
+        assert!(
+            (Sphere.decode(&chaotic.best_position).unwrap() - chaotic.best_fitness).abs() < 1e-12
+        );
+
+        let long = run(Some(search), 100);
+        assert!(long.best_fitness < 1e-20);
+        assert!(matches!(
+            HhoBuilder::new(Bounds::uniform(-1.0, 1.0, 2).unwrap())
+                .local_refiner(LocalRefiner::Chaotic(ChaoticSearch { evals: 0, ..search }))
+                .build()
+                .run(&Sphere),
+            Err(HhoError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn pattern_refiner_runs_in_the_main_loop() {
+        let run = |refiner: Option<LocalRefiner>, iters| {
+            let mut builder = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(10)
+                .max_iters(iters)
+                .seed(8);
+            if let Some(refiner) = refiner {
+                builder = builder.local_refiner(refiner);
+            }
+            builder.build().run(&Sphere).unwrap()
+        };
+        let pattern = LocalRefiner::from_name("pattern").unwrap();
+        let (plain, refined) = (run(None, 1), run(Some(pattern), 1));
+        assert!(refined.evals > plain.evals && refined.evals <= plain.evals + 10);
+        assert!(refined.best_fitness <= plain.best_fitness);
+        assert!(
+            (Sphere.decode(&refined.best_position).unwrap() - refined.best_fitness).abs() < 1e-12
+        );
+        assert!(run(Some(pattern), 100).best_fitness < 1e-20);
+        // A varredura respeita um orçamento de avaliações
+        let capped = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(10)
+            .seed(8)
+            .local_refiner(pattern)
+            .build()
+            .run_with_budget(&Sphere, Budget::Evaluations(125))
+            .unwrap();
+        assert_eq!(capped.evals, 125);
+    }
+
+    #[test]
+    fn refiner_schedule_caps_the_share_of_evaluations() {
+        let chaotic = LocalRefiner::Chaotic(ChaoticSearch {
+            evals: 50,
+            ..ChaoticSearch::default()
+        });
+        let run = |schedule: Option<RefinerSchedule>| {
+            let mut builder = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(100)
+                .seed(8)
+                .local_refiner(chaotic);
+            if let Some(schedule) = schedule {
+                builder = builder.refiner_schedule(schedule);
+            }
+            builder.build().run(&Sphere)
+        };
+        // Sem cronograma o refinador gasta o tamanho inteiro e domina o orçamento
+        let greedy = run(None).unwrap();
+        assert_eq!(greedy.refiner_evals, 100 * 50);
+        #[allow(clippy::cast_precision_loss)]
+        let share = |r: &Report| r.refiner_evals as f64 / (r.evals - 20) as f64;
+        for taper in [0.0, 1.0, -1.0] {
+            let report = run(Some(RefinerSchedule { share: 0.1, taper })).unwrap();
+            assert!(report.refiner_evals > 0);
+            assert!(
+                (share(&report) - 0.1).abs() < 0.02,
+                "taper = {taper}: {}",
+                share(&report)
+            );
+            assert!(report.best_fitness < 1e-10);
+        }
+        assert!(matches!(
+            HhoBuilder::new(Bounds::uniform(-1.0, 1.0, 2).unwrap())
+                .refiner_schedule(RefinerSchedule::default())
+                .build()
+                .run(&Sphere),
+            Err(HhoError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn orthogonal_learning_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 10).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(5)
+            .orthogonal_learning(OrthogonalLearning::default())
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn update_probability_keeps_good_coordinates() {
+        /// Rastrigin rugoso com o ótimo fora da origem, em cada dimensão num ponto diferente
+        struct Shifted;
+
+        impl Decoder for Shifted {
+            fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+                Ok(x.iter()
+                    .enumerate()
+                    .map(|(j, v)| {
+                        #[allow(clippy::cast_precision_loss)]
+                        let d = v - 0.5 * (j % 7) as f64 + 1.0;
+                        d.mul_add(d, 1.0 - (6.0 * d).cos())
+                    })
+                    .sum())
+            }
+        }
+
+        let run = |p: Option<f64>| {
+            let mut builder = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 100).unwrap())
+                .pop_size(20)
+                .max_iters(200)
+                .seed(1);
+            if let Some(p) = p {
+                builder = builder.update_probability(p);
+            }
+            builder.build().run(&Shifted)
+        };
+        let (all, some) = (run(None).unwrap(), run(Some(0.1)).unwrap());
+        assert!(some.best_fitness < 0.9 * all.best_fitness);
+        assert!(matches!(run(Some(0.0)), Err(HhoError::Config(_))));
+    }
+
+    #[test]
+    fn config_hash_ignores_the_seed() {
+        let hho = |seed: u64, pop_size: usize| {
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+                .pop_size(pop_size)
+                .max_iters(5)
+                .seed(seed)
+                .build()
+        };
+        assert_eq!(hho(1, 10).config_hash(), hho(2, 10).config_hash());
+        assert_ne!(hho(1, 10).config_hash(), hho(1, 11).config_hash());
+        let report = hho(1, 10).run(&Sphere).unwrap();
+        assert_eq!(report.config_hash, Some(hho(1, 10).config_hash()));
+        assert_eq!(report.bounds, vec![(-5.0, 5.0); 3]);
+    }
+
+    #[test]
+    fn improvements_are_stamped_with_their_evaluation() {
+        let report = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(4)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        let events = &report.improvements;
+        assert_eq!(events[0].iteration, 0);
+        assert!(events[0].evaluation <= 10);
+        for pair in events.windows(2) {
+            assert!(pair[1].fitness < pair[0].fitness);
+            assert!(pair[1].evaluation > pair[0].evaluation);
+            assert!((pair[0].fitness - pair[1].fitness - pair[1].delta).abs() < 1e-12);
+        }
+        let last = events.last().unwrap();
+        assert!(last.fitness.to_bits() == report.best_fitness.to_bits());
+        assert_eq!(
+            report.evals_to_target(report.best_fitness),
+            Some(last.evaluation)
+        );
+
+        // Mais preciso que a curva, que só conhece o fim de cada iteração
+        let target = events[events.len() / 2].fitness;
+        let from_curve = report
+            .eval_curve()
+            .find(|&(_, f)| f <= target)
+            .map(|(e, _)| e);
+        assert!(report.evals_to_target(target) <= from_curve);
+        assert_eq!(report.evals_to_target(-1.0), None);
+    }
+
+    #[test]
+    fn non_finite_fitness_is_counted_and_handled() {
+        /// Esfera que devolve NaN numa faixa do espaço
+        struct Holes;
+
+        impl Decoder for Holes {
+            fn decode(&self, x: &[f64]) -> Result<f64, DecoderError> {
+                Ok(if x[0] > 1.0 {
+                    f64::NAN
+                } else {
+                    x.iter().map(|v| v * v).sum()
+                })
+            }
+        }
+
+        let run = |policy: NonFinite| {
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+                .pop_size(10)
+                .max_iters(20)
+                .keep_population()
+                .non_finite(policy)
+                .seed(2)
+                .build()
+                .run(&Holes)
+        };
+        let kept = run(NonFinite::Keep).unwrap();
+        assert!(kept.non_finite > 0);
+        assert!(kept.best_fitness.is_finite());
+        // As ordenações por ranking também recebem os NaN mantidos
+        let ranked = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(30)
+            .max_iters(20)
+            .immigrants(Immigrants::default())
+            .rank_steps(RankSteps::default())
+            .seed(2)
+            .build()
+            .run(&Holes)
+            .unwrap();
+        assert!(ranked.non_finite > 0);
+        assert!(ranked.best_fitness.is_finite());
+        let worst = run(NonFinite::Worst).unwrap();
+        assert!(worst.non_finite > 0);
+        assert!(worst.population.iter().all(|s| !s.fitness.is_nan()));
+        assert!(worst.best_fitness.is_finite());
+        match run(NonFinite::Abort) {
+            Err(HhoError::Decoder { source, report }) => {
+                let DecoderError::NonFiniteFitness { value, position } = source else {
+                    panic!("{source}");
+                };
+                assert!(value.is_nan() && position[0] > 1.0);
+                assert_eq!(report.non_finite, 1);
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn rank_steps_converge() {
+        let run = |steps: RankSteps| {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(200)
+                .rank_steps(steps)
+                .seed(3)
+                .build()
+                .run(&Sphere)
+        };
+        let report = run(RankSteps::default()).unwrap();
+        assert!(report.best_fitness < 1e-6, "f = {}", report.best_fitness);
+        let bad = RankSteps {
+            best: -1.0,
+            ..RankSteps::default()
+        };
+        assert!(matches!(run(bad), Err(HhoError::Config(_))));
+    }
+
+    #[test]
+    fn momentum_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(6)
+            .momentum(0.3)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn a_nan_first_hawk_does_not_hide_the_rabbit() {
+        /// Esfera cuja primeira avaliação é NaN
+        struct NanFirst(std::sync::atomic::AtomicUsize);
+
+        impl Decoder for NanFirst {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                if self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed) == 0 {
+                    return Ok(f64::NAN);
+                }
+                Sphere.decode(solution)
+            }
+        }
+
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(20)
+            .seed(2)
+            .build();
+        let decoder = NanFirst(0.into());
+        // O coelho inicial já sai dos valores finitos, antes de qualquer iteração
+        let run = hho.start(&decoder).unwrap();
+        assert!(run.current_best().1.is_finite());
+        let report = run.complete();
+        assert!(report.best_fitness.is_finite());
+        assert!(
+            (Sphere.decode(&report.best_position).unwrap() - report.best_fitness).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn empty_initial_population_is_an_error() {
+        struct Nobody;
+
+        impl Initializer for Nobody {
+            fn initialize<R: Rng>(
+                &self,
+                _pop_size: usize,
+                bounds: &Bounds,
+                _rng: &mut R,
+            ) -> Result<Population, InitError> {
+                Ok(Population::new(bounds.dim()))
+            }
+        }
+
+        let hho = HhoBuilder::new(Bounds::uniform(-1.0, 1.0, 2).unwrap())
+            .initializer(Nobody)
+            .build();
+        assert!(matches!(
+            hho.run(&Sphere),
+            Err(HhoError::Init(InitError::InvalidPopSize(0)))
+        ));
+    }
+
+    #[test]
+    fn dedup_purges_clones() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(8)
+            .dedup(Dedup {
+                epsilon: 1e-3,
+                refill: Refill::Opposition,
+            })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        assert!(report.duplicates_purged > 0);
+        assert!(report.evals >= 20 + 100 * 20 + report.duplicates_purged);
+    }
+
+    #[test]
+    fn immigrants_replace_the_worst_hawks() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(1)
+            .immigrants(Immigrants {
+                fraction: 0.1,
+                refill: Refill::Opposition,
+            })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        assert!(report.phases[1..].iter().all(|p| p.immigrants == 2));
+        assert!(report.evals >= 20 + 100 * (20 + 2));
+    }
+
+    #[test]
+    fn schedules_drive_parameters_over_the_run() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(100)
+                .seed(6)
+        };
+        let report = builder()
+            .immigrants(Immigrants::default())
+            .schedule(
+                Parameter::ImmigrantFraction,
+                Schedule::Linear {
+                    start: 0.05,
+                    end: 0.5,
+                },
+            )
+            .schedule(Parameter::Momentum, Schedule::custom(|t| 0.5 * (1.0 - t)))
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        assert!(report.phases[1].immigrants <= 2);
+        assert!(report.phases.last().unwrap().immigrants >= 9);
+
+        let out_of_range = Schedule::Piecewise(vec![(0.0, 0.5), (1.0, 1.5)]);
+        for hho in [
+            builder().schedule(Parameter::UpdateProbability, out_of_range),
+            builder().schedule(Parameter::ImmigrantFraction, Schedule::Constant(0.2)),
+        ] {
+            assert!(matches!(hho.build().run(&Sphere), Err(HhoError::Config(_))));
+        }
+    }
+
+    #[test]
+    fn zoom_narrows_the_search_on_stagnation() {
+        let zoom = Zoom {
+            patience: 2,
+            factor: 0.5,
+            min_scale: 1e-4,
+        };
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(9)
+            .zoom(zoom)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+
+        let invalid = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .zoom(Zoom {
+                factor: 1.5,
+                ..zoom
+            })
+            .try_build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn reevaluation_forgets_a_lucky_sample() {
+        // A quinta avaliação sai 1000 abaixo do valor real
+        struct Lucky(std::sync::atomic::AtomicUsize);
+
+        impl Decoder for Lucky {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                let k = self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let noise = if k == 4 { -1000.0 } else { 0.0 };
+                Ok(solution.iter().map(|x| x * x).sum::<f64>() + noise)
+            }
+        }
+
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+                .pop_size(20)
+                .max_iters(100)
+                .seed(3)
+        };
+        let report = builder().build().run(&Lucky(0.into())).unwrap();
+        assert!(
+            report.best_fitness < -500.0,
+            "best = {}",
+            report.best_fitness
+        );
+
+        let report = builder()
+            .reevaluate_rabbit(1)
+            .build()
+            .run(&Lucky(0.into()))
+            .unwrap();
+        assert!(
+            (0.0..1e-3).contains(&report.best_fitness),
+            "best = {}",
+            report.best_fitness
+        );
+    }
+
+    #[test]
+    fn warm_start_continues_a_previous_run() {
+        let bounds = Bounds::uniform(-10.0, 10.0, 5).unwrap();
+        let first = HhoBuilder::new(bounds.clone())
+            .pop_size(20)
+            .max_iters(30)
+            .seed(6)
+            .keep_population()
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        let second = HhoBuilder::new(bounds)
+            .pop_size(20)
+            .max_iters(60)
+            .seed(7)
+            .warm_start(&first)
+            .continue_counters(&first)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+
+        assert!(second.best_fitness <= first.best_fitness);
+        assert_eq!(second.iters, 60);
+        assert!(second.evals >= first.evals + 20 + 30 * 20);
+    }
+
+    #[test]
+    fn constrained_wrappers_forward_the_expected_dimension() {
+        use crate::{
+            benchmarks::Engineering,
+            constraints::{EpsilonConstrained, EpsilonSchedule, Penalized, Penalty},
+        };
+
+        let bounds = Bounds::uniform(-10.0, 10.0, 5).unwrap();
+        let first = HhoBuilder::new(bounds.clone())
+            .pop_size(10)
+            .max_iters(5)
+            .seed(6)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        let warm = HhoBuilder::new(bounds)
+            .pop_size(10)
+            .max_iters(10)
+            .warm_start(&first)
+            .build();
+        let penalized = Penalized::new(Engineering::PressureVessel, Penalty::default()).unwrap();
+        let epsilon =
+            EpsilonConstrained::new(Engineering::Spring, EpsilonSchedule::default()).unwrap();
+        assert_eq!(penalized.expected_dim(), Some(4));
+        assert!(matches!(
+            warm.run(&penalized),
+            Err(HhoError::DimensionMismatch {
+                expected: 4,
+                actual: 5
+            })
+        ));
+        assert!(matches!(
+            warm.start(&epsilon),
+            Err(HhoError::DimensionMismatch {
+                expected: 3,
+                actual: 5
+            })
+        ));
+        assert!(warm.check_dim(&Sphere).is_ok());
+    }
+
+    #[test]
+    fn screening_counts_cheap_evaluations_by_their_cost() {
+        // Baixa fidelidade: a esfera com um erro pequeno e constante
+        struct Coarse {
+            exact: std::sync::atomic::AtomicUsize,
+            coarse: std::sync::atomic::AtomicUsize,
+        }
+
+        impl Decoder for Coarse {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                self.exact
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(solution.iter().map(|x| x * x).sum())
+            }
+
+            fn decode_at(&self, solution: &[f64], _: f64) -> Result<f64, DecoderError> {
+                self.coarse
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(solution.iter().map(|x| x * x).sum::<f64>() + 1e-9)
+            }
+        }
+
+        let decoder = Coarse {
+            exact: 0.into(),
+            coarse: 0.into(),
+        };
+        let report = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(4)
+            .fidelity(Fidelity {
+                level: 0.2,
+                cost: 0.25,
+            })
+            .build()
+            .run(&decoder)
+            .unwrap();
+        let exact = decoder.exact.into_inner();
+        let coarse = decoder.coarse.into_inner();
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        assert!(coarse > 0);
+        assert_eq!(report.screened, coarse);
+        assert_eq!(report.evals, exact + coarse / 4);
+
+        let invalid = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .fidelity(Fidelity {
+                level: 1.0,
+                cost: 0.25,
+            })
+            .try_build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn block_greedy_updates_converge() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 6).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(7)
+            .update_mode(UpdateMode::BlockGreedy { block_size: 2 })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+        // Cada movimento custa até três avaliações
+        assert!(report.evals > 20 + 100 * 20);
+    }
+
+    #[test]
+    fn beta_jump_strength_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(8)
+            .jump_strength(JumpStrength::Beta {
+                alpha: 2.0,
+                beta: 2.0,
+            })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn elite_centroid_attractor_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(9)
+            .attractor(Attractor::EliteCentroid { k: 3 })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn evaluation_budget_is_respected_per_iteration() {
+        let mut hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .seed(6)
+            .build();
+        let report = Optimizer::run(&mut hho, &Sphere, Budget::Evaluations(2_000)).unwrap();
+
+        assert!(report.evals >= 2_000);
+        // Uma iteração custa no máximo 2 avaliações por falcão
+        assert!(report.evals < 2_000 + 2 * 20);
+        assert!(report.best_fitness < 1e-3, "best = {}", report.best_fitness);
+    }
+
+    #[test]
+    fn evaluation_budget_is_exact() {
+        let mut hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 6).unwrap())
+            .pop_size(20)
+            .seed(6)
+            .update_mode(UpdateMode::BlockGreedy { block_size: 2 })
+            .orthogonal_learning(OrthogonalLearning::default())
+            .dynamic(Dynamic {
+                detection: crate::hho::ChangeDetection::Sentinels { count: 2 },
+                rerandomize: 0.0,
+                ..Dynamic::default()
+            })
+            .build();
+        for max in [10, 1_234] {
+            let report = Optimizer::run(&mut hho, &Sphere, Budget::Evaluations(max)).unwrap();
+            assert_eq!(report.evals, max);
+            assert_eq!(report.stop_reason, Some(TerminationReason::MaxEvals));
+        }
+    }
+
+    #[test]
+    fn termination_criterion_stops_the_run() {
+        use crate::termination::{Any, MaxStall, TargetFitness};
+
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(10_000)
+            .seed(1)
+            .termination(
+                Any::new()
+                    .or(TargetFitness {
+                        target: 0.0,
+                        tolerance: 1e-8,
+                    })
+                    .or(MaxStall(1_000)),
+            )
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.stop_reason, Some(TerminationReason::TargetReached));
+        assert!(report.best_fitness <= 1e-8);
+        assert!(report.iters < 10_000);
+    }
+
+    #[test]
+    fn observers_receive_every_hook() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::observer::{IterationContext, Observer};
+
+        #[derive(Default)]
+        struct Counter {
+            starts: usize,
+            iterations: usize,
+            improvements: usize,
+            last_best: f64,
+            ended: bool,
+        }
+
+        impl Observer for Counter {
+            fn on_start(&mut self, _ctx: &IterationContext<'_>) {
+                self.starts += 1;
+            }
+            fn on_iteration(&mut self, ctx: &IterationContext<'_>) {
+                self.iterations += 1;
+                self.last_best = ctx.best_fitness;
+            }
+            fn on_improvement(&mut self, _ctx: &IterationContext<'_>) {
+                self.improvements += 1;
+            }
+            fn on_end(&mut self, _report: &Report) {
+                self.ended = true;
+            }
+        }
+
+        let counter = Arc::new(Mutex::new(Counter::default()));
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(2)
+            .shared_observer(counter.clone())
+            .observer(Counter::default())
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        let c = counter.lock().unwrap();
+        assert_eq!(c.starts, 1);
+        assert_eq!(c.iterations, 30);
+        assert!(c.improvements > 0 && c.improvements <= 30);
+        assert!((c.last_best - report.best_fitness).abs() < f64::EPSILON);
+        assert!(c.ended);
+        drop(c);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resume_continues_from_checkpoint() {
+        use std::ops::ControlFlow;
+
+        let path = std::env::temp_dir().join(format!("kambo-hho-{}.json", std::process::id()));
+        // O arquivo temporário da gravação não pode ser este vizinho
+        let sibling = path.with_extension("tmp");
+        std::fs::write(&sibling, "keep").unwrap();
+        let bounds = Bounds::uniform(-10.0, 10.0, 4).unwrap();
+        let first = HhoBuilder::new(bounds.clone())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(4)
+            .adaptation(Adaptation::SuccessHistory { memory_size: 4 })
+            .checkpoint(&path, 10)
+            .early_stop(|ctx| {
+                if ctx.iter >= 10 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert_eq!(first.iters, 10);
+        assert_eq!(std::fs::read_to_string(&sibling).unwrap(), "keep");
+        std::fs::remove_file(&sibling).unwrap();
+
+        let hho = HhoBuilder::new(bounds)
+            .pop_size(10)
+            .max_iters(30)
+            .seed(4)
+            .adaptation(Adaptation::SuccessHistory { memory_size: 4 })
+            .build();
+        let a = hho.resume(&path, &Sphere).unwrap();
+        let b = hho.resume(&path, &Sphere).unwrap();
+        // Um decodificador de outra dimensão é recusado antes de carregar a população
+        assert!(matches!(
+            hho.resume(&path, &crate::benchmarks::Engineering::Spring),
+            Err(HhoError::DimensionMismatch {
+                expected: 3,
+                actual: 4
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(a.iters, 30);
+        assert_eq!(a.convergence_curve[..10], first.convergence_curve[..]);
+        // O RNG restaurado torna a continuação determinística
+        assert_eq!(a.convergence_curve, b.convergence_curve);
+        assert_eq!(a.best_position, b.best_position);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rolling_checkpoint_alternates_files() {
+        use std::ops::ControlFlow;
+
+        let path = std::env::temp_dir().join(format!("kambo-hho-roll-{}", std::process::id()));
+        let bounds = Bounds::uniform(-10.0, 10.0, 3).unwrap();
+        let builder = || {
+            HhoBuilder::new(bounds.clone())
+                .pop_size(8)
+                .max_iters(40)
+                .seed(5)
+        };
+        let first = builder()
+            .rolling_checkpoint(&path, 5, Duration::from_hours(1))
+            .early_stop(|ctx| {
+                if ctx.iter >= 15 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert_eq!(first.iters, 15);
+
+        // Gravações nas iterações 5, 10 e 15: a mais recente caiu no primeiro arquivo
+        let [a, b] = slots_of(&path);
+        assert_eq!(Checkpoint::load(&a).unwrap().state.iter, 15);
+        assert_eq!(Checkpoint::load(&b).unwrap().state.iter, 10);
+        std::fs::write(&a, "garbage").unwrap();
+
+        let hho = builder().build();
+        let resumed = hho.resume_rolling(&path, &Sphere).unwrap();
+        std::fs::remove_file(&a).ok();
+        std::fs::remove_file(&b).ok();
+        assert_eq!(resumed.iters, 40);
+        assert_eq!(
+            resumed.convergence_curve[..10],
+            first.convergence_curve[..10]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    fn slots_of(path: &std::path::Path) -> [std::path::PathBuf; 2] {
+        crate::hho::CheckpointPolicy {
+            path: path.to_path_buf(),
+            every: 1,
+            interval: None,
+            rotate: true,
+        }
+        .slots()
+    }
+
+    #[test]
+    fn same_seed_reproduces_the_run() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(15)
+            .max_iters(50)
+            .seed(1234)
+            .build();
+        let a = hho.run(&Sphere).unwrap();
+        let b = hho.run(&Sphere).unwrap();
+
+        assert_eq!(a.seed, Some(1234));
+        assert_eq!(a.convergence_curve, b.convergence_curve);
+        assert_eq!(a.best_position, b.best_position);
+    }
+
+    #[test]
+    fn batch_latency_notifies_observers_between_batches() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::observer::{IterationContext, Observer};
+
+        struct Slow;
+
+        impl Decoder for Slow {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                std::thread::sleep(Duration::from_micros(200));
+                Ok(solution.iter().map(|x| x * x).sum())
+            }
+        }
+
+        #[derive(Default)]
+        struct Batches(usize);
+
+        impl Observer for Batches {
+            fn on_batch(&mut self, _ctx: &IterationContext<'_>) {
+                self.0 += 1;
+            }
+        }
+
+        let batches = Arc::new(Mutex::new(Batches::default()));
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(20)
+            .max_iters(5)
+            .seed(1)
+            .batch_latency(Duration::from_millis(1))
+            .shared_observer(batches.clone())
+            .build();
+        let report = hho.run(&Slow).unwrap();
+
+        assert_eq!(report.iters, 5);
+        assert!(batches.lock().unwrap().0 >= 5);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_run_converges_and_is_reproducible() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(99)
+            .build();
+        let a = hho.run_parallel(&Sphere).unwrap();
+        let b = hho.run_parallel(&Sphere).unwrap();
+
+        assert!(a.best_fitness < 1e-6, "best = {}", a.best_fitness);
+        assert_eq!(a.evals, b.evals);
+        assert_eq!(a.convergence_curve, b.convergence_curve);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_runs_ignore_the_pool_size() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(50)
+            .mirror_dives()
+            .seed(99)
+            .build();
+        // Cada falcão sorteia do seu próprio fluxo: só os tempos dependem das threads
+        let run = |threads| {
+            let mut report = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| hho.run_parallel(&Sphere))
+                .unwrap();
+            assert_eq!(report.iters, 50);
+            report.iteration_times.clear();
+            report.eval_time = Duration::ZERO;
+            report.duration = None;
+            report.metadata.started_at = None;
+            format!("{report:?}")
+        };
+        let reference = run(1);
+        for threads in [2, 3, 8] {
+            assert_eq!(run(threads), reference, "{threads} threads");
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_evaluation_budget_is_exact() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(16)
+            .seed(1)
+            .build();
+        let report = hho
+            .run_parallel_with_budget(&Sphere, Budget::Evaluations(1_001))
+            .unwrap();
+
+        assert_eq!(report.evals, 1_001);
+        assert_eq!(report.stop_reason, Some(TerminationReason::MaxEvals));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn deterministic_parallel_runs_ignore_the_thread_count() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(16)
+            .mirror_dives()
+            .deterministic_parallel()
+            .seed(9)
+            .build();
+        let run = |threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| hho.run_parallel_with_budget(&Sphere, Budget::Evaluations(1_013)))
+                .unwrap()
+        };
+        let reference = run(1);
+        assert_eq!(reference.evals, 1_013);
+        for threads in [2, 4, 8] {
+            let report = run(threads);
+            assert_eq!(report.best_position, reference.best_position);
+            assert_eq!(report.convergence_curve, reference.convergence_curve);
+            assert_eq!(report.evals, reference.evals);
+        }
+        assert!(
+            HhoBuilder::new(Bounds::uniform(-1.0, 1.0, 2).unwrap())
+                .deterministic_parallel()
+                .batch_latency(Duration::from_millis(1))
+                .try_build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn wall_clock_budget_is_respected() {
+        let budget = Duration::from_millis(50);
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .seed(5)
+            .build();
+        let report = hho
+            .run_with_budget(&Sphere, Budget::WallClock(budget))
+            .unwrap();
+
+        // Só para antes do prazo quando a iteração mais lenta não caberia no que sobrou; a
+        // folga de cima só pega um prazo ignorado, sem depender da carga da máquina
+        let duration = report.duration.unwrap();
+        let slowest = report.iteration_times.iter().max().copied().unwrap();
+        assert!(report.iters > 0);
+        assert!(duration + slowest >= budget, "{duration:?} + {slowest:?}");
+        assert!(duration < budget + Duration::from_secs(2), "{duration:?}");
+        assert_eq!(report.stop_reason, Some(TerminationReason::TimeLimit));
+    }
+
+    #[test]
+    fn cancelled_run_returns_partial_report() {
+        let token = CancellationToken::new();
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(1_000_000)
+            .seed(7)
+            .cancellation(token.clone())
+            .build();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            token.cancel();
+        });
+        let report = hho.run(&Sphere).unwrap();
+        canceller.join().unwrap();
+
+        assert_eq!(report.stop_reason, Some(TerminationReason::Cancelled));
+        assert!(report.iters < 1_000_000);
+        assert_eq!(report.best_position.len(), 3);
+    }
+
+    #[test]
+    fn mut_observer_can_repair_the_population() {
+        use crate::observer::{IterationContextMut, MutObserver};
+
+        // Move o pior falcão para a origem a cada iteração
+        struct Repair;
+
+        impl MutObserver for Repair {
+            fn on_iteration(&mut self, ctx: &mut IterationContextMut<'_>) {
+                let (positions, fitness) = ctx.population_mut();
+                let worst = (0..fitness.len())
+                    .max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+                    .unwrap();
+                let x = vec![0.0; positions[worst].len()];
+                let Some(f) = ctx.evaluate(&x) else {
+                    return;
+                };
+                let (positions, fitness) = ctx.population_mut();
+                positions[worst].copy_from_slice(&x);
+                fitness[worst] = f;
+            }
+        }
+
+        let report = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .seed(8)
+            .mut_observer(Repair)
+            .build()
+            .run_with_budget(&Sphere, Budget::Evaluations(40))
+            .unwrap();
+
+        assert!(report.best_fitness.abs() < f64::EPSILON);
+        assert_eq!(report.evals, 40);
+    }
+
+    #[test]
+    fn decoder_error_ends_the_run() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Flaky(AtomicUsize);
+
+        impl Decoder for Flaky {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                if self.0.fetch_add(1, Ordering::Relaxed) >= 100 {
+                    return Err(DecoderError::UnknownError);
+                }
+                Sphere.decode(solution)
+            }
+        }
+
+        let err = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .seed(9)
+            .build()
+            .run(&Flaky(AtomicUsize::new(0)))
+            .unwrap_err();
+        let HhoError::Decoder { source, report } = err else {
+            panic!("expected a decoder error, got {err}");
+        };
+
+        assert!(matches!(source, DecoderError::UnknownError));
+        assert_eq!(report.stop_reason, Some(TerminationReason::Error));
+        assert!(!report.stop_reason.unwrap().converged());
+        assert_eq!(report.evals, 100);
+        assert!(report.best_fitness.is_finite());
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected_before_running() {
+        let bounds = Bounds::uniform(-10.0, 10.0, 3).unwrap();
+        let empty = HhoBuilder::new(bounds.clone()).pop_size(0).build();
+        let params = Params {
+            levy_beta: f64::NAN,
+            ..Params::default()
+        };
+        let bad = HhoBuilder::new(bounds).params(params).build();
+
+        assert!(matches!(empty.run(&Sphere), Err(HhoError::Init(_))));
+        let crowded = HhoBuilder::new(Bounds::uniform(-1.0, 1.0, 2).unwrap())
+            .pop_size(4)
+            .attractor(Attractor::EliteCentroid { k: 5 })
+            .momentum(-1.0)
+            .try_build();
+        assert!(matches!(crowded, Err(ConfigError { problems }) if problems.len() == 2));
+        assert!(
+            matches!(bad.run(&Sphere), Err(HhoError::Config(msg)) if msg.contains("levy_beta"))
+        );
+    }
+
+    #[test]
+    fn early_stop_closure_ends_the_run() {
+        use std::ops::ControlFlow;
+
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(1_000)
+            .seed(3)
+            .early_stop(|ctx| {
+                if ctx.iter >= 7 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert_eq!(report.iters, 7);
+        assert_eq!(report.stop_reason, Some(TerminationReason::EarlyStop));
+    }
+
+    #[test]
+    fn fdb_partner_selection_converges() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 5).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(3)
+            .partner_selection(PartnerSelection::FitnessDistanceBalance)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+
+        assert!(report.best_fitness < 1e-6, "best = {}", report.best_fitness);
+    }
+
+    /// Esfera cujo centro passa de 0 para 3 após `shift_after` avaliações
+    struct MovingSphere {
+        shift_after: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Decoder for MovingSphere {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            let n = self
+                .calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let c = if n >= self.shift_after { 3.0 } else { 0.0 };
+            Ok(solution.iter().map(|x| (x - c) * (x - c)).sum())
+        }
+    }
+
+    #[test]
+    fn sentinels_detect_landscape_change() {
+        use crate::hho::{ChangeDetection, Dynamic};
+
+        let decoder = MovingSphere {
+            shift_after: 500,
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+            .pop_size(20)
+            .max_iters(200)
+            .seed(4)
+            .dynamic(Dynamic {
+                detection: ChangeDetection::Sentinels { count: 2 },
+                rerandomize: 0.3,
+                ..Dynamic::default()
+            })
+            .build();
+        let report = hho.run(&decoder).unwrap();
+
+        assert_eq!(report.landscape_changes, 1);
+        assert!(
+            report.best_position.iter().all(|x| (x - 3.0).abs() < 0.1),
+            "best = {:?}",
+            report.best_position
+        );
+    }
+
+    /// Esfera com ruído de até ±5e-4, tirado da semente de cada avaliação
+    struct NoisySphere;
+
+    impl Decoder for NoisySphere {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Function::Sphere.decode(solution)
+        }
+
+        fn decode_with(&self, solution: &[f64], ctx: &EvalContext) -> Result<f64, DecoderError> {
+            #[allow(clippy::cast_precision_loss)]
+            let noise = ((ctx.seed % 1_000) as f64).mul_add(1e-6, -5e-4);
+            Ok(Function::Sphere.decode(solution)? + noise)
+        }
+    }
+
+    /// Esfera indefinida (NaN) no semiespaço `x[0] > 0`
+    struct Holes;
+
+    impl Decoder for Holes {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            if solution[0] > 0.0 {
+                return Ok(f64::NAN);
+            }
+            Function::Sphere.decode(solution)
+        }
+    }
+
+    #[test]
+    fn nan_fitness_ranks_last_for_immigrants_and_rank_steps() {
+        for seed in 2..=4 {
+            let report = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap())
+                .pop_size(40)
+                .max_iters(30)
+                .immigrants(Immigrants::default())
+                .rank_steps(RankSteps::default())
+                .seed(seed)
+                .build()
+                .run(&Holes)
+                .unwrap();
+            assert!(report.non_finite > 0);
+            assert!(report.best_fitness.is_finite(), "seed {seed}");
+            assert!(report.best_position[0] <= 0.0);
+        }
+    }
+
+    #[test]
+    fn sentinels_compare_raw_values_within_the_tolerance() {
+        let run = |decoder: &dyn Decoder, tolerance: f64, builder: HhoBuilder| {
+            builder
+                .pop_size(20)
+                .max_iters(60)
+                .seed(3)
+                .dynamic(Dynamic {
+                    detection: ChangeDetection::Sentinels { count: 20 },
+                    rerandomize: 0.5,
+                    tolerance,
+                })
+                .build()
+                .run(decoder)
+                .unwrap()
+        };
+        let builder = || HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 3).unwrap());
+
+        // A média do coelho reavaliado não entra na comparação, e o ruído fica na tolerância
+        let noisy = run(&NoisySphere, 1e-2, builder().reevaluate_rabbit(1));
+        assert_eq!(noisy.landscape_changes, 0);
+        assert!(noisy.best_fitness < 1.0, "best = {}", noisy.best_fitness);
+        let strict = run(&NoisySphere, 0.0, builder().reevaluate_rabbit(1));
+        assert!(strict.landscape_changes > 0);
+
+        // A fitness guardada dos buracos é a pior, mas o decodificador segue devolvendo NaN
+        let holes = run(&Holes, 1e-9, builder().non_finite(NonFinite::Worst));
+        assert!(holes.non_finite > 0);
+        assert_eq!(holes.landscape_changes, 0);
+        assert!(holes.best_fitness.is_finite());
+    }
+
+    /// Duas bacias separadas, em -5 e em +5, ambas com ótimo 0
+    struct TwoWells;
+
+    impl Decoder for TwoWells {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            let x = solution[0];
+            Ok(((x - 5.0) * (x - 5.0)).min((x + 5.0) * (x + 5.0)))
+        }
+    }
+
+    #[test]
+    fn speciation_finds_both_wells() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 1).unwrap())
+            .pop_size(40)
+            .max_iters(100)
+            .seed(4)
+            .niching(Niching::Speciation { radius: 2.0 })
+            .build();
+        let report = hho.run(&TwoWells).unwrap();
+
+        let near = |c: f64| {
+            report
+                .niches
+                .iter()
+                .any(|n| (n.position[0] - c).abs() < 0.5 && n.fitness < 0.25)
+        };
+        assert!(near(-5.0) && near(5.0), "niches = {:?}", report.niches);
+    }
+
+    #[test]
+    fn common_random_numbers_share_the_seed_of_an_iteration() {
+        #[derive(Default)]
+        struct Seeds(std::sync::Mutex<Vec<(usize, u64)>>);
+
+        impl Decoder for Seeds {
+            fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+                Sphere.decode(solution)
+            }
+
+            fn decode_with(&self, x: &[f64], ctx: &EvalContext) -> Result<f64, DecoderError> {
+                self.0.lock().unwrap().push((ctx.iteration, ctx.seed));
+                self.decode(x)
+            }
+        }
+
+        // Duas configurações diferentes com a mesma semente
+        let run = |pop_size: usize| {
+            let seeds = Seeds::default();
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 4).unwrap())
+                .pop_size(pop_size)
+                .max_iters(10)
+                .seed(3)
+                .common_random_numbers()
+                .build()
+                .run(&seeds)
+                .unwrap();
+            seeds.0.into_inner().unwrap()
+        };
+        let (a, b) = (run(10), run(25));
+        let mut by_iteration = std::collections::BTreeMap::new();
+        for &(iter, seed) in a.iter().chain(&b) {
+            assert_eq!(*by_iteration.entry(iter).or_insert(seed), seed);
+        }
+        assert_eq!(by_iteration.len(), 10);
+        assert!(
+            by_iteration
+                .values()
+                .collect::<std::collections::BTreeSet<_>>()
+                .len()
+                == 10
+        );
+    }
+
+    #[test]
+    fn leaders_keep_several_attractors() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 1).unwrap())
+            .pop_size(40)
+            .max_iters(100)
+            .seed(5)
+            .niching(Niching::Leaders {
+                k: 4,
+                assignment: LeaderAssignment::Nearest,
+            })
+            .build();
+        let report = hho.run(&TwoWells).unwrap();
+
+        assert_eq!(report.niches.len(), 4);
+        assert_eq!(report.niches.iter().map(|n| n.size).sum::<usize>(), 40);
+        let single = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 1).unwrap())
+            .pop_size(40)
+            .max_iters(100)
+            .seed(5)
+            .build()
+            .run(&TwoWells)
+            .unwrap();
+        // Com vários alvos a população demora mais a colapsar
+        let early = |r: &Report| r.diversity[..30].iter().sum::<f64>();
+        assert!(early(&report) > early(&single));
+
+        let bad = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 1).unwrap())
+            .pop_size(10)
+            .niching(Niching::Leaders {
+                k: 11,
+                assignment: LeaderAssignment::Rank,
+            })
+            .build();
+        assert!(bad.check().is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::core::Objective;
+
+/// Orthogonal learning (OED) step applied to the rabbit.
+///
+/// Every `every` iterations the rabbit is combined with a random elite hawk (one of the
+/// `elites` best) using a two-level orthogonal array over `factors` groups of dimensions;
+/// factor analysis then predicts the best combination, which replaces the rabbit if it is
+/// better. Costs `M + 1` evaluations, where `M` is the smallest power of two above `factors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrthogonalLearning {
+    pub every: usize,
+    pub factors: usize,
+    pub elites: usize,
+}
+
+impl Default for OrthogonalLearning {
+    fn default() -> Self {
+        Self {
+            every: 1,
+            factors: 7,
+            elites: 5,
+        }
+    }
+}
+
+/// Two-level orthogonal array `L_M(2^(M-1))` with at least `factors` columns.
+///
+/// Entry `[r][c]` is the level (`false` = first parent, `true` = second) of factor `c` in
+/// combination `r`, built as the parity of `r & (c + 1)`.
+#[must_use]
+pub fn orthogonal_array(factors: usize) -> Vec<Vec<bool>> {
+    let m = (factors + 1).next_power_of_two();
+    (0..m)
+        .map(|r| {
+            (1..m)
+                .take(factors)
+                .map(|c| (r & c).count_ones() % 2 == 1)
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits `dim` dimensions into `factors` contiguous groups and returns the group of each one.
+fn factor_of(dim: usize, factors: usize) -> impl Fn(usize) -> usize {
+    move |j| j * factors / dim
+}
+
+/// Runs one orthogonal learning step between `a` (the rabbit) and `b`.
+///
+/// `eval` is called for every tested combination. Returns the best combination found and its
+/// fitness (which may be worse than `a`; the caller decides whether to keep it).
+pub fn orthogonal_combine<O, F>(
+    a: &[f64],
+    b: &[f64],
+    factors: usize,
+    mut eval: F,
+) -> (Vec<f64>, f64)
+where
+    O: Objective,
+    F: FnMut(&[f64]) -> f64,
+{
+    crate::core::utils::strict_assert_eq!(a.len(), b.len(), "parents dim mismatch");
+    let dim = a.len();
+    let factors = factors.clamp(1, dim);
+    let group = factor_of(dim, factors);
+    let oa = orthogonal_array(factors);
+
+    let mut x = vec![0.0; dim];
+    let mut best: Option<(Vec<f64>, f64)> = None;
+    // Soma e contagem da fitness por (fator, nível)
+    let mut sums = vec![[0.0f64; 2]; factors];
+    let mut counts = vec![[0usize; 2]; factors];
+
+    for row in &oa {
+        for j in 0..dim {
+            x[j] = if row[group(j)] { b[j] } else { a[j] };
+        }
+        let f = eval(&x);
+        for (c, &lvl) in row.iter().enumerate() {
+            sums[c][usize::from(lvl)] += f;
+            counts[c][usize::from(lvl)] += 1;
+        }
+        if best.as_ref().is_none_or(|(_, bf)| O::better(f, *bf)) {
+            best = Some((x.clone(), f));
+        }
+    }
+
+    // Combinação prevista: em cada fator, o nível com a melhor média
+    #[allow(clippy::cast_precision_loss)]
+    let level: Vec<bool> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(s, n)| O::better(s[1] / n[1] as f64, s[0] / n[0] as f64))
+        .collect();
+    for j in 0..dim {
+        x[j] = if level[group(j)] { b[j] } else { a[j] };
+    }
+    let f = eval(&x);
+    match best {
+        Some((bx, bf)) if !O::better(f, bf) => (bx, bf),
+        _ => (x, f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Decoder, DecoderError, Minimization};
+
+    #[test]
+    fn array_is_orthogonal() {
+        let oa = orthogonal_array(3);
+        assert_eq!(oa.len(), 4);
+        // Cada par de colunas contém cada combinação de níveis o mesmo número de vezes
+        for c1 in 0..3 {
+            for c2 in (c1 + 1)..3 {
+                let mut seen = [0; 4];
+                for row in &oa {
+                    seen[usize::from(row[c1]) * 2 + usize::from(row[c2])] += 1;
+                }
+                assert_eq!(seen, [1, 1, 1, 1]);
+            }
+        }
+    }
+
+    struct Separable;
+
+    impl Decoder for Separable {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(solution.iter().map(|x| x * x).sum())
+        }
+    }
+
+    #[test]
+    fn combines_best_dimensions_of_each_parent() {
+        let a = [0.0, 5.0, 0.0, 5.0];
+        let b = [5.0, 0.0, 5.0, 0.0];
+        let (x, f) =
+            orthogonal_combine::<Minimization, _>(&a, &b, 4, |x| Separable.decode(x).unwrap());
+        assert_eq!(x, vec![0.0; 4]);
+        assert!(f.abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,211 @@
+//! Two configurations compared on the same seeds and the same initial populations.
+
+use std::marker::PhantomData;
+
+use rand::Rng;
+
+use crate::{
+    core::{
+        Bounds, Decoder, Direction, Objective, Population, Report, ReportSet, SeedStreams, utils,
+    },
+    experiments::{TestResult, wilcoxon},
+    hho::{Hho, HhoError},
+    init::{InitError, Initializer},
+};
+
+/// Outcome of [`Hho::compare_paired`].
+#[derive(Debug, Clone)]
+pub struct PairedComparison {
+    pub direction: Direction,
+    /// Seed of each pair of runs.
+    pub seeds: Vec<u64>,
+    /// Runs of the optimizer compared, in seed order.
+    pub candidate: ReportSet,
+    /// Runs of the baseline, in seed order.
+    pub baseline: ReportSet,
+    /// Best fitness of the candidate minus the baseline's, per seed; a better candidate has a
+    /// negative delta when minimizing and a positive one when maximizing.
+    pub deltas: Vec<f64>,
+    /// Seeds on which the candidate ended strictly better.
+    pub wins: usize,
+    /// Seeds on which the baseline ended strictly better.
+    pub losses: usize,
+    /// Wilcoxon signed-rank test of the deltas, see [`wilcoxon`].
+    pub test: Option<TestResult>,
+}
+
+impl PairedComparison {
+    /// Mean of [`PairedComparison::deltas`], or `None` without runs.
+    #[must_use]
+    pub fn mean_delta(&self) -> Option<f64> {
+        utils::mean(&self.deltas)
+    }
+
+    /// Seeds on which both ended with the same best fitness.
+    #[must_use]
+    pub const fn ties(&self) -> usize {
+        self.deltas.len() - self.wins - self.losses
+    }
+
+    /// Whether the candidate won more seeds than it lost and the test rejects equality at
+    /// level `alpha`.
+    #[must_use]
+    pub fn candidate_better(&self, alpha: f64) -> bool {
+        self.wins > self.losses && self.test.is_some_and(|t| t.p_value < alpha)
+    }
+}
+
+/// População inicial já sorteada, entregue igual às duas configurações
+struct Given(Population);
+
+impl Initializer for Given {
+    fn initialize<R: Rng>(
+        &self,
+        _pop_size: usize,
+        _bounds: &Bounds,
+        _rng: &mut R,
+    ) -> Result<Population, InitError> {
+        Ok(self.0.clone())
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Runs this optimizer and `baseline` on `n` paired seeds and compares their best
+    /// fitness seed by seed.
+    ///
+    /// Pair `k` uses [`SeedStreams::replication`] `k` of this optimizer's seed (a fresh one if
+    /// none is set) for both runs, and both start from the same population, drawn by this
+    /// optimizer's initializer: the runs of a pair differ only by their settings. Pairing
+    /// removes the spread between seeds from the comparison, so a small improvement shows with
+    /// fewer runs than with independent samples. The runs are sequential.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if the two optimizers differ in bounds or population size,
+    /// or if a checkpoint is configured; otherwise the error of the first failing run, as
+    /// [`Hho::run`] would.
+    pub fn compare_paired<I2: Initializer>(
+        &self,
+        baseline: &Hho<O, I2>,
+        n: usize,
+        decoder: &dyn Decoder,
+    ) -> Result<PairedComparison, HhoError> {
+        if self.bounds.lower() != baseline.bounds.lower()
+            || self.bounds.upper() != baseline.bounds.upper()
+            || self.settings.pop_size != baseline.settings.pop_size
+        {
+            return Err(HhoError::Config(
+                "paired runs need the same bounds and population size".to_owned(),
+            ));
+        }
+        #[cfg(feature = "serde")]
+        if self.settings.checkpoint.is_some() || baseline.settings.checkpoint.is_some() {
+            return Err(HhoError::Config(
+                "checkpoints are not supported with replicated runs".to_owned(),
+            ));
+        }
+        let master = self
+            .settings
+            .seed
+            .map_or_else(SeedStreams::from_entropy, SeedStreams::new);
+        let seeds: Vec<u64> = (0..n as u64).map(|k| master.replication(k)).collect();
+        let (mut candidate, mut base) = (Vec::with_capacity(n), Vec::with_capacity(n));
+        for &seed in &seeds {
+            let streams = SeedStreams::new(seed).with_kind(self.settings.rng);
+            let start = self.initializer.initialize(
+                self.settings.pop_size,
+                &self.bounds,
+                &mut streams.init(),
+            )?;
+            candidate.push(self.with_start(start.clone()).run_seeded(decoder, seed)?);
+            base.push(baseline.with_start(start).run_seeded(decoder, seed)?);
+        }
+
+        let direction = O::direction();
+        let fitness =
+            |reports: &[Report]| -> Vec<f64> { reports.iter().map(|r| r.best_fitness).collect() };
+        let (a, b) = (fitness(&candidate), fitness(&base));
+        let deltas: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+        let wins = a
+            .iter()
+            .zip(&b)
+            .filter(|(x, y)| direction.better(**x, **y))
+            .count();
+        let losses = a
+            .iter()
+            .zip(&b)
+            .filter(|(x, y)| direction.better(**y, **x))
+            .count();
+        Ok(PairedComparison {
+            direction,
+            seeds,
+            test: wilcoxon(&a, &b),
+            candidate: ReportSet::new(candidate),
+            baseline: ReportSet::new(base),
+            deltas,
+            wins,
+            losses,
+        })
+    }
+
+    /// Cópia do otimizador que começa de `start`
+    fn with_start(&self, start: Population) -> Hho<O, Given> {
+        Hho {
+            bounds: self.bounds.clone(),
+            initializer: Given(start),
+            settings: self.settings.clone(),
+            objective: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, hho::HhoBuilder};
+
+    #[test]
+    fn paired_runs_share_seeds_and_starts() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 5).unwrap())
+                .pop_size(10)
+                .max_iters(40)
+                .seed(8)
+        };
+        let comparison = builder()
+            .build()
+            .compare_paired(&builder().max_iters(2).build(), 12, &Function::Sphere)
+            .unwrap();
+        assert_eq!(comparison.deltas.len(), 12);
+        assert_eq!(comparison.wins + comparison.losses + comparison.ties(), 12);
+        assert!(comparison.candidate_better(0.01));
+        assert!(comparison.mean_delta().unwrap() < 0.0);
+        for (a, b) in comparison
+            .candidate
+            .reports
+            .iter()
+            .zip(&comparison.baseline.reports)
+        {
+            assert_eq!(a.seed, b.seed);
+            // Mesma população inicial, logo o mesmo primeiro registro
+            assert_eq!(a.improvements[0], b.improvements[0]);
+        }
+
+        // Contra si mesmo só há empates
+        let same = builder()
+            .build()
+            .compare_paired(&builder().build(), 3, &Function::Sphere)
+            .unwrap();
+        assert_eq!(same.ties(), 3);
+        assert!(matches!(
+            builder()
+                .build()
+                .compare_paired(&builder().pop_size(11).build(), 3, &Function::Sphere),
+            Err(HhoError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+/// Tunable coefficients of the HHO update equations.
+///
+/// The defaults reproduce the original algorithm of Heidari et al. (2019): the escape energy
+/// decays linearly, the rapid dives use the raw Lévy step and `β = 1.5`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Params {
+    /// Exponent applied to `(1 - t/T)` when computing the escape energy.
+    ///
+    /// `1.0` is the classic linear decay; larger values make the hawks switch to exploitation
+    /// earlier, smaller values keep them exploring for longer.
+    pub energy_decay: f64,
+
+    /// Multiplier applied to the Lévy flight during the progressive rapid dives.
+    pub dive_scale: f64,
+
+    /// Stability index `β` of the Lévy distribution, in `(0, 2)`.
+    pub levy_beta: f64,
+}
+
+impl Params {
+    pub const ENERGY_DECAY_RANGE: (f64, f64) = (0.1, 4.0);
+    pub const DIVE_SCALE_RANGE: (f64, f64) = (1e-3, 10.0);
+    pub const LEVY_BETA_RANGE: (f64, f64) = (0.3, 1.99);
+
+    /// Clamps every coefficient to its admissible range.
+    #[must_use]
+    pub const fn clamped(self) -> Self {
+        Self {
+            energy_decay: self
+                .energy_decay
+                .clamp(Self::ENERGY_DECAY_RANGE.0, Self::ENERGY_DECAY_RANGE.1),
+            dive_scale: self
+                .dive_scale
+                .clamp(Self::DIVE_SCALE_RANGE.0, Self::DIVE_SCALE_RANGE.1),
+            levy_beta: self
+                .levy_beta
+                .clamp(Self::LEVY_BETA_RANGE.0, Self::LEVY_BETA_RANGE.1),
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            energy_decay: 1.0,
+            dive_scale: 1.0,
+            levy_beta: 1.5,
+        }
+    }
+}
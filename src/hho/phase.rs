@@ -0,0 +1,119 @@
+/// Branch of the HHO update a hawk took in one iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// `|E| >= 1`: perching on a random hawk or around the family mean.
+    Exploration,
+    /// `|E| >= 0.5`, `r >= 0.5`.
+    SoftBesiege,
+    /// `|E| < 0.5`, `r >= 0.5`.
+    HardBesiege,
+    /// `|E| >= 0.5`, `r < 0.5`: soft besiege with progressive rapid dives.
+    SoftDives,
+    /// `|E| < 0.5`, `r < 0.5`: hard besiege with progressive rapid dives.
+    HardDives,
+    /// Quantum-behaved exploitation ([`crate::hho::Exploitation::Quantum`]).
+    Quantum,
+    /// Differential evolution step ([`crate::hho::Exploitation::Differential`]).
+    Differential,
+    /// Sine cosine step ([`crate::hho::Exploitation::SineCosine`]).
+    SineCosine,
+}
+
+impl Phase {
+    /// Every phase, in declaration order.
+    pub const ALL: [Self; 8] = [
+        Self::Exploration,
+        Self::SoftBesiege,
+        Self::HardBesiege,
+        Self::SoftDives,
+        Self::HardDives,
+        Self::Quantum,
+        Self::Differential,
+        Self::SineCosine,
+    ];
+
+    /// Position of the phase in [`Phase::ALL`].
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Whether this is one of the progressive rapid dive branches.
+    #[must_use]
+    pub const fn is_dive(self) -> bool {
+        matches!(self, Self::SoftDives | Self::HardDives)
+    }
+}
+
+/// Number of hawks that took each [`Phase`] in one iteration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseCounts {
+    pub exploration: usize,
+    pub soft_besiege: usize,
+    pub hard_besiege: usize,
+    pub soft_dives: usize,
+    pub hard_dives: usize,
+    pub quantum: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub differential: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sine_cosine: usize,
+    /// Worst hawks replaced by random immigrants after the update (see
+    /// [`crate::hho::Immigrants`]); not part of [`PhaseCounts::total`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub immigrants: usize,
+}
+
+impl PhaseCounts {
+    #[inline]
+    pub const fn add(&mut self, phase: Phase) {
+        match phase {
+            Phase::Exploration => self.exploration += 1,
+            Phase::SoftBesiege => self.soft_besiege += 1,
+            Phase::HardBesiege => self.hard_besiege += 1,
+            Phase::SoftDives => self.soft_dives += 1,
+            Phase::HardDives => self.hard_dives += 1,
+            Phase::Quantum => self.quantum += 1,
+            Phase::Differential => self.differential += 1,
+            Phase::SineCosine => self.sine_cosine += 1,
+        }
+    }
+
+    /// Hawks updated in the iteration.
+    #[inline]
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.exploration
+            + self.soft_besiege
+            + self.hard_besiege
+            + self.soft_dives
+            + self.hard_dives
+            + self.quantum
+            + self.differential
+            + self.sine_cosine
+    }
+
+    /// Hawks that took `phase`.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, phase: Phase) -> usize {
+        match phase {
+            Phase::Exploration => self.exploration,
+            Phase::SoftBesiege => self.soft_besiege,
+            Phase::HardBesiege => self.hard_besiege,
+            Phase::SoftDives => self.soft_dives,
+            Phase::HardDives => self.hard_dives,
+            Phase::Quantum => self.quantum,
+            Phase::Differential => self.differential,
+            Phase::SineCosine => self.sine_cosine,
+        }
+    }
+
+    /// Hawks in any exploitation branch.
+    #[inline]
+    #[must_use]
+    pub const fn exploitation(&self) -> usize {
+        self.total() - self.exploration
+    }
+}
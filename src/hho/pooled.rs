@@ -0,0 +1,208 @@
+//! Replications sharing one evaluation budget, moved from converged runs to improving ones.
+
+use crate::{
+    core::{Budget, Decoder, Objective, ReportSet, SeedStreams, TerminationReason},
+    hho::{Hho, HhoError, Run, sweep::DecoderRef},
+    init::Initializer,
+};
+
+/// Plateau test of [`Hho::run_many_pooled`].
+///
+/// Every `window` iterations each live run is checked: one whose best fitness improved by no
+/// more than `tolerance` times its previous magnitude over the window has converged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plateau {
+    /// Iterations between two checks, at least 1.
+    pub window: usize,
+    /// Relative improvement under which a window is flat, finite and non-negative.
+    pub tolerance: f64,
+}
+
+impl Default for Plateau {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            tolerance: 1e-6,
+        }
+    }
+}
+
+impl Plateau {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self { window, tolerance } = *self;
+        (window == 0 || !(tolerance.is_finite() && tolerance >= 0.0)).then(|| {
+            format!(
+                "plateau needs window = {window} >= 1 and a finite non-negative tolerance = \
+                 {tolerance}"
+            )
+        })
+    }
+
+    /// Se a melhora de `before` para `after` é desprezível
+    fn flat(&self, before: f64, after: f64) -> bool {
+        (before - after).abs() <= self.tolerance * before.abs()
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Runs `n` replications sharing a total of `n * evals_per_run` evaluations.
+    ///
+    /// Each replication starts with an evaluation budget of `evals_per_run` and is seeded as
+    /// in [`crate::hho::Hho::run_many`]. The replications advance in turns of
+    /// [`Plateau::window`] iterations; after each turn, while some run is still improving,
+    /// the runs found on a plateau stop with [`TerminationReason::Stalled`], and the
+    /// evaluations they leave, like those of runs that stopped by themselves (e.g. on a
+    /// target), are split evenly among the runs still going. Their budget grows, so their
+    /// escape energy decays over a longer horizon. When every run is flat, none is stopped.
+    ///
+    /// Under a fixed total budget this improves the best of the `n` results compared to
+    /// independent runs, at the price of runs of unequal lengths: use
+    /// [`crate::hho::Hho::run_many`] when the replications are compared with each other.
+    /// The runs are interleaved on the calling thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if `plateau` is out of range or a checkpoint is
+    /// configured, and otherwise the error of the first failing replication, as
+    /// [`Hho::run`] would.
+    pub fn run_many_pooled(
+        &self,
+        n: usize,
+        evals_per_run: usize,
+        plateau: Plateau,
+        decoder: &dyn Decoder,
+    ) -> Result<ReportSet, HhoError> {
+        if let Some(problem) = plateau.problem() {
+            return Err(HhoError::Config(problem));
+        }
+        #[cfg(feature = "serde")]
+        if self.settings.checkpoint.is_some() {
+            return Err(HhoError::Config(
+                "checkpoints are not supported with replicated runs".to_owned(),
+            ));
+        }
+        let master = self
+            .settings
+            .seed
+            .map_or_else(SeedStreams::from_entropy, SeedStreams::new);
+        let mut runs = (0..n as u64)
+            .map(|k| {
+                let streams = SeedStreams::new(master.replication(k));
+                let budget = Budget::Evaluations(evals_per_run);
+                let (state, history, rng) = self.fresh_from(decoder, budget, streams)?;
+                Ok(Run::new(
+                    self,
+                    DecoderRef::Local(decoder),
+                    state,
+                    history,
+                    rng,
+                ))
+            })
+            .collect::<Result<Vec<_>, HhoError>>()?;
+
+        let mut live: Vec<usize> = (0..n).collect();
+        while !live.is_empty() {
+            let mut flat = Vec::with_capacity(live.len());
+            for &k in &live {
+                let run = &mut runs[k];
+                let before = run.current_best().1;
+                for _ in 0..plateau.window {
+                    if !run.step() {
+                        break;
+                    }
+                }
+                flat.push(plateau.flat(before, run.current_best().1));
+            }
+            // Só se abandona um platô se houver para quem dar o orçamento
+            let improving = live
+                .iter()
+                .zip(&flat)
+                .any(|(&k, &f)| !f && !runs[k].stopped());
+            let mut freed = 0;
+            for (&k, &f) in live.iter().zip(&flat) {
+                if f && improving {
+                    runs[k].retire(TerminationReason::Stalled);
+                }
+                if runs[k].stopped() {
+                    freed += runs[k].evals_left();
+                }
+            }
+            live.retain(|&k| !runs[k].stopped());
+            if let Some(share) = freed.checked_div(live.len()) {
+                let extra = freed % live.len();
+                for (i, &k) in live.iter().enumerate() {
+                    runs[k].grant(share + usize::from(i < extra));
+                }
+            }
+        }
+        runs.into_iter()
+            .map(Run::try_complete)
+            .collect::<Result<_, _>>()
+            .map(ReportSet::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn converged_runs_hand_their_budget_to_improving_ones() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+                .pop_size(10)
+                .seed(5)
+        };
+        let plateau = Plateau {
+            window: 10,
+            tolerance: 1e-3,
+        };
+        let pooled = builder()
+            .build()
+            .run_many_pooled(6, 3_000, plateau, &Function::Rastrigin)
+            .unwrap();
+        let best = |set: &ReportSet| set.best_fitness().unwrap().min;
+
+        // As mesmas réplicas, cada uma com o seu próprio orçamento
+        let master = SeedStreams::new(5);
+        let alone: ReportSet = (0..6)
+            .map(|k| {
+                builder()
+                    .seed(master.replication(k))
+                    .build()
+                    .run_with_budget(&Function::Rastrigin, Budget::Evaluations(3_000))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+            .into();
+        assert!(best(&pooled) <= best(&alone));
+        let evals: Vec<usize> = pooled.reports.iter().map(|r| r.evals).collect();
+        assert!(evals.iter().sum::<usize>() <= 6 * 3_000 + 6 * 20);
+        assert!(evals.iter().any(|&e| e > 3_000));
+        assert!(
+            pooled
+                .reports
+                .iter()
+                .any(|r| r.stop_reason == Some(TerminationReason::Stalled))
+        );
+        assert_eq!(pooled.reports[0].seed, alone.reports[0].seed);
+
+        let bad = Plateau {
+            window: 0,
+            ..Plateau::default()
+        };
+        assert!(matches!(
+            builder()
+                .build()
+                .run_many_pooled(2, 100, bad, &Function::Sphere),
+            Err(HhoError::Config(_))
+        ));
+    }
+}
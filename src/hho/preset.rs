@@ -0,0 +1,107 @@
+use crate::{
+    core::Objective,
+    hho::{Attractor, Dedup, DiveShape, HhoBuilder, Immigrants, Params, PartnerSelection, Refill},
+    init::Initializer,
+};
+
+/// Starting configurations for common kinds of problems, applied with
+/// [`HhoBuilder::preset`].
+///
+/// A preset only sets a coherent combination of population size, escape-energy schedule,
+/// opposition-based immigrants and duplicate restarts; anything set after it on the builder
+/// overrides it. None of them changes the iteration limit or the stopping rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Preset {
+    /// A few hundred evaluations: 10 hawks and a fast decay of `E` (`energy_decay = 2`), so
+    /// the hawks switch to exploitation early. No extra evaluations on restarts.
+    LowBudget,
+
+    /// Dozens to hundreds of variables: 50 hawks, dive steps scaled per coordinate
+    /// ([`DiveShape::Diagonal`]) and 5% quasi-opposite immigrants per iteration to keep the
+    /// population spread out.
+    HighDim,
+
+    /// Noisy objectives: 40 hawks besieging the centroid of the 5 best
+    /// ([`Attractor::EliteCentroid`]) instead of a possibly lucky rabbit, which is also
+    /// re-evaluated every 10 iterations, and a slower decay of `E` (`energy_decay = 0.7`).
+    Noisy,
+
+    /// Many local optima: 60 hawks exploring longer (`energy_decay = 0.5`), partners drawn by
+    /// fitness–distance balance, 10% quasi-opposite immigrants per iteration and clones
+    /// restarted at the quasi-opposite point.
+    Multimodal,
+}
+
+impl Preset {
+    /// Every preset, in declaration order.
+    pub const ALL: [Self; 4] = [
+        Self::LowBudget,
+        Self::HighDim,
+        Self::Noisy,
+        Self::Multimodal,
+    ];
+
+    /// Aplica o preset ao construtor
+    pub(crate) fn apply<O: Objective, I: Initializer>(
+        self,
+        builder: HhoBuilder<O, I>,
+    ) -> HhoBuilder<O, I> {
+        let decay = |energy_decay| Params {
+            energy_decay,
+            ..Params::default()
+        };
+        let opposite = |fraction| Immigrants {
+            fraction,
+            refill: Refill::Opposition,
+        };
+        match self {
+            Self::LowBudget => builder.pop_size(10).params(decay(2.0)),
+            Self::HighDim => builder
+                .pop_size(50)
+                .dive_shape(DiveShape::Diagonal)
+                .immigrants(opposite(0.05)),
+            Self::Noisy => builder
+                .pop_size(40)
+                .params(decay(0.7))
+                .attractor(Attractor::EliteCentroid { k: 5 })
+                .reevaluate_rabbit(10),
+            Self::Multimodal => builder
+                .pop_size(60)
+                .params(decay(0.5))
+                .partner_selection(PartnerSelection::FitnessDistanceBalance)
+                .immigrants(opposite(0.1))
+                .dedup(Dedup {
+                    refill: Refill::Opposition,
+                    ..Dedup::default()
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Bounds;
+
+    #[test]
+    fn every_preset_is_valid_and_can_be_overridden() {
+        let bounds = Bounds::uniform(-5.0, 5.0, 20).unwrap();
+        for preset in Preset::ALL {
+            let hho = HhoBuilder::new(bounds.clone()).preset(preset).build();
+            assert!(hho.check().is_ok(), "{preset:?}: {:?}", hho.check());
+        }
+        let noisy = HhoBuilder::new(bounds.clone())
+            .preset(Preset::Noisy)
+            .build();
+        assert_eq!(noisy.settings.pop_size, 40);
+        assert_eq!(noisy.settings.reevaluate, Some(10));
+        let hho = HhoBuilder::new(bounds)
+            .preset(Preset::Multimodal)
+            .pop_size(15)
+            .build();
+        assert_eq!(hho.settings.pop_size, 15);
+        assert!(hho.settings.dedup.is_some());
+    }
+}
@@ -0,0 +1,145 @@
+use rand::Rng;
+
+use crate::core::Population;
+
+/// Long-term memory of successful dive displacements, replayed by other hawks.
+///
+/// Every time a progressive dive improves a hawk, the displacement it made is stored in a
+/// circular memory of `capacity` vectors. The second candidate of later dives is then, with
+/// probability `rate`, `x + s·scale·d` instead of the Lévy flight, where `d` is a remembered
+/// displacement and `s ~ U(0, 1)`. Like the success history of DE, the memory keeps the
+/// directions that worked, which follow the valleys of problems with correlated variables
+/// (e.g. Rosenbrock) far better than axis-independent Lévy steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiveReplay {
+    /// Displacements remembered, at least 1.
+    pub capacity: usize,
+    /// Probability of replaying a displacement instead of the Lévy flight, in `(0, 1]`.
+    pub rate: f64,
+    /// Largest multiple of a remembered displacement taken, finite and positive.
+    pub scale: f64,
+}
+
+impl Default for DiveReplay {
+    fn default() -> Self {
+        Self {
+            capacity: 16,
+            rate: 0.5,
+            scale: 1.0,
+        }
+    }
+}
+
+impl DiveReplay {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self {
+            capacity,
+            rate,
+            scale,
+        } = *self;
+        (capacity == 0 || !(rate > 0.0 && rate <= 1.0) || !(scale.is_finite() && scale > 0.0)).then(
+            || {
+                format!(
+                    "dive replay needs capacity = {capacity} >= 1, rate = {rate} in (0, 1] and \
+                     a finite positive scale = {scale}"
+                )
+            },
+        )
+    }
+}
+
+/// Circular memory of the displacements of [`DiveReplay`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Displacements {
+    vectors: Population,
+    capacity: usize,
+    /// Próxima posição a sobrescrever quando a memória está cheia
+    cursor: usize,
+}
+
+impl Displacements {
+    /// Empty memory of at most `capacity` (at least one) `dim`-dimensional displacements.
+    #[must_use]
+    pub fn new(capacity: usize, dim: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            vectors: Population::with_capacity(capacity, dim),
+            capacity,
+            cursor: 0,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    /// Remembered displacements, in storage order.
+    #[must_use]
+    pub const fn vectors(&self) -> &Population {
+        &self.vectors
+    }
+
+    /// Remembers the move from `from` to `to`, replacing the oldest one when full.
+    pub fn record(&mut self, from: &[f64], to: &[f64]) {
+        if self.vectors.len() < self.capacity {
+            // Um deslocamento de outra dimensão não entra na memória
+            if self.vectors.push(to).is_err() {
+                return;
+            }
+            let k = self.vectors.len() - 1;
+            for (d, &a) in self.vectors[k].iter_mut().zip(from) {
+                *d -= a;
+            }
+        } else {
+            for ((d, &a), &b) in self.vectors[self.cursor].iter_mut().zip(from).zip(to) {
+                *d = b - a;
+            }
+            self.cursor = (self.cursor + 1) % self.capacity;
+        }
+    }
+
+    /// A remembered displacement drawn uniformly, `None` while the memory is empty.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&[f64]> {
+        (!self.is_empty()).then(|| &self.vectors[rng.random_range(0..self.len())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_latest_displacements() {
+        let mut memory = Displacements::new(2, 2);
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        assert!(memory.sample(&mut rng).is_none());
+        memory.record(&[0.0, 0.0], &[1.0, 2.0]);
+        memory.record(&[1.0, 1.0], &[1.0, 0.0]);
+        memory.record(&[2.0, 2.0], &[5.0, 2.0]);
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.vectors().to_rows(), [[3.0, 0.0], [0.0, -1.0]]);
+        let d = memory.sample(&mut rng).unwrap();
+        assert!(d == [3.0, 0.0] || d == [0.0, -1.0]);
+
+        assert!(DiveReplay::default().problem().is_none());
+        let zero = DiveReplay {
+            rate: 0.0,
+            ..DiveReplay::default()
+        };
+        assert!(zero.problem().is_some());
+    }
+}
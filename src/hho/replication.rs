@@ -0,0 +1,150 @@
+//! Independent replications of a run, spread over a thread pool (feature `rayon`).
+
+use rayon::prelude::*;
+
+use crate::{
+    core::{Decoder, Objective, Report, ReportSet, Robustness, SeedStreams},
+    hho::{Hho, HhoError},
+    init::Initializer,
+};
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective + Sync,
+    I: Initializer,
+{
+    /// Runs `n` independent replications of the optimizer and returns their reports in
+    /// replication order.
+    ///
+    /// Replication `k` is seeded with `seeds[k]` or, without `seeds`, with
+    /// [`SeedStreams::replication`] of the configured seed (a fresh one if none is set), so a
+    /// seeded experiment gives the same reports for any thread count.
+    ///
+    /// The replications run on a pool of `parallelism` threads (`0` uses the global rayon
+    /// pool), each one sequentially on a single worker: this is parallelism across runs, for
+    /// many cheap runs, unlike [`Hho::run_parallel`] which parallelizes the evaluations of one
+    /// run. Observers registered on the builder are shared by every replication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if `seeds` has fewer than `n` entries, if a checkpoint is
+    /// configured (the replications would overwrite each other's file) or if the thread pool
+    /// cannot be created. Otherwise returns the error of the first failing replication, as
+    /// [`Hho::run`] would.
+    pub fn run_many(
+        &self,
+        n: usize,
+        seeds: Option<&[u64]>,
+        parallelism: usize,
+        decoder: &(dyn Decoder + Sync),
+    ) -> Result<ReportSet, HhoError> {
+        #[cfg(feature = "serde")]
+        if self.settings.checkpoint.is_some() {
+            return Err(HhoError::Config(
+                "checkpoints are not supported with replicated runs".to_owned(),
+            ));
+        }
+        let seeds: Vec<u64> = match seeds {
+            Some(s) if s.len() < n => {
+                return Err(HhoError::Config(format!(
+                    "{} seeds given for {n} replications",
+                    s.len()
+                )));
+            }
+            Some(s) => s[..n].to_vec(),
+            None => {
+                let master = self
+                    .settings
+                    .seed
+                    .map_or_else(SeedStreams::from_entropy, SeedStreams::new);
+                (0..n as u64).map(|k| master.replication(k)).collect()
+            }
+        };
+
+        let replicate = || -> Vec<Result<Report, HhoError>> {
+            seeds
+                .par_iter()
+                .map(|&seed| self.run_seeded(decoder, seed))
+                .collect()
+        };
+        let results = if parallelism == 0 {
+            replicate()
+        } else {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(parallelism)
+                .build()
+                .map_err(|e| HhoError::Config(format!("cannot build thread pool: {e}")))?
+                .install(replicate)
+        };
+        results
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .map(ReportSet::new)
+    }
+
+    /// Runs `n` replications like [`Hho::run_many`] and measures how much the best fitness
+    /// depends on the seed, see [`ReportSet::robustness`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if `n` is zero, otherwise fails like [`Hho::run_many`].
+    pub fn seed_robustness(
+        &self,
+        n: usize,
+        threshold: f64,
+        parallelism: usize,
+        decoder: &(dyn Decoder + Sync),
+    ) -> Result<Robustness, HhoError> {
+        self.run_many(n, None, parallelism, decoder)?
+            .robustness(threshold)
+            .ok_or_else(|| HhoError::Config("seed robustness needs a replication".to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, core::Bounds, hho::HhoBuilder};
+
+    fn hho() -> Hho {
+        HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(11)
+            .build()
+    }
+
+    #[test]
+    fn replications_do_not_depend_on_the_pool() {
+        let hho = hho();
+        let one = hho.run_many(6, None, 1, &Sphere).unwrap();
+        let four = hho.run_many(6, None, 4, &Sphere).unwrap();
+        let bests =
+            |set: &ReportSet| -> Vec<f64> { set.reports.iter().map(|r| r.best_fitness).collect() };
+
+        assert_eq!(one.reports.len(), 6);
+        assert_eq!(bests(&one), bests(&four));
+        // Cada réplica tem sua própria semente
+        assert!(one.reports.windows(2).all(|w| w[0].seed != w[1].seed));
+
+        let seeded = hho.run_many(2, Some(&[3, 4, 5]), 0, &Sphere).unwrap();
+        let single = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(4)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert_eq!(seeded.reports[1].seed, Some(4));
+        assert_eq!(seeded.reports[1].best_position, single.best_position);
+
+        assert!(matches!(
+            hho.run_many(3, Some(&[1]), 0, &Sphere),
+            Err(HhoError::Config(_))
+        ));
+
+        let robustness = hho.seed_robustness(6, 0.1, 0, &Sphere).unwrap();
+        assert_eq!(robustness.summary, one.best_fitness().unwrap());
+        assert!(one.reports.iter().any(|r| r.seed == robustness.worst_seed));
+    }
+}
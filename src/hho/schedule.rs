@@ -0,0 +1,231 @@
+use std::{fmt, sync::Arc};
+
+use crate::hho::{Params, optimizer::Settings};
+
+/// Value of a parameter as a function of the progress of the run, see
+/// [`crate::hho::HhoBuilder::schedule`].
+///
+/// Progress is the fraction of the budget used, in `[0, 1]`: `t/T` with an iteration budget,
+/// as in the escape energy.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Schedule {
+    Constant(f64),
+    /// From `start` at progress 0 to `end` at progress 1, linearly.
+    Linear {
+        start: f64,
+        end: f64,
+    },
+    /// From `start` to `end`, both positive, by a constant ratio per unit of progress.
+    Exponential {
+        start: f64,
+        end: f64,
+    },
+    /// Linear between the `(progress, value)` knots, sorted by progress, and flat beyond the
+    /// first and last ones.
+    Piecewise(Vec<(f64, f64)>),
+    /// Any function of the progress; it is not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+impl fmt::Debug for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(v) => f.debug_tuple("Constant").field(v).finish(),
+            Self::Linear { start, end } => f
+                .debug_struct("Linear")
+                .field("start", start)
+                .field("end", end)
+                .finish(),
+            Self::Exponential { start, end } => f
+                .debug_struct("Exponential")
+                .field("start", start)
+                .field("end", end)
+                .finish(),
+            Self::Piecewise(knots) => f.debug_tuple("Piecewise").field(knots).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl Schedule {
+    /// [`Schedule::Custom`] from a closure.
+    pub fn custom(f: impl Fn(f64) -> f64 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Value at `progress`, clamped to `[0, 1]`.
+    #[must_use]
+    pub fn at(&self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Constant(v) => *v,
+            Self::Linear { start, end } => (end - start).mul_add(t, *start),
+            Self::Exponential { start, end } => start * (end / start).powf(t),
+            Self::Piecewise(knots) => {
+                let after = knots.partition_point(|&(p, _)| p <= t);
+                match (after.checked_sub(1).map(|k| knots[k]), knots.get(after)) {
+                    (Some((p0, v0)), Some(&(p1, v1))) => {
+                        (v1 - v0).mul_add((t - p0) / (p1 - p0), v0)
+                    }
+                    (Some((_, v)), None) | (None, Some(&(_, v))) => v,
+                    (None, None) => f64::NAN,
+                }
+            }
+            Self::Custom(f) => f(t),
+        }
+    }
+
+    /// Problema nos parâmetros da agenda, se houver
+    fn problem(&self) -> Option<String> {
+        match self {
+            Self::Constant(v) => (!v.is_finite()).then(|| format!("constant {v} is not finite")),
+            Self::Linear { start, end } => (!(start.is_finite() && end.is_finite()))
+                .then(|| format!("linear needs finite start = {start} and end = {end}")),
+            Self::Exponential { start, end } => {
+                let positive = |v: f64| v.is_finite() && v > 0.0;
+                (!(positive(*start) && positive(*end)))
+                    .then(|| format!("exponential needs positive start = {start} and end = {end}"))
+            }
+            Self::Piecewise(knots) => {
+                if knots.is_empty() {
+                    Some("piecewise needs at least one knot".to_owned())
+                } else if knots.iter().any(|(p, v)| !(p.is_finite() && v.is_finite())) {
+                    Some("piecewise knots must be finite".to_owned())
+                } else if knots.windows(2).any(|w| w[1].0 <= w[0].0) {
+                    Some("piecewise knots must have increasing progress".to_owned())
+                } else {
+                    None
+                }
+            }
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+/// Numeric parameter of the HHO optimizer that a [`Schedule`] can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Parameter {
+    /// [`Params::energy_decay`].
+    EnergyDecay,
+    /// [`Params::dive_scale`].
+    DiveScale,
+    /// [`Params::levy_beta`].
+    LevyBeta,
+    /// See [`crate::hho::HhoBuilder::momentum`].
+    Momentum,
+    /// See [`crate::hho::HhoBuilder::update_probability`].
+    UpdateProbability,
+    /// [`crate::hho::Immigrants::fraction`]; immigrants must be enabled.
+    ImmigrantFraction,
+}
+
+impl Parameter {
+    /// Intervalo admissível do parâmetro
+    const fn range(self) -> (f64, f64) {
+        match self {
+            Self::EnergyDecay => Params::ENERGY_DECAY_RANGE,
+            Self::DiveScale => Params::DIVE_SCALE_RANGE,
+            Self::LevyBeta => Params::LEVY_BETA_RANGE,
+            Self::Momentum => (0.0, f64::MAX),
+            Self::UpdateProbability | Self::ImmigrantFraction => (0.0, 1.0),
+        }
+    }
+}
+
+/// Progressos em que a validação confere os valores de cada agenda
+const CHECKPOINTS: usize = 11;
+
+impl Settings {
+    /// Valor agendado de `parameter` em `progress`, levado ao intervalo admissível; `None`
+    /// sem agenda ou se ela der NaN
+    pub(crate) fn scheduled(&self, parameter: Parameter, progress: f64) -> Option<f64> {
+        let (_, schedule) = self.schedules.iter().rfind(|(p, _)| *p == parameter)?;
+        let v = schedule.at(progress);
+        let (lo, hi) = parameter.range();
+        (!v.is_nan()).then(|| v.clamp(lo, hi))
+    }
+
+    /// Coeficientes em `progress`, com as agendas aplicadas
+    pub(crate) fn params_at(&self, progress: f64) -> Params {
+        if self.schedules.is_empty() {
+            return self.params;
+        }
+        let value = |parameter, base| self.scheduled(parameter, progress).unwrap_or(base);
+        Params {
+            energy_decay: value(Parameter::EnergyDecay, self.params.energy_decay),
+            dive_scale: value(Parameter::DiveScale, self.params.dive_scale),
+            levy_beta: value(Parameter::LevyBeta, self.params.levy_beta),
+        }
+    }
+
+    /// Problemas das agendas: parâmetros inválidos, ou valores fora do intervalo do parâmetro
+    /// em algum dos progressos conferidos
+    pub(crate) fn schedule_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (parameter, schedule) in &self.schedules {
+            if let Some(problem) = schedule.problem() {
+                problems.push(format!("{parameter:?} schedule: {problem}"));
+                continue;
+            }
+            if *parameter == Parameter::ImmigrantFraction && self.immigrants.is_none() {
+                problems.push("immigrant fraction is scheduled but immigrants are off".to_owned());
+            }
+            let (lo, hi) = parameter.range();
+            #[allow(clippy::cast_precision_loss)]
+            if let Some((t, v)) = (0..CHECKPOINTS)
+                .map(|k| k as f64 / (CHECKPOINTS - 1) as f64)
+                .map(|t| (t, schedule.at(t)))
+                .find(|(_, v)| !(lo..=hi).contains(v))
+            {
+                problems.push(format!(
+                    "{parameter:?} schedule gives {v} at progress {t}, outside [{lo}, {hi}]"
+                ));
+            }
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_interpolate_over_the_progress() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        assert!(close(Schedule::Constant(0.3).at(0.7), 0.3));
+        let linear = Schedule::Linear {
+            start: 1.0,
+            end: 0.0,
+        };
+        assert!(close(linear.at(0.25), 0.75) && close(linear.at(2.0), 0.0));
+        let exponential = Schedule::Exponential {
+            start: 1.0,
+            end: 0.01,
+        };
+        assert!(close(exponential.at(0.5), 0.1));
+        let piecewise = Schedule::Piecewise(vec![(0.2, 1.0), (0.6, 3.0), (1.0, 2.0)]);
+        assert!(close(piecewise.at(0.0), 1.0));
+        assert!(close(piecewise.at(0.4), 2.0));
+        assert!(close(piecewise.at(0.8), 2.5));
+        assert!(close(Schedule::custom(|t| t * t).at(0.5), 0.25));
+        assert!(
+            Schedule::Piecewise(vec![(0.5, 1.0), (0.5, 2.0)])
+                .problem()
+                .is_some()
+        );
+        assert!(
+            Schedule::Exponential {
+                start: 0.0,
+                end: 1.0
+            }
+            .problem()
+            .is_some()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use rand::Rng;
+
+use crate::core::{Objective, Population, vecops};
+
+/// How the random family member is chosen when a hawk perches (`q >= 0.5` exploration branch).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartnerSelection {
+    /// Any hawk, uniformly at random (original HHO).
+    #[default]
+    Uniform,
+
+    /// Fitness–distance balance: hawks are drawn by roulette with a score that rewards both a
+    /// good fitness and a large distance to the rabbit (Kahraman et al., 2020).
+    FitnessDistanceBalance,
+}
+
+/// Computes the FDB score of every hawk into `out`.
+///
+/// Both fitness and Euclidean distance to `best` are min-max normalized to `[0, 1]` (better
+/// fitness and larger distance map to 1) and summed with equal weights.
+pub fn fdb_scores<O: Objective>(
+    positions: &Population,
+    fitness: &[f64],
+    best: &[f64],
+    out: &mut Vec<f64>,
+) {
+    crate::core::utils::strict_assert_eq!(positions.len(), fitness.len(), "len mismatch");
+    out.clear();
+    if fitness.is_empty() {
+        return;
+    }
+
+    let (mut f_best, mut f_worst) = (fitness[0], fitness[0]);
+    for &f in &fitness[1..] {
+        if O::better(f, f_best) {
+            f_best = f;
+        }
+        if O::better(f_worst, f) {
+            f_worst = f;
+        }
+    }
+
+    out.extend(
+        positions
+            .iter()
+            .map(|x| vecops::squared_distance(x, best).sqrt()),
+    );
+    let d_max = out.iter().copied().fold(0.0, f64::max);
+
+    let f_span = f_best - f_worst;
+    for (s, &f) in out.iter_mut().zip(fitness) {
+        let nf = if f_span == 0.0 || !f_span.is_finite() {
+            1.0
+        } else {
+            (f - f_worst) / f_span
+        };
+        let nd = if d_max > 0.0 { *s / d_max } else { 0.0 };
+        *s = f64::midpoint(nf, nd);
+    }
+}
+
+/// Roulette-wheel selection proportional to non-negative `weights`.
+///
+/// Falls back to a uniform draw when every weight is zero (or not finite).
+pub fn roulette<R: Rng + ?Sized>(weights: &[f64], rng: &mut R) -> usize {
+    let total: f64 = weights.iter().filter(|w| w.is_finite()).sum();
+    if total <= 0.0 || !total.is_finite() {
+        return rng.random_range(0..weights.len());
+    }
+    let mut target = rng.random::<f64>() * total;
+    for (i, &w) in weights.iter().enumerate() {
+        if !w.is_finite() {
+            continue;
+        }
+        if target < w {
+            return i;
+        }
+        target -= w;
+    }
+    weights.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    #[test]
+    fn fdb_prefers_good_and_distant_hawks() {
+        let positions = Population::from_flat(vec![0.0, 1.0, 5.0], 1).unwrap();
+        let fitness = vec![0.0, 10.0, 1.0];
+        let mut scores = Vec::new();
+        fdb_scores::<Minimization>(&positions, &fitness, &[0.0], &mut scores);
+
+        // O terceiro é quase tão bom quanto o melhor e está o mais distante possível
+        assert!(scores[2] > scores[0]);
+        assert!(scores[2] > scores[1]);
+        assert!((scores[0] - 0.5).abs() < 1e-12);
+    }
+}
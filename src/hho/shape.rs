@@ -0,0 +1,164 @@
+use crate::core::{Objective, Population, utils::top_k_indices, vecops};
+
+/// Shape of the Lévy perturbation of the progressive dives.
+///
+/// The original dives perturb every coordinate independently with the same scale, which is
+/// slow on ill-conditioned problems whose valleys are narrow or not aligned with the axes.
+/// The shaped variants estimate the spread of the better half of the hawks once per iteration
+/// and map the Lévy vector through it, normalized to unit mean variance so only the direction
+/// and the relative scale of the steps change, not their overall size; the shaped step keeps
+/// its direction by using one random multiplier instead of one per coordinate. Hawks that
+/// have collapsed to a point fall back to isotropic steps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DiveShape {
+    /// Same scale in every coordinate (original HHO).
+    #[default]
+    Isotropic,
+
+    /// Per-coordinate scale from the variances of the elite, `O(n·d)` per iteration.
+    Diagonal,
+
+    /// Full covariance of the elite through its Cholesky factor, so steps follow rotated
+    /// valleys as in CMA-ES. Costs `O(n·d² + d³)` per iteration.
+    Covariance,
+}
+
+impl DiveShape {
+    /// Writes into `out` the factor that shapes the Lévy vectors, estimated from the better
+    /// half of the hawks: empty for isotropic steps, `d` standard deviations for
+    /// [`DiveShape::Diagonal`] and the row-major lower-triangular `d × d` Cholesky factor for
+    /// [`DiveShape::Covariance`].
+    pub fn factor<O: Objective>(self, positions: &Population, fitness: &[f64], out: &mut Vec<f64>) {
+        out.clear();
+        let d = positions.dim();
+        let elite = top_k_indices::<O>(fitness, positions.len().div_ceil(2));
+        if self == Self::Isotropic || elite.len() < 2 {
+            return;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = elite.len() as f64;
+        let mut mean = vec![0.0; d];
+        for &i in &elite {
+            vecops::axpy(&mut mean, 1.0 / n, &positions[i]);
+        }
+        let variance = |j: usize| {
+            elite
+                .iter()
+                .map(|&i| (positions[i][j] - mean[j]).powi(2))
+                .sum::<f64>()
+                / n
+        };
+        // Dimensões sem variância (fixas nos limites, ou já convergidas) ficam fora da média
+        let varying = (0..d).filter(|&j| variance(j) > 0.0).count();
+        #[allow(clippy::cast_precision_loss)]
+        let level = (0..d).map(variance).sum::<f64>() / varying.max(1) as f64;
+        if !(level.is_finite() && level > 0.0) {
+            return;
+        }
+        let diagonal = |out: &mut Vec<f64>| {
+            out.clear();
+            out.extend((0..d).map(|j| (variance(j) / level).sqrt()));
+        };
+        match self {
+            Self::Isotropic => {}
+            Self::Diagonal => diagonal(out),
+            Self::Covariance => {
+                out.resize(d * d, 0.0);
+                for &e in &elite {
+                    let x = &positions[e];
+                    for i in 0..d {
+                        let a = x[i] - mean[i];
+                        for k in 0..=i {
+                            out[i * d + k] += a * (x[k] - mean[k]);
+                        }
+                    }
+                }
+                for i in 0..d {
+                    for k in 0..=i {
+                        out[i * d + k] /= n * level;
+                    }
+                    // Regularização mínima para elites achatadas
+                    out[i * d + i] += 1e-10;
+                }
+                if !cholesky_in_place(out, d) {
+                    diagonal(out);
+                }
+            }
+        }
+    }
+}
+
+/// Fatora a matriz simétrica (triângulo inferior de `a`, `d × d`) em `L·Lᵀ`, deixando `L` no
+/// lugar; devolve `false` se ela não for definida positiva
+fn cholesky_in_place(a: &mut [f64], d: usize) -> bool {
+    for j in 0..d {
+        let pivot = a[j * d + j] - (0..j).map(|k| a[j * d + k] * a[j * d + k]).sum::<f64>();
+        if !(pivot.is_finite() && pivot > 0.0) {
+            return false;
+        }
+        let l = pivot.sqrt();
+        a[j * d + j] = l;
+        for i in j + 1..d {
+            let dot: f64 = (0..j).map(|k| a[i * d + k] * a[j * d + k]).sum();
+            a[i * d + j] = (a[i * d + j] - dot) / l;
+        }
+    }
+    true
+}
+
+/// Applies the shaping `factor` of [`DiveShape::factor`] to `z` in place.
+pub fn shape_in_place(factor: &[f64], z: &mut [f64]) {
+    let d = z.len();
+    if factor.len() == d {
+        for (z, a) in z.iter_mut().zip(factor) {
+            *z *= a;
+        }
+    } else if factor.len() == d * d {
+        // De baixo para cima, cada linha só usa coordenadas ainda não transformadas
+        for i in (0..d).rev() {
+            z[i] = (0..=i).map(|k| factor[i * d + k] * z[k]).sum();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    #[test]
+    fn covariance_factor_reproduces_the_normalized_covariance() {
+        // Pontos ao longo da diagonal x = y, com um pouco de espessura
+        let rows: Vec<[f64; 2]> = (0..20_u32)
+            .map(|i| {
+                let t = f64::from(i) - 9.5;
+                let e = if i % 2 == 0 { 0.1 } else { -0.1 };
+                [t + e, t - e]
+            })
+            .collect();
+        let positions = Population::from_rows(&rows).unwrap();
+        // A metade melhor é a de cima da reta, que segue alinhada
+        let fitness: Vec<f64> = rows.iter().map(|x| -x[0]).collect();
+
+        let mut l = Vec::new();
+        DiveShape::Covariance.factor::<Minimization>(&positions, &fitness, &mut l);
+        assert_eq!(l.len(), 4);
+        // L·Lᵀ tem variância média 1 e correlação quase perfeita
+        let (c00, c10, c11) = (l[0] * l[0], l[2] * l[0], l[2].mul_add(l[2], l[3] * l[3]));
+        assert!((c00 + c11 - 2.0).abs() < 1e-6);
+        assert!(c10 / (c00 * c11).sqrt() > 0.99);
+        let mut z = [1.0, 0.0];
+        shape_in_place(&l, &mut z);
+        assert!((z[0] - z[1]).abs() < 0.01 * z[0].abs());
+
+        let mut diag = Vec::new();
+        DiveShape::Diagonal.factor::<Minimization>(&positions, &fitness, &mut diag);
+        assert!((diag[0] - 1.0).abs() < 1e-2 && (diag[1] - 1.0).abs() < 1e-2);
+
+        let collapsed = Population::from_rows(&[[1.0, 1.0], [1.0, 1.0]]).unwrap();
+        DiveShape::Covariance.factor::<Minimization>(&collapsed, &[0.0, 0.0], &mut l);
+        assert!(l.is_empty());
+    }
+}
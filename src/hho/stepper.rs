@@ -0,0 +1,834 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use crate::hho::{CheckpointError, checkpoint::Checkpoint};
+use crate::{
+    core::{
+        Budget, Decoder, Degradation, HallOfFame, Objective, Population, Report, RunMetadata,
+        Solution, StreamRng, TerminationReason, clock::Instant,
+    },
+    hho::{
+        HawkMemory, Hho, HhoError, Origin, SuccessHistory, niching,
+        optimizer::{Scratch, State},
+        sweep::DecoderRef,
+    },
+    init::Initializer,
+    observer::{IterationContext, IterationContextMut},
+    termination::CancellationToken,
+};
+
+/// Outcome of one [`Run::poll_run`] slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollStatus {
+    /// The slice ran out with the run still going; poll again to continue.
+    Pending { iterations: usize },
+    /// A stopping rule ended the run; get the report with [`Run::finish`] or
+    /// [`Run::try_complete`].
+    Finished { iterations: usize },
+}
+
+impl PollStatus {
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        matches!(self, Self::Finished { .. })
+    }
+
+    /// Iterations run by the slice.
+    #[must_use]
+    pub const fn iterations(&self) -> usize {
+        match *self {
+            Self::Pending { iterations } | Self::Finished { iterations } => iterations,
+        }
+    }
+}
+
+/// A run driven one iteration at a time, created by [`Hho::start`].
+///
+/// Between two [`Run::step`] calls the caller may inspect the population, inject candidate
+/// solutions and freeze dimensions, which makes human-in-the-loop steering possible. Stopping
+/// rules, observers and checkpoints behave exactly as in [`Hho::run`].
+pub struct Run<'a, O, I> {
+    hho: &'a Hho<O, I>,
+    decoder: DecoderRef<'a>,
+    state: State,
+    history: Option<SuccessHistory>,
+    rng: StreamRng,
+    scratch: Scratch,
+    /// Iteração mais lenta até agora, usada para não estourar um orçamento de tempo
+    slowest: Duration,
+    stop_reason: Option<TerminationReason>,
+    /// Falha ao gravar um checkpoint, que encerra a execução
+    #[cfg(feature = "serde")]
+    checkpoint_error: Option<CheckpointError>,
+    /// Checkpoints gravados por esta execução, com a iteração e o tempo decorrido no último
+    #[cfg(feature = "serde")]
+    saves: usize,
+    #[cfg(feature = "serde")]
+    saved_iter: Option<usize>,
+    #[cfg(feature = "serde")]
+    saved_at: Duration,
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Initializes a run with a `max_iters` budget without iterating it yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HhoError`] if the configuration is invalid (see [`Hho::validate`]) or if
+    /// the initializer rejects the population size. Decoder errors during initialization are
+    /// reported by the first [`Run::step`].
+    pub fn start<'a>(&'a self, decoder: &'a dyn Decoder) -> Result<Run<'a, O, I>, HhoError> {
+        self.start_with_budget(decoder, Budget::Iterations(self.settings.max_iters))
+    }
+
+    /// Initializes a run with the given `budget` without iterating it yet.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::start`].
+    pub fn start_with_budget<'a>(
+        &'a self,
+        decoder: &'a dyn Decoder,
+        budget: Budget,
+    ) -> Result<Run<'a, O, I>, HhoError> {
+        let (state, history, rng) = self.fresh(decoder, budget)?;
+        Ok(Run::new(
+            self,
+            DecoderRef::Local(decoder),
+            state,
+            history,
+            rng,
+        ))
+    }
+
+    /// Like [`Hho::start`], but each iteration's hawks are updated and evaluated in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::start`].
+    #[cfg(feature = "rayon")]
+    pub fn start_parallel<'a>(
+        &'a self,
+        decoder: &'a (dyn Decoder + Sync),
+    ) -> Result<Run<'a, O, I>, HhoError> {
+        self.start_parallel_with_budget(decoder, Budget::Iterations(self.settings.max_iters))
+    }
+
+    /// Like [`Hho::start_with_budget`], but each iteration's hawks are updated and evaluated in
+    /// parallel.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Hho::start`].
+    #[cfg(feature = "rayon")]
+    pub fn start_parallel_with_budget<'a>(
+        &'a self,
+        decoder: &'a (dyn Decoder + Sync),
+        budget: Budget,
+    ) -> Result<Run<'a, O, I>, HhoError> {
+        let (state, history, rng) = self.fresh(decoder, budget)?;
+        Ok(Run::new(
+            self,
+            DecoderRef::Shared(decoder),
+            state,
+            history,
+            rng,
+        ))
+    }
+}
+
+impl<'a, O, I> Run<'a, O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    pub(crate) fn new(
+        hho: &'a Hho<O, I>,
+        decoder: DecoderRef<'a>,
+        state: State,
+        history: Option<SuccessHistory>,
+        rng: StreamRng,
+    ) -> Self {
+        let mut state = state;
+        // A distância não vai para o checkpoint; vem sempre das configurações
+        if let Some(hof) = state.hall_of_fame.take() {
+            state.hall_of_fame = Some(hof.with_distance(hho.settings.distance.clone()));
+        }
+        if let Some(archive) = state.evaluations.take() {
+            state.evaluations = Some(archive.with_distance(hho.settings.distance.clone()));
+        }
+        #[cfg(feature = "serde")]
+        let saved_at = state.elapsed();
+        let run = Self {
+            hho,
+            decoder,
+            scratch: Scratch::new(hho.bounds.dim(), hho.settings.pop_size),
+            state,
+            history,
+            rng,
+            slowest: Duration::ZERO,
+            stop_reason: None,
+            #[cfg(feature = "serde")]
+            checkpoint_error: None,
+            #[cfg(feature = "serde")]
+            saves: 0,
+            #[cfg(feature = "serde")]
+            saved_iter: None,
+            #[cfg(feature = "serde")]
+            saved_at,
+        };
+        let observers = &hho.settings.observers;
+        if !observers.is_empty() {
+            let ctx = run.state.context();
+            observers.each(|o| o.on_start(&ctx));
+        }
+        run
+    }
+
+    /// Grava o estado conforme a política de checkpoint, se houver; uma falha encerra a
+    /// execução com erro
+    #[cfg(feature = "serde")]
+    fn save_checkpoint(&mut self) -> bool {
+        let Some(policy) = &self.hho.settings.checkpoint else {
+            return true;
+        };
+        let state = &mut self.state;
+        state.rebase_clock();
+        let path = policy.target(self.saves);
+        if let Err(e) = Checkpoint::save(&path, state, self.history.as_ref(), &self.rng) {
+            #[cfg(feature = "log")]
+            log::warn!("checkpoint write to {} failed: {e}", path.display());
+            self.checkpoint_error = Some(e);
+            self.stop_reason = Some(TerminationReason::Error);
+            return false;
+        }
+        self.saves += 1;
+        self.saved_iter = Some(state.iter);
+        self.saved_at = state.spent;
+        #[cfg(feature = "log")]
+        log::debug!("checkpoint written to {}", path.display());
+        true
+    }
+
+    /// Performs one iteration. Returns `false`, without iterating, once a stopping rule (budget,
+    /// criterion, early stop or cancellation) has ended the run, and also when the decoder
+    /// fails or a checkpoint cannot be written; the stop reason is then
+    /// [`TerminationReason::Error`] and [`Run::try_complete`] returns the error.
+    ///
+    /// With a checkpoint policy, a cancelled run also saves the last completed iteration, so
+    /// it can be resumed from where it was stopped.
+    pub fn step(&mut self) -> bool {
+        if self.stop_reason.is_some() {
+            return false;
+        }
+        let hho = self.hho;
+        let settings = &hho.settings;
+        let state = &mut self.state;
+        if state.failed() {
+            self.stop_reason = Some(TerminationReason::Error);
+            return false;
+        }
+        if settings
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            self.stop_reason = Some(TerminationReason::Cancelled);
+            #[cfg(feature = "serde")]
+            if self.saved_iter != Some(state.iter) {
+                self.save_checkpoint();
+            }
+            return false;
+        }
+        if state
+            .budget
+            .exhausted(state.iter, state.evals, state.elapsed() + self.slowest)
+        {
+            self.stop_reason = Some(state.budget.reason());
+            return false;
+        }
+
+        self.hho.iterate(
+            state,
+            self.history.as_mut(),
+            &mut self.scratch,
+            self.decoder,
+            &mut self.rng,
+        );
+        // O mesmo tempo que vai para `iteration_times`, para o relatório explicar a parada
+        self.slowest = self.slowest.max(state.iter_time);
+        if state.failed() {
+            // Os observadores não são notificados da iteração interrompida
+            #[cfg(feature = "log")]
+            log::warn!("decoder failed during iteration {}", state.iter + 1);
+            self.stop_reason = Some(TerminationReason::Error);
+            return false;
+        }
+        #[cfg(feature = "log")]
+        log::trace!(
+            "iteration {} done in {:?}: evals = {}, best = {}, |E| = {:.3}",
+            state.iter,
+            state.iter_time,
+            state.evals,
+            state.rabbit_fit,
+            state.energy
+        );
+
+        let observers = &settings.observers;
+        if !observers.is_empty() {
+            let ctx = state.context();
+            observers.each(|o| {
+                if state.stall == 0 {
+                    o.on_improvement(&ctx);
+                }
+                o.on_iteration(&ctx);
+            });
+        }
+        if !settings.mut_observers.is_empty() {
+            settings.mut_observers.each(&mut IterationContextMut {
+                state,
+                bounds: &self.hho.bounds,
+                decoder: self.decoder.get(),
+                evaluate: State::evaluate::<O>,
+            });
+            // O usuário pode ter encontrado (ou editado) um falcão melhor que o coelho
+            state.refresh_rabbit::<O>();
+        }
+        #[cfg(feature = "serde")]
+        if settings
+            .checkpoint
+            .as_ref()
+            .is_some_and(|p| p.due(state.iter, state.elapsed().saturating_sub(self.saved_at)))
+            && !self.save_checkpoint()
+        {
+            return false;
+        }
+        let state = &mut self.state;
+        if let Some(stop) = &settings.early_stop
+            && stop.should_stop(&state.context())
+        {
+            self.stop_reason = Some(TerminationReason::EarlyStop);
+        } else if let Some(criterion) = &settings.termination
+            && let Some(reason) = criterion.check(&state.status::<O>())
+        {
+            self.stop_reason = Some(reason);
+        }
+        self.stop_reason.is_none()
+    }
+
+    /// Steps for at most `max_iters` iterations or about `max_time`, whichever comes first,
+    /// then yields, so a run can live inside a GUI or game-engine main loop without a thread.
+    ///
+    /// The time limit is soft: it is checked between iterations, so a call lasts up to one
+    /// iteration longer than `max_time`, and always runs at least one iteration while the run
+    /// goes on. All state stays in the [`Run`]; the next call continues where this one
+    /// stopped, and the result is the same as stepping without slices.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use kambo_hho::{benchmarks::Function, core::Bounds, hho::HhoBuilder};
+    ///
+    /// let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 4).unwrap())
+    ///     .max_iters(200)
+    ///     .build();
+    /// let mut run = hho.start(&Function::Sphere).unwrap();
+    /// // Once per frame
+    /// while !run.poll_run(16, Duration::from_millis(4)).is_finished() {}
+    /// let report = run.finish();
+    /// assert_eq!(report.iters, 200);
+    /// ```
+    pub fn poll_run(&mut self, max_iters: usize, max_time: Duration) -> PollStatus {
+        let started = Instant::now();
+        let mut iterations = 0;
+        while iterations < max_iters && (iterations == 0 || started.elapsed() < max_time) {
+            if !self.step() {
+                return PollStatus::Finished { iterations };
+            }
+            iterations += 1;
+        }
+        if self.stop_reason.is_some() {
+            PollStatus::Finished { iterations }
+        } else {
+            PollStatus::Pending { iterations }
+        }
+    }
+
+    /// Steps until a stopping rule fires and returns the final [`Report`]. A decoder error or
+    /// a failed checkpoint write ends the run with [`TerminationReason::Error`]; use
+    /// [`Run::try_complete`] to get the error.
+    #[must_use]
+    pub fn complete(self) -> Report {
+        self.try_complete().unwrap_or_else(HhoError::into_report)
+    }
+
+    /// Steps until a stopping rule fires and returns the final [`Report`], or the error that
+    /// ended the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Decoder`] if the decoder failed and [`HhoError::CheckpointWrite`]
+    /// if a checkpoint could not be written, both with the report up to the failure.
+    pub fn try_complete(mut self) -> Result<Report, HhoError> {
+        while self.step() {}
+        #[cfg(feature = "serde")]
+        if let Some(source) = self.checkpoint_error.take() {
+            return Err(HhoError::CheckpointWrite {
+                source,
+                report: Box::new(self.finish()),
+            });
+        }
+        match self.state.error.take() {
+            Some(source) => Err(HhoError::Decoder {
+                source,
+                report: Box::new(self.finish()),
+            }),
+            None => Ok(self.finish()),
+        }
+    }
+
+    /// Ends the run now and returns its [`Report`]. If no stopping rule fired yet, the stop
+    /// reason is [`TerminationReason::Manual`].
+    #[must_use]
+    pub fn finish(self) -> Report {
+        let settings = &self.hho.settings;
+        let mut state = self.state;
+        let niches = niching::summarize::<O>(
+            settings.niching,
+            &state.positions,
+            &state.fitness,
+            &*settings.distance,
+        );
+
+        // A última iteração sempre entra na curva
+        if state.iter > 0 && state.curve_iters.last() != Some(&state.iter) {
+            state.sample(state.iter, settings.curve_sampling, true);
+        }
+        let elapsed = state.elapsed();
+        // A população só é copiada se couber no orçamento de memória
+        let fits = settings.memory_budget.is_none_or(|budget| {
+            let dim = state.rabbit.len();
+            state.recorded_bytes() + state.positions.len() * (dim + 1) * size_of::<f64>() <= budget
+        });
+        if settings.keep_population && !fits {
+            state.degradations.push(Degradation::PopulationDropped);
+        }
+        let population = if settings.keep_population && fits {
+            state
+                .positions
+                .iter()
+                .zip(state.fitness)
+                .map(|(position, fitness)| Solution {
+                    position: position.to_vec(),
+                    fitness,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let report = Report {
+            direction: O::direction(),
+            best_fitness: state.rabbit_fit,
+            best_position: state.rabbit,
+            convergence_curve: state.curve,
+            curve_iters: state.curve_iters,
+            curve_evals: state.curve_evals,
+            trajectory: state.trajectory,
+            diversity: state.diversity,
+            energy: state.energy_history,
+            phases: state.phase_history,
+            operators: state.operators,
+            iteration_times: state.iteration_times,
+            eval_time: state.eval_time,
+            iters: state.iter,
+            evals: state.evals,
+            duration: None,
+            niches,
+            landscape_changes: state.changes,
+            duplicates_purged: state.purged,
+            penalty_coefficients: state.penalty_history,
+            clusters: state.clusters,
+            occupancy_entropy: state.occupancy_entropy,
+            non_finite: state.non_finite,
+            screened: state.screened,
+            refiner_evals: state.refined,
+            hall_of_fame: state
+                .hall_of_fame
+                .map(HallOfFame::into_entries)
+                .unwrap_or_default(),
+            evaluations: state.evaluations,
+            population,
+            improvements: state.improvements,
+            degradations: state.degradations,
+            stop_reason: Some(self.stop_reason.unwrap_or(TerminationReason::Manual)),
+            seed: Some(state.seed),
+            bounds: self
+                .hho
+                .bounds
+                .lower()
+                .iter()
+                .copied()
+                .zip(self.hho.bounds.upper().iter().copied())
+                .collect(),
+            config_hash: Some(self.hho.config_hash()),
+            metadata: RunMetadata {
+                problem: settings.problem_name.clone(),
+                tags: settings.tags.clone(),
+                ..RunMetadata::current(elapsed)
+            },
+        }
+        .with_duration(elapsed);
+        #[cfg(feature = "log")]
+        log::info!(
+            "run finished ({}) after {} iterations and {} evaluations in {:?}, best = {}",
+            report.stop_reason.unwrap_or(TerminationReason::Manual),
+            report.iters,
+            report.evals,
+            elapsed,
+            report.best_fitness
+        );
+        settings.observers.each(|o| o.on_end(&report));
+        report
+    }
+
+    /// Read-only view of the current population.
+    #[must_use]
+    pub fn context(&self) -> IterationContext<'_> {
+        self.state.context()
+    }
+
+    /// Avaliações que restam num orçamento de avaliações (0 nos outros)
+    pub(crate) const fn evals_left(&self) -> usize {
+        match self.state.budget {
+            Budget::Evaluations(n) => n.saturating_sub(self.state.evals),
+            _ => 0,
+        }
+    }
+
+    /// Acrescenta `evals` a um orçamento de avaliações
+    pub(crate) const fn grant(&mut self, evals: usize) {
+        if let Budget::Evaluations(n) = &mut self.state.budget {
+            *n += evals;
+        }
+    }
+
+    /// Encerra a execução por `reason`, se ela ainda não tiver parado
+    pub(crate) const fn retire(&mut self, reason: TerminationReason) {
+        if self.stop_reason.is_none() {
+            self.stop_reason = Some(reason);
+        }
+    }
+
+    /// Se uma regra de parada já encerrou a execução
+    pub(crate) const fn stopped(&self) -> bool {
+        self.stop_reason.is_some()
+    }
+
+    /// Position and fitness of the rabbit (the best solution found so far).
+    #[must_use]
+    pub fn current_best(&self) -> (&[f64], f64) {
+        (&self.state.rabbit, self.state.rabbit_fit)
+    }
+
+    /// Current positions of the hawks and their fitness, in population order.
+    #[must_use]
+    pub fn population_view(&self) -> (&Population, &[f64]) {
+        (&self.state.positions, &self.state.fitness)
+    }
+
+    /// Iterations completed so far.
+    #[inline]
+    #[must_use]
+    pub const fn iteration(&self) -> usize {
+        self.state.iter
+    }
+
+    /// Evaluations spent so far, the initial population included.
+    #[inline]
+    #[must_use]
+    pub const fn evals(&self) -> usize {
+        self.state.evals
+    }
+
+    /// Mean escape energy `|E|` of the hawks in the last iteration.
+    #[inline]
+    #[must_use]
+    pub const fn energy(&self) -> f64 {
+        self.state.energy
+    }
+
+    /// Per-hawk metadata, when enabled with [`crate::hho::HhoBuilder::track_hawks`].
+    #[must_use]
+    pub const fn hawks(&self) -> Option<&HawkMemory> {
+        self.state.memory.as_ref()
+    }
+
+    /// Why the run ended, if it did.
+    #[inline]
+    #[must_use]
+    pub const fn stop_reason(&self) -> Option<TerminationReason> {
+        self.stop_reason
+    }
+
+    /// Evaluates user-provided `candidates` and puts them in place of the worst hawks (each
+    /// candidate replaces the current worst one). Candidates are projected into the bounds
+    /// first. Returns how many were injected, which is fewer than `candidates.len()` only when
+    /// an evaluation budget runs out.
+    ///
+    /// Candidates built with [`crate::core::Bounds::position`] have had their dimension
+    /// checked already.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a candidate's dimension differs from the bounds'.
+    pub fn inject<C: AsRef<[f64]>>(&mut self, candidates: &[C]) -> usize {
+        let bounds = &self.hho.bounds;
+        let state = &mut self.state;
+        let mut injected = 0;
+        for candidate in candidates {
+            let mut x = candidate.as_ref().to_vec();
+            bounds.project_slice(&mut x);
+            let Some(worst) = worst_index::<O>(&state.fitness) else {
+                break;
+            };
+            let Some(f) = state.evaluate::<O>(self.decoder.get(), &x) else {
+                break;
+            };
+            state.positions[worst].copy_from_slice(&x);
+            state.fitness[worst] = f;
+            if let Some(v) = state.velocity.get_mut(worst) {
+                v.fill(0.0);
+            }
+            if let Some(memory) = &mut state.memory {
+                memory.reset(worst, &x, f, Origin::Injected);
+            }
+            injected += 1;
+        }
+        state.refresh_rabbit::<O>();
+        injected
+    }
+
+    /// Freezes the given dimensions: until unfrozen, hawks keep their current coordinate on
+    /// them. Injected candidates are not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is out of range.
+    pub fn freeze(&mut self, dims: &[usize]) {
+        let dim = self.hho.bounds.dim();
+        let frozen = &mut self.state.frozen;
+        if frozen.is_empty() {
+            frozen.resize(dim, false);
+        }
+        for &j in dims {
+            assert!(j < dim, "dimension {j} out of range");
+            frozen[j] = true;
+        }
+    }
+
+    /// Unfreezes every dimension.
+    pub fn unfreeze(&mut self) {
+        self.state.frozen.clear();
+    }
+}
+
+fn worst_index<O: Objective>(fitness: &[f64]) -> Option<usize> {
+    let mut worst = 0;
+    for (i, &f) in fitness.iter().enumerate().skip(1) {
+        if O::better(fitness[worst], f) {
+            worst = i;
+        }
+    }
+    (!fitness.is_empty()).then_some(worst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        benchmarks::Function::Sphere,
+        core::{Bounds, Improvement, TerminationReason},
+        hho::{
+            HhoBuilder, Origin,
+            dynamic::{ChangeDetection, ChangeSignal, Dynamic},
+        },
+    };
+
+    #[test]
+    fn polled_slices_continue_the_same_run() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(25)
+            .seed(4)
+            .build();
+        let whole = hho.run(&Sphere).unwrap();
+
+        let mut run = hho.start(&Sphere).unwrap();
+        let mut slices = Vec::new();
+        loop {
+            let status = run.poll_run(10, Duration::from_mins(1));
+            slices.push(status.iterations());
+            if status.is_finished() {
+                break;
+            }
+        }
+        assert_eq!(slices, [10, 10, 5]);
+        // Uma fatia de tempo zero ainda avança uma iteração
+        let mut short = hho.start(&Sphere).unwrap();
+        assert_eq!(
+            short.poll_run(10, Duration::ZERO),
+            PollStatus::Pending { iterations: 1 }
+        );
+        assert!(run.poll_run(10, Duration::ZERO).is_finished());
+
+        let report = run.finish();
+        assert_eq!(report.iters, whole.iters);
+        assert_eq!(report.best_fitness.to_bits(), whole.best_fitness.to_bits());
+    }
+
+    #[test]
+    fn injected_candidate_becomes_the_rabbit() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(20)
+            .seed(2)
+            .build();
+        let mut run = hho.start(&Sphere).unwrap();
+        assert!(run.step());
+        assert_eq!(run.inject(&[vec![0.0; 4]]), 1);
+        assert!(run.context().best_fitness.abs() < f64::EPSILON);
+        assert_eq!(run.current_best().0, [0.0; 4]);
+        assert_eq!(run.iteration(), 1);
+        let (positions, fitness) = run.population_view();
+        assert_eq!((positions.len(), fitness.len()), (10, 10));
+        assert_eq!(run.evals(), run.context().evals);
+
+        let report = run.complete();
+        assert_eq!(report.iters, 20);
+        assert_eq!(report.stop_reason, Some(TerminationReason::MaxIters));
+    }
+
+    #[test]
+    fn frozen_dimensions_do_not_move() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(2)
+            .build();
+        let mut run = hho.start(&Sphere).unwrap();
+        run.freeze(&[1, 3]);
+        let before: Vec<(f64, f64)> = run
+            .context()
+            .positions
+            .iter()
+            .map(|x| (x[1], x[3]))
+            .collect();
+        for _ in 0..30 {
+            run.step();
+        }
+        let after: Vec<(f64, f64)> = run
+            .context()
+            .positions
+            .iter()
+            .map(|x| (x[1], x[3]))
+            .collect();
+        assert_eq!(before, after);
+        // A resposta a uma mudança re-sorteia os falcões sem mexer nas dimensões congeladas
+        let signal = ChangeSignal::new();
+        let dynamic = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(2)
+            .dynamic(Dynamic {
+                detection: ChangeDetection::Manual(signal.clone()),
+                rerandomize: 1.0,
+                ..Dynamic::default()
+            })
+            .build();
+        let mut moving = dynamic.start(&Sphere).unwrap();
+        moving.freeze(&[2]);
+        let frozen: Vec<f64> = moving.context().positions.iter().map(|x| x[2]).collect();
+        signal.notify();
+        assert!(moving.step());
+        assert_eq!(
+            moving
+                .context()
+                .positions
+                .iter()
+                .map(|x| x[2])
+                .collect::<Vec<_>>(),
+            frozen
+        );
+        assert_eq!(moving.finish().landscape_changes, 1);
+
+        run.unfreeze();
+        let report = run.finish();
+        assert_eq!(report.stop_reason, Some(TerminationReason::Manual));
+    }
+
+    #[test]
+    fn hawk_memory_follows_the_run() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .pop_size(10)
+            .max_iters(50)
+            .seed(7)
+            .track_hawks()
+            .build();
+        let mut run = hho.start(&Sphere).unwrap();
+        for _ in 0..5 {
+            run.step();
+        }
+        run.inject(&[vec![0.0; 4]]);
+        let ctx = run.context();
+        let hawks = ctx.hawks.unwrap();
+        assert_eq!(hawks.len(), 10);
+        for (i, &f) in ctx.fitness.iter().enumerate() {
+            // O melhor pessoal nunca é pior que a posição atual
+            assert!(hawks.personal_best(i).1 <= f);
+            assert!(hawks.stagnation(i) <= hawks.age(i));
+            if hawks.origin(i) == Origin::Injected {
+                assert_eq!((hawks.age(i), hawks.personal_best(i).0), (0, &[0.0; 4][..]));
+            } else {
+                assert_eq!((hawks.age(i), hawks.origin(i)), (5, Origin::Init));
+            }
+        }
+
+        let untracked = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 4).unwrap())
+            .seed(7)
+            .build();
+        assert!(untracked.start(&Sphere).unwrap().hawks().is_none());
+    }
+
+    #[test]
+    fn improvement_log_stays_within_the_memory_budget() {
+        let builder = || {
+            HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 2).unwrap())
+                .pop_size(10)
+                .max_iters(20_000)
+                .seed(5)
+        };
+        let full = builder().build().run(&Sphere).unwrap();
+        let hho = builder().memory_budget(4096).build();
+        let mut run = hho.start(&Sphere).unwrap();
+        while run.step() {
+            assert!(run.state.recorded_bytes() <= 4096);
+        }
+        let improvements = run.state.improvements.len();
+        let lean = run.finish();
+        // O registro de melhoras encolhe com as curvas e guarda a última
+        assert!(improvements * size_of::<Improvement>() < 4096);
+        assert!(lean.improvements.len() < full.improvements.len());
+        assert_eq!(
+            lean.improvements.last().map(|e| e.fitness.to_bits()),
+            full.improvements.last().map(|e| e.fitness.to_bits())
+        );
+        assert!(
+            lean.degradations
+                .iter()
+                .any(|d| matches!(d, Degradation::CurvesThinned { .. }))
+        );
+    }
+}
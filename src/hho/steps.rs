@@ -0,0 +1,70 @@
+use crate::core::{Objective, utils::argsort_by_fitness};
+
+/// Step sizes scaled by the fitness rank of each hawk.
+///
+/// Every candidate a hawk builds is pulled towards or pushed away from its current position:
+/// the displacement `y - x` is multiplied by a factor going linearly from `best` for the best
+/// hawk of the population to `worst` for the worst one, ranks taken at the start of the
+/// iteration. With `best < 1 < worst` good hawks refine where they are while bad ones explore
+/// further; `1` for both is classic HHO.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RankSteps {
+    /// Factor of the best hawk, finite and positive.
+    pub best: f64,
+    /// Factor of the worst hawk, finite and positive.
+    pub worst: f64,
+}
+
+impl Default for RankSteps {
+    fn default() -> Self {
+        Self {
+            best: 0.5,
+            worst: 1.5,
+        }
+    }
+}
+
+impl RankSteps {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self { best, worst } = *self;
+        (!(best.is_finite() && best > 0.0 && worst.is_finite() && worst > 0.0))
+            .then(|| format!("rank steps need finite positive best = {best} and worst = {worst}"))
+    }
+
+    /// Escreve em `out` o fator de cada falcão, pela posição de `fitness` no ranking
+    pub(crate) fn factors<O: Objective>(&self, fitness: &[f64], out: &mut Vec<f64>) {
+        out.clear();
+        out.resize(fitness.len(), self.best);
+        let last = fitness.len().saturating_sub(1).max(1);
+        for (rank, i) in argsort_by_fitness::<O>(fitness).into_iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let t = rank as f64 / last as f64;
+            out[i] = (self.worst - self.best).mul_add(t, self.best);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    #[test]
+    fn factors_follow_the_rank() {
+        let mut out = Vec::new();
+        RankSteps::default().factors::<Minimization>(&[3.0, 1.0, 7.0], &mut out);
+        assert_eq!(out, vec![1.0, 0.5, 1.5]);
+        RankSteps::default().factors::<Minimization>(&[2.0], &mut out);
+        assert_eq!(out, vec![0.5]);
+        assert!(
+            RankSteps {
+                best: 0.0,
+                worst: 1.0
+            }
+            .problem()
+            .is_some()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! One configuration over a batch of problems, e.g. a benchmark campaign.
+
+use std::{collections::BTreeMap, fmt, marker::PhantomData};
+
+use crate::{
+    core::{Bounds, Decoder, Objective, Report, SeedStreams},
+    experiments::Problem,
+    hho::{Hho, HhoError},
+    init::Initializer,
+};
+
+/// A problem of [`Hho::run_suite`], borrowed: its name, bounds and decoder.
+#[derive(Clone, Copy)]
+pub struct ProblemRef<'a> {
+    pub name: &'a str,
+    pub bounds: &'a Bounds,
+    pub decoder: &'a (dyn Decoder + Sync),
+}
+
+impl<'a> ProblemRef<'a> {
+    #[must_use]
+    pub const fn new(name: &'a str, bounds: &'a Bounds, decoder: &'a (dyn Decoder + Sync)) -> Self {
+        Self {
+            name,
+            bounds,
+            decoder,
+        }
+    }
+}
+
+impl<'a> From<&'a Problem> for ProblemRef<'a> {
+    fn from(problem: &'a Problem) -> Self {
+        Self::new(&problem.name, &problem.bounds, problem.decoder.as_ref())
+    }
+}
+
+impl fmt::Debug for ProblemRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProblemRef")
+            .field("name", &self.name)
+            .field("bounds", self.bounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective + Sync,
+    I: Initializer + Clone,
+{
+    /// Runs this configuration once on every problem, with the problem's bounds in place of
+    /// the configured ones, and returns the reports keyed by problem name.
+    ///
+    /// Every problem is run with the same seed (the configured one, or a fresh one shared by
+    /// the whole suite), so differences between problems are not differences of luck. With
+    /// the `rayon` feature the problems are spread over a single pool of `parallelism`
+    /// threads (`0` uses the global pool) built once for the whole suite, each run staying on
+    /// one worker as in [`Hho::run_many`]; without it they run in order and `parallelism` is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if two problems share a name, if a checkpoint is
+    /// configured or if the thread pool cannot be created. Otherwise returns the error of the
+    /// first failing problem, in problem order.
+    pub fn run_suite(
+        &self,
+        problems: &[ProblemRef<'_>],
+        parallelism: usize,
+    ) -> Result<BTreeMap<String, Report>, HhoError> {
+        #[cfg(feature = "serde")]
+        if self.settings.checkpoint.is_some() {
+            return Err(HhoError::Config(
+                "checkpoints are not supported with problem suites".to_owned(),
+            ));
+        }
+        let mut names: Vec<&str> = problems.iter().map(|p| p.name).collect();
+        names.sort_unstable();
+        if let Some([name, _]) = names.windows(2).find(|w| w[0] == w[1]) {
+            return Err(HhoError::Config(format!(
+                "problem name {name:?} is used twice"
+            )));
+        }
+        let seed = self
+            .settings
+            .seed
+            .unwrap_or_else(|| SeedStreams::from_entropy().seed());
+        let optimizers: Vec<Self> = problems
+            .iter()
+            .map(|p| Self {
+                bounds: p.bounds.clone(),
+                initializer: self.initializer.clone(),
+                settings: self.settings.clone(),
+                objective: PhantomData,
+            })
+            .collect();
+        let tasks: Vec<_> = optimizers.iter().zip(problems).collect();
+        let solve =
+            |&(hho, problem): &(&Self, &ProblemRef<'_>)| hho.run_seeded(problem.decoder, seed);
+
+        #[cfg(feature = "rayon")]
+        let results = {
+            use rayon::prelude::*;
+
+            let run = || -> Vec<_> { tasks.par_iter().map(solve).collect() };
+            if parallelism == 0 {
+                run()
+            } else {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(parallelism)
+                    .build()
+                    .map_err(|e| HhoError::Config(format!("cannot build thread pool: {e}")))?
+                    .install(run)
+            }
+        };
+        #[cfg(not(feature = "rayon"))]
+        let results: Vec<_> = {
+            let _ = parallelism;
+            tasks.iter().map(solve).collect()
+        };
+
+        problems
+            .iter()
+            .zip(results)
+            .map(|(p, r)| r.map(|report| (p.name.to_owned(), report)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, hho::HhoBuilder};
+
+    #[test]
+    fn runs_each_problem_in_its_own_bounds() {
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(5)
+            .build();
+        let (small, large) = (
+            Bounds::uniform(-1.0, 1.0, 2).unwrap(),
+            Bounds::uniform(-8.0, 8.0, 6).unwrap(),
+        );
+        let problems = [
+            ProblemRef::new("large", &large, &Sphere),
+            ProblemRef::new("small", &small, &Sphere),
+        ];
+        let reports = hho.run_suite(&problems, 2).unwrap();
+
+        assert_eq!(reports.keys().collect::<Vec<_>>(), ["large", "small"]);
+        assert_eq!(reports["small"].best_position.len(), 2);
+        let alone = HhoBuilder::new(large.clone())
+            .pop_size(10)
+            .max_iters(30)
+            .seed(5)
+            .build()
+            .run(&Sphere)
+            .unwrap();
+        assert_eq!(reports["large"].best_position, alone.best_position);
+
+        let twice = [problems[0], problems[0]];
+        assert!(matches!(hho.run_suite(&twice, 0), Err(HhoError::Config(_))));
+    }
+}
@@ -0,0 +1,1032 @@
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use rand::Rng;
+
+use crate::{
+    core::{
+        Bounds, Decoder, DecoderError, EvalContext, Objective, Population, SeedStreams, Solution,
+        clock::Instant, vecops,
+    },
+    hho::{
+        Attractor, Credit, Displacements, Exploitation, Fidelity, Hho, Niching, NonFinite,
+        Parameter, Params, PartnerSelection, Settings, SuccessHistory, UpdateMode,
+        blockwise::blockwise_merge_in,
+        exploitation::{differential_step, quantum_step, sine_cosine_step},
+        levy::levy_flight,
+        optimizer::{Scratch, State},
+        phase::{Phase, PhaseCounts},
+        selection::roulette,
+        shape::shape_in_place,
+    },
+    init::Initializer,
+};
+
+/// Decoder of a run: any decoder for sequential sweeps, or a `Sync` one for parallel sweeps.
+#[derive(Clone, Copy)]
+pub(crate) enum DecoderRef<'a> {
+    Local(&'a dyn Decoder),
+    #[cfg(feature = "rayon")]
+    Shared(&'a (dyn Decoder + Sync)),
+}
+
+impl<'a> DecoderRef<'a> {
+    /// Número de falcões que cabem num lote inicial: um por thread em paralelo
+    #[cfg_attr(not(feature = "rayon"), allow(clippy::missing_const_for_fn))]
+    fn parallelism(self) -> usize {
+        match self {
+            Self::Local(_) => 1,
+            #[cfg(feature = "rayon")]
+            Self::Shared(_) => rayon::current_num_threads(),
+        }
+    }
+
+    #[inline]
+    pub fn get(self) -> &'a dyn Decoder {
+        match self {
+            Self::Local(d) => d,
+            #[cfg(feature = "rayon")]
+            Self::Shared(d) => d,
+        }
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Updates every hawk once. With a batch latency the population is processed in batches
+    /// sized from the measured cost per hawk, and the observers hear about each batch.
+    pub(crate) fn sweep<R: Rng>(
+        &self,
+        state: &mut State,
+        mut history: Option<&mut SuccessHistory>,
+        scratch: &mut Scratch,
+        decoder: DecoderRef<'_>,
+        rng: &mut R,
+    ) {
+        let n = state.positions.len();
+        let left = AtomicUsize::new(state.remaining());
+        state.phases = PhaseCounts::default();
+        let mut energy = 0.0;
+        #[cfg(feature = "log")]
+        let (evals, t0) = (state.evals, Instant::now());
+        let latency = self.settings.batch_latency;
+        let mut start = 0;
+        while start < n && !state.failed() && left.load(Ordering::Acquire) > 0 {
+            let end = latency.map_or(n, |target| {
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    clippy::cast_precision_loss
+                )]
+                let size = scratch.per_hawk.map_or_else(
+                    || decoder.parallelism(),
+                    |c| (target.as_secs_f64() / c.as_secs_f64().max(1e-9)) as usize,
+                );
+                (start + size.max(1)).min(n)
+            });
+            let t0 = Instant::now();
+            energy += match decoder {
+                DecoderRef::Local(d) => self.sweep_sequential(
+                    state,
+                    history.as_deref_mut(),
+                    scratch,
+                    d,
+                    start..end,
+                    &left,
+                    rng,
+                ),
+                #[cfg(feature = "rayon")]
+                DecoderRef::Shared(d) => self.sweep_parallel(
+                    state,
+                    history.as_deref_mut(),
+                    scratch,
+                    d,
+                    start..end,
+                    &left,
+                ),
+            };
+            scratch.per_hawk = Some(t0.elapsed() / u32::try_from(end - start).unwrap_or(u32::MAX));
+
+            let observers = &self.settings.observers;
+            if end < n && latency.is_some() && !observers.is_empty() {
+                let ctx = state.context();
+                observers.each(|o| o.on_batch(&ctx));
+            }
+            start = end;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let updated = state.phases.total().max(1) as f64;
+        state.energy = energy / updated;
+        #[cfg(feature = "log")]
+        log::trace!(
+            "iteration {}: {} hawks updated with {} evaluations in {:?}",
+            state.iter + 1,
+            state.phases.total(),
+            state.evals - evals,
+            t0.elapsed()
+        );
+    }
+
+    /// Atualiza os falcões de `range` em ordem, cada um vendo as posições já atualizadas.
+    /// Devolve a soma de |E| dos falcões atualizados.
+    #[allow(clippy::too_many_arguments)]
+    fn sweep_sequential<R: Rng>(
+        &self,
+        state: &mut State,
+        mut history: Option<&mut SuccessHistory>,
+        scratch: &mut Scratch,
+        decoder: &dyn Decoder,
+        range: Range<usize>,
+        left: &AtomicUsize,
+        rng: &mut R,
+    ) -> f64 {
+        let Scratch {
+            x,
+            mean,
+            buffers,
+            partner_weights,
+            niche_seeds,
+            species,
+            elite,
+            shape,
+            step_factors,
+            window,
+            ..
+        } = scratch;
+        let bounds = window.as_ref().unwrap_or(&self.bounds);
+        let archive = state.hall_of_fame.is_some() || state.evaluations.is_some();
+        let mut total = 0.0;
+        for i in range {
+            if state.failed() || left.load(Ordering::Acquire) == 0 {
+                break;
+            }
+            let progress = state.progress();
+            let params = history
+                .as_deref()
+                .map_or_else(|| self.settings.params_at(progress), |h| h.sample(rng));
+            let energy = Self::energy(progress, params, rng);
+            let old = state.fitness[i];
+
+            // O falcão é atualizado numa cópia; ela mesma cobre o caso parceiro == i
+            x.copy_from_slice(&state.positions[i]);
+            let site = EvalSite {
+                streams: SeedStreams::new(state.seed),
+                iteration: state.iter,
+                hawk: i,
+                evals: state.evals,
+                progress,
+                common: state.common,
+                non_finite: self.settings.non_finite,
+                worst: O::worst(),
+            };
+            let mut tally = Tally::new(decoder, site, left, archive, self.settings.fidelity);
+            let sweep = Sweep {
+                settings: &self.settings,
+                bounds,
+                positions: &state.positions,
+                rabbit: &state.rabbit,
+                mean,
+                partner_weights,
+                niche_seeds,
+                species,
+                elite,
+                frozen: &state.frozen,
+                credit: state.credit.as_ref(),
+                displacements: state.displacements.as_ref(),
+                shape,
+                step_factors,
+                progress,
+            };
+            let mut hawk = Hawk {
+                index: i,
+                x,
+                fitness: &mut state.fitness[i],
+                velocity: state.velocity.get_mut(i),
+            };
+            let phase =
+                sweep.update_hawk::<O, _, _>(&mut hawk, energy, params, buffers, &mut tally, rng);
+            if let Some(memory) = &mut state.displacements
+                && phase.is_dive()
+                && O::better(state.fitness[i], old)
+            {
+                memory.record(&state.positions[i], x);
+            }
+            state.positions[i].copy_from_slice(x);
+            state.absorb::<O, _>(tally);
+            state.phases.add(phase);
+            let gain = state.credit_move::<O>(phase, old, state.fitness[i]);
+            total += energy.abs();
+
+            if let (Some(h), Some(g)) = (history.as_deref_mut(), gain) {
+                h.record(params, g);
+            }
+        }
+        total
+    }
+
+    /// Atualiza os falcões de `range` em paralelo a partir de um retrato da população.
+    ///
+    /// Cada falcão usa o fluxo aleatório da sua tarefa (iteração, índice) e os resultados são
+    /// consolidados na ordem dos índices, então o resultado não depende do número de threads.
+    /// No modo determinístico cada falcão consome só a sua cota do orçamento, e o que sobra das
+    /// cotas volta a `left` no fim. Devolve a soma de |E| dos falcões atualizados.
+    #[cfg(feature = "rayon")]
+    fn sweep_parallel(
+        &self,
+        state: &mut State,
+        mut history: Option<&mut SuccessHistory>,
+        scratch: &mut Scratch,
+        decoder: &(dyn Decoder + Sync),
+        range: Range<usize>,
+        left: &AtomicUsize,
+    ) -> f64 {
+        use rayon::prelude::*;
+
+        let dim = self.bounds.dim();
+        let (iter, n, first) = (state.iter as u64, state.positions.len() as u64, range.start);
+        let streams = SeedStreams::new(state.seed).with_kind(self.settings.rng);
+        let progress = state.progress();
+        let (state_iter, evals, common) = (state.iter, state.evals, state.common);
+        let archive = state.hall_of_fame.is_some() || state.evaluations.is_some();
+        let defaults = self.settings.params_at(progress);
+        let fidelity = self.settings.fidelity;
+        scratch.snapshot.clone_from(&state.positions);
+        let scratch = &*scratch;
+        let memory = history.as_deref();
+        let quotas = self
+            .settings
+            .deterministic_parallel
+            .then(|| quotas(left, range.len()));
+
+        let sweep = Sweep {
+            settings: &self.settings,
+            bounds: scratch.window.as_ref().unwrap_or(&self.bounds),
+            positions: &scratch.snapshot,
+            rabbit: &state.rabbit,
+            mean: &scratch.mean,
+            partner_weights: &scratch.partner_weights,
+            niche_seeds: &scratch.niche_seeds,
+            species: &scratch.species,
+            elite: &scratch.elite,
+            frozen: &state.frozen,
+            credit: state.credit.as_ref(),
+            displacements: state.displacements.as_ref(),
+            shape: &scratch.shape,
+            step_factors: &scratch.step_factors,
+            progress,
+        };
+        let mut velocity = state.velocity.iter_mut().skip(range.start);
+        let hawks: Vec<Hawk<'_>> = state
+            .positions
+            .iter_mut()
+            .skip(range.start)
+            .zip(&mut state.fitness[range.clone()])
+            .zip(range)
+            .map(|((x, fitness), index)| Hawk {
+                index,
+                x,
+                fitness,
+                velocity: velocity.next(),
+            })
+            .collect();
+
+        // Um conjunto de buffers por divisão do rayon, não por falcão
+        let results: Vec<_> = hawks
+            .into_par_iter()
+            .map_init(
+                || Buffers::new(dim),
+                |buffers, mut hawk| {
+                    let mut rng = streams.hawk(iter, n, hawk.index as u64);
+                    let params = memory.map_or(defaults, |h| h.sample(&mut rng));
+                    let energy = Self::energy(progress, params, &mut rng);
+                    let old = *hawk.fitness;
+                    let site = EvalSite {
+                        streams,
+                        iteration: state_iter,
+                        hawk: hawk.index,
+                        evals,
+                        progress,
+                        common,
+                        non_finite: self.settings.non_finite,
+                        worst: O::worst(),
+                    };
+                    let budget = quotas.as_ref().map_or(left, |q| &q[hawk.index - first]);
+                    let mut tally = Tally::new(decoder, site, budget, archive, fidelity);
+                    let phase = sweep.update_hawk::<O, _, _>(
+                        &mut hawk, energy, params, buffers, &mut tally, &mut rng,
+                    );
+                    (
+                        tally,
+                        params,
+                        (old, *hawk.fitness),
+                        phase,
+                        energy,
+                        hawk.index,
+                    )
+                },
+            )
+            .collect();
+        settle(left, quotas.as_deref());
+
+        let mut total = 0.0;
+        for (tally, params, (old, new), phase, energy, i) in results {
+            if let Some(memory) = &mut state.displacements
+                && phase.is_dive()
+                && O::better(new, old)
+            {
+                memory.record(&scratch.snapshot[i], &state.positions[i]);
+            }
+            state.absorb::<O, _>(tally);
+            state.phases.add(phase);
+            let gain = state.credit_move::<O>(phase, old, new);
+            total += energy.abs();
+            if let (Some(h), Some(improvement)) = (history.as_deref_mut(), gain) {
+                h.record(params, improvement);
+            }
+        }
+        total
+    }
+}
+
+/// Divide as avaliações restantes em `count` cotas que diferem em no máximo uma, as maiores
+/// primeiro
+#[cfg(feature = "rayon")]
+fn quotas(left: &AtomicUsize, count: usize) -> Vec<AtomicUsize> {
+    let available = left.load(Ordering::Acquire);
+    (0..count)
+        .map(|k| AtomicUsize::new(available / count + usize::from(k < available % count)))
+        .collect()
+}
+
+/// Devolve ao orçamento o que sobrou das cotas, se houver
+#[cfg(feature = "rayon")]
+fn settle(left: &AtomicUsize, quotas: Option<&[AtomicUsize]>) {
+    let Some(quotas) = quotas else {
+        return;
+    };
+    let unused = quotas.iter().map(|q| q.load(Ordering::Acquire)).sum();
+    left.store(unused, Ordering::Release);
+}
+
+/// Candidate buffers of a hawk update, reused from one hawk to the next.
+#[derive(Debug, Clone)]
+pub(crate) struct Buffers {
+    pub y: Vec<f64>,
+    pub z: Vec<f64>,
+    /// Reflexo do candidato de mergulho (só com mergulhos espelhados)
+    pub mirror: Vec<f64>,
+    pub merge: MergeBuffers,
+}
+
+/// Buffers of the block-greedy merge.
+#[derive(Debug, Clone)]
+pub(crate) struct MergeBuffers {
+    /// Cópia do falcão que recebe os blocos aceitos
+    pub x: Vec<f64>,
+    /// Bloco substituído, restaurado se piorar
+    pub saved: Vec<f64>,
+}
+
+impl Buffers {
+    pub fn new(dim: usize) -> Self {
+        Self {
+            y: vec![0.0; dim],
+            z: vec![0.0; dim],
+            mirror: vec![0.0; dim],
+            merge: MergeBuffers {
+                x: Vec::with_capacity(dim),
+                saved: Vec::with_capacity(dim),
+            },
+        }
+    }
+}
+
+/// De onde vêm as avaliações de um falcão, para montar o contexto entregue ao decodificador
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EvalSite {
+    pub streams: SeedStreams,
+    pub iteration: usize,
+    pub hawk: usize,
+    /// Avaliações contadas antes da atualização do falcão (ou do lote, em paralelo)
+    pub evals: usize,
+    pub progress: f64,
+    /// Números aleatórios comuns a toda a iteração
+    pub common: bool,
+    /// Política para fitness não finitas
+    pub non_finite: NonFinite,
+    /// Pior fitness do objetivo, que substitui as não finitas com [`NonFinite::Worst`]
+    pub worst: f64,
+}
+
+impl EvalSite {
+    /// Contexto da avaliação seguinte a `done` avaliações do falcão
+    const fn context(&self, done: usize) -> EvalContext {
+        let evaluation = self.evals + done;
+        EvalContext {
+            iteration: self.iteration,
+            evaluation,
+            progress: self.progress,
+            seed: self.streams.evaluation(
+                self.iteration as u64,
+                self.hawk as u64,
+                evaluation as u64,
+                self.common,
+            ),
+        }
+    }
+}
+
+/// Avaliações feitas por um falcão, consolidadas no estado ao fim da sua atualização
+pub(crate) struct Tally<'a, D: ?Sized> {
+    decoder: &'a D,
+    site: EvalSite,
+    /// Avaliações ainda disponíveis no orçamento, compartilhadas entre as threads ou, no modo
+    /// paralelo determinístico, a cota do falcão
+    left: &'a AtomicUsize,
+    evals: usize,
+    /// Tempo gasto no decodificador
+    time: Duration,
+    /// Pontos avaliados, guardados só quando o hall da fama ou o registro de avaliações está
+    /// ligado
+    offers: Option<Vec<Solution>>,
+    error: Option<DecoderError>,
+    fidelity: Option<Fidelity>,
+    screened: usize,
+    /// Fitness não finitas devolvidas pelo decodificador
+    non_finite: usize,
+    /// Custo de triagem ainda não cobrado do orçamento
+    debt: f64,
+}
+
+impl<'a, D: Decoder + ?Sized> Tally<'a, D> {
+    pub fn new(
+        decoder: &'a D,
+        site: EvalSite,
+        left: &'a AtomicUsize,
+        archive: bool,
+        fidelity: Option<Fidelity>,
+    ) -> Self {
+        Self {
+            decoder,
+            site,
+            left,
+            evals: 0,
+            time: Duration::ZERO,
+            offers: archive.then(Vec::new),
+            error: None,
+            fidelity,
+            screened: 0,
+            non_finite: 0,
+            debt: 0.0,
+        }
+    }
+
+    /// Evaluates `x` at the screening fidelity (see [`Fidelity`]), charging its cost to the
+    /// budget. Returns `None` when screening is off, once the budget is spent or after a
+    /// decoder error; the point then goes straight to [`Tally::evaluate`].
+    pub fn screen(&mut self, x: &[f64]) -> Option<f64> {
+        let fidelity = self.fidelity?;
+        if self.error.is_some() || self.left.load(Ordering::Acquire) == 0 {
+            return None;
+        }
+        let started = Instant::now();
+        let result = self.decoder.decode_at(x, fidelity.level);
+        self.time += started.elapsed();
+        let f = match result {
+            Ok(f) => f,
+            Err(e) => {
+                self.error = Some(e);
+                return None;
+            }
+        };
+        self.screened += 1;
+        self.debt += fidelity.cost;
+        let f = self.sanitize(x, f)?;
+        // Cada unidade inteira de custo vira uma avaliação completa do orçamento (o custo não
+        // passa de 1, então a dívida nunca chega a 2)
+        if self.debt >= 1.0 {
+            self.debt -= 1.0;
+            self.evals += 1;
+            let _ = self
+                .left
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1));
+        }
+        Some(f)
+    }
+
+    /// Conta e trata uma fitness não finita; `None` se a execução deve parar
+    fn sanitize(&mut self, x: &[f64], f: f64) -> Option<f64> {
+        if !f.is_finite() {
+            self.non_finite += 1;
+        }
+        self.site
+            .non_finite
+            .apply(self.site.worst, x, f)
+            .map_err(|e| self.error = Some(e))
+            .ok()
+    }
+
+    /// Evaluates `x` if the budget still allows it. Returns `None` once the budget is spent or
+    /// after a decoder error.
+    pub fn evaluate(&mut self, x: &[f64]) -> Option<f64> {
+        self.evaluate_near(x, None)
+    }
+
+    /// Like [`Tally::evaluate`] for a candidate derived from `previous`, whose fitness is
+    /// `fitness`; a [`crate::core::IncrementalDecoder`] then evaluates it from the difference.
+    pub fn evaluate_from(&mut self, x: &[f64], previous: &[f64], fitness: f64) -> Option<f64> {
+        self.evaluate_near(x, Some((previous, fitness)))
+    }
+
+    /// Avaliação completa ou, com um decodificador incremental, a partir da posição anterior
+    fn evaluate_near(&mut self, x: &[f64], previous: Option<(&[f64], f64)>) -> Option<f64> {
+        if self.error.is_some()
+            || self
+                .left
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+                .is_err()
+        {
+            return None;
+        }
+        let ctx = self.site.context(self.evals);
+        let started = Instant::now();
+        let result = previous
+            .zip(self.decoder.incremental())
+            .and_then(|((p, f), d)| d.decode_delta(p, f, x, &ctx))
+            .unwrap_or_else(|| self.decoder.decode_with(x, &ctx));
+        self.time += started.elapsed();
+        let f = match result {
+            Ok(f) => f,
+            Err(e) => {
+                self.error = Some(e);
+                return None;
+            }
+        };
+        self.evals += 1;
+        let f = self.sanitize(x, f)?;
+        if let Some(offers) = &mut self.offers {
+            offers.push(Solution {
+                position: x.to_vec(),
+                fitness: f,
+            });
+        }
+        Some(f)
+    }
+}
+
+impl State {
+    /// Folds the evaluations of one hawk into the run counters and the hall of fame.
+    pub fn absorb<O: Objective, D: ?Sized>(&mut self, tally: Tally<'_, D>) {
+        self.evals += tally.evals;
+        self.eval_time += tally.time;
+        self.screened += tally.screened;
+        self.non_finite += tally.non_finite;
+        // As frações de custo de cada falcão se somam aqui até formar avaliações inteiras
+        self.screen_debt += tally.debt;
+        if self.screen_debt >= 1.0 {
+            self.screen_debt -= 1.0;
+            self.evals += 1;
+        }
+        if let Some(e) = tally.error {
+            self.fail(e);
+        }
+        for s in tally.offers.into_iter().flatten() {
+            if let Some(hof) = &mut self.hall_of_fame {
+                hof.offer::<O>(&s.position, s.fitness);
+            }
+            if let Some(archive) = &mut self.evaluations
+                && !self.archive_capped
+            {
+                archive.push(&s.position, s.fitness);
+            }
+        }
+    }
+}
+
+/// The hawk being updated, borrowed from the population.
+pub(crate) struct Hawk<'a> {
+    pub index: usize,
+    pub x: &'a mut [f64],
+    pub fitness: &'a mut f64,
+    /// Último deslocamento (só com momento)
+    pub velocity: Option<&'a mut [f64]>,
+}
+
+impl Hawk<'_> {
+    /// Moves the hawk to `x` with fitness `f`, recording the displacement when momentum is on.
+    fn move_to(&mut self, x: &[f64], f: f64) {
+        if let Some(v) = self.velocity.as_deref_mut() {
+            vecops::sub_into(v, x, self.x);
+        }
+        self.x.copy_from_slice(x);
+        *self.fitness = f;
+    }
+}
+
+/// Read-only data shared by every hawk update of one iteration.
+pub(crate) struct Sweep<'a> {
+    pub settings: &'a Settings,
+    pub bounds: &'a Bounds,
+    /// Posições usadas para escolher parceiros; a do próprio falcão pode estar desatualizada
+    pub positions: &'a Population,
+    pub rabbit: &'a [f64],
+    pub mean: &'a [f64],
+    pub partner_weights: &'a [f64],
+    pub niche_seeds: &'a Population,
+    pub species: &'a [usize],
+    pub elite: &'a [f64],
+    pub frozen: &'a [bool],
+    /// Crédito das regras de aproveitamento (só com [`Exploitation::Adaptive`])
+    pub credit: Option<&'a Credit>,
+    /// Deslocamentos a repetir nos mergulhos (só com [`crate::hho::DiveReplay`])
+    pub displacements: Option<&'a Displacements>,
+    /// Fator de [`crate::hho::DiveShape`] (vazio com passos isotrópicos)
+    pub shape: &'a [f64],
+    /// Fator do passo de cada falcão (vazio sem [`crate::hho::RankSteps`])
+    pub step_factors: &'a [f64],
+    pub progress: f64,
+}
+
+impl Sweep<'_> {
+    /// Moves `hawk` according to its escape energy and returns the branch it took.
+    #[allow(clippy::too_many_arguments, clippy::many_single_char_names)]
+    pub fn update_hawk<O, D, R>(
+        &self,
+        hawk: &mut Hawk<'_>,
+        energy: f64,
+        params: Params,
+        buffers: &mut Buffers,
+        tally: &mut Tally<'_, D>,
+        rng: &mut R,
+    ) -> Phase
+    where
+        O: Objective,
+        D: Decoder + ?Sized,
+        R: Rng,
+    {
+        let Buffers {
+            y,
+            z,
+            mirror,
+            merge,
+        } = buffers;
+        let dim = self.bounds.dim();
+        let i = hawk.index;
+        let rabbit: &[f64] = match self.settings.niching {
+            Niching::Off => self.rabbit,
+            Niching::Speciation { .. } | Niching::Leaders { .. } => {
+                &self.niche_seeds[self.species[i]]
+            }
+        };
+
+        if energy.abs() >= 1.0 {
+            // Exploração: empoleirar com base em um membro aleatório ou na posição da família
+            let q: f64 = rng.random();
+            if q >= 0.5 {
+                let k = match self.settings.partner_selection {
+                    PartnerSelection::Uniform => rng.random_range(0..self.positions.len()),
+                    PartnerSelection::FitnessDistanceBalance => roulette(self.partner_weights, rng),
+                };
+                let (r1, r2): (f64, f64) = (rng.random(), rng.random());
+                let x = &*hawk.x;
+                let xr: &[f64] = if k == i { x } else { &self.positions[k] };
+                for j in 0..dim {
+                    y[j] = r1.mul_add(-(2.0 * r2).mul_add(-x[j], xr[j]).abs(), xr[j]);
+                }
+            } else {
+                let (r3, r4): (f64, f64) = (rng.random(), rng.random());
+                for j in 0..dim {
+                    let lo = self.bounds.lo_at(j);
+                    y[j] = r3.mul_add(
+                        -r4.mul_add(self.bounds.span_at(j), lo),
+                        rabbit[j] - self.mean[j],
+                    );
+                }
+            }
+            self.accept::<O, D>(hawk, y, merge, tally);
+            return Phase::Exploration;
+        }
+
+        // Nos cercos o alvo pode ser o centróide da elite em vez do coelho
+        let rabbit: &[f64] = match (self.settings.niching, self.settings.attractor) {
+            (Niching::Off, Attractor::EliteCentroid { .. }) => self.elite,
+            _ => rabbit,
+        };
+
+        let exploitation = match (self.settings.exploitation, self.credit) {
+            (Exploitation::Adaptive { .. }, Some(credit)) => Exploitation::ARMS[credit.select(rng)],
+            (exploitation, _) => exploitation,
+        };
+        if let Some(phase) =
+            self.alternative_step::<O, D, R>(exploitation, hawk, rabbit, y, merge, tally, rng)
+        {
+            return phase;
+        }
+
+        let r: f64 = rng.random();
+        let jump = self.settings.jump_strength.sample(self.progress, rng);
+        let x = &*hawk.x;
+
+        let soft = energy.abs() >= 0.5;
+
+        if r >= 0.5 {
+            if soft {
+                // Cerco suave
+                for j in 0..dim {
+                    y[j] = energy.mul_add(-jump.mul_add(rabbit[j], -x[j]).abs(), rabbit[j] - x[j]);
+                }
+            } else {
+                // Cerco forte
+                for j in 0..dim {
+                    y[j] = energy.mul_add(-(rabbit[j] - x[j]).abs(), rabbit[j]);
+                }
+            }
+            self.crossover(x, y, rng);
+            self.accept::<O, D>(hawk, y, merge, tally);
+            return if soft {
+                Phase::SoftBesiege
+            } else {
+                Phase::HardBesiege
+            };
+        }
+
+        // Cercos com mergulhos rápidos progressivos
+        let phase = if soft {
+            Phase::SoftDives
+        } else {
+            Phase::HardDives
+        };
+        let base: &[f64] = if soft { x } else { self.mean };
+        for j in 0..dim {
+            y[j] = energy.mul_add(-jump.mul_add(rabbit[j], -base[j]).abs(), rabbit[j]);
+        }
+        self.crossover(x, y, rng);
+        self.prepare(hawk, y);
+        if self.dive_greedy::<O, D>(hawk, y, mirror, merge, tally) {
+            return phase;
+        }
+
+        self.second_dive(hawk.x, y, z, params, rng);
+        self.crossover(hawk.x, z, rng);
+        self.scale_step(hawk, z);
+        self.bounds.project_slice(z);
+        self.pin(hawk, z);
+        self.dive_greedy::<O, D>(hawk, z, mirror, merge, tally);
+        phase
+    }
+
+    /// Devolve a `candidate` o valor de `x` em cada coordenada que não sorteou mudar (só com
+    /// probabilidade de atualização); uma coordenada aleatória sempre muda
+    fn crossover<R: Rng>(&self, x: &[f64], candidate: &mut [f64], rng: &mut R) {
+        let Some(p) = self
+            .settings
+            .scheduled(Parameter::UpdateProbability, self.progress)
+            .or(self.settings.update_probability)
+        else {
+            return;
+        };
+        let always = rng.random_range(0..candidate.len());
+        for (j, (c, &xj)) in candidate.iter_mut().zip(x).enumerate() {
+            if j != always && rng.random::<f64>() >= p {
+                *c = xj;
+            }
+        }
+    }
+
+    /// [`Sweep::greedy`] for a dive candidate; with mirrored dives its reflection about the
+    /// hawk is evaluated as well and the hawk keeps the best of the three points.
+    fn dive_greedy<O, D>(
+        &self,
+        hawk: &mut Hawk<'_>,
+        candidate: &[f64],
+        mirror: &mut [f64],
+        merge: &mut MergeBuffers,
+        tally: &mut Tally<'_, D>,
+    ) -> bool
+    where
+        O: Objective,
+        D: Decoder + ?Sized,
+    {
+        if !self.settings.mirror_dives {
+            return self.greedy::<O, D>(hawk, candidate, merge, tally);
+        }
+        // O reflexo é tomado antes que o falcão se mova
+        for ((m, &c), &x) in mirror.iter_mut().zip(candidate).zip(hawk.x.iter()) {
+            *m = 2.0_f64.mul_add(x, -c);
+        }
+        self.bounds.project_slice(mirror);
+        self.pin(hawk, mirror);
+        let moved = self.greedy::<O, D>(hawk, candidate, merge, tally);
+        self.greedy::<O, D>(hawk, mirror, merge, tally) || moved
+    }
+
+    /// Writes into `z` the second dive candidate from the first one `y`: a Lévy flight, or a
+    /// remembered displacement replayed from the hawk `x` (see [`crate::hho::DiveReplay`]).
+    fn second_dive<R: Rng>(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        z: &mut [f64],
+        params: Params,
+        rng: &mut R,
+    ) {
+        let replayed = match (self.settings.dive_replay, self.displacements) {
+            (Some(replay), Some(memory)) if rng.random::<f64>() < replay.rate => memory
+                .sample(rng)
+                .map(|d| (replay.scale * rng.random::<f64>(), d)),
+            _ => None,
+        };
+        if let Some((s, d)) = replayed {
+            for ((z, &d), &x) in z.iter_mut().zip(d).zip(x) {
+                *z = s.mul_add(d, x);
+            }
+        } else if self.shape.is_empty() {
+            levy_flight(rng, params.levy_beta, z);
+            for (z, &y) in z.iter_mut().zip(y) {
+                let s: f64 = rng.random();
+                *z = (params.dive_scale * s).mul_add(*z, y);
+            }
+        } else {
+            // Um só fator aleatório, para não desfazer a orientação do passo moldado
+            levy_flight(rng, params.levy_beta, z);
+            shape_in_place(self.shape, z);
+            let s = params.dive_scale * rng.random::<f64>();
+            for (z, &y) in z.iter_mut().zip(y) {
+                *z = s.mul_add(*z, y);
+            }
+        }
+    }
+
+    /// Applies a non-classic exploitation rule to `hawk`, returning its phase, or `None`
+    /// under the classic rule.
+    #[allow(clippy::too_many_arguments)]
+    fn alternative_step<O, D, R>(
+        &self,
+        exploitation: Exploitation,
+        hawk: &mut Hawk<'_>,
+        rabbit: &[f64],
+        y: &mut [f64],
+        merge: &mut MergeBuffers,
+        tally: &mut Tally<'_, D>,
+        rng: &mut R,
+    ) -> Option<Phase>
+    where
+        O: Objective,
+        D: Decoder + ?Sized,
+        R: Rng,
+    {
+        match exploitation {
+            Exploitation::Quantum {
+                alpha_start,
+                alpha_end,
+            } => {
+                let alpha = (alpha_end - alpha_start).mul_add(self.progress, alpha_start);
+                quantum_step(hawk.x, rabbit, self.mean, alpha, rng, y);
+                // Sem melhor pessoal (como no QPSO), a troca só ocorre se houver melhora
+                self.prepare(hawk, y);
+                self.greedy::<O, D>(hawk, y, merge, tally);
+                Some(Phase::Quantum)
+            }
+            Exploitation::Differential { f, cr } => {
+                let n = self.positions.len();
+                let (a, b) = (rng.random_range(0..n), rng.random_range(0..n));
+                let (a, b) = (&self.positions[a], &self.positions[b]);
+                differential_step(hawk.x, rabbit, a, b, f, cr, rng, y);
+                self.prepare(hawk, y);
+                self.greedy::<O, D>(hawk, y, merge, tally);
+                Some(Phase::Differential)
+            }
+            Exploitation::SineCosine { a } => {
+                let r1 = a * (1.0 - self.progress);
+                sine_cosine_step(hawk.x, rabbit, r1, rng, y);
+                self.accept::<O, D>(hawk, y, merge, tally);
+                Some(Phase::SineCosine)
+            }
+            Exploitation::Classic | Exploitation::Adaptive { .. } => None,
+        }
+    }
+
+    /// Projects `candidate`, evaluates it and unconditionally moves the hawk there.
+    fn accept<O, D>(
+        &self,
+        hawk: &mut Hawk<'_>,
+        candidate: &mut [f64],
+        merge: &mut MergeBuffers,
+        tally: &mut Tally<'_, D>,
+    ) where
+        O: Objective,
+        D: Decoder + ?Sized,
+    {
+        self.prepare(hawk, candidate);
+        if let UpdateMode::BlockGreedy { block_size } = self.settings.update_mode {
+            Self::merge_blocks::<O, D>(hawk, candidate, block_size, merge, tally);
+            return;
+        }
+        if let Some(f) = tally.evaluate_from(candidate, hawk.x, *hawk.fitness) {
+            hawk.move_to(candidate, f);
+        }
+    }
+
+    /// Evaluates `candidate` and moves the hawk there only if it improves. Returns whether it
+    /// moved.
+    fn greedy<O, D>(
+        &self,
+        hawk: &mut Hawk<'_>,
+        candidate: &[f64],
+        merge: &mut MergeBuffers,
+        tally: &mut Tally<'_, D>,
+    ) -> bool
+    where
+        O: Objective,
+        D: Decoder + ?Sized,
+    {
+        if let UpdateMode::BlockGreedy { block_size } = self.settings.update_mode {
+            return Self::merge_blocks::<O, D>(hawk, candidate, block_size, merge, tally);
+        }
+        // Com triagem, só o candidato promissor em baixa fidelidade é avaliado por inteiro
+        if let Some(screened) = tally.screen(candidate)
+            && !O::better(screened, *hawk.fitness)
+        {
+            return false;
+        }
+        let Some(f) = tally.evaluate_from(candidate, hawk.x, *hawk.fitness) else {
+            return false;
+        };
+        if O::better(f, *hawk.fitness) {
+            hawk.move_to(candidate, f);
+            return true;
+        }
+        false
+    }
+
+    /// Applies `candidate` to the hawk block by block (see [`UpdateMode::BlockGreedy`]).
+    /// Returns whether any block was accepted.
+    fn merge_blocks<O, D>(
+        hawk: &mut Hawk<'_>,
+        candidate: &[f64],
+        block_size: usize,
+        merge: &mut MergeBuffers,
+        tally: &mut Tally<'_, D>,
+    ) -> bool
+    where
+        O: Objective,
+        D: Decoder + ?Sized,
+    {
+        let MergeBuffers { x, saved } = merge;
+        x.clear();
+        x.extend_from_slice(hawk.x);
+        let old = *hawk.fitness;
+        let f = blockwise_merge_in::<O, _>(x, old, candidate, block_size, saved, |x| {
+            tally.evaluate(x).unwrap_or_else(O::worst)
+        });
+        if O::better(f, old) {
+            hawk.move_to(x, f);
+            return true;
+        }
+        false
+    }
+
+    /// Scales the step of the hawk by its rank and adds its momentum term (if enabled),
+    /// projects the candidate into bounds and restores frozen coordinates.
+    fn prepare(&self, hawk: &Hawk<'_>, candidate: &mut [f64]) {
+        self.scale_step(hawk, candidate);
+        if let Some(v) = hawk.velocity.as_deref() {
+            let momentum = self
+                .settings
+                .scheduled(Parameter::Momentum, self.progress)
+                .unwrap_or(self.settings.momentum);
+            vecops::axpy(candidate, momentum, v);
+        }
+        self.bounds.project_slice(candidate);
+        self.pin(hawk, candidate);
+    }
+
+    /// Multiplica o deslocamento do falcão até `candidate` pelo fator do seu ranking
+    fn scale_step(&self, hawk: &Hawk<'_>, candidate: &mut [f64]) {
+        let Some(&s) = self.step_factors.get(hawk.index) else {
+            return;
+        };
+        for (c, &x) in candidate.iter_mut().zip(hawk.x.iter()) {
+            *c = s.mul_add(*c - x, x);
+        }
+    }
+
+    /// Restores the frozen coordinates of the hawk in `candidate`.
+    #[inline]
+    fn pin(&self, hawk: &Hawk<'_>, candidate: &mut [f64]) {
+        for ((c, &p), _) in candidate
+            .iter_mut()
+            .zip(hawk.x.iter())
+            .zip(self.frozen)
+            .filter(|(_, f)| **f)
+        {
+            *c = p;
+        }
+    }
+}
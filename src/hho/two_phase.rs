@@ -0,0 +1,227 @@
+//! Runs split between a global search and an intensification around its best point.
+
+use std::marker::PhantomData;
+
+use crate::{
+    core::{Budget, Decoder, Objective, Report, SeedStreams, TerminationReason},
+    hho::{Hho, HhoError, Zoom},
+    init::{Initializer, WarmStart},
+    local_search::PatternSearch,
+};
+
+/// Split of the iteration budget of [`Hho::run_two_phase`].
+///
+/// The global phase runs HHO over the full bounds for `global` of the iterations. The
+/// remainder is spent inside a box of side `scale` times each range centered on the best
+/// point found: `1 - local_share` of it by HHO warm-started from that point, then the rest,
+/// converted to evaluations at the rate of the global phase, by `local` pattern search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoPhase {
+    /// Fraction of the iterations spent by the global phase, in `(0, 1]`.
+    pub global: f64,
+    /// Side of the intensification box as a fraction of each range, in `(0, 1]`.
+    pub scale: f64,
+    /// Fraction of the remaining budget spent by the local search, in `[0, 1]`.
+    pub local_share: f64,
+    pub local: PatternSearch,
+}
+
+impl Default for TwoPhase {
+    fn default() -> Self {
+        Self {
+            global: 0.7,
+            scale: 0.1,
+            local_share: 0.5,
+            local: PatternSearch::default(),
+        }
+    }
+}
+
+impl TwoPhase {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self {
+            global,
+            scale,
+            local_share,
+            ..
+        } = *self;
+        (global.is_nan()
+            || !(0.0..=1.0).contains(&global)
+            || global == 0.0
+            || scale.is_nan()
+            || !(0.0..=1.0).contains(&scale)
+            || scale == 0.0
+            || !(0.0..=1.0).contains(&local_share))
+        .then(|| {
+            format!(
+                "two-phase run needs global = {global} and scale = {scale} in (0, 1] and \
+                     local_share = {local_share} in [0, 1]"
+            )
+        })
+    }
+}
+
+impl<O, I> Hho<O, I>
+where
+    O: Objective,
+    I: Initializer,
+{
+    /// Runs the optimizer in two phases, see [`TwoPhase`], and returns a single report.
+    ///
+    /// The intensification phase continues the counters of the global one (see
+    /// [`crate::hho::HhoBuilder::continue_counters`]), so the escape energy keeps decreasing
+    /// and the records of both phases follow each other, as joined by [`Report::chain`]. It
+    /// draws from a stream split off the seed of the global phase, so a seeded run is
+    /// reproducible. The improvement of the final pattern search shows in
+    /// [`Report::best_fitness`] and [`Report::evals`] only.
+    ///
+    /// The global phase is returned as is when it stopped for another reason than running out
+    /// of iterations or stalling, e.g. because a target was reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HhoError::Config`] if `plan` is out of range or a checkpoint is configured
+    /// (both phases would write the same file). Otherwise fails like [`Hho::run`], with the
+    /// report of [`HhoError::Decoder`] covering both phases.
+    pub fn run_two_phase(&self, decoder: &dyn Decoder, plan: TwoPhase) -> Result<Report, HhoError> {
+        if let Some(problem) = plan.problem() {
+            return Err(HhoError::Config(problem));
+        }
+        #[cfg(feature = "serde")]
+        if self.settings.checkpoint.is_some() {
+            return Err(HhoError::Config(
+                "checkpoints are not supported with two-phase runs".to_owned(),
+            ));
+        }
+        let total = self.settings.max_iters;
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let global_iters = ((plan.global * total as f64).round() as usize).clamp(1, total.max(1));
+        let first = self.run_with_budget(decoder, Budget::Iterations(global_iters))?;
+        if !matches!(
+            first.stop_reason,
+            Some(TerminationReason::MaxIters | TerminationReason::Stalled)
+        ) {
+            return Ok(first);
+        }
+
+        let remaining = total.saturating_sub(first.iters);
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let (hho_iters, local_evals) = {
+            let local = plan.local_share * remaining as f64;
+            let rate = first.evals as f64 / first.iters.max(1) as f64;
+            (
+                remaining - local.round() as usize,
+                (local * rate).round() as usize,
+            )
+        };
+
+        let mut report = if hho_iters == 0 {
+            first
+        } else {
+            let mut settings = self.settings.clone();
+            settings.max_iters = first.iters + hho_iters;
+            settings.counters = Some((first.iters, first.evals));
+            settings.seed = first.seed.map(|s| SeedStreams::new(s).replication(1));
+            let intensify = Hho {
+                bounds: Zoom::window(&self.bounds, &first.best_position, plan.scale),
+                initializer: WarmStart::from_report(&first),
+                settings,
+                objective: PhantomData::<fn() -> O>,
+            };
+            match intensify.run(decoder) {
+                Ok(second) => first.chain(second),
+                Err(HhoError::Decoder { source, report }) => {
+                    return Err(HhoError::Decoder {
+                        source,
+                        report: Box::new(first.chain(*report)),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        if local_evals > 0 {
+            let window = Zoom::window(&self.bounds, &report.best_position, plan.scale);
+            let refined = match plan.local.refine::<O>(
+                decoder,
+                &window,
+                &report.best_position,
+                report.best_fitness,
+                local_evals,
+            ) {
+                Ok(refined) => refined,
+                Err(source) => {
+                    return Err(HhoError::Decoder {
+                        source,
+                        report: Box::new(report),
+                    });
+                }
+            };
+            report.evals += refined.evals;
+            if O::better(refined.fitness, report.best_fitness) {
+                report.best_fitness = refined.fitness;
+                report.best_position = refined.position;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn intensification_refines_the_global_best() {
+        let hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, 8).unwrap())
+            .pop_size(20)
+            .max_iters(100)
+            .seed(4)
+            .build();
+        let global = hho
+            .run_with_budget(&Function::Sphere, Budget::Iterations(70))
+            .unwrap();
+        let report = hho
+            .run_two_phase(&Function::Sphere, TwoPhase::default())
+            .unwrap();
+        // A fase global é a mesma execução
+        assert_eq!(report.convergence_curve[..70], global.convergence_curve[..]);
+        assert!(report.best_fitness < global.best_fitness);
+        assert_eq!(report.iters, 85);
+        assert_eq!(report.curve_iters.len(), 85);
+        assert!(report.curve_iters.windows(2).all(|w| w[0] < w[1]));
+        assert!(
+            (Function::Sphere.decode(&report.best_position).unwrap() - report.best_fitness).abs()
+                < 1e-12
+        );
+        // A busca local gasta o restante das avaliações
+        assert!(report.evals > *report.curve_evals.last().unwrap());
+        assert_eq!(
+            hho.run_two_phase(&Function::Sphere, TwoPhase::default())
+                .unwrap()
+                .best_fitness
+                .to_bits(),
+            report.best_fitness.to_bits()
+        );
+
+        let bad = TwoPhase {
+            global: 0.0,
+            ..TwoPhase::default()
+        };
+        assert!(matches!(
+            hho.run_two_phase(&Function::Sphere, bad),
+            Err(HhoError::Config(_))
+        ));
+    }
+}
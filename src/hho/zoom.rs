@@ -0,0 +1,102 @@
+use crate::core::Bounds;
+
+/// Adaptive shrinking of the search box around the rabbit.
+///
+/// After every `patience` iterations without improvement, the hawks move within a box
+/// centered on the rabbit whose side is `factor` times the previous one, down to `min_scale`
+/// of the full range in every dimension; each improvement widens the box again by
+/// `1 / factor`, back to the full bounds. The box is shifted, not cut, where it would leave
+/// the bounds. Initialization, restarts and the duplicate purge always use the full bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Zoom {
+    pub patience: usize,
+    /// Shrink factor, in `(0, 1)`.
+    pub factor: f64,
+    /// Smallest side of the box as a fraction of each range, in `(0, 1]`.
+    pub min_scale: f64,
+}
+
+impl Default for Zoom {
+    fn default() -> Self {
+        Self {
+            patience: 20,
+            factor: 0.5,
+            min_scale: 1e-3,
+        }
+    }
+}
+
+impl Zoom {
+    /// Problemas nos parâmetros
+    pub(crate) fn problems(&self) -> Vec<String> {
+        let Self {
+            patience,
+            factor,
+            min_scale,
+        } = *self;
+        let mut problems = Vec::new();
+        if patience == 0 {
+            problems.push("zoom patience must be at least 1".to_owned());
+        }
+        if !(factor > 0.0 && factor < 1.0) {
+            problems.push(format!("zoom factor = {factor} must be in (0, 1)"));
+        }
+        if !(min_scale > 0.0 && min_scale <= 1.0) {
+            problems.push(format!("zoom min_scale = {min_scale} must be in (0, 1]"));
+        }
+        problems
+    }
+
+    /// Nova escala da caixa após uma iteração com `stall` iterações sem melhora
+    pub(crate) fn rescale(&self, scale: f64, stall: usize) -> f64 {
+        if stall == 0 {
+            (scale / self.factor).min(1.0)
+        } else if stall.is_multiple_of(self.patience.max(1)) {
+            (scale * self.factor).max(self.min_scale)
+        } else {
+            scale
+        }
+    }
+
+    /// Caixa de lado `scale` vezes a largura de cada dimensão, centrada em `center` e
+    /// deslocada para dentro de `bounds`
+    pub(crate) fn window(bounds: &Bounds, center: &[f64], scale: f64) -> Bounds {
+        let (lo, hi) = (0..bounds.dim())
+            .map(|j| {
+                let (lo, hi) = (bounds.lo_at(j), bounds.hi_at(j));
+                let half = 0.5 * scale * (hi - lo);
+                let c = center[j].clamp(lo + half, hi - half);
+                (c - half, c + half)
+            })
+            .unzip();
+        Bounds::per_dim(lo, hi)
+            .expect("a window inside valid bounds is valid")
+            .with_policy(bounds.policy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_on_stagnation_and_widens_on_improvement() {
+        let zoom = Zoom {
+            patience: 3,
+            factor: 0.5,
+            min_scale: 0.2,
+        };
+        assert!((zoom.rescale(1.0, 2) - 1.0).abs() < 1e-12);
+        assert!((zoom.rescale(1.0, 3) - 0.5).abs() < 1e-12);
+        assert!((zoom.rescale(0.25, 6) - 0.2).abs() < 1e-12);
+        assert!((zoom.rescale(0.5, 0) - 1.0).abs() < 1e-12);
+        assert!((zoom.rescale(1.0, 0) - 1.0).abs() < 1e-12);
+
+        let bounds = Bounds::per_dim(vec![0.0, -10.0], vec![10.0, 10.0]).unwrap();
+        let window = Zoom::window(&bounds, &[9.5, 0.0], 0.2);
+        // Deslocada para caber em [0, 10] na primeira dimensão
+        assert_eq!(*window.lower(), [8.0, -2.0]);
+        assert_eq!(*window.upper(), [10.0, 2.0]);
+    }
+}
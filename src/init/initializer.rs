@@ -1,15 +1,23 @@
 use rand::Rng;
 use thiserror::Error;
 
-use crate::core::Bounds;
+use crate::core::{Bounds, Population};
 
 #[derive(Debug, Error)]
 pub enum InitError {
     #[error("invalid population size: {0}")]
     InvalidPopSize(usize),
+
+    #[error("initial positions have dimension {received}, expected {expected}")]
+    Dimension { expected: usize, received: usize },
+
+    #[error("invalid prior for dimension {dim}: {reason}")]
+    Prior { dim: usize, reason: String },
 }
 
 pub trait Initializer: Send + Sync {
+    /// Returns `pop_size` positions of dimension `bounds.dim()`.
+    ///
     /// # Errors
     ///
     /// Retorna um error se o tamanho da população for igual a 0
@@ -18,5 +26,5 @@ pub trait Initializer: Send + Sync {
         pop_size: usize,
         bounds: &Bounds,
         rng: &mut R,
-    ) -> Result<Vec<Vec<f64>>, InitError>;
+    ) -> Result<Population, InitError>;
 }
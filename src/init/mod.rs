@@ -1,4 +1,9 @@
 pub mod initializer;
+pub mod prior;
 pub mod random_init;
+pub mod warm;
 
 pub use initializer::{InitError, Initializer};
+pub use prior::{Prior, PriorInitializer};
+pub use random_init::RandomInitializer;
+pub use warm::WarmStart;
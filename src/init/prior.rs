@@ -0,0 +1,171 @@
+use rand::Rng;
+
+use crate::{
+    core::{Bounds, Population},
+    hho::{jump::sample_beta, levy::standard_normal},
+    init::{InitError, Initializer},
+};
+
+/// Tentativas de sorteio da normal truncada antes de recorrer à projeção nos limites
+const MAX_REJECTIONS: usize = 100;
+
+/// Initial distribution of one dimension, see [`PriorInitializer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Prior {
+    /// Uniform over the range, as [`crate::init::RandomInitializer`].
+    #[default]
+    Uniform,
+    /// Normal of mean `mean` and standard deviation `std` (positive), truncated to the range:
+    /// draws outside it are redrawn, and after 100 misses the last one is projected.
+    Normal { mean: f64, std: f64 },
+    /// Beta of shapes `alpha` and `beta` (positive), stretched over the range.
+    Beta { alpha: f64, beta: f64 },
+    /// Always this value, projected into the range.
+    Point(f64),
+}
+
+impl Prior {
+    /// Problema nos parâmetros, se houver
+    fn problem(&self) -> Option<String> {
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        match *self {
+            Self::Uniform => None,
+            Self::Normal { mean, std } => (!(mean.is_finite() && positive(std)))
+                .then(|| format!("normal needs a finite mean = {mean} and std = {std} > 0")),
+            Self::Beta { alpha, beta } => (!(positive(alpha) && positive(beta)))
+                .then(|| format!("beta needs alpha = {alpha} > 0 and beta = {beta} > 0")),
+            Self::Point(v) => (!v.is_finite()).then(|| format!("point {v} is not finite")),
+        }
+    }
+
+    /// Sorteia um valor em `[lo, hi]`; num intervalo degenerado, o próprio `lo`
+    fn sample<R: Rng>(&self, lo: f64, hi: f64, rng: &mut R) -> f64 {
+        #[allow(clippy::float_cmp)]
+        if lo == hi {
+            return lo;
+        }
+        match *self {
+            Self::Uniform => rng.random_range(lo..=hi),
+            Self::Normal { mean, std } => {
+                let mut x = mean;
+                for _ in 0..MAX_REJECTIONS {
+                    x = std.mul_add(standard_normal(rng), mean);
+                    if (lo..=hi).contains(&x) {
+                        return x;
+                    }
+                }
+                x.clamp(lo, hi)
+            }
+            Self::Beta { alpha, beta } => (hi - lo).mul_add(sample_beta(alpha, beta, rng), lo),
+            Self::Point(v) => v.clamp(lo, hi),
+        }
+    }
+}
+
+/// Initial population drawn from a [`Prior`] per dimension, to encode what is known about
+/// where good solutions lie (see [`crate::hho::HhoBuilder::initial_priors`]).
+///
+/// Dimensions are drawn independently, so priors mix freely: e.g. a normal around a nominal
+/// design on some parameters and uniform draws on the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorInitializer {
+    priors: Vec<Prior>,
+}
+
+impl PriorInitializer {
+    /// One prior per dimension of the bounds, in order.
+    #[must_use]
+    pub const fn new(priors: Vec<Prior>) -> Self {
+        Self { priors }
+    }
+}
+
+impl Initializer for PriorInitializer {
+    fn initialize<R: Rng>(
+        &self,
+        pop_size: usize,
+        bounds: &Bounds,
+        rng: &mut R,
+    ) -> Result<Population, InitError> {
+        if pop_size == 0 {
+            return Err(InitError::InvalidPopSize(pop_size));
+        }
+        if self.priors.len() != bounds.dim() {
+            return Err(InitError::Dimension {
+                expected: bounds.dim(),
+                received: self.priors.len(),
+            });
+        }
+        if let Some((dim, reason)) = self
+            .priors
+            .iter()
+            .enumerate()
+            .find_map(|(j, p)| p.problem().map(|r| (j, r)))
+        {
+            return Err(InitError::Prior { dim, reason });
+        }
+        let (lower, upper) = (bounds.lower(), bounds.upper());
+        let mut positions = Population::zeros(pop_size, bounds.dim());
+        for x in &mut positions {
+            for (j, v) in x.iter_mut().enumerate() {
+                *v = self.priors[j].sample(lower[j], upper[j], rng);
+            }
+            bounds.project_slice(x);
+        }
+        Ok(positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::core::utils::mean;
+
+    #[test]
+    fn each_dimension_follows_its_prior() {
+        let bounds = Bounds::uniform(0.0, 10.0, 4).unwrap();
+        let init = PriorInitializer::new(vec![
+            Prior::Normal {
+                mean: 9.5,
+                std: 1.0,
+            },
+            Prior::Beta {
+                alpha: 0.5,
+                beta: 4.5,
+            },
+            Prior::Point(12.0),
+            Prior::Uniform,
+        ]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let positions = init.initialize(4_000, &bounds, &mut rng).unwrap();
+        let column = |j: usize| -> Vec<f64> { positions.iter().map(|x| x[j]).collect() };
+        assert!(positions.iter().flatten().all(|v| (0.0..=10.0).contains(v)));
+        // Normal truncada em 10: média abaixo de 9.5
+        let normal = mean(&column(0)).unwrap();
+        assert!(normal > 8.7 && normal < 9.3, "{normal}");
+        // Beta(0.5, 4.5) tem média 0.1, ou 1 na escala dos limites
+        let beta = mean(&column(1)).unwrap();
+        assert!((beta - 1.0).abs() < 0.1, "{beta}");
+        assert!(column(2).iter().all(|&v| v >= 10.0));
+        assert!((mean(&column(3)).unwrap() - 5.0).abs() < 0.2);
+
+        let bad = PriorInitializer::new(vec![Prior::Normal {
+            mean: 0.0,
+            std: 0.0,
+        }]);
+        let one = Bounds::uniform(0.0, 1.0, 1).unwrap();
+        assert!(matches!(
+            bad.initialize(5, &one, &mut rng),
+            Err(InitError::Prior { dim: 0, .. })
+        ));
+        assert!(matches!(
+            init.initialize(5, &one, &mut rng),
+            Err(InitError::Dimension { .. })
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use rand::Rng;
 
 use crate::{
-    core::Bounds,
+    core::{Bounds, Population},
     init::{InitError, Initializer},
 };
 
@@ -14,18 +14,15 @@ impl Initializer for RandomInitializer {
         pop_size: usize,
         bounds: &Bounds,
         rng: &mut R,
-    ) -> Result<Vec<Vec<f64>>, InitError> {
+    ) -> Result<Population, InitError> {
         if pop_size == 0 {
             return Err(InitError::InvalidPopSize(pop_size));
         }
-        let dim = bounds.dim();
-        let mut positions = Vec::with_capacity(pop_size);
+        let mut positions = Population::zeros(pop_size, bounds.dim());
 
-        for _ in 0..pop_size {
-            let mut x = bounds.gen_random_vec(rng);
-            debug_assert_eq!(x.len(), dim);
-            bounds.project_slice(&mut x);
-            positions.push(x);
+        for x in &mut positions {
+            bounds.fill_random(x, rng);
+            bounds.project_slice(x);
         }
         Ok(positions)
     }
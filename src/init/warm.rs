@@ -0,0 +1,129 @@
+use rand::Rng;
+
+use crate::{
+    core::{Bounds, Direction, Population, Report},
+    init::{InitError, Initializer},
+};
+
+/// Initial population taken from the report of a previous run, to chain refinement runs
+/// with different settings (see [`crate::hho::HhoBuilder::warm_start`]).
+///
+/// The final population of the report, if it was recorded (see
+/// [`crate::hho::HhoBuilder::keep_population`]), fills the new population best first. The
+/// remaining hawks, or all but the first when no population was recorded, are copies of the
+/// best position moved by up to `jitter` times the span of each dimension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmStart {
+    /// Posições herdadas, da melhor para a pior
+    seeds: Population,
+    jitter: f64,
+}
+
+impl WarmStart {
+    /// Warm start from `report`, with a jitter of `0.01`.
+    #[must_use]
+    pub fn from_report(report: &Report) -> Self {
+        let mut ranked: Vec<_> = report.population.iter().collect();
+        ranked.sort_by(|a, b| match report.direction {
+            Direction::Minimize => a.fitness.total_cmp(&b.fitness),
+            Direction::Maximize => b.fitness.total_cmp(&a.fitness),
+        });
+        let rows: Vec<&[f64]> = if ranked.is_empty() {
+            vec![&report.best_position]
+        } else {
+            ranked.iter().map(|s| s.position.as_slice()).collect()
+        };
+        Self {
+            seeds: Population::from_rows(&rows).unwrap_or_default(),
+            jitter: 0.01,
+        }
+    }
+
+    /// Largest move of the jittered copies, as a fraction of the span of each dimension.
+    #[must_use]
+    pub const fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+}
+
+impl Initializer for WarmStart {
+    fn initialize<R: Rng>(
+        &self,
+        pop_size: usize,
+        bounds: &Bounds,
+        rng: &mut R,
+    ) -> Result<Population, InitError> {
+        if pop_size == 0 {
+            return Err(InitError::InvalidPopSize(pop_size));
+        }
+        let Some(best) = self.seeds.get(0).filter(|x| x.len() == bounds.dim()) else {
+            return Err(InitError::Dimension {
+                expected: bounds.dim(),
+                received: self.seeds.dim(),
+            });
+        };
+        let mut positions = Population::zeros(pop_size, bounds.dim());
+        for (i, x) in positions.iter_mut().enumerate() {
+            if let Some(seed) = self.seeds.get(i) {
+                x.copy_from_slice(seed);
+            } else {
+                x.copy_from_slice(best);
+                for (j, v) in x.iter_mut().enumerate() {
+                    *v += self.jitter * bounds.span_at(j) * rng.random_range(-1.0..=1.0);
+                }
+            }
+            bounds.project_slice(x);
+        }
+        Ok(positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::core::Solution;
+
+    #[test]
+    fn inherited_population_comes_first_best_first() {
+        let bounds = Bounds::uniform(-1.0, 1.0, 2).unwrap();
+        let mut report = Report::empty();
+        report.best_position = vec![0.5, 0.5];
+        report.population = vec![
+            Solution {
+                position: vec![0.9, 0.9],
+                fitness: 2.0,
+            },
+            Solution {
+                position: vec![0.5, 0.5],
+                fitness: 1.0,
+            },
+        ];
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let warm = WarmStart::from_report(&report).jitter(0.1);
+
+        let positions = warm.initialize(4, &bounds, &mut rng).unwrap();
+        assert_eq!(&positions[0], [0.5, 0.5]);
+        assert_eq!(&positions[1], [0.9, 0.9]);
+        assert!(positions[3].iter().all(|v| (v - 0.5).abs() <= 0.2));
+
+        report.population.clear();
+        let positions = WarmStart::from_report(&report)
+            .initialize(3, &bounds, &mut rng)
+            .unwrap();
+        assert_eq!(&positions[0], [0.5, 0.5]);
+        assert_ne!(&positions[1], [0.5, 0.5]);
+
+        let wrong = Bounds::uniform(-1.0, 1.0, 3).unwrap();
+        assert!(matches!(
+            warm.initialize(4, &wrong, &mut rng),
+            Err(InitError::Dimension {
+                expected: 3,
+                received: 2
+            })
+        ));
+    }
+}
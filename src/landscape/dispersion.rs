@@ -0,0 +1,89 @@
+use crate::{
+    core::{Bounds, Direction, Norm, Normalized, Population, Solution},
+    metrics::mean_pairwise_distance_with,
+};
+
+/// Dispersion of the best points of a sample against the whole sample.
+///
+/// Distances are taken on coordinates scaled to the unit box and divided by `√dim`, so both
+/// values lie in `[0, 1]` whatever the bounds. When the best points are much closer to each
+/// other than the sample as a whole ([`Dispersion::excess`] clearly negative) the good
+/// region is a single funnel; when they are about as spread out, good solutions are
+/// scattered over several distant basins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dispersion {
+    /// Mean pairwise distance among the best points.
+    pub top: f64,
+    /// Mean pairwise distance among all the points.
+    pub all: f64,
+}
+
+impl Dispersion {
+    /// `top - all`.
+    #[must_use]
+    pub fn excess(&self) -> f64 {
+        self.top - self.all
+    }
+}
+
+/// Dispersion of the best `fraction` of `points` (at least two) under `direction`, inside
+/// `bounds`. `None` with fewer than two points.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn dispersion(
+    points: &[Solution],
+    direction: Direction,
+    fraction: f64,
+    bounds: &Bounds,
+) -> Option<Dispersion> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut ranked: Vec<&Solution> = points.iter().collect();
+    ranked.sort_by(|a, b| match direction {
+        Direction::Minimize => a.fitness.total_cmp(&b.fitness),
+        Direction::Maximize => b.fitness.total_cmp(&a.fitness),
+    });
+    let k = ((fraction * points.len() as f64).ceil() as usize).clamp(2, points.len());
+    let rows: Vec<&[f64]> = ranked.iter().map(|s| s.position.as_slice()).collect();
+    let distance = Normalized::new(Norm::Euclidean, bounds);
+    let scale = (bounds.dim().max(1) as f64).sqrt().recip();
+    let spread = |rows: &[&[f64]]| {
+        Population::from_rows(rows).map(|p| mean_pairwise_distance_with(&p, &distance) * scale)
+    };
+    Some(Dispersion {
+        top: spread(&rows[..k])?,
+        all: spread(&rows)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_single_funnel_concentrates_the_best_points() {
+        let bounds = Bounds::uniform(-10.0, 10.0, 1).unwrap();
+        let points: Vec<Solution> = (0..21)
+            .map(|k| {
+                let x = f64::from(k) - 10.0;
+                Solution {
+                    position: vec![x],
+                    fitness: x * x,
+                }
+            })
+            .collect();
+        let d = dispersion(&points, Direction::Minimize, 0.2, &bounds).unwrap();
+        assert!(d.excess() < -0.2, "{d:?}");
+
+        // Maximizando, os melhores estão nas duas pontas
+        let d = dispersion(&points, Direction::Maximize, 0.2, &bounds).unwrap();
+        assert!(d.excess() > 0.2, "{d:?}");
+        assert!(dispersion(&points[..1], Direction::Minimize, 0.2, &bounds).is_none());
+    }
+}
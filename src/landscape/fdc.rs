@@ -0,0 +1,71 @@
+use crate::core::{Distance, Norm, Solution, utils};
+
+/// Fitness-distance correlation of `points` around `best`, with the Euclidean distance.
+///
+/// The Pearson correlation between the fitness of each point and its distance to `best`.
+/// In minimization a value near `1` means the fitness keeps improving on the way to `best`
+/// (an easy, funnel-like landscape), near `0` that distance says little, and a negative
+/// value that the landscape leads away from it (deceptive); maximization flips the sign.
+/// `None` with fewer than two points or when either quantity is constant.
+#[must_use]
+pub fn fitness_distance_correlation(points: &[Solution], best: &[f64]) -> Option<f64> {
+    fitness_distance_correlation_with(points, best, &Norm::Euclidean)
+}
+
+/// [`fitness_distance_correlation`] with any [`Distance`], e.g.
+/// [`crate::core::distance::Normalized`] on ranges of very different widths.
+#[must_use]
+pub fn fitness_distance_correlation_with(
+    points: &[Solution],
+    best: &[f64],
+    distance: &dyn Distance,
+) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let fitness: Vec<f64> = points.iter().map(|s| s.fitness).collect();
+    let dist: Vec<f64> = points
+        .iter()
+        .map(|s| distance.distance(&s.position, best))
+        .collect();
+    let (mf, md) = (utils::mean(&fitness)?, utils::mean(&dist)?);
+    let (mut cov, mut vf, mut vd) = (0.0, 0.0, 0.0);
+    for (f, d) in fitness.iter().zip(&dist) {
+        let (a, b) = (f - mf, d - md);
+        cov = a.mul_add(b, cov);
+        vf = a.mul_add(a, vf);
+        vd = b.mul_add(b, vd);
+    }
+    (vf > 0.0 && vd > 0.0).then(|| cov / (vf * vd).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_funnel_correlates_fitness_with_distance() {
+        let points: Vec<Solution> = (0..20)
+            .map(|k| {
+                let x = f64::from(k).mul_add(0.5, -5.0);
+                Solution {
+                    position: vec![x, 0.0],
+                    fitness: x.abs(),
+                }
+            })
+            .collect();
+        let fdc = fitness_distance_correlation(&points, &[0.0, 0.0]).unwrap();
+        assert!((fdc - 1.0).abs() < 1e-12, "fdc = {fdc}");
+
+        let deceptive: Vec<Solution> = points
+            .iter()
+            .map(|s| Solution {
+                fitness: -s.fitness,
+                ..s.clone()
+            })
+            .collect();
+        let fdc = fitness_distance_correlation(&deceptive, &[0.0, 0.0]).unwrap();
+        assert!((fdc + 1.0).abs() < 1e-12, "fdc = {fdc}");
+        assert!(fitness_distance_correlation(&points[..1], &[0.0, 0.0]).is_none());
+    }
+}
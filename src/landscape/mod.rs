@@ -0,0 +1,14 @@
+//! Fitness landscape analysis: how rugged, deceptive or funnelled a problem looks, to choose
+//! a configuration per problem class instead of tuning blind.
+//!
+//! [`random_walk`] and [`Ruggedness`] sample the problem directly;
+//! [`fitness_distance_correlation`] and [`dispersion`] work on points already evaluated, e.g.
+//! [`crate::core::Report::hall_of_fame`] or [`crate::core::Report::population`].
+
+pub mod dispersion;
+pub mod fdc;
+pub mod walk;
+
+pub use dispersion::{Dispersion, dispersion};
+pub use fdc::{fitness_distance_correlation, fitness_distance_correlation_with};
+pub use walk::{Ruggedness, autocorrelation, random_walk};
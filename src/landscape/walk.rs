@@ -0,0 +1,115 @@
+use rand::Rng;
+
+use crate::core::{Bounds, Decoder, DecoderError, utils};
+
+/// Fitness along a random walk of `steps` steps from a uniform random start.
+///
+/// Each step moves every coordinate by up to `step` times its span, then projects into
+/// `bounds`. Returns `steps + 1` values, the start included.
+///
+/// # Errors
+///
+/// Returns the first error of the decoder.
+pub fn random_walk<R: Rng>(
+    decoder: &dyn Decoder,
+    bounds: &Bounds,
+    steps: usize,
+    step: f64,
+    rng: &mut R,
+) -> Result<Vec<f64>, DecoderError> {
+    let mut x = bounds.gen_random_vec(rng);
+    bounds.project_slice(&mut x);
+    let mut series = Vec::with_capacity(steps + 1);
+    series.push(decoder.decode(&x)?);
+    for _ in 0..steps {
+        for (j, v) in x.iter_mut().enumerate() {
+            *v += step * bounds.span_at(j) * rng.random_range(-1.0..=1.0);
+        }
+        bounds.project_slice(&mut x);
+        series.push(decoder.decode(&x)?);
+    }
+    Ok(series)
+}
+
+/// Autocorrelation of `series` at `lag`, or `None` if it is too short or constant.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn autocorrelation(series: &[f64], lag: usize) -> Option<f64> {
+    if series.len() <= lag {
+        return None;
+    }
+    let mean = utils::mean(series)?;
+    let var: f64 = series.iter().map(|f| (f - mean).powi(2)).sum();
+    if var <= 0.0 {
+        return None;
+    }
+    let cov: f64 = series
+        .iter()
+        .zip(&series[lag..])
+        .map(|(a, b)| (a - mean) * (b - mean))
+        .sum();
+    Some(cov / var)
+}
+
+/// Ruggedness of a landscape estimated from the fitness of a random walk.
+///
+/// A smooth landscape changes little from one step to the next, so its lag-1
+/// autocorrelation is close to `1` and its correlation length is long; on a rugged one
+/// neighbouring values are nearly unrelated and both drop towards `0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ruggedness {
+    /// Lag-1 autocorrelation of the walk.
+    pub autocorrelation: f64,
+    /// `-1 / ln |ρ(1)|`, in steps: how far the walk goes before values stop being related.
+    pub correlation_length: f64,
+}
+
+impl Ruggedness {
+    /// Ruggedness of a walk such as one from [`random_walk`], or `None` if it has fewer than
+    /// two values or is constant.
+    #[must_use]
+    pub fn of(series: &[f64]) -> Option<Self> {
+        let r = autocorrelation(series, 1)?;
+        Some(Self {
+            autocorrelation: r,
+            correlation_length: -1.0 / r.abs().ln(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::benchmarks::Function::Sphere;
+
+    // Ruído determinístico: valores vizinhos não têm relação
+    struct Noise;
+
+    impl Decoder for Noise {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(((solution.iter().sum::<f64>() * 1e4).sin() * 43_758.545).fract())
+        }
+    }
+
+    #[test]
+    fn smooth_landscapes_correlate_and_rugged_ones_do_not() {
+        let bounds = Bounds::uniform(-5.0, 5.0, 3).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(2);
+
+        let smooth = random_walk(&Sphere, &bounds, 500, 0.01, &mut rng).unwrap();
+        assert_eq!(smooth.len(), 501);
+        let smooth = Ruggedness::of(&smooth).unwrap();
+        assert!(smooth.autocorrelation > 0.9, "{smooth:?}");
+
+        let rugged = random_walk(&Noise, &bounds, 500, 0.01, &mut rng).unwrap();
+        let rugged = Ruggedness::of(&rugged).unwrap();
+        assert!(rugged.autocorrelation.abs() < 0.2, "{rugged:?}");
+        assert!(rugged.correlation_length < smooth.correlation_length);
+
+        assert!(autocorrelation(&[1.0; 10], 1).is_none());
+    }
+}
@@ -5,5 +5,23 @@
     clippy::nursery,
     clippy::cargo
 )]
+#![cfg_attr(feature = "forbid-unsafe", forbid(unsafe_code))]
+#[cfg(feature = "baselines")]
+pub mod baselines;
+pub mod benchmarks;
+pub mod constraints;
 pub mod core;
+pub mod decoders;
+pub mod experiments;
+pub mod hho;
 pub mod init;
+pub mod landscape;
+pub mod local_search;
+pub mod metrics;
+pub mod observer;
+pub mod perf;
+pub mod termination;
+#[cfg(feature = "testing")]
+pub mod test_support;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,180 @@
+use crate::{
+    core::{Bounds, Decoder, DecoderError, Objective},
+    local_search::Refined,
+};
+
+/// Chaotic local search: logistic-map perturbations around a point with a shrinking radius.
+///
+/// Each trial moves every coordinate `j` of the current point to
+/// `x_j + r · span_j · (2 z_j - 1)`, where `z_j` follows the logistic map
+/// `z ← mu · z · (1 - z)` and `r` shrinks from `radius` to 0; a trial that improves becomes
+/// the current point. With `mu = 4` the map is chaotic and visits `(0, 1)` densely without
+/// repeating, so the trials cover the box around the point more evenly than random ones.
+///
+/// [`ChaoticSearch::refine`] polishes one solution; [`super::LocalRefiner::Chaotic`] spends
+/// `evals` trials around the rabbit every iteration, the radius shrinking with the progress
+/// of the run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChaoticSearch {
+    /// Trials per HHO iteration, at least 1.
+    pub evals: usize,
+    /// Initial radius as a fraction of each range, in `(0, 1]`.
+    pub radius: f64,
+    /// Parameter of the logistic map, in `(3.57, 4]` to stay chaotic.
+    pub mu: f64,
+}
+
+impl Default for ChaoticSearch {
+    fn default() -> Self {
+        Self {
+            evals: 5,
+            radius: 0.1,
+            mu: 4.0,
+        }
+    }
+}
+
+impl ChaoticSearch {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self { evals, radius, mu } = *self;
+        (evals == 0
+            || radius.is_nan()
+            || radius <= 0.0
+            || radius > 1.0
+            || mu.is_nan()
+            || mu <= 3.57
+            || mu > 4.0)
+            .then(|| {
+                format!(
+                    "chaotic search needs evals = {evals} >= 1, radius = {radius} in (0, 1] and \
+                     mu = {mu} in (3.57, 4]"
+                )
+            })
+    }
+
+    /// Starting values of the map for `dim` dimensions, spread over `(0, 1)` and away from
+    /// its fixed and periodic points.
+    #[must_use]
+    pub fn seed_map(dim: usize) -> Vec<f64> {
+        // Sequência de Weyl pelo número áureo, longe de 0, 1/4, 1/2, 3/4 e 1
+        #[allow(clippy::cast_precision_loss)]
+        (0..dim)
+            .map(|j| {
+                ((j + 1) as f64 * 0.618_033_988_749_895)
+                    .fract()
+                    .mul_add(0.9, 0.05)
+            })
+            .collect()
+    }
+
+    /// One trial around `center` with radius `radius` (fraction of each range), advancing the
+    /// map `z`; the trial is written to `out`, projected into `bounds`.
+    pub fn perturb(
+        &self,
+        bounds: &Bounds,
+        center: &[f64],
+        radius: f64,
+        z: &mut [f64],
+        out: &mut [f64],
+    ) {
+        for (j, ((o, zj), c)) in out.iter_mut().zip(z.iter_mut()).zip(center).enumerate() {
+            *zj = self.mu * *zj * (1.0 - *zj);
+            // Um ponto fixo numérico prenderia a sequência; recomeça longe dele
+            if !(*zj > 1e-9 && *zj < 1.0 - 1e-9) {
+                *zj = 0.5f64.mul_add(*zj, 0.123_456_789);
+            }
+            *o = (radius * bounds.span_at(j)).mul_add(2.0f64.mul_add(*zj, -1.0), *c);
+        }
+        bounds.project_slice(out);
+    }
+
+    /// Refines `start`, whose fitness is `fitness`, within `bounds` using exactly `max_evals`
+    /// decoder calls, the radius shrinking linearly from `radius` to 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the decoder.
+    pub fn refine<O: Objective>(
+        &self,
+        decoder: &dyn Decoder,
+        bounds: &Bounds,
+        start: &[f64],
+        fitness: f64,
+        max_evals: usize,
+    ) -> Result<Refined, DecoderError> {
+        let mut x = start.to_vec();
+        let mut f = fitness;
+        let mut chaos = Self::seed_map(x.len());
+        let mut trial = x.clone();
+        for done in 0..max_evals {
+            #[allow(clippy::cast_precision_loss)]
+            let radius = self.radius * (1.0 - done as f64 / max_evals as f64);
+            self.perturb(bounds, &x, radius, &mut chaos, &mut trial);
+            let g = decoder.decode(&trial)?;
+            if O::better(g, f) {
+                x.copy_from_slice(&trial);
+                f = g;
+            }
+        }
+        Ok(Refined {
+            position: x,
+            fitness: f,
+            evals: max_evals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    struct Shifted;
+
+    impl Decoder for Shifted {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(solution.iter().map(|x| (x - 0.3).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn shrinking_chaotic_trials_close_in_on_the_minimum() {
+        let bounds = Bounds::uniform(-5.0, 5.0, 4).unwrap();
+        let start = vec![1.0; 4];
+        let f0 = Shifted.decode(&start).unwrap();
+        let search = ChaoticSearch {
+            radius: 0.2,
+            ..ChaoticSearch::default()
+        };
+        let refined = search
+            .refine::<Minimization>(&Shifted, &bounds, &start, f0, 3_000)
+            .unwrap();
+        assert_eq!(refined.evals, 3_000);
+        assert!(refined.fitness < 1e-3 * f0, "f = {}", refined.fitness);
+        assert!(
+            search
+                .refine::<Minimization>(&Shifted, &bounds, &start, f0, 3_000)
+                .unwrap()
+                .position
+                == refined.position
+        );
+
+        // A sequência fica em (0, 1) e não se repete
+        let mut z = ChaoticSearch::seed_map(4);
+        let mut seen = Vec::new();
+        let mut out = vec![0.0; 4];
+        for _ in 0..200 {
+            search.perturb(&bounds, &start, 0.1, &mut z, &mut out);
+            assert!(z.iter().all(|&v| v > 0.0 && v < 1.0));
+            assert!(out.iter().all(|&v| (v - 1.0).abs() <= 1.0 + 1e-12));
+            seen.push(z[0].to_bits());
+        }
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 200);
+        assert!(search.problem().is_none());
+        assert!(ChaoticSearch { mu: 3.0, ..search }.problem().is_some());
+    }
+}
@@ -0,0 +1,120 @@
+//! Derivative-free local refiners that polish a single solution, e.g. the best one of a run.
+
+pub mod chaotic;
+pub mod pattern;
+pub mod schedule;
+
+pub use chaotic::ChaoticSearch;
+pub use pattern::{PatternSearch, Refined};
+pub use schedule::RefinerSchedule;
+
+use crate::core::{Bounds, Decoder, DecoderError, Objective};
+
+/// Local refiner run around the rabbit in the HHO main loop, see
+/// [`crate::hho::HhoBuilder::local_refiner`]; a [`RefinerSchedule`] caps its share of the
+/// evaluations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LocalRefiner {
+    /// One compass sweep (at most `2·dim` trials) per iteration; the steps carry over between
+    /// iterations and start again from [`PatternSearch::initial_step`] once they are spent.
+    Pattern(PatternSearch),
+    /// [`ChaoticSearch::evals`] trials per iteration, the radius shrinking with the progress
+    /// of the run.
+    Chaotic(ChaoticSearch),
+}
+
+impl LocalRefiner {
+    /// Name used in configurations and reports: `"pattern"` or `"chaotic"`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pattern(_) => "pattern",
+            Self::Chaotic(_) => "chaotic",
+        }
+    }
+
+    /// Refiner named `name` (case-insensitive) with its default parameters, see
+    /// [`LocalRefiner::name`].
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Pattern(PatternSearch::default()),
+            Self::Chaotic(ChaoticSearch::default()),
+        ]
+        .into_iter()
+        .find(|r| r.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Most decoder calls one iteration of the main loop spends on `dim` dimensions.
+    #[must_use]
+    pub const fn evals_per_iter(&self, dim: usize) -> usize {
+        match self {
+            Self::Pattern(_) => 2 * dim,
+            Self::Chaotic(search) => search.evals,
+        }
+    }
+
+    /// Refines `start`, whose fitness is `fitness`, within `bounds` using at most
+    /// `max_evals` decoder calls, with [`PatternSearch::refine`] or
+    /// [`ChaoticSearch::refine`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the decoder.
+    pub fn refine<O: Objective>(
+        &self,
+        decoder: &dyn Decoder,
+        bounds: &Bounds,
+        start: &[f64],
+        fitness: f64,
+        max_evals: usize,
+    ) -> Result<Refined, DecoderError> {
+        match self {
+            Self::Pattern(search) => search.refine::<O>(decoder, bounds, start, fitness, max_evals),
+            Self::Chaotic(search) => search.refine::<O>(decoder, bounds, start, fitness, max_evals),
+        }
+    }
+
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        match self {
+            Self::Pattern(search) => search.problem(),
+            Self::Chaotic(search) => search.problem(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function, core::Minimization};
+
+    #[test]
+    fn refiners_are_selected_by_name() {
+        assert_eq!(
+            LocalRefiner::from_name("Pattern"),
+            Some(LocalRefiner::Pattern(PatternSearch::default()))
+        );
+        assert_eq!(
+            LocalRefiner::from_name("chaotic"),
+            Some(LocalRefiner::Chaotic(ChaoticSearch::default()))
+        );
+        assert_eq!(LocalRefiner::from_name("nelder-mead"), None);
+        let pattern = LocalRefiner::from_name("pattern").unwrap();
+        assert_eq!(pattern.evals_per_iter(7), 14);
+
+        let bounds = Bounds::uniform(-5.0, 5.0, 3).unwrap();
+        let start = vec![2.0; 3];
+        let f0 = Function::Sphere.decode(&start).unwrap();
+        for name in ["pattern", "chaotic"] {
+            let refiner = LocalRefiner::from_name(name).unwrap();
+            assert_eq!(refiner.name(), name);
+            let polished = refiner
+                .refine::<Minimization>(&Function::Sphere, &bounds, &start, f0, 200)
+                .unwrap();
+            assert!(polished.fitness < f0, "{}", refiner.name());
+            assert!(refiner.problem().is_none());
+        }
+    }
+}
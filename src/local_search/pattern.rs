@@ -0,0 +1,205 @@
+use crate::core::{Bounds, Decoder, DecoderError, Objective};
+
+/// Compass (pattern) search: coordinate descent with a shrinking step.
+///
+/// Each sweep tries `x ± step_j` along every dimension `j` in turn and keeps the first move
+/// that improves; a sweep without improvement multiplies every step by `shrink`. Steps start
+/// at `initial_step` times the span of each dimension and the search stops once they fall
+/// below `min_step` times the span, or when the evaluation budget runs out. It needs only
+/// `2·dim` evaluations per sweep and no simplex, which suits high dimensions, and it moves
+/// only on strict improvements, which keeps it from chasing small noise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PatternSearch {
+    pub initial_step: f64,
+    /// Step multiplier after a sweep without improvement, in `(0, 1)`.
+    pub shrink: f64,
+    pub min_step: f64,
+}
+
+impl Default for PatternSearch {
+    fn default() -> Self {
+        Self {
+            initial_step: 0.1,
+            shrink: 0.5,
+            min_step: 1e-8,
+        }
+    }
+}
+
+/// Outcome of a local refinement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Refined {
+    pub position: Vec<f64>,
+    pub fitness: f64,
+    /// Decoder calls spent.
+    pub evals: usize,
+}
+
+impl PatternSearch {
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self {
+            initial_step,
+            shrink,
+            min_step,
+        } = *self;
+        (initial_step.is_nan()
+            || initial_step <= 0.0
+            || initial_step > 1.0
+            || shrink.is_nan()
+            || shrink <= 0.0
+            || shrink >= 1.0
+            || min_step.is_nan()
+            || min_step <= 0.0
+            || min_step >= initial_step)
+            .then(|| {
+                format!(
+                    "pattern search needs initial_step = {initial_step} in (0, 1], shrink = \
+                     {shrink} in (0, 1) and min_step = {min_step} in (0, initial_step)"
+                )
+            })
+    }
+
+    /// Starting steps, [`PatternSearch::initial_step`] times the span of each dimension.
+    #[must_use]
+    pub fn initial_steps(&self, bounds: &Bounds) -> Vec<f64> {
+        (0..bounds.dim())
+            .map(|j| self.initial_step * bounds.span_at(j))
+            .collect()
+    }
+
+    /// Whether every step fell below [`PatternSearch::min_step`] times its span.
+    #[must_use]
+    pub fn exhausted(&self, bounds: &Bounds, steps: &[f64]) -> bool {
+        steps
+            .iter()
+            .enumerate()
+            .all(|(j, s)| *s <= self.min_step * bounds.span_at(j))
+    }
+
+    /// One sweep over every dimension from `x`, whose fitness is `f`, with at most
+    /// `max_evals` trials; an improving trial moves `x` and `f`, and a sweep without one
+    /// shrinks `steps`. Stops early when `evaluate` returns `None`. Returns the trials
+    /// evaluated.
+    pub fn sweep<O: Objective>(
+        &self,
+        bounds: &Bounds,
+        x: &mut [f64],
+        f: &mut f64,
+        steps: &mut [f64],
+        max_evals: usize,
+        mut evaluate: impl FnMut(&[f64]) -> Option<f64>,
+    ) -> usize {
+        let mut evals = 0;
+        let mut improved = false;
+        let mut y = x.to_vec();
+        'dims: for j in 0..x.len() {
+            for sign in [1.0_f64, -1.0] {
+                if evals == max_evals {
+                    break 'dims;
+                }
+                y.copy_from_slice(x);
+                y[j] = sign.mul_add(steps[j], x[j]);
+                bounds.project_slice(&mut y);
+                // Um passo cortado pelos limites pode não sair do lugar
+                if (y[j] - x[j]).abs() <= f64::EPSILON * x[j].abs() {
+                    continue;
+                }
+                let Some(g) = evaluate(&y) else {
+                    return evals;
+                };
+                evals += 1;
+                if O::better(g, *f) {
+                    x.copy_from_slice(&y);
+                    *f = g;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved && evals < max_evals {
+            for s in steps {
+                *s *= self.shrink;
+            }
+        }
+        evals
+    }
+
+    /// Refines `start`, whose fitness is `fitness`, within `bounds` using at most
+    /// `max_evals` decoder calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the decoder.
+    pub fn refine<O: Objective>(
+        &self,
+        decoder: &dyn Decoder,
+        bounds: &Bounds,
+        start: &[f64],
+        fitness: f64,
+        max_evals: usize,
+    ) -> Result<Refined, DecoderError> {
+        let mut x = start.to_vec();
+        let mut f = fitness;
+        let mut steps = self.initial_steps(bounds);
+        let mut evals = 0;
+        let mut error = None;
+        while evals < max_evals && !self.exhausted(bounds, &steps) {
+            evals += self.sweep::<O>(bounds, &mut x, &mut f, &mut steps, max_evals - evals, |y| {
+                decoder.decode(y).map_err(|e| error = Some(e)).ok()
+            });
+            if let Some(e) = error {
+                return Err(e);
+            }
+        }
+        Ok(Refined {
+            position: x,
+            fitness: f,
+            evals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Minimization;
+
+    struct Shifted;
+
+    impl Decoder for Shifted {
+        fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+            Ok(solution.iter().map(|x| (x - 0.3).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn converges_on_a_separable_bowl_within_budget() {
+        let bounds = Bounds::uniform(-5.0, 5.0, 20).unwrap();
+        let start = vec![4.0; 20];
+        let f0 = Shifted.decode(&start).unwrap();
+        let search = PatternSearch::default();
+
+        let refined = search
+            .refine::<Minimization>(&Shifted, &bounds, &start, f0, 20_000)
+            .unwrap();
+        assert!(refined.fitness < 1e-10, "f = {}", refined.fitness);
+        assert!(refined.evals <= 20_000);
+
+        let short = search
+            .refine::<Minimization>(&Shifted, &bounds, &start, f0, 15)
+            .unwrap();
+        assert_eq!(short.evals, 15);
+        assert!(short.fitness < f0);
+        assert!(search.problem().is_none());
+        assert!(
+            PatternSearch {
+                shrink: 1.0,
+                ..search
+            }
+            .problem()
+            .is_some()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+/// Share of the evaluations of each HHO iteration given to the local refiner, tapered over
+/// the run, see [`crate::hho::HhoBuilder::refiner_schedule`].
+///
+/// At progress `p` the refiner may spend up to a fraction
+/// `s(p) = share · (1 + taper · (1 - 2p))` of the evaluations of an iteration, so that it
+/// gets `share` of the run on average: `taper = 0` spreads it evenly, `taper = 1` starts at
+/// `2·share` and fades to 0, and `taper = -1` starts at 0 and ends at `2·share`, polishing
+/// late. The evaluations the rest of an iteration spends earn the refiner a credit;
+/// fractions carry over, so a small share still refines every few iterations, and the
+/// refiner never spends more than its own per-iteration size in one go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefinerSchedule {
+    /// Mean share of the evaluations, with `share · (1 + |taper|)` in `(0, 1)`.
+    pub share: f64,
+    /// Slope of the share over the run, in `[-1, 1]`.
+    pub taper: f64,
+}
+
+impl Default for RefinerSchedule {
+    fn default() -> Self {
+        Self {
+            share: 0.1,
+            taper: 0.0,
+        }
+    }
+}
+
+impl RefinerSchedule {
+    /// Share `s(p)` of the evaluations at progress `p` in `[0, 1]`.
+    #[must_use]
+    pub fn share_at(&self, progress: f64) -> f64 {
+        let p = progress.clamp(0.0, 1.0);
+        self.share * self.taper.mul_add(2.0f64.mul_add(-p, 1.0), 1.0)
+    }
+
+    /// Credit earned by `spent` evaluations of the rest of an iteration at progress `p`: the
+    /// refiner evaluations that keep its share at [`RefinerSchedule::share_at`].
+    #[must_use]
+    pub fn credit(&self, spent: usize, progress: f64) -> f64 {
+        let s = self.share_at(progress);
+        #[allow(clippy::cast_precision_loss)]
+        let spent = spent as f64;
+        spent * s / (1.0 - s)
+    }
+
+    /// Problema nos parâmetros, se houver
+    pub(crate) fn problem(&self) -> Option<String> {
+        let Self { share, taper } = *self;
+        (share.is_nan()
+            || share <= 0.0
+            || taper.is_nan()
+            || !(-1.0..=1.0).contains(&taper)
+            || share * (1.0 + taper.abs()) >= 1.0)
+            .then(|| {
+                format!(
+                    "refiner schedule needs share = {share} > 0, taper = {taper} in [-1, 1] and \
+                     share * (1 + |taper|) < 1"
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_taper_around_their_mean() {
+        let even = RefinerSchedule::default();
+        assert!((even.share_at(0.0) - 0.1).abs() < 1e-15);
+        assert!((even.share_at(1.0) - 0.1).abs() < 1e-15);
+        // 90 avaliações globais rendem 10 do refinador: 10% do total
+        assert!((even.credit(90, 0.5) - 10.0).abs() < 1e-12);
+
+        let fading = RefinerSchedule {
+            share: 0.2,
+            taper: 1.0,
+        };
+        assert!((fading.share_at(0.0) - 0.4).abs() < 1e-15);
+        assert!((fading.share_at(0.5) - 0.2).abs() < 1e-15);
+        assert!(fading.share_at(1.0).abs() < 1e-15);
+        assert!(fading.credit(100, 1.0).abs() < 1e-12);
+        let late = RefinerSchedule {
+            taper: -1.0,
+            ..fading
+        };
+        assert!(late.share_at(0.0).abs() < 1e-15);
+        assert!((late.share_at(2.0) - 0.4).abs() < 1e-15);
+
+        assert!(even.problem().is_none() && fading.problem().is_none());
+        assert!(
+            RefinerSchedule {
+                share: 0.6,
+                taper: 1.0
+            }
+            .problem()
+            .is_some()
+        );
+        assert!(RefinerSchedule { taper: 2.0, ..even }.problem().is_some());
+    }
+}
@@ -0,0 +1,139 @@
+//! Distance-based diversity of a population.
+//!
+//! [`centroid_diversity`] costs O(N·D) and is cheap enough to track every iteration.
+//! [`mean_pairwise_distance`] is the classic O(N²·D) measure; it walks the pairs in tiles of
+//! rows that stay in cache and, with the `rayon` feature, spreads the tiles over threads.
+//! Partial sums are added in a fixed order, so the result does not depend on the number of
+//! threads. Both use the Euclidean distance; the `_with` variants take any [`Distance`].
+
+use crate::core::{Distance, Norm, Population};
+
+/// Linhas por bloco do cálculo par a par
+const BLOCK: usize = 64;
+
+/// Writes the mean position of `positions` into `out`.
+pub fn centroid_into(positions: &Population, out: &mut [f64]) {
+    out.fill(0.0);
+    for x in positions {
+        for (o, xi) in out.iter_mut().zip(x) {
+            *o += xi;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = positions.len() as f64;
+    for o in out {
+        *o /= n;
+    }
+}
+
+/// Mean Euclidean distance of the positions to their centroid, in O(N·D).
+#[must_use]
+pub fn centroid_diversity(positions: &Population) -> f64 {
+    centroid_diversity_with(positions, &Norm::Euclidean)
+}
+
+/// Mean `distance` of the positions to their centroid, in O(N·D).
+#[must_use]
+pub fn centroid_diversity_with(positions: &Population, distance: &dyn Distance) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let mut centroid = vec![0.0; positions.dim()];
+    centroid_into(positions, &mut centroid);
+    let total: f64 = positions
+        .iter()
+        .map(|x| distance.distance(x, &centroid))
+        .sum();
+    #[allow(clippy::cast_precision_loss)]
+    let n = positions.len() as f64;
+    total / n
+}
+
+/// Mean Euclidean distance over all pairs of positions (0 with fewer than two).
+#[must_use]
+pub fn mean_pairwise_distance(positions: &Population) -> f64 {
+    mean_pairwise_distance_with(positions, &Norm::Euclidean)
+}
+
+/// Mean `distance` over all pairs of positions (0 with fewer than two).
+#[must_use]
+pub fn mean_pairwise_distance_with(positions: &Population, distance: &dyn Distance) -> f64 {
+    let n = positions.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let sums = block_sums(positions, distance, n.div_ceil(BLOCK));
+    #[allow(clippy::cast_precision_loss)]
+    let pairs = (n * (n - 1) / 2) as f64;
+    sums.iter().sum::<f64>() / pairs
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        /// Soma de cada faixa de blocos, calculadas em paralelo
+        fn block_sums(positions: &Population, distance: &dyn Distance, blocks: usize) -> Vec<f64> {
+            use rayon::prelude::*;
+
+            (0..blocks)
+                .into_par_iter()
+                .map(|b| block_row_sum(positions, distance, b))
+                .collect()
+        }
+    } else {
+        /// Soma de cada faixa de blocos
+        fn block_sums(positions: &Population, distance: &dyn Distance, blocks: usize) -> Vec<f64> {
+            (0..blocks).map(|b| block_row_sum(positions, distance, b)).collect()
+        }
+    }
+}
+
+/// Soma das distâncias entre as linhas do bloco `b` e as de todos os blocos a partir dele
+fn block_row_sum(positions: &Population, distance: &dyn Distance, b: usize) -> f64 {
+    let n = positions.len();
+    let rows = b * BLOCK..((b + 1) * BLOCK).min(n);
+    let mut total = 0.0;
+    for start in (rows.start..n).step_by(BLOCK) {
+        let cols = start..(start + BLOCK).min(n);
+        for i in rows.clone() {
+            let xi = &positions[i];
+            // Dentro do bloco diagonal só os pares i < j
+            for j in cols.start.max(i + 1)..cols.end {
+                total += distance.distance(xi, &positions[j]);
+            }
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vecops;
+
+    #[test]
+    fn blocked_pairwise_matches_the_naive_sum() {
+        // Mais linhas que um bloco, e um resto
+        let rows: Vec<Vec<f64>> = (0..150)
+            .map(|i| {
+                let t = f64::from(i);
+                vec![(t * 0.37).sin(), (t * 0.11).cos(), t * 0.01]
+            })
+            .collect();
+        let pop = Population::from_rows(&rows).unwrap();
+
+        let mut naive = 0.0;
+        for i in 0..rows.len() {
+            for j in i + 1..rows.len() {
+                naive += vecops::squared_distance(&rows[i], &rows[j]).sqrt();
+            }
+        }
+        naive /= (150.0 * 149.0) / 2.0;
+        assert!((mean_pairwise_distance(&pop) - naive).abs() < 1e-12);
+
+        let square = Population::from_rows(&[[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]]);
+        let square = square.unwrap();
+        assert!((centroid_diversity(&square) - 2f64.sqrt()).abs() < 1e-12);
+        assert!((centroid_diversity_with(&square, &Norm::Chebyshev) - 1.0).abs() < 1e-12);
+        assert!(mean_pairwise_distance(&Population::from_rows(&[[1.0]]).unwrap()).abs() < 1e-12);
+    }
+}
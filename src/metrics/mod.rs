@@ -0,0 +1,12 @@
+//! Population and solution metrics computed outside the optimizer loop.
+
+pub mod distance;
+pub mod occupancy;
+pub mod sensitivity;
+
+pub use distance::{
+    centroid_diversity, centroid_diversity_with, mean_pairwise_distance,
+    mean_pairwise_distance_with,
+};
+pub use occupancy::{Grid, Occupancy};
+pub use sensitivity::{DimensionSensitivity, Sensitivity, sensitivity};
@@ -0,0 +1,143 @@
+//! Grid-based estimates of how many clusters a population forms.
+//!
+//! Each dimension of the bounds is cut into the same number of cells. The entropy of the share
+//! of hawks per occupied cell tells spread-out populations from crowded ones, and occupied cells
+//! that touch, corners included, are merged into clusters. Cells are hashed, so the cost is
+//! O(N·D) for the entropy and O(N²·D) for the clusters whatever the dimension.
+
+use std::collections::HashMap;
+
+use crate::core::{Bounds, Population};
+
+/// Occupancy of a [`Grid`] by a population.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Occupancy {
+    /// Cells holding at least one hawk.
+    pub cells: usize,
+    /// Groups of occupied cells connected through faces, edges or corners.
+    pub clusters: usize,
+    /// Shannon entropy of the share of hawks per cell over `ln N`: 0 when every hawk shares
+    /// one cell, 1 when each has its own.
+    pub entropy: f64,
+}
+
+/// Regular grid over a box, `cells` per dimension.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid {
+    lo: Vec<f64>,
+    span: Vec<f64>,
+    cells: usize,
+}
+
+impl Grid {
+    /// Cuts each range of `bounds` into `cells` cells (at least 1).
+    #[must_use]
+    pub fn new(bounds: &Bounds, cells: usize) -> Self {
+        Self {
+            lo: bounds.lower().into_owned(),
+            span: (0..bounds.dim()).map(|i| bounds.span_at(i)).collect(),
+            cells: cells.max(1),
+        }
+    }
+
+    /// Cell of `x`; points outside the box go to the nearest border cell.
+    #[must_use]
+    pub fn cell(&self, x: &[f64]) -> Vec<usize> {
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        x.iter()
+            .zip(self.lo.iter().zip(&self.span))
+            .map(|(xi, (lo, span))| {
+                let u = if *span > 0.0 { (xi - lo) / span } else { 0.0 };
+                ((u * self.cells as f64).floor().max(0.0) as usize).min(self.cells - 1)
+            })
+            .collect()
+    }
+
+    /// Occupancy of the grid by `positions`.
+    #[must_use]
+    pub fn occupancy(&self, positions: &Population) -> Occupancy {
+        let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+        for x in positions {
+            *counts.entry(self.cell(x)).or_default() += 1;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let n = positions.len() as f64;
+        let entropy = if positions.len() < 2 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let h: f64 = counts
+                .values()
+                .map(|&c| {
+                    let p = c as f64 / n;
+                    -p * p.ln()
+                })
+                .sum();
+            h / n.ln()
+        };
+        let occupied: Vec<&Vec<usize>> = counts.keys().collect();
+        Occupancy {
+            cells: occupied.len(),
+            clusters: components(&occupied),
+            entropy,
+        }
+    }
+}
+
+/// Componentes conexas das células, vizinhas quando diferem de no máximo 1 em cada índice
+fn components(cells: &[&Vec<usize>]) -> usize {
+    let mut parent: Vec<usize> = (0..cells.len()).collect();
+    let mut count = cells.len();
+    for i in 0..cells.len() {
+        for j in i + 1..cells.len() {
+            let touch = cells[i]
+                .iter()
+                .zip(cells[j])
+                .all(|(a, b)| a.abs_diff(*b) <= 1);
+            if touch {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                if a != b {
+                    parent[a] = b;
+                    count -= 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+/// Raiz de `i` na floresta de união, encurtando o caminho
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separated_groups_count_as_clusters() {
+        let grid = Grid::new(&Bounds::uniform(0.0, 10.0, 2).unwrap(), 10);
+        let crowded = Population::from_rows(&[[5.5, 5.5]; 4]).unwrap();
+        let occupancy = grid.occupancy(&crowded);
+        assert_eq!((occupancy.cells, occupancy.clusters), (1, 1));
+        assert!(occupancy.entropy.abs() < 1e-12);
+
+        // Duas células vizinhas pela diagonal e uma isolada no canto
+        let split =
+            Population::from_rows(&[[1.5, 1.5], [2.5, 2.5], [9.9, 9.9], [12.0, 10.0]]).unwrap();
+        let occupancy = grid.occupancy(&split);
+        assert_eq!((occupancy.cells, occupancy.clusters), (3, 2));
+        assert!(occupancy.entropy > 0.0 && occupancy.entropy < 1.0);
+        assert_eq!(grid.cell(&[-1.0, 10.0]), vec![0, 9]);
+    }
+}
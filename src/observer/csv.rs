@@ -0,0 +1,169 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    core::Report,
+    observer::{IterationContext, Observer},
+};
+
+/// Observer streaming the convergence of a run to CSV as it goes, so partial results
+/// survive a crash and can be plotted while the run is still going.
+///
+/// The header is `iteration,evals,elapsed_ms,best_fitness,mean_fitness` (the columns of
+/// [`Report::write_csv`] plus the time and the mean fitness of the population), followed by
+/// one row per iteration, starting with the initial population at iteration `0`. Non-finite
+/// numbers are left empty. The output is flushed every `flush_every` rows and at the end of
+/// the run.
+#[derive(Debug)]
+pub struct CsvLog<W> {
+    out: W,
+    flush_every: usize,
+    /// Linhas escritas desde a última descarga
+    pending: usize,
+    header: bool,
+    /// Primeiro erro de escrita; depois dele nada mais é escrito
+    error: Option<io::Error>,
+}
+
+impl<W: Write> CsvLog<W> {
+    /// Writes to `out`, flushing after every row.
+    #[must_use]
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            flush_every: 1,
+            pending: 0,
+            header: false,
+            error: None,
+        }
+    }
+
+    /// Flushes every `rows` rows instead of after each one (`0` counts as `1`).
+    #[must_use]
+    pub const fn flush_every(mut self, rows: usize) -> Self {
+        self.flush_every = if rows == 0 { 1 } else { rows };
+        self
+    }
+
+    /// The first write error, if any. Writing stops after an error.
+    #[must_use]
+    pub const fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write(&mut self, ctx: &IterationContext<'_>) -> io::Result<()> {
+        if !self.header {
+            writeln!(
+                self.out,
+                "iteration,evals,elapsed_ms,best_fitness,mean_fitness"
+            )?;
+            self.header = true;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = ctx.fitness.iter().sum::<f64>() / ctx.fitness.len().max(1) as f64;
+        writeln!(
+            self.out,
+            "{},{},{},{},{}",
+            ctx.iter,
+            ctx.evals,
+            ctx.elapsed.as_millis(),
+            Field(ctx.best_fitness),
+            Field(mean)
+        )?;
+        self.pending += 1;
+        if self.pending >= self.flush_every {
+            self.pending = 0;
+            self.out.flush()?;
+        }
+        Ok(())
+    }
+
+    fn log(&mut self, result: impl FnOnce(&mut Self) -> io::Result<()>) {
+        if self.error.is_none()
+            && let Err(e) = result(self)
+        {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl CsvLog<BufWriter<File>> {
+    /// Logs to a new file at `path`, truncating an existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send> Observer for CsvLog<W> {
+    fn on_start(&mut self, ctx: &IterationContext<'_>) {
+        self.log(|s| s.write(ctx));
+    }
+
+    fn on_iteration(&mut self, ctx: &IterationContext<'_>) {
+        self.log(|s| s.write(ctx));
+    }
+
+    fn on_end(&mut self, _report: &Report) {
+        self.log(|s| s.out.flush());
+    }
+}
+
+/// Campo numérico: valores não finitos ficam vazios
+struct Field(f64);
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_finite() {
+            write!(f, "{}", self.0)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn streams_one_row_per_iteration() {
+        let log = Arc::new(Mutex::new(CsvLog::new(Vec::new()).flush_every(4)));
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(8)
+            .max_iters(10)
+            .seed(4)
+            .shared_observer(log.clone())
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+        drop(hho);
+
+        let log = Arc::into_inner(log).unwrap().into_inner().unwrap();
+        assert!(log.error().is_none());
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(
+            lines[0],
+            "iteration,evals,elapsed_ms,best_fitness,mean_fitness"
+        );
+        assert!(lines[1].starts_with("0,8,"));
+        let last: Vec<&str> = lines[11].split(',').collect();
+        assert_eq!(last[0], "10");
+        assert!((last[3].parse::<f64>().unwrap() - report.best_fitness).abs() < 1e-12);
+    }
+}
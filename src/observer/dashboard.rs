@@ -0,0 +1,277 @@
+//! Live terminal dashboard of a run (feature `tui`), fed by [`LiveMetrics`].
+
+use std::{
+    io::{self, Stdout, Write},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    hho::Phase,
+    observer::{LiveReceiver, Snapshot, progress::format_duration},
+};
+
+#[cfg(doc)]
+use crate::observer::LiveMetrics;
+
+/// Níveis das sparklines, do menor ao maior
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Full-screen terminal dashboard of a run: progress and ETA, sparklines of the best fitness,
+/// the diversity and the escape energy, and the phases the hawks took in the last iteration.
+///
+/// It reads the [`Snapshot`]s of a [`LiveMetrics`] channel, so the optimizer never waits on
+/// the terminal, and redraws with plain ANSI escapes, which any terminal understands,
+/// including over SSH:
+///
+/// ```no_run
+/// use kambo_hho::{
+///     benchmarks::Function,
+///     core::Bounds,
+///     hho::HhoBuilder,
+///     observer::{Dashboard, LiveMetrics},
+/// };
+///
+/// let (live, rx) = LiveMetrics::channel(64);
+/// let screen = Dashboard::spawn(rx);
+/// let hho = HhoBuilder::new(Bounds::uniform(-5.12, 5.12, 30).unwrap())
+///     .max_iters(100_000)
+///     .observer(live)
+///     .build();
+/// let report = hho.run(&Function::Rastrigin).unwrap();
+/// screen.join().unwrap().unwrap();
+/// # let _ = report;
+/// ```
+#[derive(Debug)]
+pub struct Dashboard<W> {
+    out: W,
+    /// Largura das sparklines e da barra de progresso
+    width: usize,
+    interval: Duration,
+    /// Histórico das séries, limitado a `width` pontos
+    best: Vec<f64>,
+    diversity: Vec<f64>,
+    energy: Vec<f64>,
+    last: Option<Snapshot>,
+    dropped: usize,
+}
+
+impl Dashboard<Stdout> {
+    /// Dashboard on standard output.
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+
+    /// Draws the snapshots of `rx` on standard output from a new thread until the run ends.
+    #[must_use]
+    pub fn spawn(rx: LiveReceiver) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || Self::stdout().run(&rx))
+    }
+}
+
+impl<W: Write> Dashboard<W> {
+    #[must_use]
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            width: 60,
+            interval: Duration::from_millis(250),
+            best: Vec::new(),
+            diversity: Vec::new(),
+            energy: Vec::new(),
+            last: None,
+            dropped: 0,
+        }
+    }
+
+    /// Sets the width of the sparklines and the progress bar, in characters (at least 1).
+    #[must_use]
+    pub const fn with_width(mut self, width: usize) -> Self {
+        self.width = if width == 0 { 1 } else { width };
+        self
+    }
+
+    /// Sets the minimum time between redraws.
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Adds a snapshot to the series, without drawing.
+    pub fn update(&mut self, snapshot: Snapshot) {
+        for (series, value) in [
+            (&mut self.best, snapshot.best_fitness),
+            (&mut self.diversity, snapshot.diversity),
+            (&mut self.energy, snapshot.energy),
+        ] {
+            if series.len() == self.width {
+                series.remove(0);
+            }
+            series.push(value);
+        }
+        self.last = Some(snapshot);
+    }
+
+    /// Reads `rx` and redraws at most every interval until the run ends, then draws the final
+    /// state once more.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first write error.
+    pub fn run(&mut self, rx: &LiveReceiver) -> io::Result<()> {
+        while let Some(snapshot) = rx.recv() {
+            self.update(snapshot);
+            thread::sleep(self.interval);
+            for snapshot in rx.drain() {
+                self.update(snapshot);
+            }
+            self.dropped = rx.dropped();
+            self.render()?;
+        }
+        self.render()
+    }
+
+    /// Clears the screen and draws the latest snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first write error.
+    pub fn render(&mut self) -> io::Result<()> {
+        let Some(s) = &self.last else {
+            return Ok(());
+        };
+        let p = s.progress.clamp(0.0, 1.0);
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let filled = (p * self.width as f64).round() as usize;
+        let eta = if p > 0.0 {
+            format_duration(s.elapsed.mul_f64((1.0 - p) / p))
+        } else {
+            "?".to_owned()
+        };
+        // Cursor no canto e tela limpa
+        write!(self.out, "\x1b[H\x1b[J")?;
+        writeln!(
+            self.out,
+            "iter {} | evals {} | elapsed {} | ETA {eta}",
+            s.iter,
+            s.evals,
+            format_duration(s.elapsed),
+        )?;
+        writeln!(
+            self.out,
+            "[{}{}] {:>3.0}%\n",
+            "#".repeat(filled),
+            "-".repeat(self.width - filled),
+            p * 100.0,
+        )?;
+        for (label, value, series) in [
+            ("best", s.best_fitness, &self.best),
+            ("diversity", s.diversity, &self.diversity),
+            ("energy", s.energy, &self.energy),
+        ] {
+            writeln!(
+                self.out,
+                "{label:<10} {value:>12.4e}  {}",
+                sparkline(series)
+            )?;
+        }
+        writeln!(self.out, "\nphases (last iteration)")?;
+        let total = s.phases.total().max(1);
+        for phase in Phase::ALL {
+            let count = s.phases.get(phase);
+            if count > 0 {
+                let bar = "█".repeat((count * self.width).div_ceil(total));
+                writeln!(self.out, "  {:<13} {count:>5} {bar}", format!("{phase:?}"))?;
+            }
+        }
+        if s.phases.immigrants > 0 {
+            writeln!(
+                self.out,
+                "  {:<13} {:>5}",
+                "Immigrants", s.phases.immigrants
+            )?;
+        }
+        if self.dropped > 0 {
+            writeln!(self.out, "\n{} snapshots skipped", self.dropped)?;
+        }
+        self.out.flush()
+    }
+}
+
+/// Sparkline de `values` entre o mínimo e o máximo; a escala é logarítmica quando os valores
+/// são positivos e abrangem mais de duas ordens de grandeza, como a melhor fitness costuma
+fn sparkline(values: &[f64]) -> String {
+    let finite = values.iter().copied().filter(|v| v.is_finite());
+    let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    let log = lo > 0.0 && hi / lo > 100.0;
+    let scale = |v: f64| if log { v.ln() } else { v };
+    let (lo, hi) = (scale(lo), scale(hi));
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                return ' ';
+            }
+            let t = if hi > lo {
+                (scale(v) - lo) / (hi - lo)
+            } else {
+                0.0
+            };
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let level = (t * (LEVELS.len() - 1) as f64).round() as usize;
+            LEVELS[level.min(LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        benchmarks::Function::Sphere, core::Bounds, hho::HhoBuilder, observer::LiveMetrics,
+    };
+
+    #[test]
+    fn draws_the_run_from_the_live_channel() {
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0]), "▁▅█");
+        assert_eq!(sparkline(&[1e4, 1e2, 1.0]), "█▅▁");
+
+        let (live, rx) = LiveMetrics::channel(8);
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 3).unwrap())
+            .pop_size(8)
+            .max_iters(30)
+            .seed(6)
+            .observer(live)
+            .build();
+        let report = hho.run(&Sphere).unwrap();
+        let mut dashboard = Dashboard::new(Vec::new())
+            .with_width(20)
+            .with_interval(Duration::ZERO);
+        dashboard.run(&rx).unwrap();
+
+        assert_eq!(dashboard.best.len(), 8);
+        let text = String::from_utf8(dashboard.into_inner()).unwrap();
+        let frame = text.rsplit("\x1b[H\x1b[J").next().unwrap();
+        assert!(frame.starts_with(&format!("iter 30 | evals {}", report.evals)));
+        assert!(frame.contains(&format!("[{}] 100%", "#".repeat(20))));
+        assert!(frame.contains("best") && frame.contains("diversity"));
+        assert!(frame.contains("23 snapshots skipped"));
+    }
+}
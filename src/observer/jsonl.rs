@@ -0,0 +1,154 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    core::utils::dimension_wise_diversity,
+    observer::{IterationContext, Observer},
+};
+
+/// Observer writing one JSON object per iteration (JSON Lines), flushed line by line so the
+/// log can be tailed while the run is going.
+///
+/// Each line holds `iter`, `evals`, `elapsed_ms`, `best`, `mean` (mean fitness of the
+/// population), `diversity` (see [`dimension_wise_diversity`]), `energy` (mean `|E|`) and the
+/// `phases` counts. Non-finite numbers are written as `null`. The first line, with `iter = 0`,
+/// describes the initial population.
+#[derive(Debug)]
+pub struct JsonLines<W> {
+    out: W,
+    /// Primeiro erro de escrita; depois dele nada mais é escrito
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLines<W> {
+    #[must_use]
+    pub const fn new(out: W) -> Self {
+        Self { out, error: None }
+    }
+
+    /// The first write error, if any. Writing stops after an error.
+    #[must_use]
+    pub const fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write(&mut self, ctx: &IterationContext<'_>) -> io::Result<()> {
+        #[allow(clippy::cast_precision_loss)]
+        let mean = ctx.fitness.iter().sum::<f64>() / ctx.fitness.len().max(1) as f64;
+        let p = ctx.phases;
+        writeln!(
+            self.out,
+            "{{\"iter\":{},\"evals\":{},\"elapsed_ms\":{},\"best\":{},\"mean\":{},\
+             \"diversity\":{},\"energy\":{},\"phases\":{{\"exploration\":{},\
+             \"soft_besiege\":{},\"hard_besiege\":{},\"soft_dives\":{},\"hard_dives\":{},\
+             \"quantum\":{},\"differential\":{},\"sine_cosine\":{},\"immigrants\":{}}}}}",
+            ctx.iter,
+            ctx.evals,
+            ctx.elapsed.as_millis(),
+            Number(ctx.best_fitness),
+            Number(mean),
+            Number(dimension_wise_diversity(ctx.positions)),
+            Number(ctx.energy),
+            p.exploration,
+            p.soft_besiege,
+            p.hard_besiege,
+            p.soft_dives,
+            p.hard_dives,
+            p.quantum,
+            p.differential,
+            p.sine_cosine,
+            p.immigrants,
+        )?;
+        self.out.flush()
+    }
+
+    fn log(&mut self, ctx: &IterationContext<'_>) {
+        if self.error.is_none()
+            && let Err(e) = self.write(ctx)
+        {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl JsonLines<BufWriter<File>> {
+    /// Logs to a new file at `path`, truncating an existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send> Observer for JsonLines<W> {
+    fn on_start(&mut self, ctx: &IterationContext<'_>) {
+        self.log(ctx);
+    }
+
+    fn on_iteration(&mut self, ctx: &IterationContext<'_>) {
+        self.log(ctx);
+    }
+}
+
+/// Número JSON: valores não finitos viram `null`
+struct Number(f64);
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_finite() {
+            write!(f, "{}", self.0)
+        } else {
+            f.write_str("null")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn writes_one_line_per_iteration() {
+        let log = Arc::new(Mutex::new(JsonLines::new(Vec::new())));
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(8)
+            .max_iters(10)
+            .seed(3)
+            .shared_observer(log.clone())
+            .build();
+        hho.run(&Sphere).unwrap();
+        drop(hho);
+
+        let log = Arc::into_inner(log).unwrap().into_inner().unwrap();
+        assert!(log.error().is_none());
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines[0].starts_with("{\"iter\":0,\"evals\":8,"));
+        assert!(lines[10].contains("\"iter\":10,"));
+        // Cada falcão toma exatamente um ramo por iteração
+        assert!(lines[10].ends_with('}'));
+        let phases: usize = lines[10]
+            .rsplit("\"phases\":{")
+            .next()
+            .unwrap()
+            .trim_end_matches('}')
+            .split(',')
+            .map(|kv| kv.split(':').nth(1).unwrap().parse::<usize>().unwrap())
+            .sum();
+        assert_eq!(phases, 8);
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use crate::{
+    core::{Report, utils::dimension_wise_diversity},
+    hho::PhaseCounts,
+    observer::{IterationContext, Observer},
+};
+
+/// State of the run after one iteration, as sent by [`LiveMetrics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// Iterations completed (`0` for the initial population).
+    pub iter: usize,
+    pub evals: usize,
+    pub elapsed: Duration,
+    pub best_fitness: f64,
+    pub best_position: Vec<f64>,
+    /// Mean fitness of the population.
+    pub mean_fitness: f64,
+    /// See [`dimension_wise_diversity`].
+    pub diversity: f64,
+    /// Mean escape energy `|E|`.
+    pub energy: f64,
+    pub phases: PhaseCounts,
+    /// Fraction of the budget used, in `[0, 1]`.
+    pub progress: f64,
+}
+
+impl Snapshot {
+    fn of(ctx: &IterationContext<'_>) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let mean_fitness = ctx.fitness.iter().sum::<f64>() / ctx.fitness.len().max(1) as f64;
+        Self {
+            iter: ctx.iter,
+            evals: ctx.evals,
+            elapsed: ctx.elapsed,
+            best_fitness: ctx.best_fitness,
+            best_position: ctx.best_position.to_vec(),
+            mean_fitness,
+            diversity: dimension_wise_diversity(ctx.positions),
+            energy: ctx.energy,
+            phases: ctx.phases,
+            progress: ctx.progress,
+        }
+    }
+}
+
+/// Observer streaming a [`Snapshot`] per iteration to a [`LiveReceiver`], typically read by a
+/// GUI or TUI thread.
+///
+/// The channel is bounded and never blocks the optimizer: when the receiver falls behind,
+/// the oldest snapshots are dropped (see [`LiveReceiver::dropped`]), so the newest state is
+/// always available.
+#[derive(Debug)]
+pub struct LiveMetrics {
+    channel: Arc<Channel>,
+}
+
+/// Receiving end of [`LiveMetrics`].
+#[derive(Debug)]
+pub struct LiveReceiver {
+    channel: Arc<Channel>,
+}
+
+#[derive(Debug)]
+struct Channel {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+#[derive(Debug)]
+struct Queue {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+    dropped: usize,
+    /// A execução terminou (ou o observador foi descartado)
+    finished: bool,
+}
+
+impl Channel {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LiveMetrics {
+    /// A channel keeping at most `capacity` (at least 1) unread snapshots.
+    #[must_use]
+    pub fn channel(capacity: usize) -> (Self, LiveReceiver) {
+        let channel = Arc::new(Channel {
+            queue: Mutex::new(Queue {
+                snapshots: VecDeque::with_capacity(capacity.max(1)),
+                capacity: capacity.max(1),
+                dropped: 0,
+                finished: false,
+            }),
+            ready: Condvar::new(),
+        });
+        (
+            Self {
+                channel: Arc::clone(&channel),
+            },
+            LiveReceiver { channel },
+        )
+    }
+
+    fn send(&self, snapshot: Snapshot) {
+        let mut queue = self.channel.lock();
+        if queue.snapshots.len() == queue.capacity {
+            queue.snapshots.pop_front();
+            queue.dropped += 1;
+        }
+        queue.snapshots.push_back(snapshot);
+        queue.finished = false;
+        drop(queue);
+        self.channel.ready.notify_all();
+    }
+
+    fn finish(&self) {
+        self.channel.lock().finished = true;
+        self.channel.ready.notify_all();
+    }
+}
+
+impl Observer for LiveMetrics {
+    fn on_start(&mut self, ctx: &IterationContext<'_>) {
+        self.send(Snapshot::of(ctx));
+    }
+
+    fn on_iteration(&mut self, ctx: &IterationContext<'_>) {
+        self.send(Snapshot::of(ctx));
+    }
+
+    fn on_end(&mut self, _report: &Report) {
+        self.finish();
+    }
+}
+
+impl Drop for LiveMetrics {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl LiveReceiver {
+    /// The oldest unread snapshot, without waiting.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<Snapshot> {
+        self.channel.lock().snapshots.pop_front()
+    }
+
+    /// Waits for the next snapshot. Returns `None` once the run has ended and every snapshot
+    /// has been read.
+    #[must_use]
+    pub fn recv(&self) -> Option<Snapshot> {
+        let queue = self.channel.lock();
+        let mut queue = self
+            .channel
+            .ready
+            .wait_while(queue, |q| q.snapshots.is_empty() && !q.finished)
+            .unwrap_or_else(PoisonError::into_inner);
+        queue.snapshots.pop_front()
+    }
+
+    /// Like [`LiveReceiver::recv`], giving up after `timeout`.
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Snapshot> {
+        let queue = self.channel.lock();
+        self.channel
+            .ready
+            .wait_timeout_while(queue, timeout, |q| q.snapshots.is_empty() && !q.finished)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .snapshots
+            .pop_front()
+    }
+
+    /// Every unread snapshot, oldest first, without waiting; a frontend redrawing at its own
+    /// pace typically keeps only the last one.
+    #[must_use]
+    pub fn drain(&self) -> Vec<Snapshot> {
+        self.channel.lock().snapshots.drain(..).collect()
+    }
+
+    /// Snapshots dropped so far because the receiver fell behind.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.channel.lock().dropped
+    }
+
+    /// Whether the run has ended (its remaining snapshots may still be unread).
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.channel.lock().finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{benchmarks::Function::Sphere, core::Bounds, hho::HhoBuilder};
+
+    #[test]
+    fn keeps_the_newest_snapshots() {
+        let (live, rx) = LiveMetrics::channel(4);
+        let hho = HhoBuilder::new(Bounds::uniform(-5.0, 5.0, 2).unwrap())
+            .pop_size(6)
+            .max_iters(20)
+            .seed(5)
+            .observer(live)
+            .build();
+        let reader = std::thread::spawn(move || {
+            let mut last = None;
+            while let Some(s) = rx.recv() {
+                last = Some(s);
+            }
+            (last, rx)
+        });
+        let report = hho.run(&Sphere).unwrap();
+        let (last, rx) = reader.join().unwrap();
+
+        let last = last.unwrap();
+        assert_eq!(last.iter, 20);
+        assert!((last.best_fitness - report.best_fitness).abs() < 1e-12);
+        assert!(rx.is_finished());
+
+        // Sem leitor, só as últimas 4 ficam na fila
+        let report = hho.run(&Sphere).unwrap();
+        let unread = rx.drain();
+        assert_eq!(
+            unread.iter().map(|s| s.iter).collect::<Vec<_>>(),
+            [17, 18, 19, 20]
+        );
+        assert_eq!(unread[3].evals, report.evals);
+        assert!(rx.dropped() >= 17);
+    }
+}
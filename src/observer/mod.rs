@@ -0,0 +1,217 @@
+//! Hooks for watching a run from user code (dashboards, loggers, custom metrics).
+
+pub mod csv;
+#[cfg(feature = "tui")]
+pub mod dashboard;
+pub mod jsonl;
+pub mod live;
+pub mod progress;
+
+pub use csv::CsvLog;
+#[cfg(feature = "tui")]
+pub use dashboard::Dashboard;
+pub use jsonl::JsonLines;
+pub use live::{LiveMetrics, LiveReceiver, Snapshot};
+pub use progress::ProgressBar;
+
+use std::{
+    fmt::Debug,
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    core::{Bounds, Decoder, Population, Report},
+    hho::{HawkMemory, PhaseCounts, optimizer::State},
+};
+
+/// Read-only view of the optimizer state handed to the [`Observer`]s.
+#[derive(Debug, Clone, Copy)]
+pub struct IterationContext<'a> {
+    /// Iterations completed so far (`0` in [`Observer::on_start`]).
+    pub iter: usize,
+    pub evals: usize,
+    pub best_fitness: f64,
+    pub best_position: &'a [f64],
+    pub positions: &'a Population,
+    pub fitness: &'a [f64],
+    /// Iterations since the best fitness last improved.
+    pub stall_iters: usize,
+    pub elapsed: Duration,
+    /// Mean escape energy `|E|` of the hawks in the last iteration.
+    pub energy: f64,
+    /// Branches the hawks took in the last iteration (so far, in [`Observer::on_batch`]).
+    pub phases: PhaseCounts,
+    /// Fraction of the budget used so far, in `[0, 1]`.
+    pub progress: f64,
+    /// Per-hawk metadata, when enabled with [`crate::hho::HhoBuilder::track_hawks`].
+    pub hawks: Option<&'a HawkMemory>,
+}
+
+impl<'a> IterationContext<'a> {
+    /// Position and fitness of the rabbit (the best solution found so far).
+    #[must_use]
+    pub const fn current_best(&self) -> (&'a [f64], f64) {
+        (self.best_position, self.best_fitness)
+    }
+
+    /// Positions of the hawks and their fitness, in population order.
+    #[must_use]
+    pub const fn population_view(&self) -> (&'a Population, &'a [f64]) {
+        (self.positions, self.fitness)
+    }
+
+    #[must_use]
+    pub const fn iteration(&self) -> usize {
+        self.iter
+    }
+
+    #[must_use]
+    pub const fn evals(&self) -> usize {
+        self.evals
+    }
+
+    #[must_use]
+    pub const fn energy(&self) -> f64 {
+        self.energy
+    }
+}
+
+/// Receives notifications from the run loop. Every method has an empty default, so
+/// implementors only override what they need.
+pub trait Observer: Send {
+    /// Called once, after the initial population has been evaluated.
+    fn on_start(&mut self, _ctx: &IterationContext<'_>) {}
+
+    /// Called after every iteration.
+    fn on_iteration(&mut self, _ctx: &IterationContext<'_>) {}
+
+    /// Called during an iteration after each batch of hawks, when a batch latency is set
+    /// (see [`crate::hho::HhoBuilder::batch_latency`]).
+    fn on_batch(&mut self, _ctx: &IterationContext<'_>) {}
+
+    /// Called after an iteration that improved the best fitness (before `on_iteration`).
+    fn on_improvement(&mut self, _ctx: &IterationContext<'_>) {}
+
+    /// Called once with the final report.
+    fn on_end(&mut self, _report: &Report) {}
+}
+
+/// Observer shared with the optimizer. Keep a clone of the handle to read the observer back
+/// after the run.
+pub type SharedObserver = Arc<Mutex<dyn Observer>>;
+
+/// Lista de observadores registrados no builder
+#[derive(Clone, Default)]
+pub(crate) struct Observers(pub Vec<SharedObserver>);
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+impl Observers {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn each(&self, mut call: impl FnMut(&mut dyn Observer)) {
+        for o in &self.0 {
+            // Um observador que entrou em pânico não deve derrubar os demais
+            let mut guard = o.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            call(&mut *guard);
+        }
+    }
+}
+
+/// Mutable view of the population handed to the [`MutObserver`]s.
+///
+/// The population may be edited freely, provided `fitness[i]` stays the fitness of
+/// `positions[i]` and positions stay inside the bounds: re-evaluate edited hawks with
+/// [`IterationContextMut::evaluate`] (which counts against the budget) and clip them with
+/// [`IterationContextMut::project`]. The rabbit is refreshed from the population afterwards.
+pub struct IterationContextMut<'a> {
+    pub(crate) state: &'a mut State,
+    pub(crate) bounds: &'a Bounds,
+    pub(crate) decoder: &'a dyn Decoder,
+    /// `State::evaluate::<O>` do objetivo em uso
+    pub(crate) evaluate: fn(&mut State, &dyn Decoder, &[f64]) -> Option<f64>,
+}
+
+impl IterationContextMut<'_> {
+    /// Read-only view of the current state.
+    #[must_use]
+    pub fn view(&self) -> IterationContext<'_> {
+        self.state.context()
+    }
+
+    /// Positions and fitness of the hawks, for in-place edits.
+    pub fn population_mut(&mut self) -> (&mut Population, &mut [f64]) {
+        (&mut self.state.positions, &mut self.state.fitness)
+    }
+
+    /// Evaluates `x` through the optimizer, so the evaluation is counted (and offered to the
+    /// hall of fame). Returns `None` once an evaluation budget is spent.
+    pub fn evaluate(&mut self, x: &[f64]) -> Option<f64> {
+        (self.evaluate)(self.state, self.decoder, x)
+    }
+
+    /// Clips `x` into the bounds.
+    pub fn project(&self, x: &mut [f64]) {
+        self.bounds.project_slice(x);
+    }
+}
+
+/// Observer with mutable access to the population, for custom diversity maintenance or
+/// domain-specific repair. Called after every iteration, after the [`Observer`]s.
+pub trait MutObserver: Send {
+    fn on_iteration(&mut self, ctx: &mut IterationContextMut<'_>);
+}
+
+/// Lista de observadores com acesso mutável
+#[derive(Clone, Default)]
+pub(crate) struct MutObservers(pub Vec<Arc<Mutex<dyn MutObserver>>>);
+
+impl Debug for MutObservers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MutObservers({})", self.0.len())
+    }
+}
+
+impl MutObservers {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn each(&self, ctx: &mut IterationContextMut<'_>) {
+        for o in &self.0 {
+            let mut guard = o.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            guard.on_iteration(ctx);
+        }
+    }
+}
+
+/// User closure deciding after every iteration whether the run should go on.
+pub type EarlyStopFn = dyn FnMut(&IterationContext<'_>) -> ControlFlow<()> + Send;
+
+/// Closure de parada antecipada registrada no builder
+#[derive(Clone)]
+pub(crate) struct EarlyStop(pub Arc<Mutex<EarlyStopFn>>);
+
+impl Debug for EarlyStop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EarlyStop")
+    }
+}
+
+impl EarlyStop {
+    pub fn should_stop(&self, ctx: &IterationContext<'_>) -> bool {
+        let mut f = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        f(ctx).is_break()
+    }
+}
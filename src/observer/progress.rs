@@ -0,0 +1,154 @@
+use std::{
+    io::{self, Stderr, Write},
+    time::Duration,
+};
+
+use crate::{
+    core::{Report, clock::Instant},
+    observer::{IterationContext, Observer},
+};
+
+/// Observer drawing a one-line progress bar with the iteration, the current best and an ETA.
+///
+/// The line is redrawn in place (carriage return) at most every `interval`, and finished
+/// with a newline when the run ends. The ETA extrapolates the fraction of the budget used,
+/// so it works with any [`crate::core::Budget`]. Write errors are ignored.
+#[derive(Debug)]
+pub struct ProgressBar<W> {
+    out: W,
+    /// Largura da barra em caracteres
+    width: usize,
+    interval: Duration,
+    last_draw: Option<Instant>,
+}
+
+impl ProgressBar<Stderr> {
+    /// Progress bar on standard error, redrawn up to ten times per second.
+    #[must_use]
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl<W: Write> ProgressBar<W> {
+    #[must_use]
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            width: 30,
+            interval: Duration::from_millis(100),
+            last_draw: None,
+        }
+    }
+
+    /// Sets the minimum time between redraws (`Duration::ZERO` redraws every iteration).
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn draw(&mut self, ctx: &IterationContext<'_>) -> io::Result<()> {
+        let p = ctx.progress.clamp(0.0, 1.0);
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let filled = (p * self.width as f64).round() as usize;
+        let eta = if p > 0.0 {
+            format_duration(ctx.elapsed.mul_f64((1.0 - p) / p))
+        } else {
+            "?".to_owned()
+        };
+        write!(
+            self.out,
+            "\r[{}{}] {:>3.0}% iter {} | evals {} | best {:.6e} | ETA {eta}",
+            "#".repeat(filled),
+            "-".repeat(self.width - filled),
+            p * 100.0,
+            ctx.iter,
+            ctx.evals,
+            ctx.best_fitness,
+        )?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write + Send> Observer for ProgressBar<W> {
+    fn on_iteration(&mut self, ctx: &IterationContext<'_>) {
+        if self.last_draw.is_some_and(|t| t.elapsed() < self.interval) {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+        let _ = self.draw(ctx);
+    }
+
+    fn on_end(&mut self, report: &Report) {
+        let _ = writeln!(
+            self.out,
+            "\rdone: {} iterations, {} evaluations, best {:.6e} ({})",
+            report.iters,
+            report.evals,
+            report.best_fitness,
+            report.stop_reason.map_or("unknown", |r| r.as_str()),
+        );
+    }
+}
+
+/// Duração curta legível, ex. `1h02m`, `3m07s`, `12.4s`
+pub(super) fn format_duration(d: Duration) -> String {
+    let s = d.as_secs();
+    if s >= 3600 {
+        format!("{}h{:02}m", s / 3600, (s % 3600) / 60)
+    } else if s >= 60 {
+        format!("{}m{:02}s", s / 60, s % 60)
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_are_compact() {
+        assert_eq!(format_duration(Duration::from_millis(12_400)), "12.4s");
+        assert_eq!(format_duration(Duration::from_secs(187)), "3m07s");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1h02m");
+    }
+
+    #[test]
+    fn draws_bar_and_final_line() {
+        let mut bar = ProgressBar::new(Vec::new()).with_interval(Duration::ZERO);
+        let fitness = [1.0];
+        let positions = crate::core::Population::new(0);
+        let ctx = IterationContext {
+            iter: 5,
+            evals: 50,
+            best_fitness: 0.25,
+            best_position: &[],
+            positions: &positions,
+            fitness: &fitness,
+            stall_iters: 0,
+            elapsed: Duration::from_secs(10),
+            energy: 0.0,
+            phases: crate::hho::PhaseCounts::default(),
+            progress: 0.5,
+            hawks: None,
+        };
+        bar.on_iteration(&ctx);
+        bar.on_end(&Report::empty());
+
+        let text = String::from_utf8(bar.into_inner()).unwrap();
+        assert!(text.starts_with(&format!("\r[{}{}]  50%", "#".repeat(15), "-".repeat(15))));
+        assert!(text.contains("ETA 10.0s"));
+        assert!(text.ends_with("(unknown)\n"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Throughput of the HHO main loop from the library API, to check on the target hardware
+//! whether a feature (`rayon`, `simd`) or a setting pays off, and to catch regressions against
+//! a saved baseline.
+//!
+//! The `benches/` harness measures the same loop in more detail, but needs the sources of the
+//! crate; this module runs from any program that depends on it.
+
+use std::{fmt::Write as _, hint::black_box, time::Duration};
+
+use crate::{
+    core::{Bounds, Decoder, DecoderError, clock::Instant},
+    hho::{Hho, HhoBuilder},
+};
+
+/// One configuration of the suite: a population of `pop_size` hawks in `dim` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerfCase {
+    pub pop_size: usize,
+    pub dim: usize,
+    /// Updates and evaluates the hawks in parallel (needs the `rayon` feature; measured
+    /// sequentially without it).
+    pub parallel: bool,
+}
+
+impl PerfCase {
+    #[must_use]
+    pub const fn new(pop_size: usize, dim: usize) -> Self {
+        Self {
+            pop_size,
+            dim,
+            parallel: false,
+        }
+    }
+
+    /// The same case with parallel updates.
+    #[must_use]
+    pub const fn parallel(mut self) -> Self {
+        self.parallel = true;
+        self
+    }
+
+    /// Name of the case in a [`PerfReport`], e.g. `"30x10"` or `"500x1000-par"`.
+    #[must_use]
+    pub fn name(&self) -> String {
+        let suffix = if self.parallel { "-par" } else { "" };
+        format!("{}x{}{suffix}", self.pop_size, self.dim)
+    }
+}
+
+/// Throughput of one [`PerfCase`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measurement {
+    /// See [`PerfCase::name`].
+    pub name: String,
+    pub pop_size: usize,
+    pub dim: usize,
+    pub parallel: bool,
+    /// Iterations timed, after the warm-up.
+    pub iterations: usize,
+    pub seconds: f64,
+    pub iters_per_sec: f64,
+    pub evals_per_sec: f64,
+}
+
+/// Outcome of a [`PerfSuite`], a baseline for later runs; serializable with the `serde`
+/// feature, or as CSV with [`PerfReport::to_csv`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerfReport {
+    pub crate_version: String,
+    /// Performance-related features the crate was built with.
+    pub features: Vec<String>,
+    /// Threads available to parallel cases.
+    pub threads: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl PerfReport {
+    /// One line per measurement under a header, for spreadsheets and scripts.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut out = "name,pop_size,dim,parallel,iterations,seconds,iters_per_sec,evals_per_sec\n"
+            .to_owned();
+        for m in &self.measurements {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                m.name,
+                m.pop_size,
+                m.dim,
+                m.parallel,
+                m.iterations,
+                m.seconds,
+                m.iters_per_sec,
+                m.evals_per_sec
+            );
+        }
+        out
+    }
+
+    /// Speedup of each case over the case of the same name in `baseline`, in the order of
+    /// this report: above 1 is faster. Cases missing from `baseline` are left out.
+    #[must_use]
+    pub fn compare(&self, baseline: &Self) -> Vec<(String, f64)> {
+        self.measurements
+            .iter()
+            .filter_map(|m| {
+                let base = baseline.measurements.iter().find(|b| b.name == m.name)?;
+                Some((m.name.clone(), m.iters_per_sec / base.iters_per_sec))
+            })
+            .collect()
+    }
+}
+
+/// Times the HHO main loop on each of its [`PerfCase`]s with a cheap dummy decoder, so the
+/// measure is the overhead of the optimizer itself.
+///
+/// Each case runs a few warm-up iterations, then iterates until `min_time` has passed. All
+/// runs use the default settings and the same seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerfSuite {
+    cases: Vec<PerfCase>,
+    min_time: Duration,
+    warmup: usize,
+}
+
+impl PerfSuite {
+    #[must_use]
+    pub const fn new(cases: Vec<PerfCase>) -> Self {
+        Self {
+            cases,
+            min_time: Duration::from_millis(500),
+            warmup: 3,
+        }
+    }
+
+    /// Small, medium and large populations and dimensions, each also in parallel with the
+    /// `rayon` feature.
+    #[must_use]
+    pub fn standard() -> Self {
+        let sizes = [(30, 10), (30, 100), (100, 30), (100, 300), (500, 1000)];
+        let mut cases: Vec<PerfCase> = sizes.iter().map(|&(n, d)| PerfCase::new(n, d)).collect();
+        if cfg!(feature = "rayon") {
+            cases.extend(sizes.iter().map(|&(n, d)| PerfCase::new(n, d).parallel()));
+        }
+        Self::new(cases)
+    }
+
+    /// Sets the minimum time measured per case (half a second by default).
+    #[must_use]
+    pub const fn min_time(mut self, min_time: Duration) -> Self {
+        self.min_time = min_time;
+        self
+    }
+
+    /// Sets the iterations run before timing (3 by default).
+    #[must_use]
+    pub const fn warmup(mut self, iterations: usize) -> Self {
+        self.warmup = iterations;
+        self
+    }
+
+    #[must_use]
+    pub fn run(&self) -> PerfReport {
+        let features = [
+            ("rayon", cfg!(feature = "rayon")),
+            ("simd", cfg!(feature = "simd")),
+        ]
+        .iter()
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| (*name).to_owned())
+        .collect();
+        PerfReport {
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            features,
+            threads: threads(),
+            measurements: self.cases.iter().map(|case| self.measure(*case)).collect(),
+        }
+    }
+
+    /// Mede um caso
+    fn measure(&self, case: PerfCase) -> Measurement {
+        let dim = case.dim.max(1);
+        let hho: Hho = HhoBuilder::new(Bounds::uniform(-10.0, 10.0, dim).expect("dim >= 1"))
+            .pop_size(case.pop_size.max(1))
+            .max_iters(usize::MAX)
+            .seed(1)
+            .build();
+        #[cfg(feature = "rayon")]
+        let run = if case.parallel {
+            hho.start_parallel(&Dummy)
+        } else {
+            hho.start(&Dummy)
+        };
+        #[cfg(not(feature = "rayon"))]
+        let run = hho.start(&Dummy);
+        let mut run = run.expect("default settings are valid");
+        for _ in 0..self.warmup {
+            run.step();
+        }
+        let (evals, started) = (run.evals(), Instant::now());
+        let mut iterations = 0;
+        while (iterations < 3 || started.elapsed() < self.min_time) && run.step() {
+            iterations += 1;
+        }
+        let seconds = started.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+        #[allow(clippy::cast_precision_loss)]
+        let (iters, evals) = (iterations as f64, (run.evals() - evals) as f64);
+        Measurement {
+            name: case.name(),
+            pop_size: case.pop_size,
+            dim: case.dim,
+            parallel: case.parallel,
+            iterations,
+            seconds,
+            iters_per_sec: iters / seconds,
+            evals_per_sec: evals / seconds,
+        }
+    }
+}
+
+/// Runs [`PerfSuite::standard`], a few seconds in total, or longer on a slow machine.
+#[must_use]
+pub fn run_standard_suite() -> PerfReport {
+    PerfSuite::standard().run()
+}
+
+/// Esfera opaca ao otimizador do compilador: o custo medido é o do laço do HHO
+struct Dummy;
+
+impl Decoder for Dummy {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        Ok(black_box(solution).iter().map(|x| x * x).sum())
+    }
+}
+
+/// Threads disponíveis para os casos paralelos
+#[cfg(feature = "rayon")]
+fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(not(feature = "rayon"))]
+const fn threads() -> usize {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suites_measure_throughput_against_a_baseline() {
+        let suite = PerfSuite::new(vec![PerfCase::new(8, 4), PerfCase::new(8, 4).parallel()])
+            .min_time(Duration::from_millis(5))
+            .warmup(1);
+        let report = suite.run();
+        assert_eq!(report.measurements.len(), 2);
+        let m = &report.measurements[0];
+        assert_eq!(m.name, "8x4");
+        assert!(m.iterations >= 3 && m.iters_per_sec > 0.0);
+        assert!(m.evals_per_sec >= m.iters_per_sec);
+        assert_eq!(report.measurements[1].name, "8x4-par");
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(1).unwrap().starts_with("8x4,8,4,false,"));
+        let speedups = report.compare(&report);
+        assert_eq!(speedups.len(), 2);
+        assert!(speedups.iter().all(|(_, s)| (s - 1.0).abs() < 1e-12));
+        assert!(PerfSuite::standard().cases.len() >= 5);
+    }
+}
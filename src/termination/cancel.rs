@@ -0,0 +1,104 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// Handle used to stop a running optimizer from another thread (or a Ctrl-C handler).
+///
+/// Cloning the handle is cheap; every clone refers to the same flag. The run checks it before
+/// every iteration and, once set, returns the best solution found so far with the stop reason
+/// [`crate::core::TerminationReason::Cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. Has no effect on runs that already finished.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Cancels this token on `SIGINT` (Ctrl-C) or `SIGTERM`, so the run ends after the
+    /// current iteration with a [`crate::core::Report`] and, if checkpoints are enabled, a
+    /// final checkpoint. A second signal kills the process as usual.
+    ///
+    /// Only one token is wired to the signals at a time; installing another replaces it. The
+    /// handler needs `unsafe` FFI calls, so the method is left out with `forbid-unsafe`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if a handler cannot be installed.
+    #[cfg(all(feature = "signals", unix, not(feature = "forbid-unsafe")))]
+    pub fn cancel_on_signals(&self) -> std::io::Result<()> {
+        signals::install(&self.0)
+    }
+}
+
+#[cfg(all(feature = "signals", unix, not(feature = "forbid-unsafe")))]
+mod signals {
+    use std::{
+        io,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, AtomicPtr, Ordering},
+        },
+    };
+
+    /// Sinalizador do token ligado aos sinais (um `Arc` vazado, nunca liberado)
+    static FLAG: AtomicPtr<AtomicBool> = AtomicPtr::new(std::ptr::null_mut());
+
+    pub fn install(flag: &Arc<AtomicBool>) -> io::Result<()> {
+        let ptr = Arc::into_raw(Arc::clone(flag)).cast_mut();
+        FLAG.store(ptr, Ordering::Release);
+        let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        for signal in [libc::SIGINT, libc::SIGTERM] {
+            // SAFETY: o tratador só faz operações atômicas e chamadas seguras em sinais
+            if unsafe { libc::signal(signal, handler) } == libc::SIG_ERR {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+        let ptr = FLAG.load(Ordering::Acquire);
+        // SAFETY: o ponteiro vem de um `Arc` que nunca é liberado
+        let Some(flag) = (unsafe { ptr.as_ref() }) else {
+            return;
+        };
+        if flag.swap(true, Ordering::AcqRel) {
+            // Segundo sinal: volta ao comportamento padrão e encerra o processo
+            // SAFETY: `signal` e `raise` podem ser chamadas de um tratador de sinal
+            unsafe {
+                libc::signal(signal, libc::SIG_DFL);
+                libc::raise(signal);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "signals", unix, not(feature = "forbid-unsafe")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sigterm_cancels_the_token() {
+        let token = CancellationToken::new();
+        token.cancel_on_signals().unwrap();
+        // SAFETY: o tratador instalado acima só marca o token
+        unsafe {
+            libc::raise(libc::SIGTERM);
+        }
+        assert!(token.is_cancelled());
+    }
+}
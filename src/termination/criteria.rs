@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::{
+    core::TerminationReason,
+    termination::{Criterion, Status},
+};
+
+/// Stops after the given number of iterations.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxIters(pub usize);
+
+impl Criterion for MaxIters {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        (status.iter >= self.0).then_some(TerminationReason::MaxIters)
+    }
+}
+
+/// Stops once the given number of evaluations has been spent.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxEvals(pub usize);
+
+impl Criterion for MaxEvals {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        (status.evals >= self.0).then_some(TerminationReason::MaxEvals)
+    }
+}
+
+/// Stops when the best fitness is within `tolerance` of `target`, or better than it.
+#[derive(Debug, Clone, Copy)]
+pub struct TargetFitness {
+    pub target: f64,
+    pub tolerance: f64,
+}
+
+impl Criterion for TargetFitness {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        let f = status.best_fitness;
+        ((f - self.target).abs() <= self.tolerance || (status.better)(f, self.target))
+            .then_some(TerminationReason::TargetReached)
+    }
+}
+
+/// Stops after the given number of iterations without improvement of the best fitness.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxStall(pub usize);
+
+impl Criterion for MaxStall {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        (status.stall_iters >= self.0).then_some(TerminationReason::Stalled)
+    }
+}
+
+/// Stops once the given wall-clock time has elapsed.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxTime(pub Duration);
+
+impl Criterion for MaxTime {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        (status.elapsed >= self.0).then_some(TerminationReason::TimeLimit)
+    }
+}
+
+/// Fires as soon as any of its criteria fires, reporting the first one (in order).
+#[derive(Debug, Default)]
+pub struct Any(pub Vec<Box<dyn Criterion>>);
+
+impl Any {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn or(mut self, criterion: impl Criterion + 'static) -> Self {
+        self.0.push(Box::new(criterion));
+        self
+    }
+}
+
+impl Criterion for Any {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        self.0.iter().find_map(|c| c.check(status))
+    }
+}
+
+/// Fires only when all of its criteria fire at the same time, reporting the last one.
+#[derive(Debug, Default)]
+pub struct All(pub Vec<Box<dyn Criterion>>);
+
+impl All {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn and(mut self, criterion: impl Criterion + 'static) -> Self {
+        self.0.push(Box::new(criterion));
+        self
+    }
+}
+
+impl Criterion for All {
+    fn check(&self, status: &Status) -> Option<TerminationReason> {
+        let mut last = None;
+        for c in &self.0 {
+            last = Some(c.check(status)?);
+        }
+        last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Minimization, Objective};
+
+    fn status(iter: usize, best_fitness: f64, stall_iters: usize) -> Status {
+        Status {
+            iter,
+            evals: iter * 10,
+            best_fitness,
+            stall_iters,
+            elapsed: Duration::ZERO,
+            better: Minimization::better,
+        }
+    }
+
+    #[test]
+    fn combinators_report_the_firing_rule() {
+        let any = Any::new()
+            .or(TargetFitness {
+                target: 0.0,
+                tolerance: 1e-6,
+            })
+            .or(MaxStall(5));
+        assert_eq!(any.check(&status(3, 1.0, 1)), None);
+        assert_eq!(
+            any.check(&status(3, 1.0, 5)),
+            Some(TerminationReason::Stalled)
+        );
+        assert_eq!(
+            any.check(&status(3, -1.0, 0)),
+            Some(TerminationReason::TargetReached)
+        );
+
+        let all = All::new().and(MaxIters(10)).and(MaxEvals(50));
+        assert_eq!(all.check(&status(4, 0.0, 0)), None);
+        assert_eq!(
+            all.check(&status(10, 0.0, 0)),
+            Some(TerminationReason::MaxEvals)
+        );
+    }
+}
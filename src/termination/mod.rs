@@ -0,0 +1,36 @@
+//! Stopping rules checked by the run loop after every iteration.
+
+pub mod cancel;
+pub mod criteria;
+
+pub use cancel::CancellationToken;
+pub use criteria::{All, Any, MaxEvals, MaxIters, MaxStall, MaxTime, TargetFitness};
+
+use std::{fmt::Debug, time::Duration};
+
+use crate::core::TerminationReason;
+
+/// Snapshot of a run handed to the [`Criterion`]s.
+#[derive(Debug, Clone, Copy)]
+pub struct Status {
+    /// Iterations completed so far.
+    pub iter: usize,
+    /// Decoder calls so far, initialization included.
+    pub evals: usize,
+    pub best_fitness: f64,
+    /// Iterations since the best fitness last improved.
+    pub stall_iters: usize,
+    pub elapsed: Duration,
+    /// `O::better` of the objective being optimized.
+    pub better: fn(f64, f64) -> bool,
+}
+
+/// A stopping rule.
+///
+/// Criteria are stateless: everything they need is in the [`Status`], so the same criterion
+/// can be shared between runs.
+pub trait Criterion: Debug + Send + Sync {
+    /// Returns why the run should stop, or `None` to keep running. User-defined rules
+    /// usually return [`TerminationReason::Custom`].
+    fn check(&self, status: &Status) -> Option<TerminationReason>;
+}
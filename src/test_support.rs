@@ -0,0 +1,259 @@
+//! Random problems and invariant checks for testing custom decoders and initializers.
+//!
+//! Enabled by the `testing` feature. The generators take any [`Rng`], so the same code
+//! runs in a seeded loop or under the generator of a property-testing framework, and the
+//! checks return a description of the first broken invariant instead of panicking, so they fit
+//! in an `assert!`, a `prop_assert!` or a custom report:
+//!
+//! ```
+//! use kambo_hho::{init::RandomInitializer, test_support};
+//! use rand::{Rng, SeedableRng};
+//! use rand_chacha::ChaCha12Rng;
+//!
+//! let mut rng = ChaCha12Rng::seed_from_u64(7);
+//! for _ in 0..32 {
+//!     let bounds = test_support::bounds(&mut rng, 8);
+//!     let pop_size = rng.random_range(1..20);
+//!     test_support::check_initializer(&RandomInitializer, &bounds, pop_size, &mut rng).unwrap();
+//! }
+//! ```
+
+use rand::Rng;
+
+use crate::{
+    core::{Bounds, Decoder, DecoderError, Population},
+    init::Initializer,
+};
+
+/// Random box of dimension `1..=max_dim` (at least 1).
+///
+/// Centers lie in `[-100, 100]` and half-widths are log-uniform in `[1e-3, 1e3]`, so the
+/// dimensions differ by orders of magnitude; about one dimension in twenty is degenerate
+/// (`lo == hi`), a corner case initializers and decoders must survive.
+#[allow(clippy::missing_panics_doc)] // lo <= hi por construção
+pub fn bounds<R: Rng>(rng: &mut R, max_dim: usize) -> Bounds {
+    let dim = rng.random_range(1..=max_dim.max(1));
+    let (mut lo, mut hi) = (Vec::with_capacity(dim), Vec::with_capacity(dim));
+    for _ in 0..dim {
+        let center = rng.random_range(-100.0..=100.0);
+        let half = if rng.random_bool(0.05) {
+            0.0
+        } else {
+            10f64.powf(rng.random_range(-3.0..=3.0))
+        };
+        lo.push(center - half);
+        hi.push(center + half);
+    }
+    Bounds::per_dim(lo, hi).expect("lo <= hi by construction")
+}
+
+/// `n` random points inside `bounds`, with about one coordinate in ten exactly on a face of
+/// the box, where off-by-one mistakes in the handling of the limits show up.
+pub fn population<R: Rng>(rng: &mut R, bounds: &Bounds, n: usize) -> Population {
+    let mut positions = Population::zeros(n, bounds.dim());
+    for x in &mut positions {
+        bounds.fill_random(x, rng);
+        for (i, v) in x.iter_mut().enumerate() {
+            if rng.random_bool(0.1) {
+                *v = if rng.random_bool(0.5) {
+                    bounds.lo_at(i)
+                } else {
+                    bounds.hi_at(i)
+                };
+            }
+        }
+    }
+    positions
+}
+
+/// Separable quadratic with a known optimum: `value + Σ wᵢ·(xᵢ - oᵢ)²`.
+///
+/// The weights span four orders of magnitude, so the problem is ill-conditioned, and the
+/// optimum lies anywhere inside the box, not at its center. Rejects positions of another
+/// dimension with [`DecoderError::InvalidDimension`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Planted {
+    optimum: Vec<f64>,
+    weights: Vec<f64>,
+    value: f64,
+}
+
+impl Planted {
+    /// Random instance whose optimum is a uniformly random point of `bounds`.
+    pub fn random<R: Rng>(rng: &mut R, bounds: &Bounds) -> Self {
+        let d = bounds.dim();
+        Self {
+            optimum: bounds.gen_random_vec(rng),
+            weights: (0..d)
+                .map(|_| 10f64.powf(rng.random_range(-2.0..=2.0)))
+                .collect(),
+            value: rng.random_range(-10.0..=10.0),
+        }
+    }
+
+    /// Position of the minimum.
+    #[must_use]
+    pub fn optimum(&self) -> &[f64] {
+        &self.optimum
+    }
+
+    /// Fitness at the minimum.
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl Decoder for Planted {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        if solution.len() != self.optimum.len() {
+            return Err(DecoderError::InvalidDimension {
+                expected: self.optimum.len(),
+                received: solution.len(),
+            });
+        }
+        Ok(solution
+            .iter()
+            .zip(&self.optimum)
+            .zip(&self.weights)
+            .fold(self.value, |f, ((x, o), w)| (w * (x - o)).mul_add(x - o, f)))
+    }
+}
+
+/// Checks the invariants every [`Initializer`] of the crate keeps.
+///
+/// A population size of `0` is rejected, and otherwise exactly `pop_size` positions of
+/// dimension `bounds.dim()` come back, all finite and inside `bounds`.
+///
+/// # Errors
+///
+/// A description of the first broken invariant.
+pub fn check_initializer<I: Initializer, R: Rng>(
+    init: &I,
+    bounds: &Bounds,
+    pop_size: usize,
+    rng: &mut R,
+) -> Result<(), String> {
+    if init.initialize(0, bounds, rng).is_ok() {
+        return Err("a population of size 0 was accepted".into());
+    }
+    let positions = init
+        .initialize(pop_size, bounds, rng)
+        .map_err(|e| format!("pop_size = {pop_size} was rejected: {e}"))?;
+    if positions.shape() != (pop_size, bounds.dim()) {
+        return Err(format!(
+            "expected {pop_size} positions of dimension {}, got shape {:?}",
+            bounds.dim(),
+            positions.shape()
+        ));
+    }
+    for (k, x) in positions.iter().enumerate() {
+        for (i, &v) in x.iter().enumerate() {
+            if !(v.is_finite() && bounds.lo_at(i) <= v && v <= bounds.hi_at(i)) {
+                return Err(format!(
+                    "coordinate {i} of position {k} is {v}, outside [{}, {}]",
+                    bounds.lo_at(i),
+                    bounds.hi_at(i)
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks a decoder the way the optimizers use it.
+///
+/// On `samples` random points of `bounds` (see [`population`]), each decodes without error to a fitness that is not NaN (infinite
+/// penalties are allowed), and decoding the same point twice gives the same fitness, bit for
+/// bit, which seeded runs rely on to be reproducible.
+///
+/// # Errors
+///
+/// A description of the first broken invariant.
+pub fn check_decoder<R: Rng>(
+    decoder: &dyn Decoder,
+    bounds: &Bounds,
+    samples: usize,
+    rng: &mut R,
+) -> Result<(), String> {
+    for x in &population(rng, bounds, samples) {
+        let f = decoder
+            .decode(x)
+            .map_err(|e| format!("{x:?} failed to decode: {e}"))?;
+        if f.is_nan() {
+            return Err(format!("{x:?} decoded to NaN"));
+        }
+        match decoder.decode(x) {
+            Ok(g) if g.to_bits() == f.to_bits() => {}
+            again => return Err(format!("{x:?} decoded to {f} and then to {again:?}")),
+        }
+    }
+    Ok(())
+}
+
+/// Checks that positions with one coordinate too many or too few are rejected with an error
+/// instead of being decoded, as [`Decoder::decode`] asks of implementations.
+///
+/// # Errors
+///
+/// A description of the first accepted position.
+pub fn check_rejects_wrong_dimension(decoder: &dyn Decoder, bounds: &Bounds) -> Result<(), String> {
+    let d = bounds.dim();
+    for len in [d.saturating_sub(1), d + 1] {
+        if len != d
+            && let Ok(f) = decoder.decode(&vec![bounds.lo_at(0); len])
+        {
+            return Err(format!(
+                "a position of dimension {len} was decoded to {f} instead of rejected"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::{
+        benchmarks::Function,
+        core::Checked,
+        hho::HhoBuilder,
+        init::{RandomInitializer, WarmStart},
+    };
+
+    #[test]
+    fn the_crate_passes_its_own_checks() {
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        for _ in 0..64 {
+            let bounds = bounds(&mut rng, 6);
+            let n = rng.random_range(1..12);
+            check_initializer(&RandomInitializer, &bounds, n, &mut rng).unwrap();
+
+            let planted = Planted::random(&mut rng, &bounds);
+            check_decoder(&planted, &bounds, 8, &mut rng).unwrap();
+            check_rejects_wrong_dimension(&planted, &bounds).unwrap();
+            let checked = Checked::new(Function::Sphere, bounds.dim());
+            check_decoder(&checked, &bounds, 8, &mut rng).unwrap();
+            check_rejects_wrong_dimension(&checked, &bounds).unwrap();
+            assert!(check_rejects_wrong_dimension(&Function::Sphere, &bounds).is_err());
+
+            let x = planted.optimum();
+            assert!((planted.decode(x).unwrap() - planted.value()).abs() < 1e-12);
+        }
+
+        // Um warm start herda os limites da execução anterior
+        let bounds = bounds(&mut rng, 4);
+        let planted = Planted::random(&mut rng, &bounds);
+        let report = HhoBuilder::new(bounds.clone())
+            .pop_size(8)
+            .max_iters(5)
+            .seed(0)
+            .build()
+            .run(&planted)
+            .unwrap();
+        check_initializer(&WarmStart::from_report(&report), &bounds, 6, &mut rng).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+//! JavaScript bindings, built with `wasm-bindgen` for `wasm32-unknown-unknown`.
+//!
+//! ```js
+//! const hho = new Hho(new Float64Array([-5, -5]), new Float64Array([5, 5]));
+//! hho.popSize = 20;
+//! hho.maxIters = 100;
+//! hho.seed = 42n;
+//! const report = hho.run((x) => x[0] * x[0] + x[1] * x[1]);
+//! console.log(report.bestFitness, report.bestPosition, report.convergence);
+//! ```
+//!
+//! Wall-clock budgets and durations follow `Date.now()` (see [`crate::core::clock`]); the
+//! `rayon` feature must stay off, since the browser has no threads for it.
+
+// #[wasm_bindgen] não aceita const fn
+#![allow(clippy::missing_const_for_fn)]
+
+use js_sys::{Float64Array, Function};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    core::{Bounds, Decoder, DecoderError, Report as CoreReport},
+    hho::HhoBuilder,
+};
+
+/// HHO settings and entry point, mirroring [`HhoBuilder`].
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Hho {
+    bounds: Bounds,
+    pop_size: usize,
+    max_iters: usize,
+    seed: Option<u64>,
+    trajectory: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl Hho {
+    /// Searches the box `[lower, upper]`, with 30 hawks for 500 iterations.
+    ///
+    /// # Errors
+    ///
+    /// Throws if the bounds are invalid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>) -> Result<Self, JsError> {
+        Ok(Self {
+            bounds: Bounds::per_dim(lower, upper)?,
+            pop_size: 30,
+            max_iters: 500,
+            seed: None,
+            trajectory: None,
+        })
+    }
+
+    #[wasm_bindgen(getter = popSize)]
+    #[must_use]
+    pub fn pop_size(&self) -> usize {
+        self.pop_size
+    }
+
+    #[wasm_bindgen(setter = popSize)]
+    pub fn set_pop_size(&mut self, pop_size: usize) {
+        self.pop_size = pop_size;
+    }
+
+    #[wasm_bindgen(getter = maxIters)]
+    #[must_use]
+    pub fn max_iters(&self) -> usize {
+        self.max_iters
+    }
+
+    #[wasm_bindgen(setter = maxIters)]
+    pub fn set_max_iters(&mut self, max_iters: usize) {
+        self.max_iters = max_iters;
+    }
+
+    /// Master seed (a `BigInt`); `undefined` draws a fresh one per run.
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Records the rabbit every `every` iterations into [`Report::trajectory`], e.g. to
+    /// animate the search; `undefined` turns it off.
+    #[wasm_bindgen(setter = recordTrajectory)]
+    pub fn set_record_trajectory(&mut self, every: Option<usize>) {
+        self.trajectory = every;
+    }
+
+    /// Minimizes `objective`, a function from a `Float64Array` to a number.
+    ///
+    /// # Errors
+    ///
+    /// Throws if the objective throws or returns something other than a number.
+    pub fn run(&self, objective: &Function) -> Result<Report, JsError> {
+        let mut builder = HhoBuilder::new(self.bounds.clone())
+            .pop_size(self.pop_size)
+            .max_iters(self.max_iters);
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(every) = self.trajectory {
+            builder = builder.record_trajectory(every);
+        }
+        let report = builder.build().run(&JsObjective(objective))?;
+        Ok(Report(report))
+    }
+}
+
+/// Objetivo em JavaScript chamado com um `Float64Array`
+struct JsObjective<'a>(&'a Function);
+
+impl Decoder for JsObjective<'_> {
+    fn decode(&self, solution: &[f64]) -> Result<f64, DecoderError> {
+        let x = Float64Array::from(solution);
+        let value = self
+            .0
+            .call1(&JsValue::NULL, &x)
+            .map_err(|e| DecoderError::External(format!("{e:?}")))?;
+        value
+            .as_f64()
+            .ok_or_else(|| DecoderError::External(format!("objective returned {value:?}")))
+    }
+}
+
+/// Outcome of [`Hho::run`], a read-only view of [`CoreReport`].
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Report(CoreReport);
+
+#[wasm_bindgen]
+impl Report {
+    #[wasm_bindgen(getter = bestFitness)]
+    #[must_use]
+    pub fn best_fitness(&self) -> f64 {
+        self.0.best_fitness
+    }
+
+    #[wasm_bindgen(getter = bestPosition)]
+    #[must_use]
+    pub fn best_position(&self) -> Vec<f64> {
+        self.0.best_position.clone()
+    }
+
+    /// Best fitness after each recorded iteration.
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn convergence(&self) -> Vec<f64> {
+        self.0.convergence_curve.clone()
+    }
+
+    /// Recorded rabbit positions, concatenated (`dim` values each).
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn trajectory(&self) -> Vec<f64> {
+        self.0.trajectory.concat()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn iters(&self) -> usize {
+        self.0.iters
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn evals(&self) -> usize {
+        self.0.evals
+    }
+
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.0.seed
+    }
+
+    /// Human-readable summary, as printed by [`CoreReport::summary`].
+    #[must_use]
+    pub fn summary(&self) -> String {
+        self.0.summary()
+    }
+}